@@ -27585,6 +27585,16 @@ pub mod api {
                         ::core::primitive::u8,
                     >,
                 ),
+                #[codec(index = 2)]
+                ProductionEquivocation(::core::primitive::u64),
+                #[codec(index = 3)]
+                InsufficientFinalization(::core::primitive::u32),
+                #[codec(index = 4)]
+                Manual(
+                    runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >,
+                ),
             }
             #[derive(
                 :: subxt :: ext :: codec :: Decode,
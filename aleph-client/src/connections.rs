@@ -56,6 +56,37 @@ pub trait AsSigned {
     fn as_signed(&self) -> &SignedConnection;
 }
 
+/// Error returned by the fallible (`try_`) storage and RPC accessors.
+#[derive(Debug, thiserror::Error)]
+pub enum QueryError {
+    /// The node could not be reached or it rejected the request.
+    #[error("RPC transport error: {0}")]
+    Rpc(subxt::Error),
+    /// There is no value under the requested storage key, given as `Pallet::Entry`.
+    #[error("There is no value in storage {0}")]
+    MissingStorage(String),
+    /// The response does not match the type expected by the client.
+    #[error("Failed to decode the response: {0}")]
+    Decode(subxt::Error),
+}
+
+impl From<subxt::Error> for QueryError {
+    fn from(e: subxt::Error) -> Self {
+        match e {
+            subxt::Error::Codec(_) | subxt::Error::Decode(_) | subxt::Error::Metadata(_) => {
+                QueryError::Decode(e)
+            }
+            e => QueryError::Rpc(e),
+        }
+    }
+}
+
+impl From<codec::Error> for QueryError {
+    fn from(e: codec::Error) -> Self {
+        QueryError::Decode(subxt::Error::Codec(e))
+    }
+}
+
 /// Any connection should be able to request storage and submit RPC calls
 #[async_trait::async_trait]
 pub trait ConnectionApi: Sync {
@@ -95,6 +126,29 @@ pub trait ConnectionApi: Sync {
         at: Option<BlockHash>,
     ) -> Option<T>;
 
+    /// Fallible version of [`Self::get_storage_entry`], returns [`QueryError::MissingStorage`]
+    /// if there is no such value.
+    async fn try_get_storage_entry<
+        T: DecodeWithMetadata + Sync,
+        Defaultable: Sync,
+        Iterable: Sync,
+    >(
+        &self,
+        addrs: &Address<StaticStorageMapKey, T, Yes, Defaultable, Iterable>,
+        at: Option<BlockHash>,
+    ) -> Result<T, QueryError>;
+
+    /// Fallible version of [`Self::get_storage_entry_maybe`].
+    async fn try_get_storage_entry_maybe<
+        T: DecodeWithMetadata + Sync,
+        Defaultable: Sync,
+        Iterable: Sync,
+    >(
+        &self,
+        addrs: &Address<StaticStorageMapKey, T, Yes, Defaultable, Iterable>,
+        at: Option<BlockHash>,
+    ) -> Result<Option<T>, QueryError>;
+
     /// Submit a RPC call.
     ///
     /// * `func_name` - name of a RPC call
@@ -116,6 +170,13 @@ pub trait ConnectionApi: Sync {
     /// ```
     async fn rpc_call<R: Decode>(&self, func_name: String, params: RpcParams) -> anyhow::Result<R>;
 
+    /// Same as [rpc_call] but distinguishes transport errors from decoding errors.
+    async fn try_rpc_call<R: Decode>(
+        &self,
+        func_name: String,
+        params: RpcParams,
+    ) -> Result<R, QueryError>;
+
     /// Same as [rpc_call] but used for rpc endpoint that does not return values.
     async fn rpc_call_no_return(&self, func_name: String, params: RpcParams) -> anyhow::Result<()>;
 }
@@ -288,18 +349,60 @@ impl<C: AsConnection + Sync> ConnectionApi for C {
         addrs: &Address<StaticStorageMapKey, T, Yes, Defaultable, Iterable>,
         at: Option<BlockHash>,
     ) -> Option<T> {
+        self.try_get_storage_entry_maybe(addrs, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_storage_entry<
+        T: DecodeWithMetadata + Sync,
+        Defaultable: Sync,
+        Iterable: Sync,
+    >(
+        &self,
+        addrs: &Address<StaticStorageMapKey, T, Yes, Defaultable, Iterable>,
+        at: Option<BlockHash>,
+    ) -> Result<T, QueryError> {
+        self.try_get_storage_entry_maybe(addrs, at)
+            .await?
+            .ok_or_else(|| {
+                QueryError::MissingStorage(format!(
+                    "{}::{}",
+                    addrs.pallet_name(),
+                    addrs.entry_name()
+                ))
+            })
+    }
+
+    async fn try_get_storage_entry_maybe<
+        T: DecodeWithMetadata + Sync,
+        Defaultable: Sync,
+        Iterable: Sync,
+    >(
+        &self,
+        addrs: &Address<StaticStorageMapKey, T, Yes, Defaultable, Iterable>,
+        at: Option<BlockHash>,
+    ) -> Result<Option<T>, QueryError> {
         info!(target: "aleph-client", "accessing storage at {}::{} at block {:?}", addrs.pallet_name(), addrs.entry_name(), at);
 
         let storage = self.as_connection().as_client().storage();
         let block = match at {
             Some(block_hash) => storage.at(block_hash),
-            None => storage.at_latest().await.expect("Should access storage"),
+            None => storage.at_latest().await?,
         };
 
-        block.fetch(addrs).await.expect("Should access storage")
+        Ok(block.fetch(addrs).await?)
     }
 
     async fn rpc_call<R: Decode>(&self, func_name: String, params: RpcParams) -> anyhow::Result<R> {
+        Ok(self.try_rpc_call(func_name, params).await?)
+    }
+
+    async fn try_rpc_call<R: Decode>(
+        &self,
+        func_name: String,
+        params: RpcParams,
+    ) -> Result<R, QueryError> {
         info!(target: "aleph-client", "submitting rpc call `{}`, with params {:?}", func_name, params.clone().build());
         let bytes: Bytes = self
             .as_connection()
@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+
+use codec::{Decode, DecodeAll, Encode};
+use primitives::{
+    crypto::{AuthorityVerifier, IndexedSignature, Signature, SignatureSet as IndexedSignatureSet},
+    next_authority_data_storage_keys, AuthorityId, AuthoritySignature, BlakeTwo256, BlockNumber,
+    Hash, Header, HeaderT, SessionAuthorityData, SessionIndex,
+};
+use serde::{Deserialize, Serialize};
+use sp_state_machine::read_proof_check;
+use sp_trie::StorageProof;
+use subxt::ext::sp_core::Bytes;
+
+/// Multisignature as stored by the node, a map from indices of the authorities to signatures.
+type SignatureSet<S> = aleph_bft_crypto::SignatureSet<S>;
+
+/// Old format of signatures, which unnecessarily contained the index of the signer.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+struct SignatureV1 {
+    _id: u64,
+    sgn: AuthoritySignature,
+}
+
+/// Current format of justifications.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+enum AlephJustification {
+    CommitteeMultisignature(SignatureSet<Signature>),
+    EmergencySignature(AuthoritySignature),
+}
+
+/// A justification in the form in which it can be verified.
+enum Justification {
+    Committee(IndexedSignatureSet<AuthoritySignature>),
+    Emergency(AuthoritySignature),
+}
+
+impl From<SignatureSet<SignatureV1>> for Justification {
+    fn from(signatures: SignatureSet<SignatureV1>) -> Self {
+        Justification::Committee(IndexedSignatureSet(
+            signatures
+                .into_iter()
+                .map(|(index, signature)| IndexedSignature {
+                    index: index.0 as u64,
+                    signature: signature.sgn,
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl From<SignatureSet<Signature>> for Justification {
+    fn from(signatures: SignatureSet<Signature>) -> Self {
+        Justification::Committee(IndexedSignatureSet(
+            signatures
+                .into_iter()
+                .map(|(index, signature)| IndexedSignature {
+                    index: index.0 as u64,
+                    signature: signature.0,
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl From<AlephJustification> for Justification {
+    fn from(justification: AlephJustification) -> Self {
+        match justification {
+            AlephJustification::CommitteeMultisignature(signatures) => signatures.into(),
+            AlephJustification::EmergencySignature(signature) => {
+                Justification::Emergency(signature)
+            }
+        }
+    }
+}
+
+/// Decodes justifications produced before they were versioned, which may still be stored by nodes.
+fn decode_pre_compatibility(encoded: &[u8]) -> Option<Justification> {
+    match SignatureSet::<Signature>::decode_all(&mut &encoded[..]) {
+        Ok(signatures) => Some(signatures.into()),
+        Err(_) => SignatureSet::<SignatureV1>::decode_all(&mut &encoded[..])
+            .ok()
+            .map(Into::into),
+    }
+}
+
+/// Decodes a versioned justification, prefixed with its version and the length of its payload.
+/// Returns `None` if the encoding is not a versioned one and the version if it is unknown.
+fn decode_versioned(encoded: &[u8]) -> Option<Result<Justification, u16>> {
+    let input = &mut &encoded[..];
+    let version = u16::decode(input).ok()?;
+    let byte_count = u16::decode(input).ok()?;
+    let justification = match version {
+        1 => SignatureSet::<SignatureV1>::decode(input).ok()?.into(),
+        2 => SignatureSet::<Signature>::decode(input).ok()?.into(),
+        3 => AlephJustification::decode(input).ok()?.into(),
+        _ => return (input.len() == byte_count as usize).then_some(Err(version)),
+    };
+    input.is_empty().then_some(Ok(justification))
+}
+
+/// Decodes a justification in any of the formats the node may store it in.
+fn decode_justification(
+    number: BlockNumber,
+    encoded: &[u8],
+) -> Result<Justification, FinalityProofError> {
+    match decode_versioned(encoded) {
+        Some(Ok(justification)) => Ok(justification),
+        // Old justifications may coincidentally look like ones with an unknown version.
+        Some(Err(version)) => decode_pre_compatibility(encoded)
+            .ok_or(FinalityProofError::UnknownVersion(number, version)),
+        None => decode_pre_compatibility(encoded).ok_or(FinalityProofError::Decode(number)),
+    }
+}
+
+/// Ways in which a finality proof bundle can be wrong.
+#[derive(Debug, thiserror::Error)]
+pub enum FinalityProofError {
+    #[error("failed to decode the justification of block #{0}")]
+    Decode(BlockNumber),
+    #[error("the justification of block #{0} is encoded with unknown version {1}")]
+    UnknownVersion(BlockNumber, u16),
+    #[error("insufficient or incorrect signatures in the justification of block #{0}")]
+    BadMultisignature(BlockNumber),
+    #[error("incorrect emergency signature in the justification of block #{0}")]
+    BadEmergencySignature(BlockNumber),
+    #[error("the justification of block #{0} is an emergency signature, but there is no emergency finalizer")]
+    NoEmergencyFinalizer(BlockNumber),
+    #[error("no authorities of session {1} for block #{0}")]
+    MissingAuthorities(BlockNumber, SessionIndex),
+    #[error("expected an authority change at block #{expected}, got block #{got}")]
+    UnexpectedAuthorityChange {
+        expected: BlockNumber,
+        got: BlockNumber,
+    },
+    #[error("incorrect proof of the next authorities in the state of block #{0}: {1}")]
+    AuthoritiesProof(BlockNumber, String),
+}
+
+/// The authorities of a session, as returned by `AlephSessionApi::authority_data`.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAuthorities {
+    pub session: SessionIndex,
+    pub authorities: Vec<AuthorityId>,
+    pub emergency_finalizer: Option<AuthorityId>,
+}
+
+impl SessionAuthorities {
+    pub fn new(session: SessionIndex, authority_data: SessionAuthorityData) -> Self {
+        SessionAuthorities {
+            session,
+            authorities: authority_data.authorities().clone(),
+            emergency_finalizer: authority_data.emergency_finalizer().clone(),
+        }
+    }
+
+    /// Verifies that `proof` is a justification of its header issued by these authorities.
+    fn verify(&self, proof: &BlockFinalityProof) -> Result<(), FinalityProofError> {
+        let number = *proof.header.number();
+        let message = proof.header.hash().encode();
+        match decode_justification(number, &proof.justification)? {
+            Justification::Committee(signatures) => {
+                let verifier = AuthorityVerifier::new(self.authorities.clone());
+                match AuthorityVerifier::is_complete(&verifier, &message, &signatures) {
+                    true => Ok(()),
+                    false => Err(FinalityProofError::BadMultisignature(number)),
+                }
+            }
+            Justification::Emergency(signature) => {
+                let emergency_finalizer = self
+                    .emergency_finalizer
+                    .clone()
+                    .ok_or(FinalityProofError::NoEmergencyFinalizer(number))?;
+                let verifier = AuthorityVerifier::new(vec![emergency_finalizer]);
+                match verifier.verify(&message, &signature, 0) {
+                    true => Ok(()),
+                    false => Err(FinalityProofError::BadEmergencySignature(number)),
+                }
+            }
+        }
+    }
+}
+
+/// A header together with its justification, kept in the form the node stores it in.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockFinalityProof {
+    pub header: Header,
+    pub justification: Bytes,
+}
+
+/// The justified last block of a session, together with a proof of the authorities of the next
+/// session against its state.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorityChange {
+    pub last_block: BlockFinalityProof,
+    pub next_authorities_proof: Vec<Bytes>,
+}
+
+impl AuthorityChange {
+    /// Reads the authorities of the next session from the proof, checking it against the state
+    /// root of the last block.
+    fn next_authorities(
+        &self,
+        next_session: SessionIndex,
+    ) -> Result<SessionAuthorities, FinalityProofError> {
+        let number = *self.last_block.header.number();
+        let proof_error = |e: String| FinalityProofError::AuthoritiesProof(number, e);
+        let [authorities_key, emergency_finalizer_key] = next_authority_data_storage_keys();
+        let proof = StorageProof::new(self.next_authorities_proof.iter().map(|node| node.to_vec()));
+        let mut values = read_proof_check::<BlakeTwo256, _>(
+            *self.last_block.header.state_root(),
+            proof,
+            [&authorities_key, &emergency_finalizer_key],
+        )
+        .map_err(|e| proof_error(e.to_string()))?;
+        let authorities = match values.remove(&authorities_key).flatten() {
+            Some(encoded) => Vec::<AuthorityId>::decode_all(&mut encoded.as_slice())
+                .map_err(|e| proof_error(e.to_string()))?,
+            None => Vec::new(),
+        };
+        if authorities.is_empty() {
+            return Err(proof_error("no authorities".to_string()));
+        }
+        let emergency_finalizer = match values.remove(&emergency_finalizer_key).flatten() {
+            Some(encoded) => Some(
+                AuthorityId::decode_all(&mut encoded.as_slice())
+                    .map_err(|e| proof_error(e.to_string()))?,
+            ),
+            None => None,
+        };
+        Ok(SessionAuthorities {
+            session: next_session,
+            authorities,
+            emergency_finalizer,
+        })
+    }
+}
+
+/// Proofs of finality of a number of blocks, together with the authority changes leading to the
+/// sessions they belong to. Can be verified without access to a node, starting from a trusted set
+/// of authorities.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityProofBundle {
+    pub session_period: u32,
+    /// Consecutive authority changes, starting with the last block of the trusted session.
+    pub authority_changes: Vec<AuthorityChange>,
+    pub blocks: Vec<BlockFinalityProof>,
+}
+
+impl FinalityProofBundle {
+    pub fn new(session_period: u32) -> Self {
+        FinalityProofBundle {
+            session_period,
+            authority_changes: Vec::new(),
+            blocks: Vec::new(),
+        }
+    }
+
+    /// The session the given block belongs to.
+    pub fn session_of(&self, number: BlockNumber) -> SessionIndex {
+        number / self.session_period
+    }
+
+    /// The last block of the given session.
+    pub fn last_block_of_session(&self, session: SessionIndex) -> BlockNumber {
+        (session + 1) * self.session_period - 1
+    }
+
+    /// The sessions the blocks in the bundle belong to, each one listed once.
+    pub fn required_sessions(&self) -> Vec<SessionIndex> {
+        let mut sessions: Vec<_> = self
+            .blocks
+            .iter()
+            .map(|proof| self.session_of(*proof.header.number()))
+            .collect();
+        sessions.sort();
+        sessions.dedup();
+        sessions
+    }
+
+    /// Verifies the justifications of the blocks, starting from the `trusted` authorities and
+    /// following the authority changes, each justified by the authorities of the session it ends.
+    /// Returns the numbers and hashes of the blocks proven to be finalized.
+    pub fn verify(
+        &self,
+        trusted: &SessionAuthorities,
+    ) -> Result<Vec<(BlockNumber, Hash)>, FinalityProofError> {
+        let mut sessions = HashMap::new();
+        let mut current = trusted.clone();
+        for change in &self.authority_changes {
+            let expected = self.last_block_of_session(current.session);
+            let got = *change.last_block.header.number();
+            if got != expected {
+                return Err(FinalityProofError::UnexpectedAuthorityChange { expected, got });
+            }
+            current.verify(&change.last_block)?;
+            let next = change.next_authorities(current.session + 1)?;
+            sessions.insert(current.session, current);
+            current = next;
+        }
+        sessions.insert(current.session, current);
+
+        self.blocks
+            .iter()
+            .map(|proof| {
+                let number = *proof.header.number();
+                let session = self.session_of(number);
+                sessions
+                    .get(&session)
+                    .ok_or(FinalityProofError::MissingAuthorities(number, session))?
+                    .verify(proof)?;
+                Ok((number, proof.header.hash()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use aleph_bft_crypto::{NodeCount, NodeIndex};
+    use codec::{Decode, Encode};
+    use primitives::{
+        next_authority_data_storage_keys, AuthorityPair, BlakeTwo256, BlockNumber, Hash, Header,
+        HeaderT, SessionAuthorityData, SessionIndex,
+    };
+    use sp_core::{storage::StateVersion, Pair};
+    use sp_state_machine::{prove_read, InMemoryBackend};
+
+    use super::{
+        AlephJustification, AuthorityChange, BlockFinalityProof, FinalityProofBundle,
+        FinalityProofError, SessionAuthorities, SignatureSet,
+    };
+
+    const SESSION_PERIOD: u32 = 30;
+    const COMMITTEE_SIZE: usize = 4;
+
+    fn committee(session: SessionIndex) -> (Vec<AuthorityPair>, SessionAuthorities) {
+        let pairs: Vec<_> = (0..COMMITTEE_SIZE)
+            .map(|i| AuthorityPair::from_seed(&[(session * 16 + i as u32) as u8; 32]))
+            .collect();
+        let authorities = pairs.iter().map(|pair| pair.public()).collect();
+        let authorities =
+            SessionAuthorities::new(session, SessionAuthorityData::new(authorities, None));
+        (pairs, authorities)
+    }
+
+    fn header(number: BlockNumber, state_root: Hash) -> Header {
+        Header::new(
+            number,
+            Default::default(),
+            state_root,
+            Default::default(),
+            Default::default(),
+        )
+    }
+
+    fn proof(header: Header, pairs: &[AuthorityPair]) -> BlockFinalityProof {
+        let message = header.hash().encode();
+        let signatures = pairs.iter().enumerate().fold(
+            SignatureSet::with_size(NodeCount(COMMITTEE_SIZE)),
+            |signatures, (index, pair)| {
+                signatures.add_signature(&pair.sign(&message).into(), NodeIndex(index))
+            },
+        );
+        let justification = AlephJustification::CommitteeMultisignature(signatures).encode();
+        // Versioned encoding, version 3.
+        let mut encoded = 3u16.encode();
+        (justification.len() as u16).encode_to(&mut encoded);
+        encoded.extend(justification);
+        BlockFinalityProof {
+            header,
+            justification: encoded.into(),
+        }
+    }
+
+    /// The last block of `session` signed by `pairs`, proving that `next` are the authorities of
+    /// the following session.
+    fn change(
+        session: SessionIndex,
+        pairs: &[AuthorityPair],
+        next: &SessionAuthorities,
+    ) -> AuthorityChange {
+        let [authorities_key, emergency_finalizer_key] = next_authority_data_storage_keys();
+        let mut state = BTreeMap::new();
+        state.insert(authorities_key, next.authorities.encode());
+        if let Some(emergency_finalizer) = &next.emergency_finalizer {
+            state.insert(emergency_finalizer_key, emergency_finalizer.encode());
+        }
+        let backend = InMemoryBackend::<BlakeTwo256>::from((state, StateVersion::V1));
+        let header = header((session + 1) * SESSION_PERIOD - 1, *backend.root());
+        let next_authorities_proof = prove_read(backend, next_authority_data_storage_keys())
+            .expect("the keys should be provable")
+            .into_iter_nodes()
+            .map(Into::into)
+            .collect();
+        AuthorityChange {
+            last_block: proof(header, pairs),
+            next_authorities_proof,
+        }
+    }
+
+    fn block(number: BlockNumber, pairs: &[AuthorityPair]) -> BlockFinalityProof {
+        proof(header(number, Default::default()), pairs)
+    }
+
+    fn numbers(verified: Vec<(BlockNumber, Hash)>) -> Vec<BlockNumber> {
+        verified.into_iter().map(|(number, _)| number).collect()
+    }
+
+    #[test]
+    fn verifies_blocks_of_trusted_session() {
+        let (pairs, trusted) = committee(1);
+        let mut bundle = FinalityProofBundle::new(SESSION_PERIOD);
+        bundle.blocks.push(block(SESSION_PERIOD, &pairs));
+        bundle.blocks.push(block(2 * SESSION_PERIOD - 1, &pairs));
+        assert_eq!(bundle.required_sessions(), vec![1]);
+        let verified = bundle
+            .verify(&trusted)
+            .expect("the bundle should be correct");
+        assert_eq!(
+            numbers(verified),
+            vec![SESSION_PERIOD, 2 * SESSION_PERIOD - 1]
+        );
+    }
+
+    #[test]
+    fn verifies_blocks_after_authority_changes() {
+        let committees: Vec<_> = (0..3).map(committee).collect();
+        let mut bundle = FinalityProofBundle::new(SESSION_PERIOD);
+        for session in 0..2 {
+            let (pairs, _) = &committees[session];
+            let (_, next) = &committees[session + 1];
+            bundle
+                .authority_changes
+                .push(change(session as u32, pairs, next));
+        }
+        bundle.blocks.push(block(1, &committees[0].0));
+        bundle
+            .blocks
+            .push(block(2 * SESSION_PERIOD + 1, &committees[2].0));
+        let verified = bundle
+            .verify(&committees[0].1)
+            .expect("the bundle should be correct");
+        assert_eq!(numbers(verified), vec![1, 2 * SESSION_PERIOD + 1]);
+    }
+
+    #[test]
+    fn survives_encoding() {
+        let (pairs, trusted) = committee(0);
+        let (_, next) = committee(1);
+        let mut bundle = FinalityProofBundle::new(SESSION_PERIOD);
+        bundle.authority_changes.push(change(0, &pairs, &next));
+        bundle.blocks.push(block(SESSION_PERIOD, &committee(1).0));
+        let decoded = FinalityProofBundle::decode(&mut bundle.encode().as_slice())
+            .expect("the bundle should decode");
+        assert_eq!(decoded, bundle);
+        assert!(decoded.verify(&trusted).is_ok());
+    }
+
+    #[test]
+    fn rejects_bundle_forged_for_other_authorities() {
+        let (forged_pairs, _) = committee(1);
+        let (_, trusted) = committee(0);
+        let (_, forged) = committee(2);
+        let mut bundle = FinalityProofBundle::new(SESSION_PERIOD);
+        bundle
+            .authority_changes
+            .push(change(0, &forged_pairs, &forged));
+        bundle.blocks.push(block(SESSION_PERIOD, &committee(2).0));
+        match bundle.verify(&trusted) {
+            Err(FinalityProofError::BadMultisignature(number)) => {
+                assert_eq!(number, SESSION_PERIOD - 1)
+            }
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_insufficient_signatures() {
+        let (pairs, trusted) = committee(0);
+        let mut bundle = FinalityProofBundle::new(SESSION_PERIOD);
+        bundle.blocks.push(block(1, &pairs[..2]));
+        match bundle.verify(&trusted) {
+            Err(FinalityProofError::BadMultisignature(number)) => assert_eq!(number, 1),
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_authorities_proof_for_other_state() {
+        let (pairs, trusted) = committee(0);
+        let (_, next) = committee(1);
+        let mut other_state = change(0, &pairs, &trusted);
+        other_state.next_authorities_proof = change(0, &pairs, &next).next_authorities_proof;
+        let mut bundle = FinalityProofBundle::new(SESSION_PERIOD);
+        bundle.authority_changes.push(other_state);
+        match bundle.verify(&trusted) {
+            Err(FinalityProofError::AuthoritiesProof(number, _)) => {
+                assert_eq!(number, SESSION_PERIOD - 1)
+            }
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_authority_change_in_wrong_block() {
+        let (pairs, trusted) = committee(0);
+        let (_, next) = committee(1);
+        let mut bundle = FinalityProofBundle::new(SESSION_PERIOD);
+        bundle.authority_changes.push(change(1, &pairs, &next));
+        match bundle.verify(&trusted) {
+            Err(FinalityProofError::UnexpectedAuthorityChange { expected, got }) => {
+                assert_eq!(expected, SESSION_PERIOD - 1);
+                assert_eq!(got, 2 * SESSION_PERIOD - 1);
+            }
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_blocks_without_authorities() {
+        let (pairs, trusted) = committee(0);
+        let mut bundle = FinalityProofBundle::new(SESSION_PERIOD);
+        bundle.blocks.push(block(SESSION_PERIOD, &pairs));
+        match bundle.verify(&trusted) {
+            Err(FinalityProofError::MissingAuthorities(number, session)) => {
+                assert_eq!(number, SESSION_PERIOD);
+                assert_eq!(session, 1);
+            }
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+    }
+}
@@ -38,8 +38,13 @@ mod aleph_zero;
 
 mod connections;
 pub mod contract;
+/// Proofs of finality of blocks, verifiable without access to a node.
+pub mod finality_proof;
+/// Preparing, signing and submitting transactions in separate steps, e.g. with an offline signer.
+pub mod offline;
 /// API for pallets.
 pub mod pallets;
+mod reconnecting;
 mod runtime_types;
 /// Block / session / era API.
 pub mod utility;
@@ -66,8 +71,12 @@ pub type TxHash = H256;
 pub type SubxtClient = OnlineClient<AlephConfig>;
 
 pub use connections::{
-    AsConnection, AsSigned, Connection, ConnectionApi, RootConnection, SignedConnection,
-    SignedConnectionApi, SignedConnectionApiExt, SubmittableExtrinsic, SudoCall, TxInfo,
+    AsConnection, AsSigned, Connection, ConnectionApi, QueryError, RootConnection,
+    SignedConnection, SignedConnectionApi, SignedConnectionApiExt, SubmittableExtrinsic, SudoCall,
+    TxInfo,
+};
+pub use reconnecting::{
+    ConnectionHealth, ReconnectConfig, ReconnectingConnection, ReconnectingSignedConnection,
 };
 
 /// An alias for a configuration of live chain, e.g. block index type, hash type.
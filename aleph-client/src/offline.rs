@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use codec::Decode;
+use log::info;
+use primitives::{Balance, BlockNumber, Nonce};
+use serde::{Deserialize, Serialize};
+use subxt::{
+    config::extrinsic_params::Era,
+    ext::{
+        scale_value::scale::decode_as_type,
+        sp_core::{blake2_256, Bytes},
+    },
+    rpc::types::RuntimeVersion,
+    rpc_params,
+    tx::{Signer, SubmittableExtrinsic as SubxtSubmittable, TxPayload},
+    Metadata, OfflineClient,
+};
+
+use crate::{
+    connections::TxInfo, pallets::system::SystemApi, utility::BlocksApi, AccountId, AlephConfig,
+    AsConnection, BlockHash, KeyPair, ParamsBuilder, SubmittableExtrinsic, TxHash, TxStatus,
+};
+
+/// Validity period of a transaction, anchored at a known block.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Mortality {
+    /// Number of blocks the transaction stays valid for, rounded by the runtime to a power of two.
+    pub period: u64,
+    /// Number of the block the period starts at.
+    pub block_number: BlockNumber,
+    /// Hash of the block the period starts at.
+    pub block_hash: BlockHash,
+}
+
+/// A transaction together with everything that is needed to sign it without access to the chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnsignedPayload {
+    /// Account expected to sign the transaction.
+    pub signer: AccountId,
+    /// Human readable `Pallet.call` name. It has to match the call data.
+    pub call_name: String,
+    /// SCALE encoded call.
+    pub call_data: Bytes,
+    /// Nonce of the signer account.
+    pub nonce: Nonce,
+    /// Tip for the block author.
+    pub tip: Balance,
+    /// Hash of the genesis block of the chain the transaction is meant for.
+    pub genesis_hash: BlockHash,
+    /// Runtime spec version the transaction is meant for.
+    pub spec_version: u32,
+    /// Runtime transaction version the transaction is meant for.
+    pub transaction_version: u32,
+    /// Validity period of the transaction, `None` for an immortal one.
+    pub mortality: Option<Mortality>,
+    /// SCALE encoded metadata of the runtime, used to decode the call data before signing.
+    pub metadata: Bytes,
+}
+
+/// A signed extrinsic ready to be broadcast by any connection.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedPayload {
+    /// Account which signed the extrinsic.
+    pub signer: AccountId,
+    /// Hash of the extrinsic.
+    pub tx_hash: TxHash,
+    /// SCALE encoded extrinsic.
+    pub extrinsic: Bytes,
+}
+
+/// A call that is already SCALE encoded.
+struct EncodedCall<'a>(&'a [u8]);
+
+impl TxPayload for EncodedCall<'_> {
+    fn encode_call_data_to(
+        &self,
+        _metadata: &Metadata,
+        out: &mut Vec<u8>,
+    ) -> Result<(), subxt::Error> {
+        out.extend_from_slice(self.0);
+        Ok(())
+    }
+}
+
+fn decode_metadata(metadata: &[u8]) -> anyhow::Result<Metadata> {
+    Metadata::decode(&mut &metadata[..]).map_err(|e| anyhow!("Invalid runtime metadata: {}", e))
+}
+
+/// Decodes the call against the metadata. Returns its `Pallet.call` name and its arguments in a
+/// human readable form.
+fn decode_call(metadata: &Metadata, call_data: &[u8]) -> anyhow::Result<(String, String)> {
+    let (pallet_index, call_index, mut data) = match call_data {
+        [pallet_index, call_index, data @ ..] => (*pallet_index, *call_index, data),
+        _ => return Err(anyhow!("Call data is too short")),
+    };
+    let pallet = metadata
+        .pallet_by_index(pallet_index)
+        .ok_or_else(|| anyhow!("Unknown pallet with index {}", pallet_index))?;
+    let call = pallet.call_variant_by_index(call_index).ok_or_else(|| {
+        anyhow!(
+            "Unknown call with index {} in {}",
+            call_index,
+            pallet.name()
+        )
+    })?;
+    let name = format!("{}.{}", pallet.name(), call.name);
+
+    let mut args = Vec::with_capacity(call.fields.len());
+    for field in &call.fields {
+        let value = decode_as_type(&mut data, field.ty.id, metadata.types())
+            .map_err(|e| anyhow!("Failed to decode the arguments of {}: {:?}", name, e))?;
+        args.push(match &field.name {
+            Some(field_name) => format!("{field_name}: {value}"),
+            None => value.to_string(),
+        });
+    }
+    if !data.is_empty() {
+        return Err(anyhow!(
+            "Call data of {} has {} unexpected trailing bytes",
+            name,
+            data.len()
+        ));
+    }
+
+    Ok((name, args.join(", ")))
+}
+
+impl UnsignedPayload {
+    /// Fetches the nonce of `signer` and the chain parameters needed to sign `tx` offline.
+    /// * `connection` - connection to a node of the target chain
+    /// * `signer` - account which is going to sign the transaction
+    /// * `tx` - transaction payload, the same as passed to [`crate::SignedConnectionApi::send_tx`]
+    /// * `mortality` - number of blocks the transaction stays valid for counting from the last
+    ///   finalized block, `None` for an immortal transaction
+    /// * `tip` - tip for the block author
+    pub async fn prepare<C: AsConnection + Sync, Call: TxPayload>(
+        connection: &C,
+        signer: AccountId,
+        tx: Call,
+        mortality: Option<u64>,
+        tip: Balance,
+    ) -> anyhow::Result<Self> {
+        let client = connection.as_connection().as_client();
+        let metadata: Bytes = client
+            .rpc()
+            .request("state_getMetadata", rpc_params![])
+            .await?;
+        let decoded_metadata = decode_metadata(&metadata)?;
+        let call_data = tx.encode_call_data(&decoded_metadata)?;
+        let (call_name, _) = decode_call(&decoded_metadata, &call_data)?;
+        let nonce = connection.account_nonce(&signer).await?;
+        let runtime_version = client.runtime_version();
+
+        let mortality = match mortality {
+            Some(period) => {
+                let block_hash = connection.get_finalized_block_hash().await?;
+                let block_number = connection
+                    .get_block_number(block_hash)
+                    .await?
+                    .ok_or_else(|| anyhow!("The finalized block is unknown to the node"))?;
+                Some(Mortality {
+                    period,
+                    block_number,
+                    block_hash,
+                })
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            signer,
+            call_name,
+            call_data: call_data.into(),
+            nonce,
+            tip,
+            genesis_hash: client.genesis_hash(),
+            spec_version: runtime_version.spec_version,
+            transaction_version: runtime_version.transaction_version,
+            mortality,
+            metadata,
+        })
+    }
+
+    /// Decodes the call data against the metadata, e.g. to show it to the signer. Returns the
+    /// call as `Pallet.call(arguments)`.
+    /// Fails if the call data cannot be decoded, or it does not match `call_name`.
+    pub fn decoded_call(&self) -> anyhow::Result<String> {
+        let metadata = decode_metadata(&self.metadata)?;
+        let (name, args) = decode_call(&metadata, &self.call_data)?;
+        if name != self.call_name {
+            return Err(anyhow!(
+                "Payload claims to be {}, but its call data is {}",
+                self.call_name,
+                name
+            ));
+        }
+        Ok(format!("{name}({args})"))
+    }
+
+    fn extrinsic_params(&self) -> ParamsBuilder {
+        let params = ParamsBuilder::new().tip(self.tip);
+        match self.mortality {
+            Some(Mortality {
+                period,
+                block_number,
+                block_hash,
+            }) => params.era(Era::mortal(period, block_number.into()), block_hash),
+            None => params,
+        }
+    }
+
+    /// Signs the payload with `signer`. Does not need access to the chain.
+    /// Fails if `signer` does not match the account the payload was prepared for, or if the call
+    /// data does not match `call_name`, see [`UnsignedPayload::decoded_call`].
+    pub fn sign(&self, signer: &KeyPair) -> anyhow::Result<SignedPayload> {
+        if signer.account_id() != &self.signer {
+            return Err(anyhow!(
+                "Payload was prepared for {}, but the signer is {}",
+                self.signer,
+                signer.account_id()
+            ));
+        }
+        self.sign_with(&signer.inner)
+    }
+
+    fn sign_with<S: Signer<AlephConfig>>(&self, signer: &S) -> anyhow::Result<SignedPayload> {
+        let call = self.decoded_call()?;
+        info!(target: "aleph-client", "signing extrinsic {} with nonce {} on chain with genesis {:?}", call, self.nonce, self.genesis_hash);
+
+        let client = OfflineClient::<AlephConfig>::new(
+            self.genesis_hash,
+            RuntimeVersion {
+                spec_version: self.spec_version,
+                transaction_version: self.transaction_version,
+                other: HashMap::new(),
+            },
+            decode_metadata(&self.metadata)?,
+        );
+        let extrinsic = client
+            .tx()
+            .create_signed_with_nonce(
+                &EncodedCall(&self.call_data),
+                signer,
+                self.nonce.into(),
+                self.extrinsic_params(),
+            )?
+            .into_encoded();
+
+        Ok(SignedPayload {
+            signer: self.signer.clone(),
+            tx_hash: blake2_256(&extrinsic).into(),
+            extrinsic: extrinsic.into(),
+        })
+    }
+}
+
+impl SignedPayload {
+    /// Broadcasts the extrinsic through `connection`. It waits for a given tx `status`.
+    pub async fn submit<C: AsConnection + Sync>(
+        &self,
+        connection: &C,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let submittable: SubmittableExtrinsic = SubxtSubmittable::from_bytes(
+            connection.as_connection().as_client().clone(),
+            self.extrinsic.to_vec(),
+        )
+        .into();
+        let info = submittable.submit(status).await?;
+        info!(target: "aleph-client", "tx with hash {:?} included in block {:?}", info.tx_hash, info.block_hash);
+
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use codec::Encode;
+    use frame_metadata::{
+        v15::{
+            CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletCallMetadata, PalletMetadata,
+            RuntimeMetadataV15,
+        },
+        RuntimeMetadataPrefixed,
+    };
+    use scale_info::{meta_type, MetaType, TypeInfo};
+    use subxt::{
+        config::extrinsic_params::Era,
+        dynamic::{tx, Value},
+        ext::sp_core::{blake2_256, ed25519, Bytes, Pair},
+        rpc::types::RuntimeVersion,
+        tx::{PairSigner, TxPayload},
+        OfflineClient,
+    };
+
+    use super::{decode_metadata, Mortality, UnsignedPayload};
+    use crate::{keypair_from_string, AlephConfig, BlockHash, ParamsBuilder, TxHash};
+
+    #[allow(dead_code, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum SystemCall {
+        #[codec(index = 1)]
+        remark { remark: Vec<u8> },
+    }
+
+    #[allow(dead_code, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum BalancesCall {
+        #[codec(index = 3)]
+        transfer_keep_alive {
+            dest: [u8; 32],
+            #[codec(compact)]
+            value: u128,
+        },
+    }
+
+    #[allow(dead_code)]
+    #[derive(Encode, TypeInfo)]
+    enum RuntimeCall {
+        #[codec(index = 0)]
+        System(SystemCall),
+        #[codec(index = 5)]
+        Balances(BalancesCall),
+    }
+
+    fn pallet(name: &'static str, index: u8, calls: MetaType) -> PalletMetadata {
+        PalletMetadata {
+            name,
+            storage: None,
+            calls: Some(PalletCallMetadata { ty: calls }),
+            event: None,
+            constants: vec![],
+            error: None,
+            index,
+            docs: vec![],
+        }
+    }
+
+    fn metadata() -> Bytes {
+        let metadata = RuntimeMetadataV15::new(
+            vec![
+                pallet("System", 0, meta_type::<SystemCall>()),
+                pallet("Balances", 5, meta_type::<BalancesCall>()),
+            ],
+            ExtrinsicMetadata {
+                version: 4,
+                address_ty: meta_type::<()>(),
+                call_ty: meta_type::<RuntimeCall>(),
+                signature_ty: meta_type::<()>(),
+                extra_ty: meta_type::<()>(),
+                signed_extensions: vec![],
+            },
+            meta_type::<()>(),
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<RuntimeCall>(),
+                event_enum_ty: meta_type::<()>(),
+                error_enum_ty: meta_type::<()>(),
+            },
+            CustomMetadata {
+                map: BTreeMap::new(),
+            },
+        );
+        RuntimeMetadataPrefixed::from(metadata).encode().into()
+    }
+
+    fn transfer() -> (RuntimeCall, impl TxPayload) {
+        let call = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+            dest: [7; 32],
+            value: 1000,
+        });
+        let payload = tx(
+            "Balances",
+            "transfer_keep_alive",
+            vec![Value::from_bytes([7; 32]), Value::u128(1000)],
+        );
+        (call, payload)
+    }
+
+    fn long_remark() -> (RuntimeCall, impl TxPayload) {
+        let call = RuntimeCall::System(SystemCall::remark {
+            remark: vec![7; 300],
+        });
+        let payload = tx("System", "remark", vec![Value::from_bytes(vec![7; 300])]);
+        (call, payload)
+    }
+
+    fn payload(call_name: &str, call: &RuntimeCall) -> UnsignedPayload {
+        UnsignedPayload {
+            signer: keypair_from_string("//Alice").account_id().clone(),
+            call_name: call_name.to_string(),
+            call_data: call.encode().into(),
+            nonce: 7,
+            tip: 5,
+            genesis_hash: BlockHash::repeat_byte(1),
+            spec_version: 80,
+            transaction_version: 18,
+            mortality: Some(Mortality {
+                period: 64,
+                block_number: 1000,
+                block_hash: BlockHash::repeat_byte(2),
+            }),
+            metadata: metadata(),
+        }
+    }
+
+    /// Ed25519 signatures are deterministic, unlike sr25519 ones, so extrinsics signed with it
+    /// can be compared byte by byte.
+    fn signer() -> PairSigner<AlephConfig, ed25519::Pair> {
+        PairSigner::new(ed25519::Pair::from_string("//Alice", None).expect("seed is valid"))
+    }
+
+    /// Signs the call the way subxt does, without going through the payload.
+    fn signed_by_subxt(call: impl TxPayload) -> Vec<u8> {
+        let client = OfflineClient::<AlephConfig>::new(
+            BlockHash::repeat_byte(1),
+            RuntimeVersion {
+                spec_version: 80,
+                transaction_version: 18,
+                other: HashMap::new(),
+            },
+            decode_metadata(&metadata()).expect("metadata is valid"),
+        );
+        let params = ParamsBuilder::new()
+            .tip(5)
+            .era(Era::mortal(64, 1000), BlockHash::repeat_byte(2));
+        client
+            .tx()
+            .create_signed_with_nonce(&call, &signer(), 7, params)
+            .expect("call is valid")
+            .into_encoded()
+    }
+
+    #[test]
+    fn signs_like_subxt() {
+        let (call, subxt_call) = transfer();
+        let signed = payload("Balances.transfer_keep_alive", &call)
+            .sign_with(&signer())
+            .expect("payload is valid");
+
+        let expected = signed_by_subxt(subxt_call);
+        assert_eq!(signed.extrinsic.0, expected);
+        assert_eq!(signed.tx_hash, TxHash::from(blake2_256(&expected)));
+    }
+
+    #[test]
+    fn signs_long_payload_like_subxt() {
+        let (call, subxt_call) = long_remark();
+        let signed = payload("System.remark", &call)
+            .sign_with(&signer())
+            .expect("payload is valid");
+
+        assert_eq!(signed.extrinsic.0, signed_by_subxt(subxt_call));
+    }
+
+    #[test]
+    fn decodes_call() {
+        let (call, _) = transfer();
+        let decoded = payload("Balances.transfer_keep_alive", &call)
+            .decoded_call()
+            .expect("payload is valid");
+
+        assert!(decoded.starts_with("Balances.transfer_keep_alive(dest: "));
+        assert!(decoded.ends_with("value: 1000)"));
+    }
+
+    #[test]
+    fn refuses_to_sign_call_not_matching_name() {
+        let (call, _) = transfer();
+        let alice = keypair_from_string("//Alice");
+        assert!(payload("Balances.transfer_all", &call)
+            .sign(&alice)
+            .is_err());
+        assert!(payload("System.remark", &call).sign(&alice).is_err());
+    }
+
+    #[test]
+    fn refuses_to_sign_undecodable_call() {
+        let (call, _) = transfer();
+        let alice = keypair_from_string("//Alice");
+        let mut payload = payload("Balances.transfer_keep_alive", &call);
+        payload.call_data.0.push(0);
+        assert!(payload.sign(&alice).is_err());
+        payload.call_data.0[0] = 17;
+        assert!(payload.sign(&alice).is_err());
+    }
+
+    #[test]
+    fn refuses_to_sign_for_other_account() {
+        let (call, _) = transfer();
+        let bob = keypair_from_string("//Bob");
+        assert!(payload("Balances.transfer_keep_alive", &call)
+            .sign(&bob)
+            .is_err());
+    }
+}
@@ -12,7 +12,8 @@ use crate::{
     sp_core::Bytes,
     AccountId, AlephKeyPair, BlockHash, BlockNumber,
     Call::Aleph,
-    ConnectionApi, Pair, RootConnection, SessionIndex, SudoCall, TxStatus, Version,
+    ConnectionApi, Pair, QueryError, RootConnection, SessionAuthorityData, SessionIndex, SudoCall,
+    TxStatus, Version,
 };
 
 // TODO replace docs with link to pallet aleph docs, once they are published
@@ -21,10 +22,24 @@ use crate::{
 pub trait AlephApi {
     /// Gets the current finality version.
     async fn finality_version(&self, at: Option<BlockHash>) -> Version;
+    /// Fallible version of [`Self::finality_version`].
+    async fn try_finality_version(&self, at: Option<BlockHash>) -> Result<Version, QueryError>;
     /// Gets the finality version for the next session.
     async fn next_session_finality_version(&self, at: Option<BlockHash>) -> Version;
+    /// Fallible version of [`Self::next_session_finality_version`].
+    async fn try_next_session_finality_version(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Version, QueryError>;
     /// Gets the emergency finalizer
     async fn emergency_finalizer(&self, at: Option<BlockHash>) -> Option<[u8; 32]>;
+    /// Fallible version of [`Self::emergency_finalizer`].
+    async fn try_emergency_finalizer(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Option<[u8; 32]>, QueryError>;
+    /// Gets the authorities able to finalize blocks in the session of the block `at`.
+    async fn authority_data(&self, at: Option<BlockHash>) -> anyhow::Result<SessionAuthorityData>;
 }
 
 /// Pallet aleph API that requires sudo.
@@ -72,25 +87,56 @@ pub trait AlephRpc {
 #[async_trait::async_trait]
 impl<C: ConnectionApi> AlephApi for C {
     async fn finality_version(&self, at: Option<BlockHash>) -> Version {
+        self.try_finality_version(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_finality_version(&self, at: Option<BlockHash>) -> Result<Version, QueryError> {
         let addrs = api::storage().aleph().finality_version();
 
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 
     async fn next_session_finality_version(&self, hash: Option<BlockHash>) -> Version {
+        self.try_next_session_finality_version(hash).await.unwrap()
+    }
+
+    async fn try_next_session_finality_version(
+        &self,
+        hash: Option<BlockHash>,
+    ) -> Result<Version, QueryError> {
         let method = "state_call";
         let api_method = "AlephSessionApi_next_session_finality_version";
         let params = rpc_params![api_method, "0x", hash];
 
-        self.rpc_call(method.to_string(), params).await.unwrap()
+        self.try_rpc_call(method.to_string(), params).await
     }
 
     async fn emergency_finalizer(&self, at: Option<BlockHash>) -> Option<[u8; 32]> {
+        self.try_emergency_finalizer(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_emergency_finalizer(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Option<[u8; 32]>, QueryError> {
         let addrs = api::storage().aleph().emergency_finalizer();
 
-        self.get_storage_entry_maybe(&addrs, at)
-            .await
-            .map(|public| public.0 .0)
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
+            .map(|public| public.0 .0))
+    }
+
+    async fn authority_data(&self, at: Option<BlockHash>) -> anyhow::Result<SessionAuthorityData> {
+        let method = "state_call";
+        let api_method = "AlephSessionApi_authority_data";
+        let params = rpc_params![api_method, "0x", at];
+
+        self.rpc_call(method.to_string(), params).await
     }
 }
 
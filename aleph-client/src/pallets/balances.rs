@@ -7,7 +7,7 @@ use crate::{
     pallets::utility::UtilityApi,
     AccountId, AsConnection, Balance, BlockHash,
     Call::Balances,
-    ConnectionApi, ParamsBuilder, SignedConnectionApi, TxStatus,
+    ConnectionApi, ParamsBuilder, QueryError, SignedConnectionApi, TxStatus,
 };
 
 /// Pallet balances read-only API.
@@ -22,6 +22,13 @@ pub trait BalanceApi {
         at: Option<BlockHash>,
     ) -> Vec<BalanceLock<Balance>>;
 
+    /// Fallible version of [`Self::locks_for_account`].
+    async fn try_locks_for_account(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<BalanceLock<Balance>>, QueryError>;
+
     /// API for [`locks`](https://paritytech.github.io/substrate/master/pallet_balances/pallet/struct.Pallet.html#method.locks) call.
     /// * `accounts` - a list of accounts to query locked balance for
     /// * `at` - optional hash of a block to query state from
@@ -31,9 +38,19 @@ pub trait BalanceApi {
         at: Option<BlockHash>,
     ) -> Vec<Vec<BalanceLock<Balance>>>;
 
+    /// Fallible version of [`Self::locks`].
+    async fn try_locks(
+        &self,
+        accounts: &[AccountId],
+        at: Option<BlockHash>,
+    ) -> Result<Vec<Vec<BalanceLock<Balance>>>, QueryError>;
+
     /// Returns [`total_issuance`](https://paritytech.github.io/substrate/master/pallet_balances/pallet/type.TotalIssuance.html).
     async fn total_issuance(&self, at: Option<BlockHash>) -> Balance;
 
+    /// Fallible version of [`Self::total_issuance`].
+    async fn try_total_issuance(&self, at: Option<BlockHash>) -> Result<Balance, QueryError>;
+
     /// Returns [`existential_deposit`](https://paritytech.github.io/substrate/master/pallet_balances/index.html#terminology).
     async fn existential_deposit(&self) -> anyhow::Result<Balance>;
 }
@@ -92,9 +109,19 @@ impl<C: ConnectionApi + AsConnection> BalanceApi for C {
         account: AccountId,
         at: Option<BlockHash>,
     ) -> Vec<BalanceLock<Balance>> {
+        self.try_locks_for_account(account, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_locks_for_account(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<BalanceLock<Balance>>, QueryError> {
         let address = aleph_zero::api::storage().balances().locks(Static(account));
 
-        self.get_storage_entry(&address, at).await.0
+        Ok(self.try_get_storage_entry(&address, at).await?.0)
     }
 
     async fn locks(
@@ -102,19 +129,35 @@ impl<C: ConnectionApi + AsConnection> BalanceApi for C {
         accounts: &[AccountId],
         at: Option<BlockHash>,
     ) -> Vec<Vec<BalanceLock<Balance>>> {
+        self.try_locks(accounts, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_locks(
+        &self,
+        accounts: &[AccountId],
+        at: Option<BlockHash>,
+    ) -> Result<Vec<Vec<BalanceLock<Balance>>>, QueryError> {
         let mut locks = vec![];
 
         for account in accounts {
-            locks.push(self.locks_for_account(account.clone(), at).await);
+            locks.push(self.try_locks_for_account(account.clone(), at).await?);
         }
 
-        locks
+        Ok(locks)
     }
 
     async fn total_issuance(&self, at: Option<BlockHash>) -> Balance {
+        self.try_total_issuance(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_total_issuance(&self, at: Option<BlockHash>) -> Result<Balance, QueryError> {
         let address = api::storage().balances().total_issuance();
 
-        self.get_storage_entry(&address, at).await
+        self.try_get_storage_entry(&address, at).await
     }
 
     async fn existential_deposit(&self) -> anyhow::Result<Balance> {
@@ -1,7 +1,11 @@
+use anyhow::anyhow;
 use codec::{DecodeAll, Encode};
 use primitives::{SessionCommittee, SessionValidatorError};
 use subxt::{
-    ext::{sp_core::Bytes, sp_runtime::Perquintill},
+    ext::{
+        sp_core::{blake2_64, Bytes},
+        sp_runtime::Perquintill,
+    },
     rpc_params,
     utils::Static,
 };
@@ -13,8 +17,8 @@ use crate::{
         ban_from_committee, set_ban_config, set_lenient_threshold,
     },
     primitives::{ProductionBanConfig, BanInfo, BanReason},
-    AccountId, AsConnection, BlockHash, ConnectionApi, EraIndex, RootConnection, SessionCount,
-    SessionIndex, SudoCall, TxInfo, TxStatus,
+    AccountId, AsConnection, BlockHash, ConnectionApi, EraIndex, QueryError, RootConnection,
+    SessionCount, SessionIndex, SudoCall, TxInfo, TxStatus,
 };
 
 /// Pallet CommitteeManagement read-only api.
@@ -24,6 +28,12 @@ pub trait CommitteeManagementApi {
     /// * `at` - optional hash of a block to query state from
     async fn get_ban_config(&self, at: Option<BlockHash>) -> ProductionBanConfig;
 
+    /// Fallible version of [`Self::get_ban_config`].
+    async fn try_get_ban_config(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<ProductionBanConfig, QueryError>;
+
     /// Returns `committee-management.session_validator_block_count` of a given validator.
     /// * `validator` - a validator stash account id
     /// * `at` - optional hash of a block to query state from
@@ -33,6 +43,13 @@ pub trait CommitteeManagementApi {
         at: Option<BlockHash>,
     ) -> Option<u32>;
 
+    /// Fallible version of [`Self::get_validator_block_count`].
+    async fn try_get_validator_block_count(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<u32>, QueryError>;
+
     /// Returns `committee-management.underperformed_validator_session_count` storage of a given validator.
     /// * `validator` - a validator stash account id
     /// * `at` - optional hash of a block to query state from
@@ -42,6 +59,13 @@ pub trait CommitteeManagementApi {
         at: Option<BlockHash>,
     ) -> Option<SessionCount>;
 
+    /// Fallible version of [`Self::get_underperformed_validator_session_count`].
+    async fn try_get_underperformed_validator_session_count(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<SessionCount>, QueryError>;
+
     /// Returns `committee-management.banned.reason` storage of a given validator.
     /// * `validator` - a validator stash account id
     /// * `at` - optional hash of a block to query state from
@@ -51,6 +75,13 @@ pub trait CommitteeManagementApi {
         at: Option<BlockHash>,
     ) -> Option<BanReason>;
 
+    /// Fallible version of [`Self::get_ban_reason_for_validator`].
+    async fn try_get_ban_reason_for_validator(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BanReason>, QueryError>;
+
     /// Returns `committee-management.banned` storage of a given validator.
     /// * `validator` - a validator stash account id
     /// * `at` - optional hash of a block to query state from
@@ -60,6 +91,23 @@ pub trait CommitteeManagementApi {
         at: Option<BlockHash>,
     ) -> Option<BanInfo>;
 
+    /// Fallible version of [`Self::get_ban_info_for_validator`].
+    async fn try_get_ban_info_for_validator(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BanInfo>, QueryError>;
+
+    /// Returns the most recent bans of a given validator, oldest first. Fails on runtimes
+    /// older than `AlephSessionApi` version 5, which do not keep the history.
+    /// * `validator` - a validator stash account id
+    /// * `at` - optional hash of a block to query state from
+    async fn get_ban_history(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> anyhow::Result<Vec<primitives::BanInfo>>;
+
     /// Returns `committee-management.session_period` const of the committee-management pallet.
     async fn get_session_period(&self) -> anyhow::Result<u32>;
 
@@ -74,6 +122,12 @@ pub trait CommitteeManagementApi {
 
     /// Returns `committee-management.lenient_threshold` for the current era.
     async fn get_lenient_threshold_percentage(&self, at: Option<BlockHash>) -> Option<Perquintill>;
+
+    /// Fallible version of [`Self::get_lenient_threshold_percentage`].
+    async fn try_get_lenient_threshold_percentage(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Option<Perquintill>, QueryError>;
 }
 
 /// any object that implements pallet committee-management api that requires sudo
@@ -113,12 +167,50 @@ pub trait CommitteeManagementSudoApi {
     ) -> anyhow::Result<TxInfo>;
 }
 
+/// The first version of `AlephSessionApi` with `ban_history`.
+const BAN_HISTORY_API_VERSION: u32 = 5;
+
+/// Returns the version of `AlephSessionApi` implemented by the runtime at the given block.
+async fn aleph_session_api_version<C: AsConnection + Sync>(
+    connection: &C,
+    at: Option<BlockHash>,
+) -> anyhow::Result<u32> {
+    let runtime_version = connection
+        .as_connection()
+        .as_client()
+        .rpc()
+        .runtime_version(at)
+        .await?;
+    let apis: Vec<(String, u32)> = serde_json::from_value(
+        runtime_version
+            .other
+            .get("apis")
+            .cloned()
+            .ok_or_else(|| anyhow!("Runtime version does not list the runtime apis"))?,
+    )?;
+    let api_id = format!("0x{}", hex::encode(blake2_64(b"AlephSessionApi")));
+
+    apis.into_iter()
+        .find(|(id, _)| *id == api_id)
+        .map(|(_, version)| version)
+        .ok_or_else(|| anyhow!("Runtime does not implement AlephSessionApi"))
+}
+
 #[async_trait::async_trait]
 impl<C: ConnectionApi + AsConnection> CommitteeManagementApi for C {
     async fn get_ban_config(&self, at: Option<BlockHash>) -> ProductionBanConfig {
+        self.try_get_ban_config(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_ban_config(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<ProductionBanConfig, QueryError> {
         let addrs = api::storage().committee_management().production_ban_config();
 
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 
     async fn get_validator_block_count(
@@ -126,11 +218,21 @@ impl<C: ConnectionApi + AsConnection> CommitteeManagementApi for C {
         validator: AccountId,
         at: Option<BlockHash>,
     ) -> Option<u32> {
+        self.try_get_validator_block_count(validator, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_validator_block_count(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<u32>, QueryError> {
         let addrs = api::storage()
             .committee_management()
             .session_validator_block_count(Static::from(validator));
 
-        self.get_storage_entry_maybe(&addrs, at).await
+        self.try_get_storage_entry_maybe(&addrs, at).await
     }
 
     async fn get_underperformed_validator_session_count(
@@ -138,11 +240,21 @@ impl<C: ConnectionApi + AsConnection> CommitteeManagementApi for C {
         validator: AccountId,
         at: Option<BlockHash>,
     ) -> Option<SessionCount> {
+        self.try_get_underperformed_validator_session_count(validator, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_underperformed_validator_session_count(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<SessionCount>, QueryError> {
         let addrs = api::storage()
             .committee_management()
             .underperformed_validator_session_count(Static::from(validator));
 
-        self.get_storage_entry_maybe(&addrs, at).await
+        self.try_get_storage_entry_maybe(&addrs, at).await
     }
 
     async fn get_ban_reason_for_validator(
@@ -150,13 +262,20 @@ impl<C: ConnectionApi + AsConnection> CommitteeManagementApi for C {
         validator: AccountId,
         at: Option<BlockHash>,
     ) -> Option<BanReason> {
-        let addrs = api::storage()
-            .committee_management()
-            .banned(Static::from(validator));
-
-        self.get_storage_entry_maybe(&addrs, at)
+        self.try_get_ban_reason_for_validator(validator, at)
             .await
-            .map(|x| x.reason)
+            .expect("Should access storage")
+    }
+
+    async fn try_get_ban_reason_for_validator(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BanReason>, QueryError> {
+        Ok(self
+            .try_get_ban_info_for_validator(validator, at)
+            .await?
+            .map(|x| x.reason))
     }
 
     async fn get_ban_info_for_validator(
@@ -164,11 +283,40 @@ impl<C: ConnectionApi + AsConnection> CommitteeManagementApi for C {
         validator: AccountId,
         at: Option<BlockHash>,
     ) -> Option<BanInfo> {
+        self.try_get_ban_info_for_validator(validator, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_ban_info_for_validator(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BanInfo>, QueryError> {
         let addrs = api::storage()
             .committee_management()
             .banned(Static::from(validator));
 
-        self.get_storage_entry_maybe(&addrs, at).await
+        self.try_get_storage_entry_maybe(&addrs, at).await
+    }
+
+    async fn get_ban_history(
+        &self,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> anyhow::Result<Vec<primitives::BanInfo>> {
+        let api_version = aleph_session_api_version(self, at).await?;
+        if api_version < BAN_HISTORY_API_VERSION {
+            return Err(anyhow!(
+                "Ban history requires AlephSessionApi version {BAN_HISTORY_API_VERSION}, the runtime has {api_version}"
+            ));
+        }
+
+        let method = "state_call";
+        let api_method = "AlephSessionApi_ban_history";
+        let params = rpc_params![api_method, Bytes(validator.encode()), at];
+
+        self.rpc_call(method.to_string(), params).await
     }
 
     async fn get_session_period(&self) -> anyhow::Result<u32> {
@@ -193,11 +341,22 @@ impl<C: ConnectionApi + AsConnection> CommitteeManagementApi for C {
     }
 
     async fn get_lenient_threshold_percentage(&self, at: Option<BlockHash>) -> Option<Perquintill> {
+        self.try_get_lenient_threshold_percentage(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_lenient_threshold_percentage(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Option<Perquintill>, QueryError> {
         let addrs = api::storage().committee_management().lenient_threshold();
 
-        self.get_storage_entry_maybe(&addrs, at)
-            .await
-            .map(|lt| Perquintill::decode_all(&mut &*lt.encode()).unwrap())
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
+            .map(|lt| Perquintill::decode_all(&mut &*lt.encode()))
+            .transpose()?)
     }
 }
 
@@ -7,7 +7,8 @@ use crate::{
     api::runtime_types,
     pallet_contracts::wasm::{CodeInfo, Determinism},
     sp_weights::weight_v2::Weight,
-    AccountId, Balance, BlockHash, CodeHash, ConnectionApi, SignedConnectionApi, TxInfo, TxStatus,
+    AccountId, Balance, BlockHash, CodeHash, ConnectionApi, QueryError, SignedConnectionApi,
+    TxInfo, TxStatus,
 };
 
 /// The Event that was emitted during execution of calls.
@@ -38,6 +39,13 @@ pub trait ContractsApi {
     /// * `code_hash` - a code hash
     /// * `at` - optional hash of a block to query state from
     async fn get_code_info(&self, code_hash: CodeHash, at: Option<BlockHash>) -> Option<CodeInfo>;
+
+    /// Fallible version of [`Self::get_code_info`].
+    async fn try_get_code_info(
+        &self,
+        code_hash: CodeHash,
+        at: Option<BlockHash>,
+    ) -> Result<Option<CodeInfo>, QueryError>;
 }
 
 /// Pallet contracts api.
@@ -110,9 +118,19 @@ pub trait ContractRpc {
 #[async_trait::async_trait]
 impl<C: ConnectionApi> ContractsApi for C {
     async fn get_code_info(&self, code_hash: CodeHash, at: Option<BlockHash>) -> Option<CodeInfo> {
+        self.try_get_code_info(code_hash, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_code_info(
+        &self,
+        code_hash: CodeHash,
+        at: Option<BlockHash>,
+    ) -> Result<Option<CodeInfo>, QueryError> {
         let addrs = api::storage().contracts().code_info_of(code_hash);
 
-        self.get_storage_entry_maybe(&addrs, at).await
+        self.try_get_storage_entry_maybe(&addrs, at).await
     }
 }
 
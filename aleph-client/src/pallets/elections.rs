@@ -8,7 +8,7 @@ use crate::{
     primitives::ElectionOpenness,
     AccountId, BlockHash,
     Call::Elections,
-    ConnectionApi, RootConnection, SudoCall, TxStatus,
+    ConnectionApi, QueryError, RootConnection, SudoCall, TxStatus,
 };
 
 // TODO once pallet elections docs are published, replace api docs with links to public docs
@@ -19,21 +19,51 @@ pub trait ElectionsApi {
     /// * `at` - optional hash of a block to query state from
     async fn get_committee_seats(&self, at: Option<BlockHash>) -> CommitteeSeats;
 
+    /// Fallible version of [`Self::get_committee_seats`].
+    async fn try_get_committee_seats(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<CommitteeSeats, QueryError>;
+
     /// Returns `elections.next_era_committee_seats` storage of the elections pallet.
     /// * `at` - optional hash of a block to query state from
     async fn get_next_era_committee_seats(&self, at: Option<BlockHash>) -> CommitteeSeats;
 
+    /// Fallible version of [`Self::get_next_era_committee_seats`].
+    async fn try_get_next_era_committee_seats(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<CommitteeSeats, QueryError>;
+
     /// Returns `elections.current_era_validators` storage of the elections pallet.
     /// * `at` - optional hash of a block to query state from
     async fn get_current_era_validators(&self, at: Option<BlockHash>) -> EraValidators<AccountId>;
 
+    /// Fallible version of [`Self::get_current_era_validators`].
+    async fn try_get_current_era_validators(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<EraValidators<AccountId>, QueryError>;
+
     /// Returns `elections.next_era_reserved_validators` storage of the elections pallet.
     /// * `at` - optional hash of a block to query state from
     async fn get_next_era_reserved_validators(&self, at: Option<BlockHash>) -> Vec<AccountId>;
 
+    /// Fallible version of [`Self::get_next_era_reserved_validators`].
+    async fn try_get_next_era_reserved_validators(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<AccountId>, QueryError>;
+
     /// Returns `elections.next_era_non_reserved_validators` storage of the elections pallet.
     /// * `at` - optional hash of a block to query state from
     async fn get_next_era_non_reserved_validators(&self, at: Option<BlockHash>) -> Vec<AccountId>;
+
+    /// Fallible version of [`Self::get_next_era_non_reserved_validators`].
+    async fn try_get_next_era_non_reserved_validators(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<AccountId>, QueryError>;
 }
 
 /// any object that implements pallet elections api that requires sudo
@@ -65,21 +95,48 @@ pub trait ElectionsSudoApi {
 #[async_trait::async_trait]
 impl<C: ConnectionApi + AsConnection> ElectionsApi for C {
     async fn get_committee_seats(&self, at: Option<BlockHash>) -> CommitteeSeats {
+        self.try_get_committee_seats(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_committee_seats(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<CommitteeSeats, QueryError> {
         let addrs = api::storage().elections().committee_size();
 
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 
     async fn get_next_era_committee_seats(&self, at: Option<BlockHash>) -> CommitteeSeats {
+        self.try_get_next_era_committee_seats(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_next_era_committee_seats(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<CommitteeSeats, QueryError> {
         let addrs = api::storage().elections().next_era_committee_size();
 
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 
     async fn get_current_era_validators(&self, at: Option<BlockHash>) -> EraValidators<AccountId> {
+        self.try_get_current_era_validators(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_current_era_validators(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<EraValidators<AccountId>, QueryError> {
         let addrs = api::storage().elections().current_era_validators();
-        let era_validators_with_static_account_ids = self.get_storage_entry(&addrs, at).await;
-        return EraValidators {
+        let era_validators_with_static_account_ids = self.try_get_storage_entry(&addrs, at).await?;
+        Ok(EraValidators {
             reserved: era_validators_with_static_account_ids
                 .reserved
                 .into_iter()
@@ -90,29 +147,49 @@ impl<C: ConnectionApi + AsConnection> ElectionsApi for C {
                 .into_iter()
                 .map(|x| x.0)
                 .collect(),
-        };
+        })
     }
 
     async fn get_next_era_reserved_validators(&self, at: Option<BlockHash>) -> Vec<AccountId> {
+        self.try_get_next_era_reserved_validators(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_next_era_reserved_validators(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<AccountId>, QueryError> {
         let addrs = api::storage().elections().next_era_reserved_validators();
 
-        self.get_storage_entry(&addrs, at)
-            .await
+        Ok(self
+            .try_get_storage_entry(&addrs, at)
+            .await?
             .into_iter()
             .map(|x| x.0)
-            .collect()
+            .collect())
     }
 
     async fn get_next_era_non_reserved_validators(&self, at: Option<BlockHash>) -> Vec<AccountId> {
+        self.try_get_next_era_non_reserved_validators(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_next_era_non_reserved_validators(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<AccountId>, QueryError> {
         let addrs = api::storage()
             .elections()
             .next_era_non_reserved_validators();
 
-        self.get_storage_entry(&addrs, at)
-            .await
+        Ok(self
+            .try_get_storage_entry(&addrs, at)
+            .await?
             .into_iter()
             .map(|x| x.0)
-            .collect()
+            .collect())
     }
 }
 
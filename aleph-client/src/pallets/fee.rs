@@ -1,7 +1,7 @@
 use crate::{
     api,
     sp_runtime::{traits::One, FixedU128},
-    BlockHash, ConnectionApi,
+    BlockHash, ConnectionApi, QueryError,
 };
 
 /// Transaction payment pallet API.
@@ -9,15 +9,31 @@ use crate::{
 pub trait TransactionPaymentApi {
     /// API for [`next_fee_multiplier`](https://paritytech.github.io/substrate/master/pallet_transaction_payment/pallet/struct.Pallet.html#method.next_fee_multiplier) call.
     async fn get_next_fee_multiplier(&self, at: Option<BlockHash>) -> FixedU128;
+
+    /// Fallible version of [`Self::get_next_fee_multiplier`].
+    async fn try_get_next_fee_multiplier(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<FixedU128, QueryError>;
 }
 
 #[async_trait::async_trait]
 impl<C: ConnectionApi> TransactionPaymentApi for C {
     async fn get_next_fee_multiplier(&self, at: Option<BlockHash>) -> FixedU128 {
+        self.try_get_next_fee_multiplier(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_next_fee_multiplier(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<FixedU128, QueryError> {
         let addrs = api::storage().transaction_payment().next_fee_multiplier();
 
-        self.get_storage_entry_maybe(&addrs, at)
-            .await
-            .map_or(FixedU128::one(), |f| FixedU128::from_inner(f.0))
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
+            .map_or(FixedU128::one(), |f| FixedU128::from_inner(f.0)))
     }
 }
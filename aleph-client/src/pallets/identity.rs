@@ -0,0 +1,146 @@
+use subxt::utils::Static;
+
+use crate::{
+    api,
+    connections::TxInfo,
+    pallet_identity::{
+        legacy::IdentityInfo,
+        types::{Judgement, Registration},
+    },
+    AccountId, AsConnection, Balance, BlockHash, ConnectionApi, QueryError, SignedConnectionApi,
+    TxStatus,
+};
+
+/// Pallet identity read-only API.
+#[async_trait::async_trait]
+pub trait IdentityApi {
+    /// Returns [`identity_of`](https://paritytech.github.io/polkadot-sdk/master/pallet_identity/pallet/type.IdentityOf.html) for a given account,
+    /// i.e. its identity information together with the judgements it received.
+    /// * `account` - account id
+    /// * `at` - optional hash of a block to query state from
+    async fn get_identity(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Option<Registration<Balance, IdentityInfo>>;
+
+    /// Fallible version of [`Self::get_identity`].
+    async fn try_get_identity(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<Registration<Balance, IdentityInfo>>, QueryError>;
+
+    /// Returns judgements of the identity of a given account, as pairs of registrar index and
+    /// judgement. Empty if the account has no identity set.
+    /// * `account` - account id
+    /// * `at` - optional hash of a block to query state from
+    async fn get_judgements(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Vec<(u32, Judgement<Balance>)>;
+
+    /// Fallible version of [`Self::get_judgements`].
+    async fn try_get_judgements(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(u32, Judgement<Balance>)>, QueryError>;
+}
+
+/// Pallet identity API.
+#[async_trait::async_trait]
+pub trait IdentityUserApi {
+    /// API for [`set_identity`](https://paritytech.github.io/polkadot-sdk/master/pallet_identity/pallet/struct.Pallet.html#method.set_identity) call.
+    async fn set_identity(&self, info: IdentityInfo, status: TxStatus) -> anyhow::Result<TxInfo>;
+
+    /// API for [`clear_identity`](https://paritytech.github.io/polkadot-sdk/master/pallet_identity/pallet/struct.Pallet.html#method.clear_identity) call.
+    async fn clear_identity(&self, status: TxStatus) -> anyhow::Result<TxInfo>;
+
+    /// API for [`request_judgement`](https://paritytech.github.io/polkadot-sdk/master/pallet_identity/pallet/struct.Pallet.html#method.request_judgement) call.
+    /// * `registrar_index` - index of the registrar to request the judgement from
+    /// * `max_fee` - the maximum fee the signer is willing to pay the registrar
+    async fn request_judgement(
+        &self,
+        registrar_index: u32,
+        max_fee: Balance,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+}
+
+#[async_trait::async_trait]
+impl<C: ConnectionApi + AsConnection> IdentityApi for C {
+    async fn get_identity(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Option<Registration<Balance, IdentityInfo>> {
+        self.try_get_identity(account, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_identity(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<Registration<Balance, IdentityInfo>>, QueryError> {
+        let addrs = api::storage().identity().identity_of(Static(account));
+
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
+            .map(|(registration, _username)| registration))
+    }
+
+    async fn get_judgements(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Vec<(u32, Judgement<Balance>)> {
+        self.try_get_judgements(account, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_judgements(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(u32, Judgement<Balance>)>, QueryError> {
+        Ok(self
+            .try_get_identity(account, at)
+            .await?
+            .map(|registration| registration.judgements.0)
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: SignedConnectionApi> IdentityUserApi for S {
+    async fn set_identity(&self, info: IdentityInfo, status: TxStatus) -> anyhow::Result<TxInfo> {
+        let tx = api::tx().identity().set_identity(info);
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn clear_identity(&self, status: TxStatus) -> anyhow::Result<TxInfo> {
+        let tx = api::tx().identity().clear_identity();
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn request_judgement(
+        &self,
+        registrar_index: u32,
+        max_fee: Balance,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx()
+            .identity()
+            .request_judgement(registrar_index, max_fee);
+
+        self.send_tx(tx, status).await
+    }
+}
@@ -13,8 +13,12 @@ pub mod elections;
 
 /// Pallet transaction payment API
 pub mod fee;
+/// Pallet identity API
+pub mod identity;
 /// Pallet multisig API
 pub mod multisig;
+/// Pallet nomination pools API
+pub mod nomination_pools;
 /// Pallet Proxy API
 pub mod proxy;
 /// Pallet safe-mode API
@@ -29,6 +33,8 @@ pub mod system;
 pub mod timestamp;
 /// Pallet treasury API
 pub mod treasury;
+/// Pallet tx-pause API
+pub mod tx_pause;
 /// Pallet utility API
 pub mod utility;
 /// Pallet vesting API
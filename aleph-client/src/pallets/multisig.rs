@@ -7,7 +7,8 @@ use subxt::utils::Static;
 use crate::{
     account_from_keypair, aleph_runtime::RuntimeCall, api, api::runtime_types, connections::TxInfo,
     sp_core::blake2_256, sp_runtime::traits::TrailingZeroInput, sp_weights::weight_v2::Weight,
-    AccountId, Balance, BlockHash, BlockNumber, ConnectionApi, SignedConnectionApi, TxStatus,
+    AccountId, Balance, BlockHash, BlockNumber, ConnectionApi, QueryError, SignedConnectionApi,
+    TxStatus,
 };
 
 /// An alias for a call hash.
@@ -212,6 +213,14 @@ pub trait MultisigApiExt {
         call_hash: &CallHash,
         block_hash: Option<BlockHash>,
     ) -> Timepoint;
+
+    /// Fallible version of [`Self::get_timepoint`].
+    async fn try_get_timepoint(
+        &self,
+        party_account: &AccountId,
+        call_hash: &CallHash,
+        block_hash: Option<BlockHash>,
+    ) -> Result<Timepoint, QueryError>;
 }
 
 #[async_trait::async_trait]
@@ -222,10 +231,24 @@ impl<C: ConnectionApi> MultisigApiExt for C {
         call_hash: &CallHash,
         block_hash: Option<BlockHash>,
     ) -> Timepoint {
+        self.try_get_timepoint(party_account, call_hash, block_hash)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_timepoint(
+        &self,
+        party_account: &AccountId,
+        call_hash: &CallHash,
+        block_hash: Option<BlockHash>,
+    ) -> Result<Timepoint, QueryError> {
         let multisigs = api::storage()
             .multisig()
             .multisigs(Static(party_account.clone()), call_hash);
-        self.get_storage_entry(&multisigs, block_hash).await.when
+        Ok(self
+            .try_get_storage_entry(&multisigs, block_hash)
+            .await?
+            .when)
     }
 }
 
@@ -0,0 +1,236 @@
+use subxt::utils::{MultiAddress, Static};
+
+use crate::{
+    api,
+    connections::TxInfo,
+    pallet_nomination_pools::{BondExtra, BondedPoolInner, PoolMember},
+    AccountId, AsConnection, Balance, BlockHash, ConnectionApi, QueryError, SignedConnectionApi,
+    TxStatus,
+};
+
+/// Pallet nomination pools read-only API.
+#[async_trait::async_trait]
+pub trait NominationPoolsApi {
+    /// Returns [`last_pool_id`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/type.LastPoolId.html),
+    /// i.e. the id of the most recently created pool.
+    /// * `at` - optional hash of a block to query state from
+    async fn get_last_pool_id(&self, at: Option<BlockHash>) -> u32;
+
+    /// Fallible version of [`Self::get_last_pool_id`].
+    async fn try_get_last_pool_id(&self, at: Option<BlockHash>) -> Result<u32, QueryError>;
+
+    /// Returns [`bonded_pools`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/type.BondedPools.html) for a given pool id.
+    /// * `pool_id` - id of the pool
+    /// * `at` - optional hash of a block to query state from
+    async fn get_bonded_pool(&self, pool_id: u32, at: Option<BlockHash>)
+        -> Option<BondedPoolInner>;
+
+    /// Fallible version of [`Self::get_bonded_pool`].
+    async fn try_get_bonded_pool(
+        &self,
+        pool_id: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BondedPoolInner>, QueryError>;
+
+    /// Returns [`pool_members`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/type.PoolMembers.html) for a given account.
+    /// * `member` - account id of the pool member
+    /// * `at` - optional hash of a block to query state from
+    async fn get_pool_member(&self, member: AccountId, at: Option<BlockHash>)
+        -> Option<PoolMember>;
+
+    /// Fallible version of [`Self::get_pool_member`].
+    async fn try_get_pool_member(
+        &self,
+        member: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<PoolMember>, QueryError>;
+}
+
+/// Pallet nomination pools API.
+#[async_trait::async_trait]
+pub trait NominationPoolsUserApi {
+    /// API for [`create`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/struct.Pallet.html#method.create) call.
+    /// * `amount` - initial bond of the pool creator
+    /// * `root`, `nominator`, `bouncer` - accounts given the respective pool roles
+    async fn create_pool(
+        &self,
+        amount: Balance,
+        root: AccountId,
+        nominator: AccountId,
+        bouncer: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// API for [`join`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/struct.Pallet.html#method.join) call.
+    async fn join_pool(
+        &self,
+        amount: Balance,
+        pool_id: u32,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// API for [`bond_extra`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/struct.Pallet.html#method.bond_extra) call.
+    /// * `extra` - either free balance to bond, or a request to bond the pending rewards
+    async fn bond_extra_to_pool(
+        &self,
+        extra: BondExtra<Balance>,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// API for [`unbond`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/struct.Pallet.html#method.unbond) call.
+    /// * `member` - account of the member to unbond, the signer unless they are permitted to unbond others
+    /// * `unbonding_points` - how many of the member's points to unbond
+    async fn unbond_from_pool(
+        &self,
+        member: AccountId,
+        unbonding_points: Balance,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// API for [`withdraw_unbonded`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/struct.Pallet.html#method.withdraw_unbonded) call.
+    /// * `member` - account of the member to withdraw funds for
+    /// * `num_slashing_spans` - number of slashing spans of the pool's stash
+    async fn withdraw_unbonded_from_pool(
+        &self,
+        member: AccountId,
+        num_slashing_spans: u32,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// API for [`claim_payout`](https://paritytech.github.io/polkadot-sdk/master/pallet_nomination_pools/pallet/struct.Pallet.html#method.claim_payout) call.
+    async fn claim_pool_payout(&self, status: TxStatus) -> anyhow::Result<TxInfo>;
+}
+
+#[async_trait::async_trait]
+impl<C: ConnectionApi + AsConnection> NominationPoolsApi for C {
+    async fn get_last_pool_id(&self, at: Option<BlockHash>) -> u32 {
+        self.try_get_last_pool_id(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_last_pool_id(&self, at: Option<BlockHash>) -> Result<u32, QueryError> {
+        let addrs = api::storage().nomination_pools().last_pool_id();
+
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
+            .unwrap_or_default())
+    }
+
+    async fn get_bonded_pool(
+        &self,
+        pool_id: u32,
+        at: Option<BlockHash>,
+    ) -> Option<BondedPoolInner> {
+        self.try_get_bonded_pool(pool_id, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_bonded_pool(
+        &self,
+        pool_id: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BondedPoolInner>, QueryError> {
+        let addrs = api::storage().nomination_pools().bonded_pools(pool_id);
+
+        self.try_get_storage_entry_maybe(&addrs, at).await
+    }
+
+    async fn get_pool_member(
+        &self,
+        member: AccountId,
+        at: Option<BlockHash>,
+    ) -> Option<PoolMember> {
+        self.try_get_pool_member(member, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_pool_member(
+        &self,
+        member: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<PoolMember>, QueryError> {
+        let addrs = api::storage()
+            .nomination_pools()
+            .pool_members(Static(member));
+
+        self.try_get_storage_entry_maybe(&addrs, at).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: SignedConnectionApi> NominationPoolsUserApi for S {
+    async fn create_pool(
+        &self,
+        amount: Balance,
+        root: AccountId,
+        nominator: AccountId,
+        bouncer: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx().nomination_pools().create(
+            amount,
+            MultiAddress::Id(Static(root)),
+            MultiAddress::Id(Static(nominator)),
+            MultiAddress::Id(Static(bouncer)),
+        );
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn join_pool(
+        &self,
+        amount: Balance,
+        pool_id: u32,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx().nomination_pools().join(amount, pool_id);
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn bond_extra_to_pool(
+        &self,
+        extra: BondExtra<Balance>,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx().nomination_pools().bond_extra(extra);
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn unbond_from_pool(
+        &self,
+        member: AccountId,
+        unbonding_points: Balance,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx()
+            .nomination_pools()
+            .unbond(MultiAddress::Id(Static(member)), unbonding_points);
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn withdraw_unbonded_from_pool(
+        &self,
+        member: AccountId,
+        num_slashing_spans: u32,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx()
+            .nomination_pools()
+            .withdraw_unbonded(MultiAddress::Id(Static(member)), num_slashing_spans);
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn claim_pool_payout(&self, status: TxStatus) -> anyhow::Result<TxInfo> {
+        let tx = api::tx().nomination_pools().claim_payout();
+
+        self.send_tx(tx, status).await
+    }
+}
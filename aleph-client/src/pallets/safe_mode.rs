@@ -3,8 +3,8 @@ use crate::{
     api,
     connections::TxInfo,
     pallet_safe_mode::pallet::Call::{force_enter, force_exit, force_extend},
-    AsConnection, BlockHash, BlockNumber, ConnectionApi, RootConnection, SignedConnectionApi,
-    SudoCall, TxStatus,
+    AsConnection, BlockHash, BlockNumber, ConnectionApi, QueryError, RootConnection,
+    SignedConnectionApi, SudoCall, TxStatus,
 };
 
 /// Pallet SafeMode API which does not require sudo.
@@ -13,8 +13,17 @@ pub trait SafeModeApi {
     /// Gets the last block number that the safe-mode will remain entered in
     async fn entered_until(&self, at: Option<BlockHash>) -> Option<BlockNumber>;
 
+    /// Fallible version of [`Self::entered_until`].
+    async fn try_entered_until(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BlockNumber>, QueryError>;
+
     /// Gets the safe mode enter and extend duration.
     async fn safe_mode_config(&self) -> (BlockNumber, BlockNumber);
+
+    /// Fallible version of [`Self::safe_mode_config`].
+    async fn try_safe_mode_config(&self) -> Result<(BlockNumber, BlockNumber), QueryError>;
 }
 
 /// Pallet SafeMode user API.
@@ -43,12 +52,27 @@ pub trait SafeModeSudoApi {
 #[async_trait::async_trait]
 impl<C: ConnectionApi + AsConnection> SafeModeApi for C {
     async fn entered_until(&self, at: Option<BlockHash>) -> Option<BlockNumber> {
+        self.try_entered_until(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_entered_until(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BlockNumber>, QueryError> {
         let addrs = api::storage().safe_mode().entered_until();
 
-        self.get_storage_entry_maybe(&addrs, at).await
+        self.try_get_storage_entry_maybe(&addrs, at).await
     }
 
     async fn safe_mode_config(&self) -> (BlockNumber, BlockNumber) {
+        self.try_safe_mode_config()
+            .await
+            .expect("Constant should be set on chain")
+    }
+
+    async fn try_safe_mode_config(&self) -> Result<(BlockNumber, BlockNumber), QueryError> {
         let enter_duration_addrs = api::constants().safe_mode().enter_duration();
         let extend_duration_addrs = api::constants().safe_mode().extend_duration();
 
@@ -56,16 +80,14 @@ impl<C: ConnectionApi + AsConnection> SafeModeApi for C {
             .as_connection()
             .as_client()
             .constants()
-            .at(&enter_duration_addrs)
-            .expect("Constant should be set on chain");
+            .at(&enter_duration_addrs)?;
         let extend_duration = self
             .as_connection()
             .as_client()
             .constants()
-            .at(&extend_duration_addrs)
-            .expect("Constant should be set on chain");
+            .at(&extend_duration_addrs)?;
 
-        (enter_duration, extend_duration)
+        Ok((enter_duration, extend_duration))
     }
 }
 
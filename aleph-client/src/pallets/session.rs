@@ -2,7 +2,7 @@ use subxt::utils::Static;
 
 use crate::{
     api, connections::TxInfo, primitives::AlephNodeSessionKeys as SessionKeys, AccountId,
-    BlockHash, ConnectionApi, SessionIndex, SignedConnectionApi, TxStatus,
+    BlockHash, ConnectionApi, QueryError, SessionIndex, SignedConnectionApi, TxStatus,
 };
 
 /// Pallet session read-only api.
@@ -15,11 +15,25 @@ pub trait SessionApi {
         at: Option<BlockHash>,
     ) -> Option<SessionKeys>;
 
+    /// Fallible version of [`Self::get_next_session_keys`].
+    async fn try_get_next_session_keys(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<SessionKeys>, QueryError>;
+
     /// API for [`current_index`](https://paritytech.github.io/substrate/master/pallet_session/pallet/struct.Pallet.html#method.current_index) call.
     async fn get_session(&self, at: Option<BlockHash>) -> SessionIndex;
 
+    /// Fallible version of [`Self::get_session`].
+    async fn try_get_session(&self, at: Option<BlockHash>) -> Result<SessionIndex, QueryError>;
+
     /// API for [`validators`](https://paritytech.github.io/substrate/master/pallet_session/pallet/struct.Pallet.html#method.validators) call.
     async fn get_validators(&self, at: Option<BlockHash>) -> Vec<AccountId>;
+
+    /// Fallible version of [`Self::get_validators`].
+    async fn try_get_validators(&self, at: Option<BlockHash>)
+        -> Result<Vec<AccountId>, QueryError>;
 }
 
 /// any object that implements pallet session api
@@ -36,27 +50,54 @@ impl<C: ConnectionApi> SessionApi for C {
         account: AccountId,
         at: Option<BlockHash>,
     ) -> Option<SessionKeys> {
+        self.try_get_next_session_keys(account, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_next_session_keys(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<SessionKeys>, QueryError> {
         let addrs = api::storage().session().next_keys(Static::from(account));
 
-        self.get_storage_entry_maybe(&addrs, at).await
+        self.try_get_storage_entry_maybe(&addrs, at).await
     }
 
     async fn get_session(&self, at: Option<BlockHash>) -> SessionIndex {
+        self.try_get_session(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_session(&self, at: Option<BlockHash>) -> Result<SessionIndex, QueryError> {
         let addrs = api::storage().session().current_index();
 
-        self.get_storage_entry_maybe(&addrs, at)
-            .await
-            .unwrap_or_default()
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
+            .unwrap_or_default())
     }
 
     async fn get_validators(&self, at: Option<BlockHash>) -> Vec<AccountId> {
+        self.try_get_validators(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_validators(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<AccountId>, QueryError> {
         let addrs = api::storage().session().validators();
 
-        self.get_storage_entry(&addrs, at)
-            .await
+        Ok(self
+            .try_get_storage_entry(&addrs, at)
+            .await?
             .into_iter()
             .map(|x| x.0)
-            .collect()
+            .collect())
     }
 }
 
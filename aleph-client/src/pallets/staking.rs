@@ -22,7 +22,7 @@ use crate::{
     sp_staking::{Exposure, IndividualExposure},
     AccountId, Balance, BlockHash,
     Call::{Staking, Sudo},
-    ConnectionApi, EraIndex, RootConnection, SignedConnectionApi, SudoCall, TxStatus,
+    ConnectionApi, EraIndex, QueryError, RootConnection, SignedConnectionApi, SudoCall, TxStatus,
 };
 
 /// Any object that implemnts pallet staking read-only api.
@@ -32,25 +32,52 @@ pub trait StakingApi {
     /// * `at` - optional hash of a block to query state from
     async fn get_active_era(&self, at: Option<BlockHash>) -> EraIndex;
 
+    /// Fallible version of [`Self::get_active_era`].
+    async fn try_get_active_era(&self, at: Option<BlockHash>) -> Result<EraIndex, QueryError>;
+
     /// Returns [`current_era`](https://paritytech.github.io/substrate/master/pallet_staking/struct.Pallet.html#method.current_era).
     /// * `at` - optional hash of a block to query state from
     async fn get_current_era(&self, at: Option<BlockHash>) -> EraIndex;
 
+    /// Fallible version of [`Self::get_current_era`].
+    async fn try_get_current_era(&self, at: Option<BlockHash>) -> Result<EraIndex, QueryError>;
+
     /// Returns [`bonded`](https://paritytech.github.io/substrate/master/pallet_staking/struct.Pallet.html#method.bonded) for a given stash account.
     /// * `stash` - a stash account id
     /// * `at` - optional hash of a block to query state from
     async fn get_bonded(&self, stash: AccountId, at: Option<BlockHash>) -> Option<AccountId>;
 
+    /// Fallible version of [`Self::get_bonded`].
+    async fn try_get_bonded(
+        &self,
+        stash: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<AccountId>, QueryError>;
+
     /// Returns [`ledger`](https://paritytech.github.io/substrate/master/pallet_staking/struct.Pallet.html#method.ledger) for a given controller account.
     /// * `controller` - a controller account id
     /// * `at` - optional hash of a block to query state from
     async fn get_ledger(&self, controller: AccountId, at: Option<BlockHash>) -> StakingLedger;
 
+    /// Fallible version of [`Self::get_ledger`].
+    async fn try_get_ledger(
+        &self,
+        controller: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<StakingLedger, QueryError>;
+
     /// Returns [`eras_validator_reward`](https://paritytech.github.io/substrate/master/pallet_staking/struct.Pallet.html#method.eras_validator_reward) for a given era.
     /// * `era` - an era index
     /// * `at` - optional hash of a block to query state from
     async fn get_payout_for_era(&self, era: EraIndex, at: Option<BlockHash>) -> Balance;
 
+    /// Fallible version of [`Self::get_payout_for_era`].
+    async fn try_get_payout_for_era(
+        &self,
+        era: EraIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Balance, QueryError>;
+
     /// Returns [`eras_stakers`](https://paritytech.github.io/substrate/master/pallet_staking/struct.Pallet.html#method.eras_stakers) for a given era and account id.
     /// * `era` - an era index
     /// * `account_id` - an account id
@@ -62,6 +89,14 @@ pub trait StakingApi {
         at: Option<BlockHash>,
     ) -> Exposure<AccountId, Balance>;
 
+    /// Fallible version of [`Self::get_exposure`].
+    async fn try_get_exposure(
+        &self,
+        era: EraIndex,
+        account_id: &AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Exposure<AccountId, Balance>, QueryError>;
+
     /// Returns [`eras_reward_points`](https://paritytech.github.io/substrate/master/pallet_staking/struct.Pallet.html#method.eras_reward_points) for a given era.
     /// * `era` - an era index
     /// * `at` - optional hash of a block to query state from
@@ -71,10 +106,23 @@ pub trait StakingApi {
         at: Option<BlockHash>,
     ) -> Option<EraRewardPoints<AccountId>>;
 
+    /// Fallible version of [`Self::get_era_reward_points`].
+    async fn try_get_era_reward_points(
+        &self,
+        era: EraIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Option<EraRewardPoints<AccountId>>, QueryError>;
+
     /// Returns [`minimum_validator_count`](https://paritytech.github.io/substrate/master/pallet_staking/struct.Pallet.html#method.minimum_validator_count).
     /// * `at` - optional hash of a block to query state from
     async fn get_minimum_validator_count(&self, at: Option<BlockHash>) -> u32;
 
+    /// Fallible version of [`Self::get_minimum_validator_count`].
+    async fn try_get_minimum_validator_count(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<u32, QueryError>;
+
     /// Returns [`SessionsPerEra`](https://paritytech.github.io/substrate/master/pallet_staking/trait.Config.html#associatedtype.SessionsPerEra) const.
     async fn get_session_per_era(&self) -> anyhow::Result<u32>;
 
@@ -88,6 +136,14 @@ pub trait StakingApi {
         validator: AccountId,
         at: Option<BlockHash>,
     ) -> Vec<u32>;
+
+    /// Fallible version of [`Self::get_claimed_rewards`].
+    async fn try_get_claimed_rewards(
+        &self,
+        era: u32,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<u32>, QueryError>;
 }
 
 /// Pallet staking api
@@ -277,33 +333,78 @@ pub trait StakingRawApi {
 #[async_trait::async_trait]
 impl<C: ConnectionApi + AsConnection> StakingApi for C {
     async fn get_active_era(&self, at: Option<BlockHash>) -> EraIndex {
+        self.try_get_active_era(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_active_era(&self, at: Option<BlockHash>) -> Result<EraIndex, QueryError> {
         let addrs = api::storage().staking().active_era();
 
-        self.get_storage_entry(&addrs, at).await.index
+        Ok(self.try_get_storage_entry(&addrs, at).await?.index)
     }
 
     async fn get_current_era(&self, at: Option<BlockHash>) -> EraIndex {
+        self.try_get_current_era(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_current_era(&self, at: Option<BlockHash>) -> Result<EraIndex, QueryError> {
         let addrs = api::storage().staking().current_era();
 
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 
     async fn get_bonded(&self, stash: AccountId, at: Option<BlockHash>) -> Option<AccountId> {
+        self.try_get_bonded(stash, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_bonded(
+        &self,
+        stash: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<AccountId>, QueryError> {
         let addrs = api::storage().staking().bonded(Static(stash));
 
-        self.get_storage_entry_maybe(&addrs, at).await.map(|x| x.0)
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
+            .map(|x| x.0))
     }
 
     async fn get_ledger(&self, controller: AccountId, at: Option<BlockHash>) -> StakingLedger {
+        self.try_get_ledger(controller, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_ledger(
+        &self,
+        controller: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<StakingLedger, QueryError> {
         let addrs = api::storage().staking().ledger(Static(controller));
 
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 
     async fn get_payout_for_era(&self, era: EraIndex, at: Option<BlockHash>) -> Balance {
+        self.try_get_payout_for_era(era, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_payout_for_era(
+        &self,
+        era: EraIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Balance, QueryError> {
         let addrs = api::storage().staking().eras_validator_reward(era);
 
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 
     async fn get_exposure(
@@ -312,30 +413,41 @@ impl<C: ConnectionApi + AsConnection> StakingApi for C {
         account_id: &AccountId,
         at: Option<BlockHash>,
     ) -> Exposure<AccountId, Balance> {
+        self.try_get_exposure(era, account_id, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_exposure(
+        &self,
+        era: EraIndex,
+        account_id: &AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Exposure<AccountId, Balance>, QueryError> {
         let overview = self
-            .get_storage_entry_maybe(
+            .try_get_storage_entry_maybe(
                 &api::storage()
                     .staking()
                     .eras_stakers_overview(era, Static(account_id.clone())),
                 at,
             )
-            .await;
+            .await?;
 
         let exposure = match overview {
             None => {
-                self.get_storage_entry(
+                self.try_get_storage_entry(
                     &api::storage()
                         .staking()
                         .eras_stakers(era, Static(account_id.clone())),
                     at,
                 )
-                .await
+                .await?
             }
             Some(overview) => {
                 let mut others = Vec::with_capacity(overview.nominator_count as usize);
                 for page in 0..overview.page_count {
                     let nominators = self
-                        .get_storage_entry_maybe(
+                        .try_get_storage_entry_maybe(
                             &api::storage().staking().eras_stakers_paged(
                                 era,
                                 Static(account_id.clone()),
@@ -343,7 +455,7 @@ impl<C: ConnectionApi + AsConnection> StakingApi for C {
                             ),
                             at,
                         )
-                        .await;
+                        .await?;
                     others.append(&mut nominators.map(|n| n.others).unwrap_or_default());
                 }
                 Exposure {
@@ -354,7 +466,7 @@ impl<C: ConnectionApi + AsConnection> StakingApi for C {
             }
         };
 
-        Exposure {
+        Ok(Exposure {
             total: exposure.total,
             own: exposure.own,
             others: exposure
@@ -365,7 +477,7 @@ impl<C: ConnectionApi + AsConnection> StakingApi for C {
                     value: x.value,
                 })
                 .collect(),
-        }
+        })
     }
 
     async fn get_era_reward_points(
@@ -373,10 +485,21 @@ impl<C: ConnectionApi + AsConnection> StakingApi for C {
         era: EraIndex,
         at: Option<BlockHash>,
     ) -> Option<EraRewardPoints<AccountId>> {
+        self.try_get_era_reward_points(era, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_era_reward_points(
+        &self,
+        era: EraIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Option<EraRewardPoints<AccountId>>, QueryError> {
         let addrs = api::storage().staking().eras_reward_points(era);
 
-        self.get_storage_entry_maybe(&addrs, at)
-            .await
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
             .map(|x| EraRewardPoints {
                 total: x.total,
                 individual: x
@@ -384,13 +507,22 @@ impl<C: ConnectionApi + AsConnection> StakingApi for C {
                     .into_iter()
                     .map(|(k, v)| (k.0, v))
                     .collect::<KeyedVec<AccountId, u32>>(),
-            })
+            }))
     }
 
     async fn get_minimum_validator_count(&self, at: Option<BlockHash>) -> u32 {
+        self.try_get_minimum_validator_count(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_minimum_validator_count(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<u32, QueryError> {
         let addrs = api::storage().staking().minimum_validator_count();
 
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 
     async fn get_session_per_era(&self) -> anyhow::Result<u32> {
@@ -408,10 +540,21 @@ impl<C: ConnectionApi + AsConnection> StakingApi for C {
         validator: AccountId,
         at: Option<BlockHash>,
     ) -> Vec<u32> {
+        self.try_get_claimed_rewards(era, validator, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_claimed_rewards(
+        &self,
+        era: u32,
+        validator: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<u32>, QueryError> {
         let addrs = api::storage()
             .staking()
             .claimed_rewards(era, Static(validator));
-        self.get_storage_entry(&addrs, at).await
+        self.try_get_storage_entry(&addrs, at).await
     }
 }
 
@@ -3,7 +3,8 @@ use subxt::utils::Static;
 
 use crate::{
     api, connections::TxInfo, frame_system::pallet::Call::set_code, AccountId, AsConnection,
-    Balance, BlockHash, Call::System, ConnectionApi, RootConnection, SudoCall, TxStatus,
+    Balance, BlockHash, Call::System, ConnectionApi, QueryError, RootConnection, SudoCall,
+    TxStatus,
 };
 
 /// Pallet system read-only api.
@@ -16,6 +17,13 @@ pub trait SystemApi {
     /// it uses [`system.account`](https://paritytech.github.io/substrate/master/frame_system/pallet/struct.Pallet.html#method.account) storage
     async fn get_free_balance(&self, account: AccountId, at: Option<BlockHash>) -> Balance;
 
+    /// Fallible version of [`Self::get_free_balance`].
+    async fn try_get_free_balance(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Balance, QueryError>;
+
     /// returns account nonce of a given account
     /// * `account` - account id
     async fn account_nonce(&self, account: &AccountId) -> anyhow::Result<Nonce>;
@@ -40,12 +48,22 @@ impl SystemSudoApi for RootConnection {
 #[async_trait::async_trait]
 impl<C: AsConnection + Sync> SystemApi for C {
     async fn get_free_balance(&self, account: AccountId, at: Option<BlockHash>) -> Balance {
+        self.try_get_free_balance(account, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_free_balance(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Balance, QueryError> {
         let addrs = api::storage().system().account(Static(account));
 
-        match self.get_storage_entry_maybe(&addrs, at).await {
+        Ok(match self.try_get_storage_entry_maybe(&addrs, at).await? {
             None => 0,
             Some(account) => account.data.free,
-        }
+        })
     }
 
     async fn account_nonce(&self, account: &AccountId) -> anyhow::Result<Nonce> {
@@ -1,16 +1,25 @@
-use crate::{api, BlockHash, ConnectionApi};
+use crate::{api, BlockHash, ConnectionApi, QueryError};
 
 /// Timestamp payment pallet API.
 #[async_trait::async_trait]
 pub trait TimestampApi {
     /// API for [`get`](https://paritytech.github.io/substrate/master/pallet_timestamp/pallet/struct.Pallet.html#method.get) call.
     async fn get_timestamp(&self, at: Option<BlockHash>) -> Option<u64>;
+
+    /// Fallible version of [`Self::get_timestamp`].
+    async fn try_get_timestamp(&self, at: Option<BlockHash>) -> Result<Option<u64>, QueryError>;
 }
 
 #[async_trait::async_trait]
 impl<C: ConnectionApi> TimestampApi for C {
     async fn get_timestamp(&self, at: Option<BlockHash>) -> Option<u64> {
+        self.try_get_timestamp(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_timestamp(&self, at: Option<BlockHash>) -> Result<Option<u64>, QueryError> {
         let addrs = api::storage().timestamp().now();
-        self.get_storage_entry_maybe(&addrs, at).await
+        self.try_get_storage_entry_maybe(&addrs, at).await
     }
 }
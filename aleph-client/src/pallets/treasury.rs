@@ -9,7 +9,7 @@ use crate::{
     sp_runtime::traits::AccountIdConversion,
     AccountId, Balance, BlockHash,
     Call::Treasury,
-    ConnectionApi, RootConnection, SignedConnectionApi, SudoCall, TxStatus,
+    ConnectionApi, QueryError, RootConnection, SignedConnectionApi, SudoCall, TxStatus,
 };
 
 // Copied from `frame_support`.
@@ -27,9 +27,15 @@ pub trait TreasuryApi {
     /// * `at` - an optional block hash to query state from
     async fn proposals_count(&self, at: Option<BlockHash>) -> Option<u32>;
 
+    /// Fallible version of [`Self::proposals_count`].
+    async fn try_proposals_count(&self, at: Option<BlockHash>) -> Result<Option<u32>, QueryError>;
+
     /// Returns storage `approvals`.
     /// * `at` - an optional block hash to query state from
     async fn approvals(&self, at: Option<BlockHash>) -> Vec<u32>;
+
+    /// Fallible version of [`Self::approvals`].
+    async fn try_approvals(&self, at: Option<BlockHash>) -> Result<Vec<u32>, QueryError>;
 }
 
 /// Pallet treasury api.
@@ -69,15 +75,25 @@ impl<C: ConnectionApi> TreasuryApi for C {
     }
 
     async fn proposals_count(&self, at: Option<BlockHash>) -> Option<u32> {
+        self.try_proposals_count(at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_proposals_count(&self, at: Option<BlockHash>) -> Result<Option<u32>, QueryError> {
         let addrs = api::storage().treasury().proposal_count();
 
-        self.get_storage_entry_maybe(&addrs, at).await
+        self.try_get_storage_entry_maybe(&addrs, at).await
     }
 
     async fn approvals(&self, at: Option<BlockHash>) -> Vec<u32> {
+        self.try_approvals(at).await.expect("Should access storage")
+    }
+
+    async fn try_approvals(&self, at: Option<BlockHash>) -> Result<Vec<u32>, QueryError> {
         let addrs = api::storage().treasury().approvals();
 
-        self.get_storage_entry(&addrs, at).await.0
+        Ok(self.try_get_storage_entry(&addrs, at).await?.0)
     }
 }
 
@@ -0,0 +1,86 @@
+use crate::{
+    aleph_runtime::RuntimeCall::TxPause,
+    api,
+    bounded_collections::bounded_vec::BoundedVec,
+    connections::TxInfo,
+    pallet_tx_pause::pallet::Call::{pause, unpause},
+    AsConnection, BlockHash, ConnectionApi, QueryError, RootConnection, SudoCall, TxStatus,
+};
+
+/// Pallet TxPause API which does not require sudo.
+#[async_trait::async_trait]
+pub trait TxPauseApi {
+    /// Returns whether the call is in [`paused_calls`](https://paritytech.github.io/polkadot-sdk/master/pallet_tx_pause/pallet/type.PausedCalls.html).
+    /// * `pallet` - name of the pallet, as in the runtime, e.g. `Balances`
+    /// * `call` - name of the call, e.g. `transfer_keep_alive`
+    /// * `at` - optional hash of a block to query state from
+    async fn is_paused(&self, pallet: &str, call: &str, at: Option<BlockHash>) -> bool;
+
+    /// Fallible version of [`Self::is_paused`].
+    async fn try_is_paused(
+        &self,
+        pallet: &str,
+        call: &str,
+        at: Option<BlockHash>,
+    ) -> Result<bool, QueryError>;
+}
+
+/// Pallet TxPause API that requires sudo.
+#[async_trait::async_trait]
+pub trait TxPauseSudoApi {
+    /// API for [`pause`](https://paritytech.github.io/polkadot-sdk/master/pallet_tx_pause/pallet/struct.Pallet.html#method.pause) call.
+    async fn pause(&self, pallet: &str, call: &str, status: TxStatus) -> anyhow::Result<TxInfo>;
+
+    /// API for [`unpause`](https://paritytech.github.io/polkadot-sdk/master/pallet_tx_pause/pallet/struct.Pallet.html#method.unpause) call.
+    async fn unpause(&self, pallet: &str, call: &str, status: TxStatus) -> anyhow::Result<TxInfo>;
+}
+
+fn full_name(pallet: &str, call: &str) -> (BoundedVec<u8>, BoundedVec<u8>) {
+    (
+        BoundedVec(pallet.as_bytes().to_vec()),
+        BoundedVec(call.as_bytes().to_vec()),
+    )
+}
+
+#[async_trait::async_trait]
+impl<C: ConnectionApi + AsConnection> TxPauseApi for C {
+    async fn is_paused(&self, pallet: &str, call: &str, at: Option<BlockHash>) -> bool {
+        self.try_is_paused(pallet, call, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_is_paused(
+        &self,
+        pallet: &str,
+        call: &str,
+        at: Option<BlockHash>,
+    ) -> Result<bool, QueryError> {
+        let (pallet, call) = full_name(pallet, call);
+        let addrs = api::storage().tx_pause().paused_calls(pallet, call);
+
+        Ok(self
+            .try_get_storage_entry_maybe(&addrs, at)
+            .await?
+            .is_some())
+    }
+}
+
+#[async_trait::async_trait]
+impl TxPauseSudoApi for RootConnection {
+    async fn pause(&self, pallet: &str, call: &str, status: TxStatus) -> anyhow::Result<TxInfo> {
+        let call = TxPause(pause {
+            full_name: full_name(pallet, call),
+        });
+
+        self.sudo_unchecked(call, status).await
+    }
+
+    async fn unpause(&self, pallet: &str, call: &str, status: TxStatus) -> anyhow::Result<TxInfo> {
+        let call = TxPause(unpause {
+            ident: full_name(pallet, call),
+        });
+
+        self.sudo_unchecked(call, status).await
+    }
+}
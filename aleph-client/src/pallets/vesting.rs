@@ -2,7 +2,7 @@ use subxt::utils::{MultiAddress, Static};
 
 use crate::{
     api, connections::TxInfo, pallet_vesting::vesting_info::VestingInfo, AccountId, Balance,
-    BlockHash, BlockNumber, ConnectionApi, SignedConnectionApi, TxStatus,
+    BlockHash, BlockNumber, ConnectionApi, QueryError, SignedConnectionApi, TxStatus,
 };
 
 /// Read only pallet vesting API.
@@ -16,6 +16,13 @@ pub trait VestingApi {
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Vec<VestingInfo<Balance, BlockNumber>>;
+
+    /// Fallible version of [`Self::get_vesting`].
+    async fn try_get_vesting(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<VestingInfo<Balance, BlockNumber>>, QueryError>;
 }
 
 /// Pallet vesting api.
@@ -51,9 +58,19 @@ impl<C: ConnectionApi> VestingApi for C {
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Vec<VestingInfo<Balance, BlockNumber>> {
+        self.try_get_vesting(who, at)
+            .await
+            .expect("Should access storage")
+    }
+
+    async fn try_get_vesting(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<VestingInfo<Balance, BlockNumber>>, QueryError> {
         let addrs = api::storage().vesting().vesting(Static(who));
 
-        self.get_storage_entry(&addrs, at).await.0
+        Ok(self.try_get_storage_entry(&addrs, at).await?.0)
     }
 }
 
@@ -0,0 +1,783 @@
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use futures::{lock::Mutex as AsyncMutex, stream, StreamExt};
+use jsonrpsee::{
+    client_transport::ws::{Uri, WsTransportClientBuilder},
+    core::{
+        client::{Client, ClientBuilder},
+        Error as JsonRpseeError,
+    },
+};
+use log::{info, warn};
+use subxt::{
+    error::RpcError,
+    rpc::{RawValue, RpcClientT, RpcFuture, RpcSubscription, RpcSubscriptionStream},
+};
+use tokio::time::sleep;
+
+use crate::{
+    connections::{AsConnection, AsSigned},
+    Connection, KeyPair, SignedConnection, SubxtClient,
+};
+
+const MAX_NOTIFICATIONS_PER_SUBSCRIPTION: usize = 4096;
+const GENESIS_HASH_METHOD: &str = "chain_getBlockHash";
+/// Subscriptions that are not re-established when the connection breaks, as subscribing again
+/// would submit the transaction again.
+const NOT_RESUBSCRIBABLE: [&str; 1] = ["author_submitAndWatchExtrinsic"];
+
+/// How a [`ReconnectingConnection`] tries to get the connection back.
+#[derive(Clone, Debug)]
+pub struct ReconnectConfig {
+    /// How long to wait after the first failed connection attempt.
+    pub initial_backoff: Duration,
+    /// The wait doubles after every failed attempt, up to this limit.
+    pub max_backoff: Duration,
+    /// After this many failed attempts in a row requests fail, instead of waiting for the
+    /// connection. `None` means trying forever.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        ReconnectConfig {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+/// A snapshot of the state of a [`ReconnectingConnection`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectionHealth {
+    /// The endpoint that is, or was last, connected to.
+    pub endpoint: String,
+    /// Whether the connection to the endpoint is up.
+    pub connected: bool,
+    /// How many times the connection was re-established since it was created.
+    pub reconnects: u64,
+    /// How many connection attempts failed since the connection was last up.
+    pub failed_attempts: u32,
+}
+
+/// A connection to a single endpoint.
+trait EndpointClient: RpcClientT {
+    /// Whether the connection is up. Errors returned after the connection went down are caused
+    /// by the connection breaking, rather than by the node.
+    fn is_connected(&self) -> bool;
+}
+
+impl EndpointClient for Client {
+    fn is_connected(&self) -> bool {
+        Client::is_connected(self)
+    }
+}
+
+/// Opens connections to endpoints.
+#[async_trait::async_trait]
+trait Connector: Send + Sync + 'static {
+    type Client: EndpointClient;
+
+    async fn connect(&self, endpoint: &str) -> anyhow::Result<Self::Client>;
+}
+
+/// Opens websocket connections.
+struct WsConnector;
+
+#[async_trait::async_trait]
+impl Connector for WsConnector {
+    type Client = Client;
+
+    async fn connect(&self, endpoint: &str) -> anyhow::Result<Client> {
+        Ok(ws_client(endpoint).await?)
+    }
+}
+
+struct State<C> {
+    client: Arc<C>,
+    endpoint: usize,
+    reconnects: u64,
+    failed_attempts: u32,
+}
+
+/// An RPC client that fails over between endpoints. Whenever the current connection breaks,
+/// requests wait until it is re-established, possibly to another endpoint, and are then retried.
+/// Subscriptions are re-established the same way, except for those in [`NOT_RESUBSCRIBABLE`].
+struct FailoverRpcClient<C: Connector> {
+    connector: C,
+    endpoints: Vec<String>,
+    config: ReconnectConfig,
+    genesis_hash: String,
+    state: Mutex<State<C::Client>>,
+    reconnecting: AsyncMutex<()>,
+}
+
+async fn ws_client(endpoint: &str) -> Result<Client, JsonRpseeError> {
+    let uri = Uri::from_str(endpoint).map_err(|e| JsonRpseeError::Transport(e.into()))?;
+    let (sender, receiver) = WsTransportClientBuilder::default()
+        .build(uri)
+        .await
+        .map_err(|e| JsonRpseeError::Transport(e.into()))?;
+    Ok(ClientBuilder::default()
+        .max_notifs_per_subscription(MAX_NOTIFICATIONS_PER_SUBSCRIPTION)
+        .build_with_tokio(sender, receiver))
+}
+
+async fn genesis_hash<C: RpcClientT>(client: &C) -> Result<String, RpcError> {
+    let params = RawValue::from_string("[0]".to_string()).expect("is valid JSON");
+    let hash = client
+        .request_raw(GENESIS_HASH_METHOD, Some(params))
+        .await?;
+    Ok(hash.get().to_string())
+}
+
+impl<C: Connector> FailoverRpcClient<C> {
+    /// Connects to the first endpoint that works, trying each of them once.
+    async fn new(
+        connector: C,
+        endpoints: Vec<String>,
+        config: ReconnectConfig,
+    ) -> anyhow::Result<Self> {
+        for (idx, endpoint) in endpoints.iter().enumerate() {
+            let client = match connector.connect(endpoint).await {
+                Ok(client) => client,
+                Err(e) => {
+                    warn!(target: "aleph-client", "Failed to connect to {}: {}", endpoint, e);
+                    continue;
+                }
+            };
+            let genesis_hash = genesis_hash(&client).await?;
+            info!(target: "aleph-client", "Connected to {}", endpoint);
+            return Ok(FailoverRpcClient {
+                connector,
+                endpoints,
+                config,
+                genesis_hash,
+                state: Mutex::new(State {
+                    client: Arc::new(client),
+                    endpoint: idx,
+                    reconnects: 0,
+                    failed_attempts: 0,
+                }),
+                reconnecting: AsyncMutex::new(()),
+            });
+        }
+        Err(anyhow!("Failed to connect to any of {:?}", endpoints))
+    }
+
+    fn client(&self) -> Arc<C::Client> {
+        self.state
+            .lock()
+            .expect("the lock is not poisoned")
+            .client
+            .clone()
+    }
+
+    fn health(&self) -> ConnectionHealth {
+        let state = self.state.lock().expect("the lock is not poisoned");
+        ConnectionHealth {
+            endpoint: self.endpoints[state.endpoint].clone(),
+            connected: state.client.is_connected(),
+            reconnects: state.reconnects,
+            failed_attempts: state.failed_attempts,
+        }
+    }
+
+    /// Replaces the `broken` client with a working one, unless that already happened.
+    async fn reconnect(&self, broken: &Arc<C::Client>) -> Result<Arc<C::Client>, RpcError> {
+        let _guard = self.reconnecting.lock().await;
+        let current = self.client();
+        if !Arc::ptr_eq(&current, broken) {
+            return Ok(current);
+        }
+
+        let first_endpoint = self
+            .state
+            .lock()
+            .expect("the lock is not poisoned")
+            .endpoint;
+        let mut backoff = self.config.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            // Start from the next endpoint, so that a single broken one does not hold us up.
+            let idx = (first_endpoint + 1 + attempt as usize) % self.endpoints.len();
+            let endpoint = &self.endpoints[idx];
+            match self.try_connect(endpoint).await {
+                Ok(client) => {
+                    info!(target: "aleph-client", "Reconnected to {}", endpoint);
+                    let client = Arc::new(client);
+                    let mut state = self.state.lock().expect("the lock is not poisoned");
+                    state.client = client.clone();
+                    state.endpoint = idx;
+                    state.reconnects += 1;
+                    state.failed_attempts = 0;
+                    return Ok(client);
+                }
+                Err(e) => {
+                    warn!(target: "aleph-client", "Failed to reconnect to {}: {}", endpoint, e);
+                    attempt += 1;
+                    self.state
+                        .lock()
+                        .expect("the lock is not poisoned")
+                        .failed_attempts = attempt;
+                }
+            }
+            if self.config.max_attempts.map_or(false, |max| attempt >= max) {
+                return Err(RpcError::ClientError(
+                    anyhow!("Failed to reconnect after {} attempts", attempt).into(),
+                ));
+            }
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(self.config.max_backoff);
+        }
+    }
+
+    async fn try_connect(&self, endpoint: &str) -> anyhow::Result<C::Client> {
+        let client = self.connector.connect(endpoint).await?;
+        let genesis_hash = genesis_hash(&client).await?;
+        if genesis_hash != self.genesis_hash {
+            return Err(anyhow!(
+                "endpoint is on a different chain, genesis {} instead of {}",
+                genesis_hash,
+                self.genesis_hash
+            ));
+        }
+        Ok(client)
+    }
+
+    async fn subscribe(
+        self: Arc<Self>,
+        sub: String,
+        params: Option<Box<RawValue>>,
+        unsub: String,
+    ) -> Result<RpcSubscription, RpcError> {
+        let (client, subscription) = self.subscribe_once(&sub, &params, &unsub).await?;
+        let id = subscription.id.clone();
+        let subscription = ResubscribingStream {
+            rpc: self,
+            client,
+            stream: Some(subscription.stream),
+            sub,
+            params,
+            unsub,
+        };
+        Ok(RpcSubscription {
+            stream: subscription.into_stream(),
+            id,
+        })
+    }
+
+    async fn subscribe_once(
+        &self,
+        sub: &str,
+        params: &Option<Box<RawValue>>,
+        unsub: &str,
+    ) -> Result<(Arc<C::Client>, RpcSubscription), RpcError> {
+        let mut client = self.client();
+        loop {
+            match client.subscribe_raw(sub, params.clone(), unsub).await {
+                Err(e) if !client.is_connected() => {
+                    warn!(target: "aleph-client", "Connection lost while subscribing to {}: {}", sub, e);
+                    client = self.reconnect(&client).await?;
+                }
+                result => return result.map(|subscription| (client, subscription)),
+            }
+        }
+    }
+}
+
+/// A subscription that is re-established whenever the connection breaks. Subscriptions in
+/// [`NOT_RESUBSCRIBABLE`] return an error and end instead.
+struct ResubscribingStream<C: Connector> {
+    rpc: Arc<FailoverRpcClient<C>>,
+    client: Arc<C::Client>,
+    stream: Option<RpcSubscriptionStream>,
+    sub: String,
+    params: Option<Box<RawValue>>,
+    unsub: String,
+}
+
+impl<C: Connector> ResubscribingStream<C> {
+    fn into_stream(self) -> RpcSubscriptionStream {
+        Box::pin(stream::unfold(self, |mut this| async move {
+            loop {
+                let item = this.stream.as_mut()?.next().await;
+                match item {
+                    Some(Err(_)) | None
+                        if !this.client.is_connected()
+                            && NOT_RESUBSCRIBABLE.contains(&this.sub.as_str()) =>
+                    {
+                        warn!(target: "aleph-client", "Subscription {} lost, it cannot be re-established", this.sub);
+                        let error = match item {
+                            Some(Err(e)) => e,
+                            _ => RpcError::ClientError(
+                                anyhow!("Connection lost during `{}`", this.sub).into(),
+                            ),
+                        };
+                        this.stream = None;
+                        return Some((Err(error), this));
+                    }
+                    Some(Err(_)) | None if !this.client.is_connected() => {
+                        warn!(target: "aleph-client", "Subscription {} lost, resubscribing", this.sub);
+                        match this
+                            .rpc
+                            .subscribe_once(&this.sub, &this.params, &this.unsub)
+                            .await
+                        {
+                            Ok((client, subscription)) => {
+                                this.client = client;
+                                this.stream = Some(subscription.stream);
+                            }
+                            Err(e) => {
+                                this.stream = None;
+                                return Some((Err(e), this));
+                            }
+                        }
+                    }
+                    Some(item) => return Some((item, this)),
+                    None => return None,
+                }
+            }
+        }))
+    }
+}
+
+/// Subscriptions have to own the client to re-establish themselves, which `&self` in
+/// [`RpcClientT`] does not allow.
+struct SharedRpcClient<C: Connector>(Arc<FailoverRpcClient<C>>);
+
+impl<C: Connector> RpcClientT for SharedRpcClient<C> {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move {
+            let mut client = self.0.client();
+            loop {
+                match client.request_raw(method, params.clone()).await {
+                    Err(e) if !client.is_connected() => {
+                        warn!(target: "aleph-client", "Connection lost during `{}`: {}", method, e);
+                        client = self.0.reconnect(&client).await?;
+                    }
+                    result => return result,
+                }
+            }
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RpcFuture<'a, RpcSubscription> {
+        Box::pin(
+            self.0
+                .clone()
+                .subscribe(sub.to_string(), params, unsub.to_string()),
+        )
+    }
+}
+
+/// A connection to any of a number of nodes of the same chain, that survives their restarts.
+///
+/// Whenever the connection breaks, calls wait until it is re-established and are then retried,
+/// possibly against another endpoint. Subscriptions, e.g. those used by
+/// [`AlephWaiting`](crate::waiting::AlephWaiting), are re-established as well. Transactions are
+/// resubmitted if the connection broke while they were being submitted, which might result in
+/// an error if they made it to the node the first time. Watching a submitted transaction is not
+/// re-established though, as that would submit it again, so it fails instead.
+#[derive(Clone)]
+pub struct ReconnectingConnection {
+    connection: Connection,
+    rpc: Arc<FailoverRpcClient<WsConnector>>,
+}
+
+/// A [`ReconnectingConnection`] signed by some key.
+#[derive(Clone)]
+pub struct ReconnectingSignedConnection {
+    connection: SignedConnection,
+    rpc: Arc<FailoverRpcClient<WsConnector>>,
+}
+
+impl ReconnectingConnection {
+    /// Creates a new connection to the first of the endpoints that works, with the default
+    /// [`ReconnectConfig`].
+    /// * `endpoints` - addresses in websocket format, e.g. `ws://127.0.0.1:9943`
+    pub async fn new(endpoints: Vec<String>) -> anyhow::Result<Self> {
+        Self::with_config(endpoints, ReconnectConfig::default()).await
+    }
+
+    /// Creates a new connection to the first of the endpoints that works.
+    /// * `endpoints` - addresses in websocket format, e.g. `ws://127.0.0.1:9943`
+    /// * `config` - how to behave when the connection breaks
+    pub async fn with_config(
+        endpoints: Vec<String>,
+        config: ReconnectConfig,
+    ) -> anyhow::Result<Self> {
+        let rpc = Arc::new(FailoverRpcClient::new(WsConnector, endpoints, config).await?);
+        let client = SubxtClient::from_rpc_client(Arc::new(SharedRpcClient(rpc.clone()))).await?;
+        Ok(ReconnectingConnection {
+            connection: Connection { client },
+            rpc,
+        })
+    }
+
+    /// Returns the current state of the connection.
+    pub fn health(&self) -> ConnectionHealth {
+        self.rpc.health()
+    }
+
+    /// Creates a signed connection sharing this connection.
+    /// * `signer` - a [`KeyPair`] of signing account
+    pub fn sign(&self, signer: KeyPair) -> ReconnectingSignedConnection {
+        ReconnectingSignedConnection {
+            connection: SignedConnection::from_connection(self.connection.clone(), signer),
+            rpc: self.rpc.clone(),
+        }
+    }
+}
+
+impl ReconnectingSignedConnection {
+    /// Creates a new signed connection to the first of the endpoints that works, with the
+    /// default [`ReconnectConfig`].
+    /// * `endpoints` - addresses in websocket format, e.g. `ws://127.0.0.1:9943`
+    /// * `signer` - a [`KeyPair`] of signing account
+    pub async fn new(endpoints: Vec<String>, signer: KeyPair) -> anyhow::Result<Self> {
+        Ok(ReconnectingConnection::new(endpoints).await?.sign(signer))
+    }
+
+    /// Returns the current state of the connection.
+    pub fn health(&self) -> ConnectionHealth {
+        self.rpc.health()
+    }
+}
+
+impl AsConnection for ReconnectingConnection {
+    fn as_connection(&self) -> &Connection {
+        &self.connection
+    }
+}
+
+impl AsSigned for ReconnectingSignedConnection {
+    fn as_signed(&self) -> &SignedConnection {
+        &self.connection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    use anyhow::anyhow;
+    use futures::{channel::mpsc, StreamExt};
+    use subxt::{
+        error::RpcError,
+        rpc::{RawValue, RpcClientT, RpcFuture, RpcSubscription},
+    };
+    use tokio::time::{sleep, Instant};
+
+    use super::{
+        Connector, EndpointClient, FailoverRpcClient, ReconnectConfig, SharedRpcClient,
+        GENESIS_HASH_METHOD,
+    };
+
+    const GENESIS: &str = "\"0x01\"";
+    const OTHER_GENESIS: &str = "\"0x02\"";
+    const NEW_HEADS: &str = "chain_subscribeNewHeads";
+    const SUBMIT_AND_WATCH: &str = "author_submitAndWatchExtrinsic";
+
+    type Notifications = mpsc::UnboundedSender<Result<Box<RawValue>, RpcError>>;
+
+    struct FakeNode {
+        genesis: &'static str,
+        up: bool,
+        connections: Vec<Arc<AtomicBool>>,
+        subscribers: Vec<Notifications>,
+        subscriptions: Vec<String>,
+    }
+
+    /// Fake endpoints that can be brought down and up. They answer every request, and start
+    /// every subscription, with their own name.
+    #[derive(Clone)]
+    struct FakeEndpoints(Arc<Mutex<HashMap<String, FakeNode>>>);
+
+    impl FakeEndpoints {
+        fn new(endpoints: &[(&str, &'static str)]) -> Self {
+            let nodes = endpoints
+                .iter()
+                .map(|&(endpoint, genesis)| {
+                    let node = FakeNode {
+                        genesis,
+                        up: true,
+                        connections: Vec::new(),
+                        subscribers: Vec::new(),
+                        subscriptions: Vec::new(),
+                    };
+                    (endpoint.to_string(), node)
+                })
+                .collect();
+            FakeEndpoints(Arc::new(Mutex::new(nodes)))
+        }
+
+        fn kill(&self, endpoint: &str) {
+            let mut nodes = self.0.lock().expect("the lock is not poisoned");
+            let node = nodes.get_mut(endpoint).expect("the endpoint exists");
+            node.up = false;
+            for connection in node.connections.drain(..) {
+                connection.store(false, Ordering::SeqCst);
+            }
+            node.subscribers.clear();
+        }
+
+        fn revive(&self, endpoint: &str) {
+            let mut nodes = self.0.lock().expect("the lock is not poisoned");
+            nodes.get_mut(endpoint).expect("the endpoint exists").up = true;
+        }
+
+        fn subscriptions(&self, endpoint: &str) -> Vec<String> {
+            let nodes = self.0.lock().expect("the lock is not poisoned");
+            nodes[endpoint].subscriptions.clone()
+        }
+    }
+
+    struct FakeClient {
+        endpoint: String,
+        endpoints: FakeEndpoints,
+        connected: Arc<AtomicBool>,
+    }
+
+    fn name(endpoint: &str) -> String {
+        format!("\"{endpoint}\"")
+    }
+
+    fn json(value: &str) -> Box<RawValue> {
+        RawValue::from_string(value.to_string()).expect("is valid JSON")
+    }
+
+    impl EndpointClient for FakeClient {
+        fn is_connected(&self) -> bool {
+            self.connected.load(Ordering::SeqCst)
+        }
+    }
+
+    impl RpcClientT for FakeClient {
+        fn request_raw<'a>(
+            &'a self,
+            method: &'a str,
+            _params: Option<Box<RawValue>>,
+        ) -> RpcFuture<'a, Box<RawValue>> {
+            Box::pin(async move {
+                if !self.is_connected() {
+                    return Err(RpcError::ClientError(anyhow!("connection closed").into()));
+                }
+                let nodes = self.endpoints.0.lock().expect("the lock is not poisoned");
+                match method {
+                    GENESIS_HASH_METHOD => Ok(json(nodes[&self.endpoint].genesis)),
+                    _ => Ok(json(&name(&self.endpoint))),
+                }
+            })
+        }
+
+        fn subscribe_raw<'a>(
+            &'a self,
+            sub: &'a str,
+            _params: Option<Box<RawValue>>,
+            _unsub: &'a str,
+        ) -> RpcFuture<'a, RpcSubscription> {
+            Box::pin(async move {
+                if !self.is_connected() {
+                    return Err(RpcError::ClientError(anyhow!("connection closed").into()));
+                }
+                let (notifications, stream) = mpsc::unbounded();
+                notifications
+                    .unbounded_send(Ok(json(&name(&self.endpoint))))
+                    .expect("the stream exists");
+                let mut nodes = self.endpoints.0.lock().expect("the lock is not poisoned");
+                let node = nodes.get_mut(&self.endpoint).expect("the endpoint exists");
+                node.subscriptions.push(sub.to_string());
+                node.subscribers.push(notifications);
+                Ok(RpcSubscription {
+                    stream: Box::pin(stream),
+                    id: None,
+                })
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Connector for FakeEndpoints {
+        type Client = FakeClient;
+
+        async fn connect(&self, endpoint: &str) -> anyhow::Result<FakeClient> {
+            let mut nodes = self.0.lock().expect("the lock is not poisoned");
+            let node = nodes
+                .get_mut(endpoint)
+                .ok_or_else(|| anyhow!("unknown endpoint {}", endpoint))?;
+            if !node.up {
+                return Err(anyhow!("{} is down", endpoint));
+            }
+            let connected = Arc::new(AtomicBool::new(true));
+            node.connections.push(connected.clone());
+            Ok(FakeClient {
+                endpoint: endpoint.to_string(),
+                endpoints: self.clone(),
+                connected,
+            })
+        }
+    }
+
+    fn config(max_attempts: Option<u32>) -> ReconnectConfig {
+        ReconnectConfig {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(4),
+            max_attempts,
+        }
+    }
+
+    async fn connect(
+        endpoints: &FakeEndpoints,
+        names: &[&str],
+        config: ReconnectConfig,
+    ) -> SharedRpcClient<FakeEndpoints> {
+        let names = names.iter().map(|name| name.to_string()).collect();
+        let rpc = FailoverRpcClient::new(endpoints.clone(), names, config)
+            .await
+            .expect("some endpoint is up");
+        SharedRpcClient(Arc::new(rpc))
+    }
+
+    async fn request(client: &SharedRpcClient<FakeEndpoints>) -> Result<String, RpcError> {
+        let answer = client.request_raw("system_name", None).await?;
+        Ok(answer.get().to_string())
+    }
+
+    async fn next(subscription: &mut RpcSubscription) -> Option<Result<String, RpcError>> {
+        let item = subscription.stream.next().await?;
+        Some(item.map(|value| value.get().to_string()))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn connects_to_first_working_endpoint() {
+        let endpoints = FakeEndpoints::new(&[("a", GENESIS), ("b", GENESIS)]);
+        endpoints.kill("a");
+        let client = connect(&endpoints, &["a", "b"], config(None)).await;
+
+        assert_eq!(request(&client).await.expect("b is up"), name("b"));
+        assert_eq!(client.0.health().endpoint, "b");
+        assert_eq!(client.0.health().reconnects, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fails_over_to_next_endpoint() {
+        let endpoints = FakeEndpoints::new(&[("a", GENESIS), ("b", GENESIS), ("c", GENESIS)]);
+        let client = connect(&endpoints, &["a", "b", "c"], config(None)).await;
+        assert_eq!(request(&client).await.expect("a is up"), name("a"));
+
+        endpoints.kill("a");
+        assert_eq!(request(&client).await.expect("b is up"), name("b"));
+        let health = client.0.health();
+        assert_eq!(health.endpoint, "b");
+        assert!(health.connected);
+        assert_eq!(health.reconnects, 1);
+        assert_eq!(health.failed_attempts, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn skips_endpoints_of_other_chains() {
+        let endpoints = FakeEndpoints::new(&[("a", GENESIS), ("b", OTHER_GENESIS), ("c", GENESIS)]);
+        let client = connect(&endpoints, &["a", "b", "c"], config(None)).await;
+
+        endpoints.kill("a");
+        assert_eq!(request(&client).await.expect("c is up"), name("c"));
+        assert_eq!(client.0.health().endpoint, "c");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn backs_off_exponentially_up_to_limit() {
+        let endpoints = FakeEndpoints::new(&[("a", GENESIS), ("b", GENESIS)]);
+        let client = connect(&endpoints, &["a", "b"], config(Some(5))).await;
+        endpoints.kill("a");
+        endpoints.kill("b");
+
+        let start = Instant::now();
+        assert!(request(&client).await.is_err());
+        // Waits of 1, 2, 4 and 4 seconds between the 5 attempts.
+        assert_eq!(start.elapsed(), Duration::from_secs(11));
+        let health = client.0.health();
+        assert!(!health.connected);
+        assert_eq!(health.failed_attempts, 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_for_endpoint_to_come_back() {
+        let endpoints = FakeEndpoints::new(&[("a", GENESIS)]);
+        let client = connect(&endpoints, &["a"], config(None)).await;
+        endpoints.kill("a");
+        let revived = endpoints.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_secs(10)).await;
+            revived.revive("a");
+        });
+
+        let start = Instant::now();
+        assert_eq!(request(&client).await.expect("a is back"), name("a"));
+        // Attempts after 0, 1, 3, 7 and 11 seconds, the last one succeeds.
+        assert_eq!(start.elapsed(), Duration::from_secs(11));
+        assert_eq!(client.0.health().reconnects, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn resubscribes_after_connection_loss() {
+        let endpoints = FakeEndpoints::new(&[("a", GENESIS), ("b", GENESIS)]);
+        let client = connect(&endpoints, &["a", "b"], config(None)).await;
+        let mut subscription = client
+            .subscribe_raw(NEW_HEADS, None, "chain_unsubscribeNewHeads")
+            .await
+            .expect("a is up");
+        assert_eq!(
+            next(&mut subscription).await.expect("a notifies").ok(),
+            Some(name("a"))
+        );
+
+        endpoints.kill("a");
+        assert_eq!(
+            next(&mut subscription).await.expect("b notifies").ok(),
+            Some(name("b"))
+        );
+        assert_eq!(endpoints.subscriptions("b"), vec![NEW_HEADS.to_string()]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn does_not_resubscribe_to_submitted_extrinsic() {
+        let endpoints = FakeEndpoints::new(&[("a", GENESIS), ("b", GENESIS)]);
+        let client = connect(&endpoints, &["a", "b"], config(None)).await;
+        let mut subscription = client
+            .subscribe_raw(SUBMIT_AND_WATCH, None, "author_unwatchExtrinsic")
+            .await
+            .expect("a is up");
+        assert_eq!(
+            next(&mut subscription).await.expect("a notifies").ok(),
+            Some(name("a"))
+        );
+
+        endpoints.kill("a");
+        assert!(matches!(next(&mut subscription).await, Some(Err(_))));
+        assert!(next(&mut subscription).await.is_none());
+        assert!(endpoints.subscriptions("b").is_empty());
+    }
+}
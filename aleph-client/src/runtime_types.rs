@@ -1,3 +1,5 @@
+use codec::Decode;
+
 pub use crate::aleph_zero::api::runtime_types::*;
 use crate::{
     api::runtime_types::{
@@ -5,6 +7,7 @@ use crate::{
         sp_consensus_aura::sr25519::app_sr25519::Public as AuraPublic,
         sp_core::{ed25519::Public as EdPublic, sr25519::Public as SrPublic},
     },
+    pallet_identity::types::Data,
     pallet_staking::EraRewardPoints,
     primitives::AlephNodeSessionKeys as SessionKeys,
     sp_weights::weight_v2::Weight,
@@ -50,6 +53,19 @@ impl TryFrom<String> for SessionKeys {
     }
 }
 
+impl TryFrom<Vec<u8>> for Data {
+    type Error = ();
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        if bytes.len() > 32 {
+            return Err(());
+        }
+        // `Data::RawN` is encoded as the variant index `N + 1` followed by the `N` bytes.
+        let encoded = [vec![bytes.len() as u8 + 1], bytes].concat();
+        Data::decode(&mut &encoded[..]).map_err(|_| ())
+    }
+}
+
 impl Weight {
     /// Returns new instance of weight v2 object.
     pub const fn new(ref_time: u64, proof_size: u64) -> Self {
@@ -59,3 +75,28 @@ impl Weight {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::pallet_identity::types::Data;
+
+    #[test]
+    fn converts_empty_bytes_to_raw0() {
+        assert_eq!(Data::try_from(vec![]), Ok(Data::Raw0([])));
+    }
+
+    #[test]
+    fn converts_single_byte_to_raw1() {
+        assert_eq!(Data::try_from(vec![7]), Ok(Data::Raw1([7])));
+    }
+
+    #[test]
+    fn converts_32_bytes_to_raw32() {
+        assert_eq!(Data::try_from(vec![7; 32]), Ok(Data::Raw32([7; 32])));
+    }
+
+    #[test]
+    fn refuses_more_than_32_bytes() {
+        assert_eq!(Data::try_from(vec![7; 33]), Err(()));
+    }
+}
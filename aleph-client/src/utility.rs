@@ -1,12 +1,14 @@
 use anyhow::anyhow;
+use codec::{Decode, Encode};
 use log::debug;
-use primitives::Balance;
+use primitives::{Balance, Header, ALEPH_ENGINE_ID};
 use subxt::{blocks::ExtrinsicEvents, config::Hasher, Config};
 
 use crate::{
     api::transaction_payment::events::TransactionFeePaid,
     connections::{AsConnection, TxInfo},
     pallets::{committee_management::CommitteeManagementApi, staking::StakingApi},
+    sp_core::Bytes,
     AlephConfig, BlockHash, BlockNumber, EraIndex, SessionIndex,
 };
 
@@ -42,6 +44,25 @@ pub trait BlocksApi {
         block: Option<BlockHash>,
     ) -> anyhow::Result<Option<BlockNumber>>;
 
+    /// Returns the header of a given block together with its Aleph justification, if the given
+    /// block exists, otherwise `None`. The justification is missing for blocks that were not
+    /// finalized directly, but only through one of their descendants.
+    /// * `block` - hash of the block to query
+    async fn get_justified_header(
+        &self,
+        block: BlockHash,
+    ) -> anyhow::Result<Option<(Header, Option<Vec<u8>>)>>;
+
+    /// Returns a proof of the values of the given storage keys in the state of a given block,
+    /// as a list of trie nodes.
+    /// * `keys` - storage keys to prove
+    /// * `block` - hash of the block to prove the values at
+    async fn get_storage_proof(
+        &self,
+        keys: &[Vec<u8>],
+        block: BlockHash,
+    ) -> anyhow::Result<Vec<Bytes>>;
+
     /// Fetch all events that corresponds to the transaction identified by `tx_info`.
     async fn get_tx_events(&self, tx_info: TxInfo) -> anyhow::Result<ExtrinsicEvents<AlephConfig>>;
 
@@ -109,6 +130,46 @@ impl<C: AsConnection + Sync> BlocksApi for C {
             .map_err(|e| e.into())
     }
 
+    async fn get_justified_header(
+        &self,
+        block: BlockHash,
+    ) -> anyhow::Result<Option<(Header, Option<Vec<u8>>)>> {
+        let response = match self
+            .as_connection()
+            .as_client()
+            .rpc()
+            .block(Some(block))
+            .await?
+        {
+            Some(response) => response,
+            None => return Ok(None),
+        };
+        let header = Header::decode(&mut &response.block.header.encode()[..])?;
+        let justification = response
+            .justifications
+            .unwrap_or_default()
+            .into_iter()
+            .find(|(engine_id, _)| *engine_id == ALEPH_ENGINE_ID)
+            .map(|(_, justification)| justification);
+
+        Ok(Some((header, justification)))
+    }
+
+    async fn get_storage_proof(
+        &self,
+        keys: &[Vec<u8>],
+        block: BlockHash,
+    ) -> anyhow::Result<Vec<Bytes>> {
+        let proof = self
+            .as_connection()
+            .as_client()
+            .rpc()
+            .read_proof(keys.iter().map(|key| key.as_slice()), Some(block))
+            .await?;
+
+        Ok(proof.proof.into_iter().map(|node| node.0.into()).collect())
+    }
+
     async fn get_tx_events(&self, tx_info: TxInfo) -> anyhow::Result<ExtrinsicEvents<AlephConfig>> {
         let block_body = self
             .as_connection()
@@ -102,6 +102,25 @@ pub struct ContractRemoveCode {
     pub code_hash: BlockHash,
 }
 
+#[derive(Debug, Clone, Args)]
+pub struct IdentityFields {
+    /// Display name, at most 32 bytes, like the other fields
+    #[clap(long)]
+    pub display: Option<String>,
+    /// Legal name
+    #[clap(long)]
+    pub legal: Option<String>,
+    /// Website address
+    #[clap(long)]
+    pub web: Option<String>,
+    /// Email address
+    #[clap(long)]
+    pub email: Option<String>,
+    /// Twitter handle
+    #[clap(long)]
+    pub twitter: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChangeValidatorArgs {
     pub reserved_validators: Option<Vec<AccountId>>,
@@ -139,6 +158,62 @@ impl From<ExtrinsicState> for TxStatus {
     }
 }
 
+/// Formats in which finality proofs can be saved.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ProofFormat {
+    /// Human readable, with binary data hex encoded.
+    Json,
+    /// The SCALE encoding of the proof.
+    Scale,
+}
+
+/// Calls which can be prepared with `prepare` and signed offline.
+#[derive(Debug, Clone, Subcommand)]
+pub enum OfflineCall {
+    /// Transfer funds via balances pallet, keeping the sender alive.
+    TransferKeepAlive {
+        /// Number of tokens to send.
+        #[clap(long)]
+        amount_in_tokens: u64,
+
+        /// SS58 id of target account.
+        #[clap(long)]
+        to_account: String,
+    },
+
+    /// Bond the signer's funds.
+    Bond {
+        /// Stake to bond (in tokens).
+        #[clap(long)]
+        initial_stake_tokens: u32,
+    },
+
+    /// Declare the desire to validate.
+    Validate {
+        /// Validator commission percentage.
+        #[clap(long)]
+        commission_percentage: u8,
+    },
+
+    /// Make a proposal to the treasury.
+    TreasuryPropose {
+        /// How many tokens we intend to give to the beneficiary.
+        #[clap(long)]
+        amount_in_tokens: u64,
+
+        /// SS58 id of the beneficiary account.
+        #[clap(long)]
+        beneficiary: String,
+    },
+
+    /// Update the runtime. The signer has to be sudo.
+    UpdateRuntime {
+        /// Path to WASM file with runtime.
+        #[clap(long)]
+        runtime: String,
+    },
+}
+
 #[derive(Debug, Clone, Subcommand)]
 pub enum Command {
     /// Staking call to bond stash with controller
@@ -158,6 +233,30 @@ pub enum Command {
     /// Force new era in staking world. Requires sudo.
     ForceNewEra,
 
+    /// Set identity information of the signer account. Fields not given are left empty.
+    SetIdentity(IdentityFields),
+
+    /// Clear identity information of the signer account, returning its deposit.
+    ClearIdentity,
+
+    /// Request a judgement of the signer's identity from a registrar.
+    RequestJudgement {
+        /// Index of the registrar.
+        #[clap(long)]
+        registrar_index: u32,
+
+        /// Maximum fee the registrar can charge (in rappens, not in tokens).
+        #[clap(long)]
+        max_fee: Balance,
+    },
+
+    /// Print identity information and judgements of the specified account.
+    IdentityOf {
+        /// SS58 id of the account.
+        #[clap(long)]
+        account_id: String,
+    },
+
     /// Finalize the specified block using seed as emergency finalizer.
     Finalize {
         /// Block number to finalize.
@@ -182,6 +281,93 @@ pub enum Command {
         finalizer_seed: Option<String>,
     },
 
+    /// Save proofs of finality of a range of blocks to a file, verifiable without a node against
+    /// the authorities of the session of the first block.
+    ///
+    /// Blocks finalized without a justification of their own are skipped.
+    ExportFinalityProof {
+        /// The first block of the range.
+        #[clap(long)]
+        from: BlockNumber,
+
+        /// The last block of the range, inclusive. Has to be finalized.
+        #[clap(long)]
+        to: BlockNumber,
+
+        /// Path to save the proof to.
+        #[clap(long, parse(from_os_str))]
+        output: PathBuf,
+
+        /// Path to save the authorities of the session of the first block to, as reported by the
+        /// node. The proof can be verified only against authorities obtained from a trusted
+        /// source, so these should be compared with such.
+        #[clap(long, parse(from_os_str))]
+        authorities_output: Option<PathBuf>,
+
+        #[clap(long, value_enum, default_value_t=ProofFormat::Json)]
+        format: ProofFormat,
+    },
+
+    /// Verify proofs of finality saved with `export-finality-proof`, without connecting to a node.
+    VerifyFinalityProof {
+        /// Path to the proof.
+        #[clap(long, parse(from_os_str))]
+        input: PathBuf,
+
+        /// Path to the JSON encoded authorities of the session the proof starts at, obtained from
+        /// a trusted source.
+        #[clap(long, parse(from_os_str))]
+        trusted_authorities: PathBuf,
+
+        #[clap(long, value_enum, default_value_t=ProofFormat::Json)]
+        format: ProofFormat,
+    },
+
+    /// Fetch the nonce and chain parameters needed to sign a call offline and save them, together
+    /// with the call, as an unsigned payload. Does not need a seed.
+    Prepare {
+        /// SS58 id of the account which is going to sign the call.
+        #[clap(long)]
+        signer: String,
+
+        /// Number of blocks the transaction stays valid for, counting from the last finalized
+        /// block. If not given, the transaction is immortal.
+        #[clap(long)]
+        mortality: Option<u64>,
+
+        /// Tip for the block author (in rappens, not in tokens).
+        #[clap(long, default_value = "0")]
+        tip: Balance,
+
+        /// Path to save the unsigned payload to.
+        #[clap(long, parse(from_os_str))]
+        output: PathBuf,
+
+        #[clap(subcommand)]
+        call: OfflineCall,
+    },
+
+    /// Sign a payload saved with `prepare`, without connecting to a node.
+    Sign {
+        /// Path to the unsigned payload.
+        #[clap(long, parse(from_os_str))]
+        input: PathBuf,
+
+        /// Path to save the signed extrinsic to.
+        #[clap(long, parse(from_os_str))]
+        output: PathBuf,
+    },
+
+    /// Broadcast an extrinsic signed with `sign`. Does not need a seed.
+    Submit {
+        /// Path to the signed extrinsic.
+        #[clap(long, parse(from_os_str))]
+        input: PathBuf,
+
+        #[clap(long, value_enum, default_value_t=ExtrinsicState::Finalized)]
+        expected_state: ExtrinsicState,
+    },
+
     /// Gets next session keys for a validator with specified AccountId
     NextSessionKeys {
         /// SS58 id of the validator for which we want to retrieve the keys
@@ -195,6 +381,108 @@ pub enum Command {
         nominee: String,
     },
 
+    /// Pause a call, so that it cannot be dispatched. Requires sudo.
+    PauseCall {
+        /// Name of the pallet, as in the runtime, e.g. Balances.
+        #[clap(long)]
+        pallet: String,
+
+        /// Name of the call, e.g. transfer_keep_alive.
+        #[clap(long)]
+        call: String,
+    },
+
+    /// Unpause a call paused with `pause-call`. Requires sudo.
+    UnpauseCall {
+        /// Name of the pallet, as in the runtime, e.g. Balances.
+        #[clap(long)]
+        pallet: String,
+
+        /// Name of the call, e.g. transfer_keep_alive.
+        #[clap(long)]
+        call: String,
+    },
+
+    /// Print whether a call is paused.
+    IsCallPaused {
+        /// Name of the pallet, as in the runtime, e.g. Balances.
+        #[clap(long)]
+        pallet: String,
+
+        /// Name of the call, e.g. transfer_keep_alive.
+        #[clap(long)]
+        call: String,
+    },
+
+    /// Create a nomination pool, bonding the given stake.
+    PoolCreate {
+        /// Initial stake of the pool (in tokens).
+        #[clap(long)]
+        amount_in_tokens: u64,
+
+        /// SS58 id of the pool root. Defaults to the signer.
+        #[clap(long)]
+        root: Option<String>,
+
+        /// SS58 id of the pool nominator. Defaults to the signer.
+        #[clap(long)]
+        nominator: Option<String>,
+
+        /// SS58 id of the pool bouncer. Defaults to the signer.
+        #[clap(long)]
+        bouncer: Option<String>,
+    },
+
+    /// Join a nomination pool.
+    PoolJoin {
+        /// Stake to bond (in tokens).
+        #[clap(long)]
+        amount_in_tokens: u64,
+
+        /// Id of the pool to join.
+        #[clap(long)]
+        pool_id: u32,
+    },
+
+    /// Bond more funds to the pool the signer is a member of.
+    PoolBondExtra {
+        /// Extra stake to bond (in tokens). If not given, pending rewards are bonded instead.
+        #[clap(long)]
+        amount_in_tokens: Option<u64>,
+    },
+
+    /// Unbond points of a pool member.
+    PoolUnbond {
+        /// SS58 id of the member. Defaults to the signer.
+        #[clap(long)]
+        member: Option<String>,
+
+        /// Number of points to unbond.
+        #[clap(long)]
+        unbonding_points: Balance,
+    },
+
+    /// Withdraw unbonded funds of a pool member, once the bonding duration passed.
+    PoolWithdrawUnbonded {
+        /// SS58 id of the member. Defaults to the signer.
+        #[clap(long)]
+        member: Option<String>,
+
+        /// Number of slashing spans of the pool's stash.
+        #[clap(long, default_value = "0")]
+        num_slashing_spans: u32,
+    },
+
+    /// Claim pending pool rewards of the signer.
+    PoolClaimPayout,
+
+    /// Print the state of a nomination pool.
+    PoolInfo {
+        /// Id of the pool.
+        #[clap(long)]
+        pool_id: u32,
+    },
+
     /// Associate the node with a specific staking account.
     PrepareKeys,
 
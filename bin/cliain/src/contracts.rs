@@ -244,10 +244,13 @@ pub async fn call(
     Ok(())
 }
 
-pub async fn code_info(connection: Connection, command: ContractCodeInfo) -> Option<CodeInfo> {
+pub async fn code_info(
+    connection: Connection,
+    command: ContractCodeInfo,
+) -> anyhow::Result<Option<CodeInfo>> {
     let ContractCodeInfo { code_hash } = command;
 
-    connection.get_code_info(code_hash, None).await
+    Ok(connection.try_get_code_info(code_hash, None).await?)
 }
 
 pub async fn remove_code(
@@ -0,0 +1,158 @@
+use std::{fs, path::PathBuf};
+
+use aleph_client::{
+    finality_proof::{
+        AuthorityChange, BlockFinalityProof, FinalityProofBundle, SessionAuthorities,
+    },
+    next_authority_data_storage_keys,
+    pallets::{aleph::AlephApi, committee_management::CommitteeManagementApi},
+    utility::BlocksApi,
+    Connection,
+};
+use anyhow::{anyhow, Context};
+use codec::{Decode, Encode};
+use log::{info, warn};
+use primitives::{BlockHash, BlockNumber, HeaderT};
+
+use crate::commands::ProofFormat;
+
+fn write_bundle(
+    bundle: &FinalityProofBundle,
+    path: &PathBuf,
+    format: ProofFormat,
+) -> anyhow::Result<()> {
+    let bytes = match format {
+        ProofFormat::Json => serde_json::to_vec_pretty(bundle)?,
+        ProofFormat::Scale => bundle.encode(),
+    };
+    fs::write(path, bytes).with_context(|| format!("Failed to write proof to {path:?}"))
+}
+
+fn read_bundle(path: &PathBuf, format: ProofFormat) -> anyhow::Result<FinalityProofBundle> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read proof from {path:?}"))?;
+    Ok(match format {
+        ProofFormat::Json => serde_json::from_slice(&bytes)?,
+        ProofFormat::Scale => FinalityProofBundle::decode(&mut bytes.as_slice())?,
+    })
+}
+
+async fn justified_block(
+    connection: &Connection,
+    number: BlockNumber,
+) -> anyhow::Result<Option<BlockFinalityProof>> {
+    let hash = connection
+        .get_block_hash(number)
+        .await?
+        .ok_or_else(|| anyhow!("Block #{number} is unknown to the node"))?;
+    match connection.get_justified_header(hash).await? {
+        Some((header, justification)) => {
+            Ok(justification.map(|justification| BlockFinalityProof {
+                header,
+                justification: justification.into(),
+            }))
+        }
+        None => Err(anyhow!("Block #{number} is unknown to the node")),
+    }
+}
+
+/// Fetches the justifications of the finalized blocks in the range `from..=to`, together with
+/// proofs of the authority changes since the session of `from`, verifies them and saves them to
+/// `output`. The authorities of the session of `from`, which the proof has to be verified
+/// against, are saved to `authorities_output` if given.
+pub async fn export_finality_proof(
+    connection: Connection,
+    from: BlockNumber,
+    to: BlockNumber,
+    output: PathBuf,
+    authorities_output: Option<PathBuf>,
+    format: ProofFormat,
+) -> anyhow::Result<()> {
+    let finalized = connection.get_finalized_block_hash().await?;
+    let finalized = connection
+        .get_block_number(finalized)
+        .await?
+        .ok_or_else(|| anyhow!("The finalized block is unknown to the node"))?;
+    if to > finalized {
+        return Err(anyhow!(
+            "Block #{to} is not finalized yet, the last finalized block is #{finalized}"
+        ));
+    }
+
+    let session_period = connection.get_session_period().await?;
+    let mut bundle = FinalityProofBundle::new(session_period);
+    for number in from..=to {
+        match justified_block(&connection, number).await? {
+            Some(proof) => bundle.blocks.push(proof),
+            None => warn!("Block #{number} has no justification, skipping."),
+        }
+    }
+
+    let first_session = bundle.session_of(from);
+    let first_block = connection
+        .first_block_of_session(first_session)
+        .await?
+        .ok_or_else(|| anyhow!("The first block of session {first_session} is unknown"))?;
+    let trusted = SessionAuthorities::new(
+        first_session,
+        connection.authority_data(Some(first_block)).await?,
+    );
+
+    for session in first_session..bundle.session_of(to) {
+        let number = bundle.last_block_of_session(session);
+        let last_block = justified_block(&connection, number)
+            .await?
+            .ok_or_else(|| anyhow!("The last block of session {session} has no justification"))?;
+        let next_authorities_proof = connection
+            .get_storage_proof(
+                &next_authority_data_storage_keys(),
+                BlockHash::from(last_block.header.hash().0),
+            )
+            .await?;
+        bundle.authority_changes.push(AuthorityChange {
+            last_block,
+            next_authorities_proof,
+        });
+    }
+
+    let verified = bundle
+        .verify(&trusted)
+        .map_err(|e| anyhow!("The node returned an incorrect proof: {e}"))?;
+    write_bundle(&bundle, &output, format)?;
+    info!(
+        "Saved proofs of finality of {} blocks with {} authority changes to {:?}.",
+        verified.len(),
+        bundle.authority_changes.len(),
+        output
+    );
+    if let Some(path) = authorities_output {
+        let bytes = serde_json::to_vec_pretty(&trusted)?;
+        fs::write(&path, bytes).with_context(|| format!("Failed to write {path:?}"))?;
+        info!(
+            "Saved the authorities of session {} to {:?}.",
+            trusted.session, path
+        );
+    }
+
+    Ok(())
+}
+
+/// Verifies the proofs saved in `input` against the authorities saved in `trusted_authorities`,
+/// without connecting to a node. The authorities have to come from a source trusted by the user.
+pub fn verify_finality_proof(
+    input: PathBuf,
+    trusted_authorities: PathBuf,
+    format: ProofFormat,
+) -> anyhow::Result<()> {
+    let bundle = read_bundle(&input, format)?;
+    let bytes = fs::read(&trusted_authorities)
+        .with_context(|| format!("Failed to read {trusted_authorities:?}"))?;
+    let trusted: SessionAuthorities = serde_json::from_slice(&bytes)?;
+    let verified = bundle
+        .verify(&trusted)
+        .map_err(|e| anyhow!("The proof is incorrect: {e}"))?;
+    for (number, hash) in verified {
+        info!("Block #{number} ({hash:?}) is finalized.");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use aleph_client::{
+    bounded_collections::bounded_vec::BoundedVec,
+    pallet_identity::{legacy::IdentityInfo, types::Data},
+    pallets::identity::{IdentityApi, IdentityUserApi},
+    AccountId, Balance, Connection, SignedConnection, Ss58Codec, TxStatus,
+};
+use log::error;
+
+use crate::commands::IdentityFields;
+
+fn to_data(field: Option<String>) -> Data {
+    match field {
+        Some(field) => Data::try_from(field.into_bytes())
+            .expect("Identity fields should be at most 32 bytes long"),
+        None => Data::None,
+    }
+}
+
+pub async fn set_identity(connection: SignedConnection, fields: IdentityFields) {
+    let IdentityFields {
+        display,
+        legal,
+        web,
+        email,
+        twitter,
+    } = fields;
+    let info = IdentityInfo {
+        additional: BoundedVec(vec![]),
+        display: to_data(display),
+        legal: to_data(legal),
+        web: to_data(web),
+        riot: Data::None,
+        email: to_data(email),
+        pgp_fingerprint: None,
+        image: Data::None,
+        twitter: to_data(twitter),
+    };
+    connection
+        .set_identity(info, TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn clear_identity(connection: SignedConnection) {
+    connection
+        .clear_identity(TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn request_judgement(
+    connection: SignedConnection,
+    registrar_index: u32,
+    max_fee: Balance,
+) {
+    connection
+        .request_judgement(registrar_index, max_fee, TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn identity_of(connection: Connection, account_id: String) {
+    let account_id = AccountId::from_ss58check(&account_id).expect("Address is valid");
+    match connection.try_get_identity(account_id, None).await {
+        Ok(Some(registration)) => println!("{registration:#?}"),
+        Ok(None) => error!("No identity set for the specified account."),
+        Err(e) => error!("Failed to read identity: {}.", e),
+    }
+}
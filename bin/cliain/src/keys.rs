@@ -42,14 +42,15 @@ pub async fn rotate_keys(connection: Connection) {
 
 pub async fn next_session_keys(connection: Connection, account_id: String) {
     let account_id = AccountId::from_ss58check(&account_id).expect("Address is valid");
-    match connection.get_next_session_keys(account_id, None).await {
-        Some(keys) => {
+    match connection.try_get_next_session_keys(account_id, None).await {
+        Ok(Some(keys)) => {
             let keys_json = json!({
                 "aura": "0x".to_owned() + keys.aura.0.0.encode_hex::<String>().as_str(),
                 "aleph": "0x".to_owned() + keys.aleph.0.0.encode_hex::<String>().as_str(),
             });
             println!("{}", serde_json::to_string_pretty(&keys_json).unwrap());
         }
-        None => error!("No keys set for the specified account."),
+        Ok(None) => error!("No keys set for the specified account."),
+        Err(e) => error!("Failed to read session keys: {}.", e),
     }
 }
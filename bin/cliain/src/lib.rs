@@ -1,23 +1,36 @@
 mod commands;
 mod contracts;
+mod finality_proof;
 mod finalization;
+mod identity;
 mod keys;
+mod nomination_pools;
+mod offline;
 mod runtime;
 mod secret;
 mod staking;
 mod transfer;
 mod treasury;
+mod tx_pause;
 mod validators;
 mod version_upgrade;
 mod vesting;
 
 use aleph_client::{keypair_from_string, Connection, RootConnection, SignedConnection};
-pub use commands::{Command};
+pub use commands::{Command, OfflineCall, ProofFormat};
 pub use contracts::{
     call, code_info, instantiate, instantiate_with_code, remove_code, upload_code,
 };
+pub use finality_proof::{export_finality_proof, verify_finality_proof};
 pub use finalization::{finalize, set_emergency_finalizer};
+pub use identity::{clear_identity, identity_of, request_judgement, set_identity};
 pub use keys::{next_session_keys, prepare_keys, rotate_keys, set_keys};
+pub use nomination_pools::{
+    bond_extra as pool_bond_extra, claim_payout as pool_claim_payout, create as pool_create,
+    info as pool_info, join as pool_join, unbond as pool_unbond,
+    withdraw_unbonded as pool_withdraw_unbonded,
+};
+pub use offline::{prepare as offline_prepare, sign as offline_sign, submit as offline_submit};
 pub use runtime::update_runtime;
 pub use secret::prompt_password_hidden;
 pub use staking::{bond, force_new_era, nominate, set_staking_limits, validate};
@@ -25,6 +38,7 @@ pub use transfer::transfer_keep_alive;
 pub use treasury::{
     approve as treasury_approve, propose as treasury_propose, reject as treasury_reject,
 };
+pub use tx_pause::{is_call_paused, pause_call, unpause_call};
 pub use validators::change_validators;
 pub use version_upgrade::schedule_upgrade;
 pub use vesting::{vest, vest_other, vested_transfer};
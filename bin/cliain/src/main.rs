@@ -3,12 +3,15 @@ use std::env;
 use aleph_client::{account_from_keypair, aleph_keypair_from_string, keypair_from_string, Pair};
 use clap::Parser;
 use cliain::{
-    bond, call, change_validators, code_info, finalize, force_new_era, instantiate,
-    instantiate_with_code, next_session_keys, nominate, prepare_keys, prompt_password_hidden,
-    remove_code, rotate_keys, schedule_upgrade, set_emergency_finalizer, set_keys,
-    set_staking_limits, transfer_keep_alive, treasury_approve, treasury_propose,
-    treasury_reject, update_runtime, upload_code, validate, vest, vest_other, vested_transfer,
-    Command, ConnectionConfig,
+    bond, call, change_validators, clear_identity, code_info, export_finality_proof, finalize,
+    force_new_era, identity_of, instantiate, instantiate_with_code, is_call_paused,
+    next_session_keys, nominate, offline_prepare, offline_sign, offline_submit, pause_call,
+    pool_bond_extra, pool_claim_payout, pool_create, pool_info, pool_join, pool_unbond,
+    pool_withdraw_unbonded, prepare_keys, prompt_password_hidden, remove_code, request_judgement,
+    rotate_keys, schedule_upgrade, set_emergency_finalizer, set_identity, set_keys,
+    set_staking_limits, transfer_keep_alive, treasury_approve, treasury_propose, treasury_reject,
+    unpause_call, update_runtime, upload_code, validate, verify_finality_proof, vest, vest_other,
+    vested_transfer, Command, ConnectionConfig,
 };
 use log::{error, info};
 
@@ -37,7 +40,14 @@ fn read_seed(command: &Command, seed: Option<String>) -> String {
             hash: _,
             finalizer_seed: _,
         }
+        | Command::ExportFinalityProof { .. }
+        | Command::VerifyFinalityProof { .. }
+        | Command::Prepare { .. }
+        | Command::Submit { .. }
         | Command::NextSessionKeys { .. }
+        | Command::IdentityOf { .. }
+        | Command::IsCallPaused { .. }
+        | Command::PoolInfo { .. }
         | Command::RotateKeys
         | Command::SeedToSS58 { .. }
         | Command::ContractCodeInfo { .. } => String::new(),
@@ -87,6 +97,50 @@ async fn main() -> anyhow::Result<()> {
             let finalizer = aleph_keypair_from_string(&finalizer_seed);
             finalize(cfg.get_connection().await, block, hash, finalizer).await;
         }
+        Command::ExportFinalityProof {
+            from,
+            to,
+            output,
+            authorities_output,
+            format,
+        } => {
+            export_finality_proof(
+                cfg.get_connection().await,
+                from,
+                to,
+                output,
+                authorities_output,
+                format,
+            )
+            .await?
+        }
+        Command::VerifyFinalityProof {
+            input,
+            trusted_authorities,
+            format,
+        } => verify_finality_proof(input, trusted_authorities, format)?,
+        Command::Prepare {
+            signer,
+            mortality,
+            tip,
+            output,
+            call,
+        } => {
+            offline_prepare(
+                cfg.get_connection().await,
+                signer,
+                call,
+                mortality,
+                tip,
+                output,
+            )
+            .await?
+        }
+        Command::Sign { input, output } => offline_sign(keypair_from_string(&seed), input, output)?,
+        Command::Submit {
+            input,
+            expected_state,
+        } => offline_submit(cfg.get_connection().await, input, expected_state.into()).await?,
         Command::SetEmergencyFinalizer { finalizer_seed } => {
             let finalizer_seed = read_secret(finalizer_seed, "Provide finalizer seed:");
             let finalizer = aleph_keypair_from_string(&finalizer_seed);
@@ -149,6 +203,65 @@ async fn main() -> anyhow::Result<()> {
         Command::ForceNewEra => {
             force_new_era(cfg.get_root_connection().await).await;
         }
+        Command::SetIdentity(fields) => {
+            set_identity(cfg.get_signed_connection().await, fields).await
+        }
+        Command::ClearIdentity => clear_identity(cfg.get_signed_connection().await).await,
+        Command::RequestJudgement {
+            registrar_index,
+            max_fee,
+        } => request_judgement(cfg.get_signed_connection().await, registrar_index, max_fee).await,
+        Command::IdentityOf { account_id } => {
+            identity_of(cfg.get_connection().await, account_id).await
+        }
+        Command::PauseCall { pallet, call } => {
+            pause_call(cfg.get_root_connection().await, pallet, call).await
+        }
+        Command::UnpauseCall { pallet, call } => {
+            unpause_call(cfg.get_root_connection().await, pallet, call).await
+        }
+        Command::IsCallPaused { pallet, call } => {
+            is_call_paused(cfg.get_connection().await, pallet, call).await
+        }
+        Command::PoolCreate {
+            amount_in_tokens,
+            root,
+            nominator,
+            bouncer,
+        } => {
+            pool_create(
+                cfg.get_signed_connection().await,
+                amount_in_tokens,
+                root,
+                nominator,
+                bouncer,
+            )
+            .await
+        }
+        Command::PoolJoin {
+            amount_in_tokens,
+            pool_id,
+        } => pool_join(cfg.get_signed_connection().await, amount_in_tokens, pool_id).await,
+        Command::PoolBondExtra { amount_in_tokens } => {
+            pool_bond_extra(cfg.get_signed_connection().await, amount_in_tokens).await
+        }
+        Command::PoolUnbond {
+            member,
+            unbonding_points,
+        } => pool_unbond(cfg.get_signed_connection().await, member, unbonding_points).await,
+        Command::PoolWithdrawUnbonded {
+            member,
+            num_slashing_spans,
+        } => {
+            pool_withdraw_unbonded(
+                cfg.get_signed_connection().await,
+                member,
+                num_slashing_spans,
+            )
+            .await
+        }
+        Command::PoolClaimPayout => pool_claim_payout(cfg.get_signed_connection().await).await,
+        Command::PoolInfo { pool_id } => pool_info(cfg.get_connection().await, pool_id).await,
         Command::SeedToSS58 { input } => {
             let input = read_secret(input, "Provide seed:");
             info!(
@@ -207,10 +320,10 @@ async fn main() -> anyhow::Result<()> {
             }
         }
         Command::ContractCodeInfo(command) => {
-            println!(
-                "{:#?}",
-                code_info(cfg.get_connection().await, command).await
-            )
+            match code_info(cfg.get_connection().await, command).await {
+                Ok(result) => println!("{result:#?}"),
+                Err(why) => error!("Contract code info query failed {:?}", why),
+            }
         }
         Command::ContractRemoveCode(command) => {
             match remove_code(cfg.get_signed_connection().await, command).await {
@@ -0,0 +1,97 @@
+use aleph_client::{
+    pallet_nomination_pools::BondExtra,
+    pallets::nomination_pools::{NominationPoolsApi, NominationPoolsUserApi},
+    AccountId, Balance, Connection, SignedConnection, SignedConnectionApi, Ss58Codec, TxStatus,
+};
+use log::error;
+use primitives::TOKEN;
+
+fn account_or_signer(connection: &SignedConnection, account: Option<String>) -> AccountId {
+    match account {
+        Some(account) => AccountId::from_ss58check(&account).expect("Address is valid"),
+        None => connection.account_id().clone(),
+    }
+}
+
+pub async fn create(
+    connection: SignedConnection,
+    amount_in_tokens: u64,
+    root: Option<String>,
+    nominator: Option<String>,
+    bouncer: Option<String>,
+) {
+    let root = account_or_signer(&connection, root);
+    let nominator = account_or_signer(&connection, nominator);
+    let bouncer = account_or_signer(&connection, bouncer);
+    connection
+        .create_pool(
+            amount_in_tokens as Balance * TOKEN,
+            root,
+            nominator,
+            bouncer,
+            TxStatus::Finalized,
+        )
+        .await
+        .unwrap();
+}
+
+pub async fn join(connection: SignedConnection, amount_in_tokens: u64, pool_id: u32) {
+    connection
+        .join_pool(
+            amount_in_tokens as Balance * TOKEN,
+            pool_id,
+            TxStatus::Finalized,
+        )
+        .await
+        .unwrap();
+}
+
+pub async fn bond_extra(connection: SignedConnection, amount_in_tokens: Option<u64>) {
+    let extra = match amount_in_tokens {
+        Some(amount) => BondExtra::FreeBalance(amount as Balance * TOKEN),
+        None => BondExtra::Rewards,
+    };
+    connection
+        .bond_extra_to_pool(extra, TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn unbond(
+    connection: SignedConnection,
+    member: Option<String>,
+    unbonding_points: Balance,
+) {
+    let member = account_or_signer(&connection, member);
+    connection
+        .unbond_from_pool(member, unbonding_points, TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn withdraw_unbonded(
+    connection: SignedConnection,
+    member: Option<String>,
+    num_slashing_spans: u32,
+) {
+    let member = account_or_signer(&connection, member);
+    connection
+        .withdraw_unbonded_from_pool(member, num_slashing_spans, TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn claim_payout(connection: SignedConnection) {
+    connection
+        .claim_pool_payout(TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn info(connection: Connection, pool_id: u32) {
+    match connection.try_get_bonded_pool(pool_id, None).await {
+        Ok(Some(pool)) => println!("{pool:#?}"),
+        Ok(None) => error!("There is no pool with id {}.", pool_id),
+        Err(e) => error!("Failed to read pool {}: {}.", pool_id, e),
+    }
+}
@@ -0,0 +1,142 @@
+use std::{fs, path::PathBuf};
+
+use aleph_client::{
+    aleph_runtime::RuntimeCall::System,
+    api,
+    frame_system::pallet::Call::set_code,
+    offline::{SignedPayload, UnsignedPayload},
+    pallet_staking::{RewardDestination, ValidatorPrefs},
+    sp_arithmetic::per_things::Perbill,
+    sp_runtime::Perbill as SPerbill,
+    sp_weights::weight_v2::Weight,
+    AccountId, Balance, Connection, KeyPair, Ss58Codec, TxStatus,
+};
+use anyhow::{anyhow, Context};
+use log::info;
+use primitives::TOKEN;
+use serde::{de::DeserializeOwned, Serialize};
+use subxt::utils::{MultiAddress, Static};
+
+use crate::commands::OfflineCall;
+
+fn write_json<T: Serialize>(value: &T, path: &PathBuf) -> anyhow::Result<()> {
+    let bytes = serde_json::to_vec_pretty(value)?;
+    fs::write(path, bytes).with_context(|| format!("Failed to write {path:?}"))
+}
+
+fn read_json<T: DeserializeOwned>(path: &PathBuf) -> anyhow::Result<T> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {path:?}"))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn parse_account(account: &str) -> anyhow::Result<AccountId> {
+    AccountId::from_ss58check(account).map_err(|e| anyhow!("Invalid address {account}: {e:?}"))
+}
+
+/// Builds the call the same way the corresponding online command does, fetches the nonce of
+/// `signer` and the chain parameters, and saves the unsigned payload to `output`.
+pub async fn prepare(
+    connection: Connection,
+    signer: String,
+    call: OfflineCall,
+    mortality: Option<u64>,
+    tip: Balance,
+    output: PathBuf,
+) -> anyhow::Result<()> {
+    let signer = parse_account(&signer)?;
+    let payload = match call {
+        OfflineCall::TransferKeepAlive {
+            amount_in_tokens,
+            to_account,
+        } => {
+            let tx = api::tx().balances().transfer_keep_alive(
+                MultiAddress::Id(Static(parse_account(&to_account)?)),
+                amount_in_tokens as Balance * TOKEN,
+            );
+            UnsignedPayload::prepare(&connection, signer, tx, mortality, tip).await?
+        }
+        OfflineCall::Bond {
+            initial_stake_tokens,
+        } => {
+            let tx = api::tx().staking().bond(
+                initial_stake_tokens as Balance * TOKEN,
+                RewardDestination::Staked,
+            );
+            UnsignedPayload::prepare(&connection, signer, tx, mortality, tip).await?
+        }
+        OfflineCall::Validate {
+            commission_percentage,
+        } => {
+            let tx = api::tx().staking().validate(ValidatorPrefs {
+                commission: Perbill(
+                    SPerbill::from_percent(commission_percentage as u32).deconstruct(),
+                ),
+                blocked: false,
+            });
+            UnsignedPayload::prepare(&connection, signer, tx, mortality, tip).await?
+        }
+        OfflineCall::TreasuryPropose {
+            amount_in_tokens,
+            beneficiary,
+        } => {
+            let tx = api::tx().treasury().propose_spend(
+                amount_in_tokens as Balance * TOKEN,
+                MultiAddress::Id(Static(parse_account(&beneficiary)?)),
+            );
+            UnsignedPayload::prepare(&connection, signer, tx, mortality, tip).await?
+        }
+        OfflineCall::UpdateRuntime { runtime } => {
+            let code = fs::read(&runtime).with_context(|| format!("Failed to read {runtime:?}"))?;
+            let tx = api::tx().sudo().sudo_unchecked_weight(
+                System(set_code { code }),
+                Weight {
+                    ref_time: 0,
+                    proof_size: 0,
+                },
+            );
+            UnsignedPayload::prepare(&connection, signer, tx, mortality, tip).await?
+        }
+    };
+
+    write_json(&payload, &output)?;
+    info!(
+        "Saved unsigned {} with nonce {} to {:?}.",
+        payload.call_name, payload.nonce, output
+    );
+
+    Ok(())
+}
+
+/// Signs the payload saved with `prepare`, without connecting to a node.
+pub fn sign(signer: KeyPair, input: PathBuf, output: PathBuf) -> anyhow::Result<()> {
+    let payload: UnsignedPayload = read_json(&input)?;
+    info!(
+        "Signing {} for {} with nonce {}, tip {}, genesis {:?}, spec version {}, transaction version {}, mortality {:?}.",
+        payload.decoded_call()?,
+        payload.signer,
+        payload.nonce,
+        payload.tip,
+        payload.genesis_hash,
+        payload.spec_version,
+        payload.transaction_version,
+        payload.mortality,
+    );
+
+    let signed = payload.sign(&signer)?;
+    write_json(&signed, &output)?;
+    info!("Saved extrinsic {:?} to {:?}.", signed.tx_hash, output);
+
+    Ok(())
+}
+
+/// Broadcasts the extrinsic saved with `sign`.
+pub async fn submit(
+    connection: Connection,
+    input: PathBuf,
+    status: TxStatus,
+) -> anyhow::Result<()> {
+    let signed: SignedPayload = read_json(&input)?;
+    signed.submit(&connection, status).await?;
+
+    Ok(())
+}
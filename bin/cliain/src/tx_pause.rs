@@ -0,0 +1,26 @@
+use aleph_client::{
+    pallets::tx_pause::{TxPauseApi, TxPauseSudoApi},
+    Connection, RootConnection, TxStatus,
+};
+use log::error;
+
+pub async fn pause_call(connection: RootConnection, pallet: String, call: String) {
+    connection
+        .pause(&pallet, &call, TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn unpause_call(connection: RootConnection, pallet: String, call: String) {
+    connection
+        .unpause(&pallet, &call, TxStatus::Finalized)
+        .await
+        .unwrap();
+}
+
+pub async fn is_call_paused(connection: Connection, pallet: String, call: String) {
+    match connection.try_is_paused(&pallet, &call, None).await {
+        Ok(paused) => println!("{paused}"),
+        Err(e) => error!("Failed to read paused calls: {}.", e),
+    }
+}
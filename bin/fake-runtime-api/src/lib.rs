@@ -9,8 +9,9 @@ use pallet_transaction_payment::FeeDetails;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 use primitives::{
     crypto::SignatureSet, AccountId, ApiError as AlephApiError, AuraId, AuthorityId as AlephId,
-    AuthoritySignature, Balance, Block, Nonce, Perbill, Score, SessionAuthorityData,
-    SessionCommittee, SessionIndex, SessionValidatorError, Version as FinalityVersion,
+    AuthoritySignature, Balance, BanInfo, Block, ElectionPreview, Nonce, Perbill,
+    ProductionEquivocationProof, Score, SessionAuthorityData, SessionCommittee, SessionIndex,
+    SessionValidatorError, Version as FinalityVersion,
 };
 use sp_consensus_aura::SlotDuration;
 use sp_core::OpaqueMetadata;
@@ -141,6 +142,7 @@ pub mod fake_runtime {
             }
         }
 
+        #[api_version(5)]
          impl crate::AlephSessionApi<Block> for Runtime {
             fn millisecs_per_block() -> u64 {
                 unimplemented!()
@@ -199,6 +201,22 @@ pub mod fake_runtime {
             fn submit_abft_score(_score: Score, _signature: SignatureSet<AuthoritySignature>) -> Option<()>{
                 unimplemented!()
             }
+
+            fn submit_production_equivocation_report(_proof: ProductionEquivocationProof) -> Option<()> {
+                unimplemented!()
+            }
+
+            fn next_era_election_preview() -> Option<ElectionPreview<AccountId>> {
+                unimplemented!()
+            }
+
+            fn abft_score_history() -> Vec<Score> {
+                unimplemented!()
+            }
+
+            fn ban_history(_validator: AccountId) -> Vec<BanInfo> {
+                unimplemented!()
+            }
         }
 
         /// There’s an important remark on how this fake runtime must be implemented - it does not need to
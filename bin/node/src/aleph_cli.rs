@@ -35,6 +35,21 @@ pub struct AlephCli {
     #[clap(long, value_name = "PATH", group = "backup")]
     backup_path: Option<PathBuf>,
 
+    /// The number of sessions preceding the current one for which backups are kept.
+    ///
+    /// Backups of older sessions are removed when a new session starts. Keeping a few of them
+    /// around can help debugging, but they are never used for recovery.
+    #[clap(long, default_value_t = 0)]
+    backup_retained_sessions: u32,
+
+    /// The path of the record of sessions in which the node signed AlephBFT units.
+    ///
+    /// The record is kept even with `--no-backup`. After a restart the node does not sign in
+    /// sessions it already signed in, unless it can restore the units from the backup, so that
+    /// it cannot equivocate. Defaults to a file in the base path. Please do not remove it.
+    #[clap(long, value_name = "PATH")]
+    slashing_protection_path: Option<PathBuf>,
+
     /// The maximum number of nonfinalized blocks, after which block production should be locally
     /// stopped. DO NOT CHANGE THIS, PRODUCING MORE OR FEWER BLOCKS MIGHT BE CONSIDERED MALICIOUS
     /// BEHAVIOUR AND PUNISHED ACCORDINGLY!
@@ -55,10 +70,33 @@ pub struct AlephCli {
     #[clap(long, default_value_t = 5*1024*1024)]
     substrate_network_bit_rate: u64,
 
+    /// Maximum upload bit-rate in bits per second of the alephbft validator network.
+    /// Uploads are not limited if this option is not provided.
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    alephbft_network_upload_bit_rate: Option<u64>,
+
+    /// Maximum upload bit-rate in bits per second of the substrate network.
+    /// Uploads are not limited if this option is not provided.
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    substrate_network_upload_bit_rate: Option<u64>,
+
     /// Don't spend some extra time to collect more debugging data (e.g. validator network details).
     /// By default collecting is enabled, as the impact on performance is negligible, if any.
     #[clap(long, default_value_t = false)]
     no_collection_of_extra_debugging_data: bool,
+
+    /// The path to the Unix socket of a remote signer keeping the Aleph session keys, which are
+    /// taken from the keystore of the node otherwise. See the `remote-signer` subcommand.
+    #[clap(long, value_name = "PATH", requires = "remote_signer_secret_file")]
+    remote_signer_socket: Option<PathBuf>,
+
+    /// The path to the file with the secret shared with the remote signer.
+    #[clap(long, value_name = "PATH", requires = "remote_signer_socket")]
+    remote_signer_secret_file: Option<PathBuf>,
+
+    /// Whether to warp sync using Aleph justifications, set based on the `--sync` option.
+    #[clap(skip)]
+    warp_sync: bool,
 }
 
 impl AlephCli {
@@ -86,6 +124,14 @@ impl AlephCli {
         self.no_backup
     }
 
+    pub fn backup_retained_sessions(&self) -> u32 {
+        self.backup_retained_sessions
+    }
+
+    pub fn slashing_protection_path(&self) -> Option<PathBuf> {
+        self.slashing_protection_path.clone()
+    }
+
     pub fn max_nonfinalized_blocks(&self) -> u32 {
         if self.max_nonfinalized_blocks != DEFAULT_MAX_NON_FINALIZED_BLOCKS {
             warn!("Running block production with a value of max-nonfinalized-blocks {}, which is not the default of 20. THIS MIGHT BE CONSIDERED MALICIOUS BEHAVIOUR AND RESULT IN PENALTIES!", self.max_nonfinalized_blocks);
@@ -105,7 +151,31 @@ impl AlephCli {
         self.substrate_network_bit_rate
     }
 
+    pub fn alephbft_network_upload_bit_rate(&self) -> Option<u64> {
+        self.alephbft_network_upload_bit_rate
+    }
+
+    pub fn substrate_network_upload_bit_rate(&self) -> Option<u64> {
+        self.substrate_network_upload_bit_rate
+    }
+
     pub fn no_collection_of_extra_debugging_data(&self) -> bool {
         self.no_collection_of_extra_debugging_data
     }
+
+    pub fn remote_signer_socket(&self) -> Option<PathBuf> {
+        self.remote_signer_socket.clone()
+    }
+
+    pub fn remote_signer_secret_file(&self) -> Option<PathBuf> {
+        self.remote_signer_secret_file.clone()
+    }
+
+    pub fn warp_sync(&self) -> bool {
+        self.warp_sync
+    }
+
+    pub fn set_warp_sync(&mut self, warp_sync: bool) {
+        self.warp_sync = warp_sync;
+    }
 }
@@ -1,18 +1,28 @@
 use std::{collections::HashMap, sync::Arc};
 
 use finality_aleph::{
-    AlephJustification, BlockId, Justification, JustificationTranslator, ValidatorAddressCache,
-    ValidatorAddressingInfo,
+    backwards_compatible_decode, AbftScoreStatus, AbftStatus, AlephJustification, BlockId,
+    CommitteeStatus, Justification, JustificationInfo, JustificationTranslator, NodeStatus,
+    SessionPeriod, SyncStatus, ValidatorAddressCache, ValidatorAddressingInfo,
 };
-use futures::channel::mpsc;
+use futures::{channel::mpsc, stream, FutureExt, StreamExt};
 use jsonrpsee::{
     core::{error::Error as JsonRpseeError, RpcResult},
     proc_macros::rpc,
-    types::error::{CallError, ErrorObject},
+    types::{
+        error::{CallError, ErrorObject},
+        SubscriptionResult,
+    },
+    SubscriptionSink,
 };
-use parity_scale_codec::Decode;
-use primitives::{AccountId, Block, BlockHash, BlockNumber, Signature};
-use sc_client_api::StorageProvider;
+use log::warn;
+use parity_scale_codec::{Decode, Encode};
+use primitives::{
+    AccountId, AuthorityId, Block, BlockHash, BlockNumber, Header, Signature, ALEPH_ENGINE_ID,
+};
+use sc_client_api::{BlockBackend, BlockchainEvents, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{Deserialize, Serialize};
 use sp_arithmetic::traits::Zero;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::SyncOracle;
@@ -61,6 +71,15 @@ pub enum Error {
     /// Network info caching is not enabled.
     #[error("Unable to get any data, because network info caching is not enabled.")]
     NetworkInfoCachingNotEnabled,
+    /// Failed to read justifications.
+    #[error("Failed to read justifications of a block {0}: {1:?}.")]
+    FailedJustificationRead(String, sp_blockchain::Error),
+    /// Failed to decode justification.
+    #[error("Failed to decode justification of a block {0}: {1}.")]
+    FailedJustificationDecoding(String, String),
+    /// Failed to read the number of a block.
+    #[error("Failed to read the number of a block {0}: {1:?}.")]
+    FailedBlockNumberRead(String, sp_blockchain::Error),
 }
 
 // Base code for all system errors.
@@ -85,6 +104,12 @@ const FAILED_HEADER_DECODING_ERROR: i32 = BASE_ERROR + 8;
 const UNKNOWN_HASH_ERROR: i32 = BASE_ERROR + 9;
 /// Network info caching is not enabled.
 const NETWORK_INFO_CACHING_NOT_ENABLED_ERROR: i32 = BASE_ERROR + 10;
+/// Failed to read justifications.
+const FAILED_JUSTIFICATION_READ_ERROR: i32 = BASE_ERROR + 11;
+/// Failed to decode justification.
+const FAILED_JUSTIFICATION_DECODING_ERROR: i32 = BASE_ERROR + 12;
+/// Failed to read the number of a block.
+const FAILED_BLOCK_NUMBER_READ_ERROR: i32 = BASE_ERROR + 13;
 
 impl From<Error> for JsonRpseeError {
     fn from(e: Error) -> Self {
@@ -145,6 +170,21 @@ impl From<Error> for JsonRpseeError {
                 "Unable to get any data, because network info caching is not enabled.",
                 None::<()>,
             )),
+            Error::FailedJustificationRead(hash, err) => CallError::Custom(ErrorObject::owned(
+                FAILED_JUSTIFICATION_READ_ERROR,
+                format!("Failed to read justifications of a block {hash}: {err:?}."),
+                None::<()>,
+            )),
+            Error::FailedJustificationDecoding(hash, err) => CallError::Custom(ErrorObject::owned(
+                FAILED_JUSTIFICATION_DECODING_ERROR,
+                format!("Failed to decode justification of a block {hash}: {err}."),
+                None::<()>,
+            )),
+            Error::FailedBlockNumberRead(hash, err) => CallError::Custom(ErrorObject::owned(
+                FAILED_BLOCK_NUMBER_READ_ERROR,
+                format!("Failed to read the number of a block {hash}: {err:?}."),
+                None::<()>,
+            )),
         }
         .into()
     }
@@ -172,6 +212,61 @@ pub trait AlephNodeApi<BE> {
 
     #[method(name = "unstable_validatorNetworkInfo")]
     fn validator_network_info(&self) -> RpcResult<HashMap<AccountId, ValidatorAddressingInfo>>;
+
+    /// The AlephBFT session the node is currently running, if any.
+    #[method(name = "abftStatus")]
+    fn abft_status(&self) -> RpcResult<Option<AbftStatus>>;
+
+    /// The running performance score of the members of the current AlephBFT committee, as
+    /// computed by the node. `None` if the node is not a member of the committee.
+    #[method(name = "abftScore")]
+    fn abft_score(&self) -> RpcResult<Option<AbftScoreStatus>>;
+
+    /// The committees of the sessions the node takes part in as a validator, together with the
+    /// validator network peers of their members.
+    #[method(name = "committeeStatus")]
+    fn committee_status(&self) -> RpcResult<Vec<CommitteeStatus>>;
+
+    /// The Aleph justification of the block with given hash, or of the last finalized block if no
+    /// hash is given. Returns `None` if the block has no justification stored.
+    #[method(name = "justification")]
+    fn justification(&self, hash: Option<BlockHash>) -> RpcResult<Option<BlockJustification>>;
+
+    /// The state of the block sync.
+    #[method(name = "syncStatus")]
+    fn sync_status(&self) -> RpcResult<Option<SyncStatus>>;
+
+    /// Subscribe to newly finalized blocks that have an Aleph justification. When many blocks are
+    /// finalized at once, all of them that have a justification are reported, oldest first.
+    #[subscription(
+        name = "subscribeJustifications" => "justification",
+        unsubscribe = "unsubscribeJustifications",
+        item = FinalizedJustification,
+    )]
+    fn subscribe_justifications(&self);
+}
+
+/// A justification of a block, with the signatures replaced by the indices of the signers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockJustification {
+    pub block: BlockId,
+    pub justification: JustificationInfo,
+}
+
+/// A finalized block together with the proof of its finality.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalizedJustification {
+    pub header: Header,
+    /// The justification encoded in the current format, no matter which format it was stored in.
+    pub justification: Bytes,
+    /// Indices of the authorities that signed the block, empty for an emergency signature.
+    pub signers: Vec<u32>,
+    /// The authorities of the session the block belongs to, `None` if the state of the first
+    /// block of the session is not available anymore.
+    pub authorities: Option<Vec<AuthorityId>>,
+    /// Whether the block was finalized by an emergency signature instead of the committee.
+    pub emergency: bool,
 }
 
 /// Aleph Node API implementation
@@ -181,6 +276,9 @@ pub struct AlephNode<Client, SO> {
     client: Arc<Client>,
     sync_oracle: SO,
     validator_address_cache: Option<ValidatorAddressCache>,
+    node_status: NodeStatus,
+    session_period: SessionPeriod,
+    executor: SubscriptionTaskExecutor,
 }
 
 impl<Client, SO> AlephNode<Client, SO>
@@ -193,6 +291,9 @@ where
         client: Arc<Client>,
         sync_oracle: SO,
         validator_address_cache: Option<ValidatorAddressCache>,
+        node_status: NodeStatus,
+        session_period: SessionPeriod,
+        executor: SubscriptionTaskExecutor,
     ) -> Self {
         AlephNode {
             import_justification_tx,
@@ -200,6 +301,9 @@ where
             client,
             sync_oracle,
             validator_address_cache,
+            node_status,
+            session_period,
+            executor,
         }
     }
 }
@@ -207,7 +311,13 @@ where
 impl<Client, BE, SO> AlephNodeApiServer<BE> for AlephNode<Client, SO>
 where
     BE: sc_client_api::Backend<Block> + 'static,
-    Client: HeaderBackend<Block> + StorageProvider<Block, BE> + 'static,
+    Client: HeaderBackend<Block>
+        + BlockBackend<Block>
+        + BlockchainEvents<Block>
+        + StorageProvider<Block, BE>
+        + Send
+        + Sync
+        + 'static,
     SO: SyncOracle + Send + Sync + 'static,
 {
     fn emergency_finalize(
@@ -274,6 +384,168 @@ where
             .map(|c| c.snapshot())
             .ok_or(Error::NetworkInfoCachingNotEnabled.into())
     }
+
+    fn abft_status(&self) -> RpcResult<Option<AbftStatus>> {
+        Ok(self.node_status.abft())
+    }
+
+    fn abft_score(&self) -> RpcResult<Option<AbftScoreStatus>> {
+        Ok(current_abft_score(&self.node_status))
+    }
+
+    fn committee_status(&self) -> RpcResult<Vec<CommitteeStatus>> {
+        Ok(self.node_status.committees())
+    }
+
+    fn justification(&self, hash: Option<BlockHash>) -> RpcResult<Option<BlockJustification>> {
+        let hash = hash.unwrap_or_else(|| self.client.info().finalized_hash);
+        Ok(block_justification(self.client.as_ref(), hash)?)
+    }
+
+    fn sync_status(&self) -> RpcResult<Option<SyncStatus>> {
+        Ok(self.node_status.sync())
+    }
+
+    fn subscribe_justifications(&self, sink: SubscriptionSink) -> SubscriptionResult {
+        let client = self.client.clone();
+        let session_period = self.session_period;
+        let stream = self
+            .client
+            .finality_notification_stream()
+            .flat_map(move |notification| {
+                let finalized = notification
+                    .tree_route
+                    .iter()
+                    .cloned()
+                    .chain(Some(notification.hash));
+                stream::iter(finalized_justifications(
+                    client.as_ref(),
+                    session_period,
+                    finalized,
+                    |hash| read_storage("Aleph", "Authorities", &client, hash).ok(),
+                ))
+            });
+        self.executor.spawn(
+            "aleph-rpc-justifications-subscription",
+            Some("rpc"),
+            async move {
+                sink.pipe_from_stream(stream).await;
+            }
+            .boxed(),
+        );
+        Ok(())
+    }
+}
+
+/// The score is only reported for the session the node is currently running, older scores are
+/// discarded.
+fn current_abft_score(node_status: &NodeStatus) -> Option<AbftScoreStatus> {
+    let current_session = node_status.abft().map(|status| status.session);
+    node_status
+        .abft_score()
+        .filter(|score| Some(score.session) == current_session)
+}
+
+fn block_justification<Client>(
+    client: &Client,
+    hash: BlockHash,
+) -> Result<Option<BlockJustification>, Error>
+where
+    Client: HeaderBackend<Block> + BlockBackend<Block>,
+{
+    let number = client
+        .number(hash)
+        .map_err(|e| Error::FailedBlockNumberRead(hash.to_string(), e))?
+        .ok_or(Error::UnknownHash(hash.to_string()))?;
+    let justification = match client
+        .justifications(hash)
+        .map_err(|e| Error::FailedJustificationRead(hash.to_string(), e))?
+        .and_then(|justifications| justifications.into_justification(ALEPH_ENGINE_ID))
+    {
+        Some(justification) => justification,
+        None => return Ok(None),
+    };
+    let justification = backwards_compatible_decode(justification)
+        .map_err(|e| Error::FailedJustificationDecoding(hash.to_string(), e.to_string()))?;
+
+    Ok(Some(BlockJustification {
+        block: BlockId::new(hash, number),
+        justification: justification.into(),
+    }))
+}
+
+/// Blocks finalized without a justification of their own are skipped. The authorities of the
+/// session of every block are read with `session_authorities` at the first block of the session.
+fn finalized_justifications<Client>(
+    client: &Client,
+    session_period: SessionPeriod,
+    finalized: impl IntoIterator<Item = BlockHash>,
+    session_authorities: impl Fn(BlockHash) -> Option<Vec<AuthorityId>>,
+) -> Vec<FinalizedJustification>
+where
+    Client: HeaderBackend<Block> + BlockBackend<Block>,
+{
+    finalized
+        .into_iter()
+        .filter_map(|hash| {
+            finalized_justification(client, session_period, hash, &session_authorities)
+        })
+        .collect()
+}
+
+fn finalized_justification<Client>(
+    client: &Client,
+    session_period: SessionPeriod,
+    hash: BlockHash,
+    session_authorities: impl Fn(BlockHash) -> Option<Vec<AuthorityId>>,
+) -> Option<FinalizedJustification>
+where
+    Client: HeaderBackend<Block> + BlockBackend<Block>,
+{
+    let justification = match client.justifications(hash) {
+        Ok(justifications) => justifications?.into_justification(ALEPH_ENGINE_ID)?,
+        Err(e) => {
+            warn!(target: "aleph-rpc", "Failed to read justifications of block {hash}: {e:?}.");
+            return None;
+        }
+    };
+    let header = match client.header(hash) {
+        Ok(Some(header)) => header,
+        Ok(None) => {
+            warn!(target: "aleph-rpc", "Missing header of finalized block {hash}.");
+            return None;
+        }
+        Err(e) => {
+            warn!(target: "aleph-rpc", "Failed to read header of block {hash}: {e:?}.");
+            return None;
+        }
+    };
+    let justification = match backwards_compatible_decode(justification) {
+        Ok(justification) => justification,
+        Err(e) => {
+            warn!(target: "aleph-rpc", "Failed to decode justification of block {hash}: {e}.");
+            return None;
+        }
+    };
+    let signers = match JustificationInfo::from(justification.clone()) {
+        JustificationInfo::CommitteeMultisignature { signers, .. } => signers,
+        JustificationInfo::EmergencySignature => Vec::new(),
+    };
+    let emergency = matches!(justification, AlephJustification::EmergencySignature(_));
+    let first_block_of_session = header.number() / session_period.0 * session_period.0;
+    let authorities = client
+        .hash(first_block_of_session)
+        .ok()
+        .flatten()
+        .and_then(session_authorities);
+
+    Some(FinalizedJustification {
+        header,
+        justification: justification.encode().into(),
+        signers,
+        authorities,
+        emergency,
+    })
 }
 
 fn read_storage<
@@ -313,3 +585,265 @@ fn read_storage<
         Error::FailedStorageDecoding(pallet, pallet_item, block_hash.to_string(), e).into()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use finality_aleph::{
+        status_channel, versioned_encode, AbftScoreStatus, AbftStatus, AlephJustification,
+        BlockId, JustificationInfo, SessionId, SessionPeriod,
+    };
+    use parity_scale_codec::Encode;
+    use primitives::{
+        AuthorityId, AuthorityPair, Block, BlockHash, BlockNumber, Header, ALEPH_ENGINE_ID,
+    };
+    use sc_client_api::BlockBackend;
+    use sp_blockchain::{BlockStatus, HeaderBackend, Info};
+    use sp_core::Pair;
+    use sp_runtime::{
+        generic::SignedBlock,
+        traits::{Block as BlockT, Header as HeaderT},
+        Digest, Justifications,
+    };
+
+    use super::{
+        block_justification, current_abft_score, finalized_justifications, BlockJustification,
+        Error,
+    };
+
+    /// A chain of blocks, some of them with justifications.
+    #[derive(Default)]
+    struct MockClient {
+        blocks: HashMap<BlockHash, (Header, Option<Justifications>)>,
+        failing: bool,
+    }
+
+    impl MockClient {
+        fn import(&mut self, number: BlockNumber, justification: Option<AlephJustification>) {
+            let header = Header::new(
+                number,
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Digest::default(),
+            );
+            let justifications = justification.map(|justification| {
+                Justifications::from((ALEPH_ENGINE_ID, versioned_encode(justification)))
+            });
+            self.blocks
+                .insert(header.hash(), (header.clone(), justifications));
+        }
+
+        fn check(&self) -> sp_blockchain::Result<()> {
+            match self.failing {
+                true => Err(sp_blockchain::Error::Backend("backend failure".into())),
+                false => Ok(()),
+            }
+        }
+    }
+
+    impl HeaderBackend<Block> for MockClient {
+        fn header(&self, hash: BlockHash) -> sp_blockchain::Result<Option<Header>> {
+            self.check()?;
+            Ok(self.blocks.get(&hash).map(|(header, _)| header.clone()))
+        }
+
+        fn info(&self) -> Info<Block> {
+            unimplemented!()
+        }
+
+        fn status(&self, _hash: BlockHash) -> sp_blockchain::Result<BlockStatus> {
+            unimplemented!()
+        }
+
+        fn number(&self, hash: BlockHash) -> sp_blockchain::Result<Option<BlockNumber>> {
+            Ok(self.header(hash)?.map(|header| *header.number()))
+        }
+
+        fn hash(&self, number: BlockNumber) -> sp_blockchain::Result<Option<BlockHash>> {
+            self.check()?;
+            Ok(self
+                .blocks
+                .values()
+                .find(|(header, _)| *header.number() == number)
+                .map(|(header, _)| header.hash()))
+        }
+    }
+
+    impl BlockBackend<Block> for MockClient {
+        fn block_body(
+            &self,
+            _hash: BlockHash,
+        ) -> sp_blockchain::Result<Option<Vec<<Block as BlockT>::Extrinsic>>> {
+            unimplemented!()
+        }
+
+        fn block_indexed_body(&self, _hash: BlockHash) -> sp_blockchain::Result<Option<Vec<Vec<u8>>>> {
+            unimplemented!()
+        }
+
+        fn block(&self, _hash: BlockHash) -> sp_blockchain::Result<Option<SignedBlock<Block>>> {
+            unimplemented!()
+        }
+
+        fn block_status(&self, _hash: BlockHash) -> sp_blockchain::Result<sp_consensus::BlockStatus> {
+            unimplemented!()
+        }
+
+        fn justifications(&self, hash: BlockHash) -> sp_blockchain::Result<Option<Justifications>> {
+            self.check()?;
+            Ok(self
+                .blocks
+                .get(&hash)
+                .and_then(|(_, justifications)| justifications.clone()))
+        }
+
+        fn block_hash(&self, _number: BlockNumber) -> sp_blockchain::Result<Option<BlockHash>> {
+            unimplemented!()
+        }
+
+        fn indexed_transaction(&self, _hash: BlockHash) -> sp_blockchain::Result<Option<Vec<u8>>> {
+            unimplemented!()
+        }
+
+        fn requires_full_sync(&self) -> bool {
+            unimplemented!()
+        }
+    }
+
+    fn emergency_justification() -> AlephJustification {
+        let pair = AuthorityPair::from_seed(&[1; 32]);
+        AlephJustification::EmergencySignature(pair.sign(b"block"))
+    }
+
+    fn hash_of(client: &MockClient, number: BlockNumber) -> BlockHash {
+        client
+            .hash(number)
+            .expect("backend works")
+            .expect("block was imported")
+    }
+
+    fn authority(seed: u8) -> AuthorityId {
+        AuthorityPair::from_seed(&[seed; 32]).public()
+    }
+
+    #[test]
+    fn reports_score_of_the_current_session_only() {
+        let (reporter, status) = status_channel();
+        let score = |session| AbftScoreStatus {
+            session: SessionId(session),
+            rounds_behind: vec![0, 2],
+        };
+        reporter.report_abft_score(score(1));
+        assert_eq!(current_abft_score(&status), None);
+
+        reporter.report_abft(AbftStatus {
+            session: SessionId(2),
+            node_index: Some(0),
+        });
+        assert_eq!(current_abft_score(&status), None);
+
+        reporter.report_abft_score(score(2));
+        assert_eq!(current_abft_score(&status), Some(score(2)));
+    }
+
+    #[test]
+    fn reads_justification_of_a_block() {
+        let mut client = MockClient::default();
+        client.import(7, Some(emergency_justification()));
+        let hash = hash_of(&client, 7);
+
+        assert_eq!(
+            block_justification(&client, hash).expect("block is known"),
+            Some(BlockJustification {
+                block: BlockId::new(hash, 7),
+                justification: JustificationInfo::EmergencySignature,
+            })
+        );
+    }
+
+    #[test]
+    fn returns_none_for_block_without_justification() {
+        let mut client = MockClient::default();
+        client.import(7, None);
+        let hash = hash_of(&client, 7);
+
+        assert_eq!(
+            block_justification(&client, hash).expect("block is known"),
+            None
+        );
+    }
+
+    #[test]
+    fn fails_for_unknown_block() {
+        let client = MockClient::default();
+
+        assert!(matches!(
+            block_justification(&client, BlockHash::repeat_byte(1)),
+            Err(Error::UnknownHash(_))
+        ));
+    }
+
+    #[test]
+    fn reports_backend_failure_as_read_error() {
+        let mut client = MockClient::default();
+        client.import(7, Some(emergency_justification()));
+        let hash = hash_of(&client, 7);
+        client.failing = true;
+
+        assert!(matches!(
+            block_justification(&client, hash),
+            Err(Error::FailedBlockNumberRead(_, _))
+        ));
+    }
+
+    #[test]
+    fn reports_every_justified_block_finalized_at_once() {
+        let mut client = MockClient::default();
+        for number in 1..=5 {
+            let justification = [2, 4, 5]
+                .contains(&number)
+                .then(emergency_justification);
+            client.import(number, justification);
+        }
+        let finalized: Vec<_> = (1..=5).map(|number| hash_of(&client, number)).collect();
+
+        let justifications =
+            finalized_justifications(&client, SessionPeriod(10), finalized, |_| None);
+
+        let numbers: Vec<_> = justifications
+            .iter()
+            .map(|justification| *justification.header.number())
+            .collect();
+        assert_eq!(numbers, vec![2, 4, 5]);
+        for justification in justifications {
+            assert!(justification.emergency);
+            assert!(justification.signers.is_empty());
+            assert_eq!(
+                justification.justification.0,
+                emergency_justification().encode()
+            );
+        }
+    }
+
+    #[test]
+    fn reads_authorities_at_the_first_block_of_the_session() {
+        let mut client = MockClient::default();
+        for number in 8..=13 {
+            client.import(number, (number == 13).then(emergency_justification));
+        }
+        let session_start = hash_of(&client, 10);
+        let authorities = vec![authority(1), authority(2)];
+
+        let justifications = finalized_justifications(
+            &client,
+            SessionPeriod(10),
+            vec![hash_of(&client, 13)],
+            |hash| (hash == session_start).then(|| authorities.clone()),
+        );
+
+        assert_eq!(justifications.len(), 1);
+        assert_eq!(justifications[0].authorities, Some(authorities));
+    }
+}
@@ -7,6 +7,7 @@ pub type AlephNodeChainSpec = sc_service::GenericChainSpec<()>;
 
 use crate::{
     aleph_cli::AlephCli,
+    remote_signer::RemoteSignerCmd,
     resources::{mainnet_chainspec, testnet_chainspec},
 };
 
@@ -96,6 +97,9 @@ pub enum Subcommand {
     /// Revert the chain to a previous state.
     Revert(sc_cli::RevertCmd),
 
+    /// Run a remote signer keeping the Aleph session keys of validators outside of their nodes.
+    RemoteSigner(RemoteSignerCmd),
+
     /// The custom benchmark subcommand benchmarking runtime pallets.
     #[cfg(feature = "runtime-benchmarks")]
     #[clap(subcommand)]
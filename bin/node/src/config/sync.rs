@@ -1,31 +1,40 @@
-use log::warn;
+use log::{info, warn};
 use sc_cli::arg_enums::SyncMode;
 
 use crate::Cli;
 
-/// Modifies the sync config to ensure only full sync is used.
+/// Modifies the sync config to ensure only full or warp sync is used.
+///
+/// Warp sync is handled entirely by us, based on Aleph justifications, so substrate itself is
+/// always configured to perform full sync.
 pub struct SyncConfigValidator {
     overwritten: Option<SyncMode>,
+    warp: bool,
 }
 
 impl SyncConfigValidator {
     /// Modifies the settings.
     pub fn process(cli: &mut Cli) -> Self {
-        let overwritten = match cli.run.network_params.sync {
-            SyncMode::Full => None,
-            mode => Some(mode),
+        let (overwritten, warp) = match cli.run.network_params.sync {
+            SyncMode::Full => (None, false),
+            SyncMode::Warp => (None, true),
+            mode => (Some(mode), false),
         };
         cli.run.network_params.sync = SyncMode::Full;
-        SyncConfigValidator { overwritten }
+        cli.aleph.set_warp_sync(warp);
+        SyncConfigValidator { overwritten, warp }
     }
 
-    /// Warns the user if they attempted to use a sync setting other than full.
+    /// Warns the user if they attempted to use a sync setting other than full or warp.
     pub fn report(self) {
         if let Some(mode) = self.overwritten {
             warn!(
-                "Only full sync mode is supported, ignoring request for {:?} mode.",
+                "Only full and warp sync modes are supported, ignoring request for {:?} mode.",
                 mode
             );
         }
+        if self.warp {
+            info!("Warp sync requested, the node will not keep blocks older than the warp target.");
+        }
     }
 }
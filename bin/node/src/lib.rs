@@ -3,6 +3,7 @@ mod aleph_node_rpc;
 mod cli;
 mod config;
 mod executor;
+mod remote_signer;
 mod resources;
 mod rpc;
 mod service;
@@ -0,0 +1,77 @@
+use std::{
+    fs, io,
+    os::unix::net::UnixListener,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use finality_aleph::{DoubleSignGuard, LocalSigner, RemoteSignerServer};
+use log::info;
+use sc_cli::clap::{self, Parser};
+use sc_keystore::LocalKeystore;
+use sp_core::crypto::SecretString;
+
+/// Reads the secret shared by the node and the remote signer, ignoring trailing whitespace.
+pub fn read_signer_secret(path: &Path) -> io::Result<Vec<u8>> {
+    let mut secret = fs::read(path)?;
+    while secret
+        .last()
+        .map_or(false, |byte| byte.is_ascii_whitespace())
+    {
+        secret.pop();
+    }
+    Ok(secret)
+}
+
+#[derive(Debug, Parser)]
+pub struct RemoteSignerCmd {
+    /// The path of the Unix socket to listen on for nodes.
+    #[clap(long, value_name = "PATH")]
+    socket: PathBuf,
+
+    /// The path to the file with the secret shared with the nodes.
+    #[clap(long, value_name = "PATH")]
+    secret_file: PathBuf,
+
+    /// The path to the keystore with the Aleph session keys.
+    #[clap(long, value_name = "PATH")]
+    keystore_path: PathBuf,
+
+    /// The path to the file with the password of the keystore.
+    #[clap(long, value_name = "PATH")]
+    password_filename: Option<PathBuf>,
+
+    /// The path to the file with the record of the units signed for the nodes, which makes sure
+    /// no two different units of one round are signed, also across restarts of the signer.
+    #[clap(long, value_name = "PATH")]
+    signed_units_path: PathBuf,
+}
+
+impl RemoteSignerCmd {
+    pub fn run(&self) -> sc_cli::Result<()> {
+        sc_cli::LoggerBuilder::new("")
+            .init()
+            .map_err(|e| format!("failed to initialize logging: {e}"))?;
+
+        let password = match &self.password_filename {
+            Some(path) => Some(SecretString::new(
+                fs::read_to_string(path)?.trim_end().to_string(),
+            )),
+            None => None,
+        };
+        let keystore = LocalKeystore::open(&self.keystore_path, password)
+            .map_err(|e| format!("failed to open the keystore: {e}"))?;
+        let secret = read_signer_secret(&self.secret_file)?;
+        let guard = DoubleSignGuard::open(self.signed_units_path.clone())?;
+        let listener = UnixListener::bind(&self.socket)?;
+        info!("Remote signer listening at {:?}.", self.socket);
+
+        Arc::new(RemoteSignerServer::new(
+            LocalSigner::new(Arc::new(keystore)),
+            secret,
+            guard,
+        ))
+        .run(listener)
+        .map_err(|e| format!("remote signer failed: {e}").into())
+    }
+}
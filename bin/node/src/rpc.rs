@@ -7,11 +7,14 @@
 
 use std::sync::Arc;
 
-use finality_aleph::{Justification, JustificationTranslator, ValidatorAddressCache};
+use finality_aleph::{
+    Justification, JustificationTranslator, NodeStatus, SessionPeriod, ValidatorAddressCache,
+};
 use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
 use primitives::{AccountId, Balance, Block, Nonce};
-use sc_client_api::StorageProvider;
+use sc_client_api::{BlockBackend, BlockchainEvents, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -31,6 +34,10 @@ pub struct FullDeps<C, P, SO> {
     pub justification_translator: JustificationTranslator,
     pub sync_oracle: SO,
     pub validator_address_cache: Option<ValidatorAddressCache>,
+    pub node_status: NodeStatus,
+    pub session_period: SessionPeriod,
+    /// Executor for the subscriptions.
+    pub subscription_executor: SubscriptionTaskExecutor,
 }
 
 /// Instantiate all full RPC extensions.
@@ -41,6 +48,8 @@ where
     C: ProvideRuntimeApi<Block>
         + HeaderBackend<Block>
         + HeaderMetadata<Block, Error = BlockChainError>
+        + BlockBackend<Block>
+        + BlockchainEvents<Block>
         + StorageProvider<Block, BE>
         + Send
         + Sync
@@ -64,6 +73,9 @@ where
         justification_translator,
         sync_oracle,
         validator_address_cache,
+        node_status,
+        session_period,
+        subscription_executor,
     } = deps;
 
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
@@ -78,6 +90,9 @@ where
             client,
             sync_oracle,
             validator_address_cache,
+            node_status,
+            session_period,
+            subscription_executor,
         )
         .into_rpc(),
     )?;
@@ -7,19 +7,21 @@ use std::{
 
 use fake_runtime_api::fake_runtime::RuntimeApi;
 use finality_aleph::{
-    build_network, get_aleph_block_import, run_validator_node, AlephConfig, BlockImporter,
-    BuildNetworkOutput, ChannelProvider, FavouriteSelectChainProvider, Justification,
-    JustificationTranslator, MillisecsPerBlock, RateLimiterConfig, RedirectingBlockImport,
-    SessionPeriod, SubstrateChainStatus, SyncOracle, ValidatorAddressCache,
+    build_network, get_aleph_block_import, run_validator_node, status_channel, AlephConfig,
+    BlockImporter, BuildNetworkOutput, ChannelProvider, FavouriteSelectChainProvider,
+    Justification, JustificationTranslator, LocalSigner, MillisecsPerBlock, RateLimiterConfig,
+    RedirectingBlockImport, RemoteSigner, SessionPeriod, Signer, StateImportTarget,
+    SubstrateChainStatus, SyncOracle, ValidatorAddressCache,
 };
 use log::warn;
 use pallet_aleph_runtime_api::AlephSessionApi;
-use primitives::{Block, DEFAULT_BACKUP_FOLDER, MAX_BLOCK_SIZE};
+use primitives::{Block, DEFAULT_BACKUP_FOLDER, DEFAULT_SLASHING_PROTECTION_FILE, MAX_BLOCK_SIZE};
 use sc_basic_authorship::ProposerFactory;
 use sc_client_api::HeaderBackend;
 use sc_consensus::{ImportQueue, Link};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_slots::BackoffAuthoringBlocksStrategy;
+use sc_keystore::LocalKeystore;
 use sc_service::{
     error::Error as ServiceError, Configuration, KeystoreContainer, TFullClient, TaskManager,
 };
@@ -32,6 +34,7 @@ use sp_consensus_aura::{sr25519::AuthorityPair as AuraPair, Slot};
 use crate::{
     aleph_cli::AlephCli,
     executor::aleph_executor,
+    remote_signer::read_signer_secret,
     rpc::{create_full as create_full_rpc, FullDeps as RpcFullDeps},
 };
 
@@ -50,6 +53,7 @@ pub struct ServiceComponents {
     pub transaction_pool: Arc<FullPool>,
     pub keystore_container: KeystoreContainer,
     pub justification_channel_provider: ChannelProvider<Justification>,
+    pub state_import_target: StateImportTarget,
     pub telemetry: Option<Telemetry>,
 }
 struct LimitNonfinalized(u32);
@@ -89,6 +93,31 @@ fn backup_path(aleph_config: &AlephCli, base_path: &Path) -> Option<PathBuf> {
     }
 }
 
+fn slashing_protection_path(aleph_config: &AlephCli, base_path: &Path) -> PathBuf {
+    aleph_config
+        .slashing_protection_path()
+        .unwrap_or_else(|| base_path.join(DEFAULT_SLASHING_PROTECTION_FILE))
+}
+
+fn aleph_signer(
+    aleph_config: &AlephCli,
+    keystore: Arc<LocalKeystore>,
+) -> Result<Arc<dyn Signer>, ServiceError> {
+    let (socket, secret_file) = match (
+        aleph_config.remote_signer_socket(),
+        aleph_config.remote_signer_secret_file(),
+    ) {
+        (Some(socket), Some(secret_file)) => (socket, secret_file),
+        _ => return Ok(Arc::new(LocalSigner::new(keystore))),
+    };
+    let secret = read_signer_secret(&secret_file).map_err(|e| {
+        ServiceError::Other(format!("failed to read the remote signer secret: {e}"))
+    })?;
+    let signer = RemoteSigner::connect(socket, secret)
+        .map_err(|e| ServiceError::Other(format!("failed to connect to the remote signer: {e}")))?;
+    Ok(Arc::new(signer))
+}
+
 pub fn new_partial(config: &Configuration) -> Result<ServiceComponents, ServiceError> {
     let telemetry = config
         .telemetry_endpoints
@@ -133,11 +162,13 @@ pub fn new_partial(config: &Configuration) -> Result<ServiceComponents, ServiceE
             .map_err(|e| ServiceError::Other(format!("failed to set up chain status: {e}")))?,
     );
     let justification_channel_provider = ChannelProvider::new();
+    let state_import_target = StateImportTarget::default();
     let aleph_block_import = get_aleph_block_import(
         client.clone(),
         justification_channel_provider.get_sender(),
         justification_translator,
         select_chain_provider.select_chain(),
+        state_import_target.clone(),
     );
 
     let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
@@ -178,6 +209,7 @@ pub fn new_partial(config: &Configuration) -> Result<ServiceComponents, ServiceE
         select_chain_provider,
         transaction_pool,
         justification_channel_provider,
+        state_import_target,
         telemetry,
     })
 }
@@ -235,7 +267,9 @@ fn get_proposer_factory(
 fn get_rate_limit_config(aleph_config: &AlephCli) -> RateLimiterConfig {
     RateLimiterConfig {
         alephbft_network_bit_rate: aleph_config.alephbft_network_bit_rate(),
+        alephbft_network_upload_bit_rate: aleph_config.alephbft_network_upload_bit_rate(),
         substrate_network_bit_rate: aleph_config.substrate_network_bit_rate(),
+        substrate_network_upload_bit_rate: aleph_config.substrate_network_upload_bit_rate(),
     }
 }
 
@@ -255,6 +289,11 @@ pub fn new_authority(
     let mut service_components = new_partial(&config)?;
 
     let backup_path = backup_path(&aleph_config, config.base_path.path());
+    let slashing_protection_path = slashing_protection_path(&aleph_config, config.base_path.path());
+    let signer = aleph_signer(
+        &aleph_config,
+        service_components.keystore_container.local_keystore(),
+    )?;
 
     let backoff_authoring_blocks = Some(LimitNonfinalized(aleph_config.max_nonfinalized_blocks()));
     let prometheus_registry = config.prometheus_registry().cloned();
@@ -297,6 +336,7 @@ pub fn new_authority(
     let rate_limiter_config = get_rate_limit_config(&aleph_config);
     let network_config = finality_aleph::SubstrateNetworkConfig {
         substrate_network_bit_rate: rate_limiter_config.substrate_network_bit_rate,
+        substrate_network_upload_bit_rate: rate_limiter_config.substrate_network_upload_bit_rate,
         network_config: config.network.clone(),
     };
 
@@ -304,6 +344,7 @@ pub fn new_authority(
         network,
         authentication_network,
         block_sync_network,
+        warp_sync_network,
         sync_service,
         tx_handler_controller,
         system_rpc_tx,
@@ -323,6 +364,11 @@ pub fn new_authority(
     let chain_status = SubstrateChainStatus::new(service_components.backend.clone())
         .map_err(|e| ServiceError::Other(format!("failed to set up chain status: {e}")))?;
     let validator_address_cache = get_validator_address_cache(&aleph_config);
+    let (status_reporter, node_status) = status_channel();
+    let AlephRuntimeVars {
+        millisecs_per_block,
+        session_period,
+    } = get_aleph_runtime_vars(&service_components.client);
     let rpc_builder = {
         let client = service_components.client.clone();
         let pool = service_components.transaction_pool.clone();
@@ -332,7 +378,7 @@ pub fn new_authority(
             .justification_channel_provider
             .get_sender();
         let chain_status = chain_status.clone();
-        Box::new(move |deny_unsafe, _| {
+        Box::new(move |deny_unsafe, subscription_executor| {
             let deps = RpcFullDeps {
                 client: client.clone(),
                 pool: pool.clone(),
@@ -341,6 +387,9 @@ pub fn new_authority(
                 justification_translator: JustificationTranslator::new(chain_status.clone()),
                 sync_oracle: sync_oracle.clone(),
                 validator_address_cache: validator_address_cache.clone(),
+                node_status: node_status.clone(),
+                session_period,
+                subscription_executor,
             };
 
             Ok(create_full_rpc(deps)?)
@@ -373,14 +422,10 @@ pub fn new_authority(
         .spawn_essential_handle()
         .spawn_blocking("aura", None, aura);
 
-    let AlephRuntimeVars {
-        millisecs_per_block,
-        session_period,
-    } = get_aleph_runtime_vars(&service_components.client);
-
     let aleph_config = AlephConfig {
         authentication_network,
         block_sync_network,
+        warp_sync_network,
         client: service_components.client,
         chain_status,
         import_queue_handle,
@@ -389,17 +434,23 @@ pub fn new_authority(
         millisecs_per_block,
         spawn_handle: service_components.task_manager.spawn_handle().into(),
         keystore: service_components.keystore_container.local_keystore(),
+        signer,
         justification_channel_provider: service_components.justification_channel_provider,
         block_rx,
         registry: prometheus_registry,
         unit_creation_delay: aleph_config.unit_creation_delay(),
         backup_saving_path: backup_path,
+        backup_retained_sessions: aleph_config.backup_retained_sessions(),
+        slashing_protection_path,
         external_addresses: aleph_config.external_addresses(),
         validator_port: aleph_config.validator_port(),
         rate_limiter_config,
         sync_oracle,
         validator_address_cache,
+        status_reporter,
         transaction_pool: service_components.transaction_pool,
+        warp_sync: aleph_config.warp_sync(),
+        state_import_target: service_components.state_import_target,
     };
 
     service_components
@@ -46,11 +46,13 @@ use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::{
     crypto::SignatureSet, staking::MAX_NOMINATORS_REWARDED_PER_VALIDATOR, wrap_methods, Address,
     AlephNodeSessionKeys as SessionKeys, ApiError as AlephApiError, AuraId, AuthorityId as AlephId,
-    AuthoritySignature, BlockNumber as AlephBlockNumber, Header as AlephHeader, Score,
-    SessionAuthorityData, SessionCommittee, SessionIndex, SessionInfoProvider,
-    SessionValidatorError, TotalIssuanceProvider as TotalIssuanceProviderT,
-    Version as FinalityVersion, ADDRESSES_ENCODING, DEFAULT_BAN_REASON_LENGTH, DEFAULT_MAX_WINNERS,
-    DEFAULT_SESSIONS_PER_ERA, DEFAULT_SESSION_PERIOD, MAX_BLOCK_SIZE, MILLISECS_PER_BLOCK, TOKEN,
+    AuthoritySignature, BanInfo, BlockNumber as AlephBlockNumber, ElectionPreview,
+    Header as AlephHeader, ProductionEquivocationProof, Score, SessionAuthorityData,
+    SessionCommittee, SessionIndex, SessionInfoProvider, SessionValidatorError,
+    TotalIssuanceProvider as TotalIssuanceProviderT, Version as FinalityVersion,
+    ADDRESSES_ENCODING, DEFAULT_ABFT_SCORE_HISTORY_DEPTH, DEFAULT_BAN_HISTORY_LENGTH,
+    DEFAULT_BAN_REASON_LENGTH, DEFAULT_MAX_WINNERS, DEFAULT_SESSIONS_PER_ERA,
+    DEFAULT_SESSION_PERIOD, MAX_BLOCK_SIZE, MILLISECS_PER_BLOCK, TOKEN,
 };
 pub use primitives::{AccountId, AccountIndex, Balance, Hash, Nonce, Signature};
 use sp_api::impl_runtime_apis;
@@ -69,7 +71,11 @@ use sp_runtime::{
     ApplyExtrinsicResult, FixedU128, RuntimeDebug, SaturatedConversion,
 };
 pub use sp_runtime::{FixedPointNumber, Perbill, Permill, Saturating};
-use sp_staking::{currency_to_vote::U128CurrencyToVote, EraIndex};
+use sp_staking::{
+    currency_to_vote::U128CurrencyToVote,
+    offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
+    EraIndex,
+};
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -342,6 +348,8 @@ impl pallet_aleph::Config for Runtime {
     >;
     type NextSessionAuthorityProvider = Session;
     type TotalIssuanceProvider = TotalIssuanceProvider;
+    type AbftScoreHistoryDepth = ConstU32<DEFAULT_ABFT_SCORE_HISTORY_DEPTH>;
+    type WeightInfo = pallet_aleph::weights::AlephWeight<Runtime>;
 }
 
 parameter_types! {
@@ -356,6 +364,7 @@ impl pallet_elections::Config for Runtime {
     type ValidatorProvider = Staking;
     type MaxWinners = MaxWinners;
     type BannedValidators = CommitteeManagement;
+    type WeightInfo = pallet_elections::weights::AlephWeight<Runtime>;
 }
 
 impl pallet_operations::Config for Runtime {
@@ -365,6 +374,7 @@ impl pallet_operations::Config for Runtime {
     type NextKeysSessionProvider = Session;
     type BondedStashProvider = Staking;
     type ContractInfoProvider = Contracts;
+    type WeightInfo = pallet_operations::weights::AlephWeight<Runtime>;
 }
 
 impl pallet_committee_management::Config for Runtime {
@@ -376,7 +386,35 @@ impl pallet_committee_management::Config for Runtime {
     type ValidatorExtractor = Staking;
     type FinalityCommitteeManager = Aleph;
     type SessionPeriod = SessionPeriod;
+    type MaxBanHistory = ConstU32<DEFAULT_BAN_HISTORY_LENGTH>;
     type AbftScoresProvider = Aleph;
+    type ProducerKeysProvider = Session;
+    type EquivocationSlasher = StakingEquivocationSlasher;
+    type StashProvider = Staking;
+    type Currency = Balances;
+    type UnbanBondSlash = Treasury;
+    type WeightInfo = pallet_committee_management::weights::AlephWeight<Runtime>;
+}
+
+/// Slashes equivocating block producers through the regular staking offence handling.
+pub struct StakingEquivocationSlasher;
+
+impl pallet_committee_management::EquivocationSlasher<AccountId> for StakingEquivocationSlasher {
+    fn slash(offender: &AccountId, session: SessionIndex, fraction: Perbill) {
+        let Some(exposure) = pallet_staking::ExposureOf::<Runtime>::convert(offender.clone())
+        else {
+            return;
+        };
+        let details = OffenceDetails {
+            offender: (offender.clone(), exposure),
+            reporters: Vec::new(),
+        };
+        <Staking as OnOffenceHandler<
+            AccountId,
+            pallet_session::historical::IdentificationTuple<Runtime>,
+            Weight,
+        >>::on_offence(&[details], &[fraction], session, DisableStrategy::Never);
+    }
 }
 
 impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
@@ -1045,7 +1083,12 @@ pub type Executive = frame_executive::Executive<
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
-    frame_benchmarking::define_benchmarks!();
+    frame_benchmarking::define_benchmarks!(
+        [pallet_aleph, Aleph]
+        [pallet_committee_management, CommitteeManagement]
+        [pallet_elections, Elections]
+        [pallet_operations, Operations]
+    );
 }
 
 type EventRecord = frame_system::EventRecord<RuntimeEvent, Hash>;
@@ -1165,6 +1208,7 @@ impl_runtime_apis! {
         }
     }
 
+    #[api_version(5)]
     impl pallet_aleph_runtime_api::AlephSessionApi<Block> for Runtime {
         fn millisecs_per_block() -> u64 {
             MILLISECS_PER_BLOCK
@@ -1243,6 +1287,22 @@ impl_runtime_apis! {
         fn submit_abft_score(score: Score, signature: SignatureSet<AuthoritySignature>) -> Option<()> {
             Aleph::submit_abft_score(score, signature)
         }
+
+        fn submit_production_equivocation_report(proof: ProductionEquivocationProof) -> Option<()> {
+            CommitteeManagement::submit_production_equivocation_report(proof)
+        }
+
+        fn next_era_election_preview() -> Option<ElectionPreview<AccountId>> {
+            Elections::preview_election().ok()
+        }
+
+        fn abft_score_history() -> Vec<Score> {
+            Aleph::abft_score_history()
+        }
+
+        fn ban_history(validator: AccountId) -> Vec<BanInfo> {
+            CommitteeManagement::ban_history(validator).into_inner()
+        }
     }
 
     impl pallet_nomination_pools_runtime_api::NominationPoolsApi<Block, AccountId, Balance> for Runtime {
@@ -1599,6 +1659,29 @@ mod tests {
         };
     }
 
+    #[test]
+    // Warp sync proves the authorities of the next session under these keys, they must not change.
+    fn test_next_authority_data_storage_keys() {
+        let [authorities_key, emergency_finalizer_key] =
+            primitives::next_authority_data_storage_keys();
+        let authorities: Vec<AlephId> = vec![
+            sp_core::ed25519::Public::from_raw([1; 32]).into(),
+            sp_core::ed25519::Public::from_raw([2; 32]).into(),
+        ];
+        let emergency_finalizer: AlephId = sp_core::ed25519::Public::from_raw([3; 32]).into();
+
+        sp_io::TestExternalities::default().execute_with(|| {
+            sp_io::storage::set(&authorities_key, &authorities.encode());
+            sp_io::storage::set(&emergency_finalizer_key, &emergency_finalizer.encode());
+
+            assert_eq!(Aleph::next_authorities(), authorities);
+            assert_eq!(
+                Aleph::queued_emergency_finalizer(),
+                Some(emergency_finalizer)
+            );
+        });
+    }
+
     #[test]
     fn state_version_must_be_zero() {
         assert_eq!(0, VERSION.state_version);
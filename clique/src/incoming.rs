@@ -6,6 +6,7 @@ use log::{debug, info};
 use crate::{
     metrics::Metrics,
     protocols::{protocol, ProtocolError, ProtocolNegotiationError, ResultForService},
+    rate_limiting::PeerRateLimiters,
     Data, PublicKey, SecretKey, Splittable, LOG_TARGET,
 };
 
@@ -43,6 +44,7 @@ async fn manage_incoming<SK: SecretKey, D: Data, S: Splittable>(
     data_for_user: mpsc::UnboundedSender<D>,
     authorization_requests_sender: mpsc::UnboundedSender<(SK::PublicKey, oneshot::Sender<bool>)>,
     metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
 ) -> Result<(), IncomingError<SK::PublicKey>> {
     debug!(
         target: LOG_TARGET,
@@ -58,6 +60,7 @@ async fn manage_incoming<SK: SecretKey, D: Data, S: Splittable>(
             data_for_user,
             authorization_requests_sender,
             metrics,
+            peer_rate_limiters,
         )
         .await?)
 }
@@ -74,6 +77,7 @@ pub async fn incoming<SK: SecretKey, D: Data, S: Splittable>(
     data_for_user: mpsc::UnboundedSender<D>,
     authorization_requests_sender: mpsc::UnboundedSender<(SK::PublicKey, oneshot::Sender<bool>)>,
     metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
 ) {
     let addr = stream.peer_address_info();
     if let Err(e) = manage_incoming(
@@ -83,6 +87,7 @@ pub async fn incoming<SK: SecretKey, D: Data, S: Splittable>(
         data_for_user,
         authorization_requests_sender,
         metrics,
+        peer_rate_limiters,
     )
     .await
     {
@@ -52,6 +52,7 @@ impl From<Error> for SendError {
 pub enum ReceiveError {
     Error(Error),
     DataCorrupted,
+    DecryptionFailed,
 }
 
 impl Display for ReceiveError {
@@ -60,6 +61,7 @@ impl Display for ReceiveError {
         match self {
             Error(e) => write!(f, "{e}"),
             DataCorrupted => write!(f, "received corrupted data"),
+            DecryptionFailed => write!(f, "failed to decrypt received data"),
         }
     }
 }
@@ -1,4 +1,6 @@
-use substrate_prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+use substrate_prometheus_endpoint::{
+    register, CounterVec, Gauge, Opts, PrometheusError, Registry, U64,
+};
 
 #[derive(Clone)]
 pub enum Metrics {
@@ -7,6 +9,8 @@ pub enum Metrics {
         missing_incoming_connections: Gauge<U64>,
         outgoing_connections: Gauge<U64>,
         missing_outgoing_connections: Gauge<U64>,
+        peer_sent_bytes: CounterVec<U64>,
+        peer_received_bytes: CounterVec<U64>,
     },
     Noop,
 }
@@ -22,6 +26,13 @@ pub enum Event {
     DisconnectedIncoming,
 }
 
+/// Direction of the traffic with a peer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Traffic {
+    Sent,
+    Received,
+}
+
 impl Metrics {
     pub fn new(registry: Option<Registry>) -> Result<Self, PrometheusError> {
         match registry {
@@ -54,6 +65,26 @@ impl Metrics {
                     )?,
                     &registry,
                 )?,
+                peer_sent_bytes: register(
+                    CounterVec::new(
+                        Opts::new(
+                            "clique_network_peer_sent_bytes",
+                            "bytes of data sent to an authenticated peer",
+                        ),
+                        &["peer"],
+                    )?,
+                    &registry,
+                )?,
+                peer_received_bytes: register(
+                    CounterVec::new(
+                        Opts::new(
+                            "clique_network_peer_received_bytes",
+                            "bytes of data received from an authenticated peer",
+                        ),
+                        &["peer"],
+                    )?,
+                    &registry,
+                )?,
             }),
             None => Ok(Metrics::Noop),
         }
@@ -70,6 +101,7 @@ impl Metrics {
             outgoing_connections,
             missing_incoming_connections,
             missing_outgoing_connections,
+            ..
         } = self
         {
             match event {
@@ -96,4 +128,35 @@ impl Metrics {
             }
         }
     }
+
+    pub fn report_peer_traffic(&self, peer: &str, traffic: Traffic, bytes: usize) {
+        if let Metrics::Prometheus {
+            peer_sent_bytes,
+            peer_received_bytes,
+            ..
+        } = self
+        {
+            let counter = match traffic {
+                Traffic::Sent => peer_sent_bytes,
+                Traffic::Received => peer_received_bytes,
+            };
+            counter
+                .with_label_values(&[peer])
+                .inc_by(bytes.try_into().unwrap_or(u64::MAX));
+        }
+    }
+
+    /// Stops reporting the traffic of a peer we are no longer connected to.
+    pub fn remove_peer(&self, peer: &str) {
+        if let Metrics::Prometheus {
+            peer_sent_bytes,
+            peer_received_bytes,
+            ..
+        } = self
+        {
+            // The peer might have never sent or received anything.
+            let _ = peer_sent_bytes.remove_label_values(&[peer]);
+            let _ = peer_received_bytes.remove_label_values(&[peer]);
+        }
+    }
 }
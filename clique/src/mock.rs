@@ -10,7 +10,7 @@ use std::{
 
 use futures::{
     channel::{mpsc, mpsc::UnboundedReceiver, oneshot},
-    Future, StreamExt,
+    Future, FutureExt, StreamExt,
 };
 use log::info;
 use parity_scale_codec::{Decode, Encode, Output};
@@ -21,7 +21,9 @@ use tokio::{
 };
 
 use crate::{
-    protocols::{ProtocolError, ResultForService},
+    metrics::Metrics,
+    protocols::{Protocol, ProtocolError, ResultForService},
+    rate_limiting::PeerRateLimiters,
     AddressingInformation, ConnectionInfo, Data, Dialer, Listener, Network, NetworkIdentity,
     PeerAddressInfo, PeerId, PublicKey, SecretKey, Splittable, LOG_TARGET,
 };
@@ -671,3 +673,86 @@ pub struct MockPrelims<D> {
     pub result_from_outgoing: UnboundedReceiver<ResultForService<MockPublicKey, D>>,
     pub authorization_requests: mpsc::UnboundedReceiver<(MockPublicKey, oneshot::Sender<bool>)>,
 }
+
+impl<D: Data> MockPrelims<D> {
+    /// Prepares a pair of connected peers running the given version of the protocol.
+    pub fn new(protocol: Protocol) -> Self {
+        let (stream_incoming, stream_outgoing) = MockSplittable::new(4096);
+        let (id_incoming, pen_incoming) = key();
+        let (id_outgoing, pen_outgoing) = key();
+        assert_ne!(id_incoming, id_outgoing);
+        let (incoming_result_for_service, result_from_incoming) = mpsc::unbounded();
+        let (outgoing_result_for_service, result_from_outgoing) = mpsc::unbounded();
+        let (incoming_data_for_user, data_from_incoming) = mpsc::unbounded::<D>();
+        let (outgoing_data_for_user, data_from_outgoing) = mpsc::unbounded::<D>();
+        let (authorization_requests_sender, authorization_requests) = mpsc::unbounded();
+        let incoming_handle = {
+            let pen_incoming = pen_incoming.clone();
+            Box::pin(async move {
+                protocol
+                    .manage_incoming(
+                        stream_incoming,
+                        pen_incoming,
+                        incoming_result_for_service,
+                        incoming_data_for_user,
+                        authorization_requests_sender,
+                        Metrics::noop(),
+                        PeerRateLimiters::unlimited(),
+                    )
+                    .await
+            })
+        };
+        let outgoing_handle = {
+            let pen_outgoing = pen_outgoing.clone();
+            let id_incoming = id_incoming.clone();
+            Box::pin(async move {
+                protocol
+                    .manage_outgoing(
+                        stream_outgoing,
+                        pen_outgoing,
+                        id_incoming,
+                        outgoing_result_for_service,
+                        outgoing_data_for_user,
+                        Metrics::noop(),
+                        PeerRateLimiters::unlimited(),
+                    )
+                    .await
+            })
+        };
+        MockPrelims {
+            id_incoming,
+            pen_incoming,
+            id_outgoing,
+            pen_outgoing,
+            incoming_handle,
+            outgoing_handle,
+            data_from_incoming,
+            data_from_outgoing: Some(data_from_outgoing),
+            result_from_incoming,
+            result_from_outgoing,
+            authorization_requests,
+        }
+    }
+}
+
+/// Answers the first authorization request with the result of `handler`.
+pub fn handle_authorization<PK: Send + 'static>(
+    mut authorization_requests: mpsc::UnboundedReceiver<(PK, oneshot::Sender<bool>)>,
+    handler: impl FnOnce(PK) -> bool + Send + 'static,
+) -> impl Future<Output = Result<(), ()>> {
+    tokio::spawn(async move {
+        let (public_key, response_sender) = authorization_requests
+            .next()
+            .await
+            .expect("We should recieve at least one authorization request.");
+        let authorization_result = handler(public_key);
+        response_sender
+            .send(authorization_result)
+            .expect("We should be able to send back an authorization response.");
+        Result::<(), ()>::Ok(())
+    })
+    .map(|result| match result {
+        Ok(ok) => ok,
+        Err(_) => Err(()),
+    })
+}
@@ -7,6 +7,7 @@ use tokio::time::{sleep, timeout, Duration};
 use crate::{
     metrics::Metrics,
     protocols::{protocol, ProtocolError, ProtocolNegotiationError, ResultForService},
+    rate_limiting::PeerRateLimiters,
     ConnectionInfo, Data, Dialer, PeerAddressInfo, PublicKey, SecretKey, LOG_TARGET,
 };
 
@@ -43,6 +44,7 @@ async fn manage_outgoing<SK: SecretKey, D: Data, A: Data, ND: Dialer<A>>(
     result_for_parent: mpsc::UnboundedSender<ResultForService<SK::PublicKey, D>>,
     data_for_user: mpsc::UnboundedSender<D>,
     metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
 ) -> Result<(), OutgoingError<SK::PublicKey, A, ND>> {
     debug!(target: LOG_TARGET, "Trying to connect to {}.", public_key);
     let stream = timeout(DIAL_TIMEOUT, dialer.connect(address))
@@ -66,6 +68,7 @@ async fn manage_outgoing<SK: SecretKey, D: Data, A: Data, ND: Dialer<A>>(
             result_for_parent,
             data_for_user,
             metrics,
+            peer_rate_limiters,
         )
         .await
         .map_err(|e| OutgoingError::Protocol(peer_address_info.clone(), e))
@@ -84,6 +87,7 @@ pub async fn outgoing<SK: SecretKey, D: Data, A: Data + Debug, ND: Dialer<A>>(
     result_for_parent: mpsc::UnboundedSender<ResultForService<SK::PublicKey, D>>,
     data_for_user: mpsc::UnboundedSender<D>,
     metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
 ) {
     if let Err(e) = manage_outgoing(
         secret_key,
@@ -93,6 +97,7 @@ pub async fn outgoing<SK: SecretKey, D: Data, A: Data + Debug, ND: Dialer<A>>(
         result_for_parent.clone(),
         data_for_user,
         metrics,
+        peer_rate_limiters,
     )
     .await
     {
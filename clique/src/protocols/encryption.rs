@@ -0,0 +1,290 @@
+use std::fmt::{Debug, Error as FmtError, Formatter};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use hkdf::Hkdf;
+use parity_scale_codec::DecodeAll;
+use sha2::Sha256;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    io::{Error, ReceiveError, SendError, MAX_DATA_SIZE},
+    Data, Splittable,
+};
+
+/// Size of the authentication tag appended to every encrypted frame.
+const TAG_SIZE: u32 = 16;
+
+const DIALER_KEY_INFO: &[u8] = b"aleph-clique-v2-dialer-key";
+const LISTENER_KEY_INFO: &[u8] = b"aleph-clique-v2-listener-key";
+
+/// The side of the connection we are on, determines which key is used in which direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// We accepted the connection.
+    Listener,
+    /// We initiated the connection.
+    Dialer,
+}
+
+/// Symmetric keys for a single connection, one for each direction.
+pub struct SessionKeys {
+    dialer_key: [u8; 32],
+    listener_key: [u8; 32],
+}
+
+impl SessionKeys {
+    /// Derive the session keys from the result of the key exchange, bound to the transcript of
+    /// the handshake.
+    pub fn derive(shared_secret: &[u8; 32], transcript: &[u8]) -> Self {
+        let hkdf = Hkdf::<Sha256>::new(Some(transcript), shared_secret);
+        let mut dialer_key = [0; 32];
+        let mut listener_key = [0; 32];
+        hkdf.expand(DIALER_KEY_INFO, &mut dialer_key)
+            .expect("32 bytes is a valid output length");
+        hkdf.expand(LISTENER_KEY_INFO, &mut listener_key)
+            .expect("32 bytes is a valid output length");
+        SessionKeys {
+            dialer_key,
+            listener_key,
+        }
+    }
+
+    /// Split the stream into halves encrypting and decrypting the data with the appropriate keys.
+    pub fn split<S: Splittable>(
+        self,
+        stream: S,
+        role: Role,
+    ) -> (EncryptedSender<S::Sender>, EncryptedReceiver<S::Receiver>) {
+        let (sending_key, receiving_key) = match role {
+            Role::Dialer => (self.dialer_key, self.listener_key),
+            Role::Listener => (self.listener_key, self.dialer_key),
+        };
+        let (sender, receiver) = stream.split();
+        (
+            EncryptedSender::new(sender, &sending_key),
+            EncryptedReceiver::new(receiver, &receiving_key),
+        )
+    }
+}
+
+fn nonce(counter: u64) -> Nonce {
+    let mut nonce = [0; 12];
+    nonce[4..].copy_from_slice(&counter.to_le_bytes());
+    nonce.into()
+}
+
+/// The sending half of a connection, encrypting and authenticating all the data it sends.
+/// Every frame uses a fresh nonce, so the receiver detects dropped, reordered or replayed frames.
+pub struct EncryptedSender<S> {
+    sender: S,
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+}
+
+impl<S> Debug for EncryptedSender<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("EncryptedSender")
+            .field("counter", &self.counter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: AsyncWrite + Unpin> EncryptedSender<S> {
+    fn new(sender: S, key: &[u8; 32]) -> Self {
+        EncryptedSender {
+            sender,
+            cipher: ChaCha20Poly1305::new(Key::from_slice(key)),
+            counter: 0,
+        }
+    }
+
+    /// Sends some data, encrypted.
+    pub async fn send_data<D: Data>(mut self, data: D) -> Result<Self, SendError> {
+        let encoded = data.encode();
+        let len = u32::try_from(encoded.len()).map_err(|_| Error::DataTooLong(u32::MAX))?;
+        if len > MAX_DATA_SIZE {
+            return Err(Error::DataTooLong(len).into());
+        }
+        let encrypted = self
+            .cipher
+            .encrypt(&nonce(self.counter), encoded.as_slice())
+            .map_err(|_| Error::DataTooLong(len))?;
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .expect("we will never send 2^64 messages over a single connection");
+        let encrypted_len = (len + TAG_SIZE).to_le_bytes().to_vec();
+        self.sender
+            .write_all(&encrypted_len)
+            .await
+            .map_err(Error::ConnectionClosed)?;
+        self.sender
+            .write_all(&encrypted)
+            .await
+            .map_err(Error::ConnectionClosed)?;
+        Ok(self)
+    }
+}
+
+/// The receiving half of a connection, decrypting and verifying all the data it receives.
+pub struct EncryptedReceiver<R> {
+    receiver: R,
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+}
+
+impl<R> Debug for EncryptedReceiver<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("EncryptedReceiver")
+            .field("counter", &self.counter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: AsyncRead + Unpin> EncryptedReceiver<R> {
+    fn new(receiver: R, key: &[u8; 32]) -> Self {
+        EncryptedReceiver {
+            receiver,
+            cipher: ChaCha20Poly1305::new(Key::from_slice(key)),
+            counter: 0,
+        }
+    }
+
+    /// Attempts to receive some data and decrypt it.
+    pub async fn receive_data<D: Data>(mut self) -> Result<(Self, D), ReceiveError> {
+        let mut buf = [0; 4];
+        self.receiver
+            .read_exact(&mut buf[..])
+            .await
+            .map_err(Error::ConnectionClosed)?;
+        let len = u32::from_le_bytes(buf);
+        if len > MAX_DATA_SIZE + TAG_SIZE {
+            return Err(Error::DataTooLong(len).into());
+        }
+        let mut buf: Vec<u8> = vec![0; len as usize];
+        self.receiver
+            .read_exact(&mut buf[..])
+            .await
+            .map_err(Error::ConnectionClosed)?;
+        let decrypted = self
+            .cipher
+            .decrypt(&nonce(self.counter), buf.as_slice())
+            .map_err(|_| ReceiveError::DecryptionFailed)?;
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or(ReceiveError::DecryptionFailed)?;
+        let data = D::decode_all(&mut &decrypted[..]).map_err(|_| ReceiveError::DataCorrupted)?;
+        Ok((self, data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::{Role, SessionKeys};
+    use crate::{io::ReceiveError, mock::MockSplittable, Splittable};
+
+    fn keys() -> SessionKeys {
+        SessionKeys::derive(&[43; 32], b"transcript")
+    }
+
+    #[tokio::test]
+    async fn sends_and_receives_correct_data() {
+        let (stream_dialer, stream_listener) = MockSplittable::new(4096);
+        let (sender, _) = keys().split(stream_dialer, Role::Dialer);
+        let (_, receiver) = keys().split(stream_listener, Role::Listener);
+        let data: Vec<i32> = vec![4, 3, 43];
+        let sender = sender
+            .send_data(data.clone())
+            .await
+            .expect("data should send");
+        let _sender = sender
+            .send_data(data.clone())
+            .await
+            .expect("data should send");
+        let (receiver, received_data) = receiver
+            .receive_data::<Vec<i32>>()
+            .await
+            .expect("should receive data");
+        assert_eq!(data, received_data);
+        let (_receiver, received_data) = receiver
+            .receive_data::<Vec<i32>>()
+            .await
+            .expect("should receive data");
+        assert_eq!(data, received_data);
+    }
+
+    #[tokio::test]
+    async fn does_not_send_plaintext() {
+        let (stream_dialer, stream_listener) = MockSplittable::new(4096);
+        let (sender, _) = keys().split(stream_dialer, Role::Dialer);
+        let (_, mut raw_receiver) = stream_listener.split();
+        let data: Vec<u8> = vec![43; 32];
+        let _sender = sender
+            .send_data(data.clone())
+            .await
+            .expect("data should send");
+        let mut buf = [0; 4 + 1 + 32 + 16];
+        raw_receiver
+            .read_exact(&mut buf)
+            .await
+            .expect("should receive frame");
+        assert!(!buf.windows(data.len()).any(|window| window == data));
+    }
+
+    #[tokio::test]
+    async fn fails_to_decrypt_with_wrong_key() {
+        let (stream_dialer, stream_listener) = MockSplittable::new(4096);
+        let (sender, _) = keys().split(stream_dialer, Role::Dialer);
+        let (_, receiver) =
+            SessionKeys::derive(&[44; 32], b"transcript").split(stream_listener, Role::Listener);
+        let _sender = sender
+            .send_data(vec![4, 3, 43])
+            .await
+            .expect("data should send");
+        match receiver.receive_data::<Vec<i32>>().await {
+            Err(ReceiveError::DecryptionFailed) => (),
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("decrypted data with a wrong key"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_to_decrypt_own_direction() {
+        let (stream_dialer, stream_listener) = MockSplittable::new(4096);
+        let (sender, _) = keys().split(stream_dialer, Role::Dialer);
+        let (_, receiver) = keys().split(stream_listener, Role::Dialer);
+        let _sender = sender
+            .send_data(vec![4, 3, 43])
+            .await
+            .expect("data should send");
+        match receiver.receive_data::<Vec<i32>>().await {
+            Err(ReceiveError::DecryptionFailed) => (),
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("decrypted data with the key for the other direction"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_to_decrypt_tampered_data() {
+        let (stream_dialer, stream_listener) = MockSplittable::new(4096);
+        let (mut raw_sender, _) = stream_dialer.split();
+        let (_, receiver) = keys().split(stream_listener, Role::Listener);
+        let mut frame = 20u32.to_le_bytes().to_vec();
+        frame.extend_from_slice(&[43; 20]);
+        raw_sender
+            .write_all(&frame)
+            .await
+            .expect("sending should work");
+        match receiver.receive_data::<Vec<i32>>().await {
+            Err(ReceiveError::DecryptionFailed) => (),
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("decrypted forged data"),
+        }
+    }
+}
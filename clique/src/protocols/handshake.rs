@@ -3,9 +3,11 @@ use std::fmt::{Display, Error as FmtError, Formatter};
 use parity_scale_codec::{Decode, Encode};
 use rand::Rng;
 use tokio::time::{timeout, Duration};
+use x25519_dalek::{EphemeralSecret, PublicKey as EphemeralPublicKey};
 
 use crate::{
     io::{receive_data, send_data, ReceiveError, SendError},
+    protocols::encryption::{EncryptedReceiver, EncryptedSender, Role, SessionKeys},
     PublicKey, SecretKey, Splittable,
 };
 
@@ -22,6 +24,8 @@ pub enum HandshakeError<PK: PublicKey> {
     SignatureError,
     /// Challenge contains invalid peer id.
     ChallengeError(PK, PK),
+    /// Key exchange resulted in a weak shared secret.
+    KeyExchangeError,
     /// Timeout.
     TimedOut,
 }
@@ -37,6 +41,7 @@ impl<PK: PublicKey> Display for HandshakeError<PK> {
                 f,
                 "challenge error, expected peer {expected}, received from {got}"
             ),
+            KeyExchangeError => write!(f, "key exchange error"),
             TimedOut => write!(f, "timed out"),
         }
     }
@@ -97,6 +102,73 @@ impl<PK: PublicKey> Response<PK> {
     }
 }
 
+/// Handshake challenge for the encrypted handshake. Additionally contains an ephemeral key of the
+/// creator, used for deriving the session keys.
+#[derive(Debug, Clone, Encode, Decode)]
+struct KeyExchangeChallenge<PK: PublicKey> {
+    challenge: Challenge<PK>,
+    ephemeral_key: [u8; 32],
+}
+
+impl<PK: PublicKey> KeyExchangeChallenge<PK> {
+    /// Prepare new challenge that contains ID of the creator and its ephemeral key.
+    fn new(public_key: PK, ephemeral_key: &EphemeralPublicKey) -> Self {
+        Self {
+            challenge: Challenge::new(public_key),
+            ephemeral_key: ephemeral_key.to_bytes(),
+        }
+    }
+}
+
+/// Response in the encrypted handshake. Contains public key and ephemeral key of the creator,
+/// and a signature of the whole transcript so far.
+#[derive(Debug, Clone, Encode, Decode)]
+struct KeyExchangeResponse<PK: PublicKey> {
+    public_key: PK,
+    ephemeral_key: [u8; 32],
+    signature: PK::Signature,
+}
+
+/// Final message of the encrypted handshake, the creator of the challenge proves its identity by
+/// signing the whole transcript.
+#[derive(Debug, Clone, Encode, Decode)]
+struct KeyExchangeConfirmation<PK: PublicKey> {
+    signature: PK::Signature,
+}
+
+const DIALER_SIGNATURE_CONTEXT: &[u8] = b"aleph-clique-v2-dialer";
+const LISTENER_SIGNATURE_CONTEXT: &[u8] = b"aleph-clique-v2-listener";
+
+/// The bytes the keys are bound to: the challenge together with the identity and ephemeral key
+/// of the responder.
+fn transcript<PK: PublicKey>(
+    challenge: &KeyExchangeChallenge<PK>,
+    public_key: &PK,
+    ephemeral_key: &[u8; 32],
+) -> Vec<u8> {
+    let mut transcript = challenge.encode();
+    transcript.extend(public_key.encode());
+    transcript.extend_from_slice(ephemeral_key);
+    transcript
+}
+
+fn signed_message(context: &[u8], transcript: &[u8]) -> Vec<u8> {
+    [context, transcript].concat()
+}
+
+fn session_keys<PK: PublicKey>(
+    our_ephemeral_secret: EphemeralSecret,
+    their_ephemeral_key: [u8; 32],
+    transcript: &[u8],
+) -> Result<SessionKeys, HandshakeError<PK>> {
+    let shared_secret =
+        our_ephemeral_secret.diffie_hellman(&EphemeralPublicKey::from(their_ephemeral_key));
+    if !shared_secret.was_contributory() {
+        return Err(HandshakeError::KeyExchangeError);
+    }
+    Ok(SessionKeys::derive(shared_secret.as_bytes(), transcript))
+}
+
 /// Performs the handshake with a peer that called us.
 /// The goal is to obtain the public key of the peer, and split
 /// the communication stream into two halves.
@@ -179,13 +251,147 @@ pub async fn v0_handshake_outgoing<SK: SecretKey, S: Splittable>(
     .map_err(|_| HandshakeError::TimedOut)?
 }
 
+/// Performs the encrypted handshake with a peer that called us.
+/// The goal is to obtain the public key of the peer, and to establish encrypted communication
+/// channels with it. Both sides prove their identity by signing the transcript containing
+/// the ephemeral keys used for deriving the session keys, so the resulting channels are
+/// confidential and authenticated.
+pub async fn execute_v2_handshake_incoming<SK: SecretKey, S: Splittable>(
+    stream: S,
+    secret_key: SK,
+) -> Result<
+    (
+        EncryptedSender<S::Sender>,
+        EncryptedReceiver<S::Receiver>,
+        SK::PublicKey,
+    ),
+    HandshakeError<SK::PublicKey>,
+> {
+    let ephemeral_secret = EphemeralSecret::random_from_rng(rand::thread_rng());
+    // send challenge
+    let our_challenge = KeyExchangeChallenge::new(
+        secret_key.public_key(),
+        &EphemeralPublicKey::from(&ephemeral_secret),
+    );
+    let stream = send_data(stream, our_challenge.clone()).await?;
+    // receive response
+    let (stream, peer_response) =
+        receive_data::<_, KeyExchangeResponse<SK::PublicKey>>(stream).await?;
+    // validate response
+    let transcript = transcript(
+        &our_challenge,
+        &peer_response.public_key,
+        &peer_response.ephemeral_key,
+    );
+    if !peer_response.public_key.verify(
+        &signed_message(DIALER_SIGNATURE_CONTEXT, &transcript),
+        &peer_response.signature,
+    ) {
+        return Err(HandshakeError::SignatureError);
+    }
+    // send confirmation
+    let our_confirmation = KeyExchangeConfirmation::<SK::PublicKey> {
+        signature: secret_key.sign(&signed_message(LISTENER_SIGNATURE_CONTEXT, &transcript)),
+    };
+    let stream = send_data(stream, our_confirmation).await?;
+    let session_keys = session_keys(ephemeral_secret, peer_response.ephemeral_key, &transcript)?;
+    let (sender, receiver) = session_keys.split(stream, Role::Listener);
+    Ok((sender, receiver, peer_response.public_key))
+}
+
+/// Performs the encrypted handshake with a peer that we called. We assume that their public key
+/// is known to us.
+/// The goal is to authenticate ourselves, verify the identity of the peer, and to establish
+/// encrypted communication channels with it.
+pub async fn execute_v2_handshake_outgoing<SK: SecretKey, S: Splittable>(
+    stream: S,
+    secret_key: SK,
+    public_key: SK::PublicKey,
+) -> Result<
+    (EncryptedSender<S::Sender>, EncryptedReceiver<S::Receiver>),
+    HandshakeError<SK::PublicKey>,
+> {
+    // receive challenge
+    let (stream, peer_challenge) =
+        receive_data::<_, KeyExchangeChallenge<SK::PublicKey>>(stream).await?;
+    if public_key != peer_challenge.challenge.public_key {
+        return Err(HandshakeError::ChallengeError(
+            public_key,
+            peer_challenge.challenge.public_key,
+        ));
+    }
+    // send response
+    let ephemeral_secret = EphemeralSecret::random_from_rng(rand::thread_rng());
+    let our_public_key = secret_key.public_key();
+    let our_ephemeral_key = EphemeralPublicKey::from(&ephemeral_secret).to_bytes();
+    let transcript = transcript(&peer_challenge, &our_public_key, &our_ephemeral_key);
+    let our_response = KeyExchangeResponse {
+        public_key: our_public_key,
+        ephemeral_key: our_ephemeral_key,
+        signature: secret_key.sign(&signed_message(DIALER_SIGNATURE_CONTEXT, &transcript)),
+    };
+    let stream = send_data(stream, our_response).await?;
+    // receive confirmation
+    let (stream, peer_confirmation) =
+        receive_data::<_, KeyExchangeConfirmation<SK::PublicKey>>(stream).await?;
+    if !public_key.verify(
+        &signed_message(LISTENER_SIGNATURE_CONTEXT, &transcript),
+        &peer_confirmation.signature,
+    ) {
+        return Err(HandshakeError::SignatureError);
+    }
+    let session_keys = session_keys(ephemeral_secret, peer_challenge.ephemeral_key, &transcript)?;
+    Ok(session_keys.split(stream, Role::Dialer))
+}
+
+/// Wrapper that adds timeout to the function performing the encrypted handshake.
+pub async fn v2_handshake_incoming<SK: SecretKey, S: Splittable>(
+    stream: S,
+    secret_key: SK,
+) -> Result<
+    (
+        EncryptedSender<S::Sender>,
+        EncryptedReceiver<S::Receiver>,
+        SK::PublicKey,
+    ),
+    HandshakeError<SK::PublicKey>,
+> {
+    timeout(
+        HANDSHAKE_TIMEOUT,
+        execute_v2_handshake_incoming(stream, secret_key),
+    )
+    .await
+    .map_err(|_| HandshakeError::TimedOut)?
+}
+
+/// Wrapper that adds timeout to the function performing the encrypted handshake.
+pub async fn v2_handshake_outgoing<SK: SecretKey, S: Splittable>(
+    stream: S,
+    secret_key: SK,
+    public_key: SK::PublicKey,
+) -> Result<
+    (EncryptedSender<S::Sender>, EncryptedReceiver<S::Receiver>),
+    HandshakeError<SK::PublicKey>,
+> {
+    timeout(
+        HANDSHAKE_TIMEOUT,
+        execute_v2_handshake_outgoing(stream, secret_key, public_key),
+    )
+    .await
+    .map_err(|_| HandshakeError::TimedOut)?
+}
+
 #[cfg(test)]
 mod tests {
     use futures::{join, try_join};
+    use parity_scale_codec::Encode;
+    use x25519_dalek::{EphemeralSecret, PublicKey as EphemeralPublicKey};
 
     use super::{
-        execute_v0_handshake_incoming, execute_v0_handshake_outgoing, Challenge, HandshakeError,
-        Response,
+        execute_v0_handshake_incoming, execute_v0_handshake_outgoing,
+        execute_v2_handshake_incoming, execute_v2_handshake_outgoing, Challenge, HandshakeError,
+        KeyExchangeChallenge, KeyExchangeConfirmation, KeyExchangeResponse, Response,
+        DIALER_SIGNATURE_CONTEXT,
     };
     use crate::{
         io::{receive_data, send_data},
@@ -361,4 +567,129 @@ mod tests {
             .expect("should send");
         assert_send_error(execute_v0_handshake_outgoing(stream_b, pen_b, id_a).await);
     }
+
+    #[tokio::test]
+    async fn encrypted_handshake() {
+        let (stream_a, stream_b) = MockSplittable::new(4096);
+        let (id_a, pen_a) = key();
+        let (id_b, pen_b) = key();
+        assert_ne!(id_a, id_b);
+        let ((sender_a, _, received_id_b), (_, receiver_b)) = try_join!(
+            execute_v2_handshake_incoming(stream_a, pen_a),
+            execute_v2_handshake_outgoing(stream_b, pen_b, id_a),
+        )
+        .expect("handshake should work");
+        assert_eq!(id_b, received_id_b);
+        let _sender_a = sender_a
+            .send_data(vec![4, 3, 43])
+            .await
+            .expect("should send");
+        let (_, data) = receiver_b
+            .receive_data::<Vec<i32>>()
+            .await
+            .expect("should receive");
+        assert_eq!(data, vec![4, 3, 43]);
+    }
+
+    #[tokio::test]
+    async fn encrypted_handshake_with_impersonating_server_peer() {
+        async fn execute_impersonating_v2_handshake_incoming<S: Splittable>(
+            stream: S,
+            impersonated_id: MockPublicKey,
+            secret_key: MockSecretKey,
+        ) {
+            // send challenge with the impersonated id
+            let ephemeral_secret = EphemeralSecret::random_from_rng(rand::thread_rng());
+            let our_challenge = KeyExchangeChallenge::new(
+                impersonated_id,
+                &EphemeralPublicKey::from(&ephemeral_secret),
+            );
+            let stream = send_data(stream, our_challenge).await.expect("should send");
+            let (stream, _) = receive_data::<_, KeyExchangeResponse<MockPublicKey>>(stream)
+                .await
+                .expect("should receive");
+            // we cannot sign as the impersonated peer
+            let confirmation = KeyExchangeConfirmation::<MockPublicKey> {
+                signature: secret_key.sign(b"whatever"),
+            };
+            send_data(stream, confirmation).await.expect("should send");
+            futures::future::pending::<()>().await;
+        }
+
+        let (stream_a, stream_b) = MockSplittable::new(4096);
+        let (id_a, _) = key();
+        let (_, pen_b) = key();
+        let (_, pen_c) = key();
+        tokio::select! {
+            _ = execute_impersonating_v2_handshake_incoming(stream_a, id_a.clone(), pen_c) => panic!("should wait"),
+            result = execute_v2_handshake_outgoing(stream_b, pen_b, id_a) => assert_signature_error(result),
+        }
+    }
+
+    #[tokio::test]
+    async fn encrypted_handshake_with_malicious_client_peer_fake_signature() {
+        pub async fn execute_malicious_v2_handshake_outgoing_fake_signature<S: Splittable>(
+            stream: S,
+            secret_key: MockSecretKey,
+        ) {
+            // receive challenge
+            let (stream, _) = receive_data::<_, KeyExchangeChallenge<MockPublicKey>>(stream)
+                .await
+                .expect("should receive");
+            // send response signed with a different key
+            let (fake_id, _) = key();
+            let ephemeral_secret = EphemeralSecret::random_from_rng(rand::thread_rng());
+            let our_response = KeyExchangeResponse {
+                public_key: fake_id,
+                ephemeral_key: EphemeralPublicKey::from(&ephemeral_secret).to_bytes(),
+                signature: secret_key.sign(b"whatever"),
+            };
+            send_data(stream, our_response).await.expect("should send");
+            futures::future::pending::<()>().await;
+        }
+
+        let (stream_a, stream_b) = MockSplittable::new(4096);
+        let (_, pen_a) = key();
+        let (_, pen_b) = key();
+        tokio::select! {
+            result = execute_v2_handshake_incoming(stream_a, pen_a) => assert_signature_error(result),
+            _ = execute_malicious_v2_handshake_outgoing_fake_signature(stream_b, pen_b) => panic!("should wait"),
+        }
+    }
+
+    #[tokio::test]
+    async fn encrypted_handshake_rejects_weak_ephemeral_key() {
+        pub async fn execute_malicious_v2_handshake_outgoing_weak_key<S: Splittable>(
+            stream: S,
+            secret_key: MockSecretKey,
+        ) {
+            let (stream, challenge) =
+                receive_data::<_, KeyExchangeChallenge<MockPublicKey>>(stream)
+                    .await
+                    .expect("should receive");
+            // the identity point results in an all-zero shared secret
+            let ephemeral_key = [0; 32];
+            let mut transcript = challenge.encode();
+            transcript.extend(secret_key.public_key().encode());
+            transcript.extend_from_slice(&ephemeral_key);
+            let our_response = KeyExchangeResponse {
+                public_key: secret_key.public_key(),
+                ephemeral_key,
+                signature: secret_key.sign(&[DIALER_SIGNATURE_CONTEXT, &transcript[..]].concat()),
+            };
+            send_data(stream, our_response).await.expect("should send");
+            futures::future::pending::<()>().await;
+        }
+
+        let (stream_a, stream_b) = MockSplittable::new(4096);
+        let (_, pen_a) = key();
+        let (_, pen_b) = key();
+        tokio::select! {
+            result = execute_v2_handshake_incoming(stream_a, pen_a) => match result {
+                Err(HandshakeError::KeyExchangeError) => (),
+                x => panic!("should end with HandshakeError::KeyExchangeError, but we got {x:?}"),
+            },
+            _ = execute_malicious_v2_handshake_outgoing_weak_key(stream_b, pen_b) => panic!("should wait"),
+        }
+    }
 }
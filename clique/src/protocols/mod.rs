@@ -5,12 +5,15 @@ use futures::channel::{mpsc, oneshot};
 use crate::{
     io::{ReceiveError, SendError},
     metrics::Metrics,
+    rate_limiting::PeerRateLimiters,
     Data, PublicKey, SecretKey, Splittable,
 };
 
+mod encryption;
 mod handshake;
 mod negotiation;
 mod v1;
+mod v2;
 
 use handshake::HandshakeError;
 pub use negotiation::{protocol, ProtocolNegotiationError};
@@ -22,12 +25,15 @@ pub type Version = u32;
 /// connection was unsuccessful and should be reestablished.
 pub type ResultForService<PK, D> = (PK, Option<mpsc::UnboundedSender<D>>);
 
-/// Defines the protocol for communication. Currently single variant, but left in case of protocol change.
-#[derive(Debug, PartialEq, Eq)]
+/// Defines the protocol for communication.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Protocol {
-    /// The current version of the protocol, with pseudorandom connection direction and
-    /// multiplexing.
+    /// The first version of the protocol, with pseudorandom connection direction and
+    /// multiplexing. The data is sent unencrypted, kept for compatibility with older nodes.
     V1,
+    /// The current version of the protocol, like V1, but the handshake establishes session keys
+    /// and all subsequent data is encrypted and authenticated.
+    V2,
 }
 
 /// Protocol error.
@@ -90,7 +96,7 @@ impl Protocol {
     const MIN_VERSION: Version = 1;
 
     /// Maximal supported protocol version.
-    const MAX_VERSION: Version = 1;
+    const MAX_VERSION: Version = 2;
 
     /// Launches the proper variant of the protocol (receiver half).
     pub async fn manage_incoming<SK: SecretKey, D: Data, S: Splittable>(
@@ -104,6 +110,7 @@ impl Protocol {
             oneshot::Sender<bool>,
         )>,
         metrics: Metrics,
+        peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
     ) -> Result<(), ProtocolError<SK::PublicKey>> {
         use Protocol::*;
         match self {
@@ -115,6 +122,19 @@ impl Protocol {
                     result_for_parent,
                     data_for_user,
                     metrics,
+                    peer_rate_limiters,
+                )
+                .await
+            }
+            V2 => {
+                v2::incoming(
+                    stream,
+                    secret_key,
+                    authorization_requests_sender,
+                    result_for_parent,
+                    data_for_user,
+                    metrics,
+                    peer_rate_limiters,
                 )
                 .await
             }
@@ -130,6 +150,7 @@ impl Protocol {
         result_for_service: mpsc::UnboundedSender<ResultForService<SK::PublicKey, D>>,
         data_for_user: mpsc::UnboundedSender<D>,
         metrics: Metrics,
+        peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
     ) -> Result<(), ProtocolError<SK::PublicKey>> {
         use Protocol::*;
         match self {
@@ -141,6 +162,19 @@ impl Protocol {
                     result_for_service,
                     data_for_user,
                     metrics,
+                    peer_rate_limiters,
+                )
+                .await
+            }
+            V2 => {
+                v2::outgoing(
+                    stream,
+                    secret_key,
+                    public_key,
+                    result_for_service,
+                    data_for_user,
+                    metrics,
+                    peer_rate_limiters,
                 )
                 .await
             }
@@ -154,6 +188,7 @@ impl TryFrom<Version> for Protocol {
     fn try_from(version: Version) -> Result<Self, Self::Error> {
         match version {
             1 => Ok(Protocol::V1),
+            2 => Ok(Protocol::V2),
             unknown_version => Err(unknown_version),
         }
     }
@@ -16,19 +16,20 @@ use crate::{
         handshake::{v0_handshake_incoming, v0_handshake_outgoing},
         ProtocolError, ResultForService,
     },
+    rate_limiting::{PeerRateLimiters, PeerTrafficLimiter},
     Data, PublicKey, SecretKey, Splittable, LOG_TARGET,
 };
 
-const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(5);
-const MAX_MISSED_HEARTBEATS: u32 = 4;
+pub(super) const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(5);
+pub(super) const MAX_MISSED_HEARTBEATS: u32 = 4;
 
 #[derive(Debug, Clone, Encode, Decode)]
-enum Message<D: Data> {
+pub(super) enum Message<D: Data> {
     Data(D),
     Heartbeat,
 }
 
-async fn check_authorization<SK: SecretKey>(
+pub(super) async fn check_authorization<SK: SecretKey>(
     authorization_requests_sender: mpsc::UnboundedSender<(SK::PublicKey, oneshot::Sender<bool>)>,
     public_key: SK::PublicKey,
 ) -> Result<bool, ProtocolError<SK::PublicKey>> {
@@ -44,6 +45,7 @@ async fn check_authorization<SK: SecretKey>(
 async fn sending<PK: PublicKey, D: Data, S: AsyncWrite + Unpin + Send>(
     mut sender: S,
     mut data_from_user: mpsc::UnboundedReceiver<D>,
+    mut rate_limiter: PeerTrafficLimiter,
 ) -> Result<(), ProtocolError<PK>> {
     use Message::*;
     loop {
@@ -55,6 +57,7 @@ async fn sending<PK: PublicKey, D: Data, S: AsyncWrite + Unpin + Send>(
             },
             _ => Heartbeat,
         };
+        rate_limiter = rate_limiter.account(to_send.encoded_size()).await;
         sender = timeout(
             MAX_MISSED_HEARTBEATS * HEARTBEAT_TIMEOUT,
             send_data(sender, to_send),
@@ -67,6 +70,7 @@ async fn sending<PK: PublicKey, D: Data, S: AsyncWrite + Unpin + Send>(
 async fn receiving<PK: PublicKey, D: Data, S: AsyncRead + Unpin + Send>(
     mut stream: S,
     data_for_user: mpsc::UnboundedSender<D>,
+    mut rate_limiter: PeerTrafficLimiter,
 ) -> Result<(), ProtocolError<PK>> {
     use Message::*;
     loop {
@@ -77,12 +81,14 @@ async fn receiving<PK: PublicKey, D: Data, S: AsyncRead + Unpin + Send>(
         .await
         .map_err(|_| ProtocolError::CardiacArrest)??;
         stream = old_stream;
+        let message_size = message.encoded_size();
         match message {
             Data(data) => data_for_user
                 .unbounded_send(data)
                 .map_err(|_| ProtocolError::NoUserConnection)?,
             Heartbeat => (),
         }
+        rate_limiter = rate_limiter.account(message_size).await;
     }
 }
 
@@ -96,9 +102,10 @@ async fn manage_connection<
     receiver: R,
     data_from_user: mpsc::UnboundedReceiver<D>,
     data_for_user: mpsc::UnboundedSender<D>,
+    (received_limiter, sent_limiter): (PeerTrafficLimiter, PeerTrafficLimiter),
 ) -> Result<(), ProtocolError<PK>> {
-    let sending = sending(sender, data_from_user);
-    let receiving = receiving(receiver, data_for_user);
+    let sending = sending(sender, data_from_user, sent_limiter);
+    let receiving = receiving(receiver, data_for_user, received_limiter);
     tokio::select! {
         result = receiving => result,
         result = sending => result,
@@ -114,6 +121,7 @@ pub async fn outgoing<SK: SecretKey, D: Data, S: Splittable>(
     result_for_parent: mpsc::UnboundedSender<ResultForService<SK::PublicKey, D>>,
     data_for_user: mpsc::UnboundedSender<D>,
     metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
 ) -> Result<(), ProtocolError<SK::PublicKey>> {
     use Event::*;
     trace!(target: LOG_TARGET, "Extending hand to {}.", public_key);
@@ -132,7 +140,15 @@ pub async fn outgoing<SK: SecretKey, D: Data, S: Splittable>(
         target: LOG_TARGET,
         "Starting worker for communicating with {}.", public_key
     );
-    let result = manage_connection(sender, receiver, data_from_user, data_for_user).await;
+    let peer_limiters = peer_rate_limiters.for_peer(&public_key, metrics.clone());
+    let result = manage_connection(
+        sender,
+        receiver,
+        data_from_user,
+        data_for_user,
+        peer_limiters,
+    )
+    .await;
     metrics.report_event(DisconnectedOutgoing);
     result
 }
@@ -147,6 +163,7 @@ pub async fn incoming<SK: SecretKey, D: Data, S: Splittable>(
     result_for_parent: mpsc::UnboundedSender<ResultForService<SK::PublicKey, D>>,
     data_for_user: mpsc::UnboundedSender<D>,
     metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
 ) -> Result<(), ProtocolError<SK::PublicKey>> {
     use Event::*;
     trace!(target: LOG_TARGET, "Waiting for extended hand...");
@@ -169,7 +186,15 @@ pub async fn incoming<SK: SecretKey, D: Data, S: Splittable>(
         target: LOG_TARGET,
         "Starting worker for communicating with {}.", public_key
     );
-    let result = manage_connection(sender, receiver, data_from_user, data_for_user).await;
+    let peer_limiters = peer_rate_limiters.for_peer(&public_key, metrics.clone());
+    let result = manage_connection(
+        sender,
+        receiver,
+        data_from_user,
+        data_for_user,
+        peer_limiters,
+    )
+    .await;
     metrics.report_event(DisconnectedIncoming);
     result
 }
@@ -182,75 +207,13 @@ mod tests {
     };
 
     use crate::{
-        metrics::Metrics,
-        mock::{key, MockPrelims, MockSplittable},
-        protocols::{
-            v1::{incoming, outgoing},
-            ProtocolError,
-        },
+        mock::{handle_authorization, MockPrelims},
+        protocols::{Protocol, ProtocolError},
         Data,
     };
 
     fn prepare<D: Data>() -> MockPrelims<D> {
-        let (stream_incoming, stream_outgoing) = MockSplittable::new(4096);
-        let (id_incoming, pen_incoming) = key();
-        let (id_outgoing, pen_outgoing) = key();
-        assert_ne!(id_incoming, id_outgoing);
-        let (incoming_result_for_service, result_from_incoming) = mpsc::unbounded();
-        let (outgoing_result_for_service, result_from_outgoing) = mpsc::unbounded();
-        let (incoming_data_for_user, data_from_incoming) = mpsc::unbounded::<D>();
-        let (outgoing_data_for_user, data_from_outgoing) = mpsc::unbounded::<D>();
-        let (authorization_requests_sender, authorization_requests) = mpsc::unbounded();
-        let incoming_handle = Box::pin(incoming(
-            stream_incoming,
-            pen_incoming.clone(),
-            authorization_requests_sender,
-            incoming_result_for_service,
-            incoming_data_for_user,
-            Metrics::noop(),
-        ));
-        let outgoing_handle = Box::pin(outgoing(
-            stream_outgoing,
-            pen_outgoing.clone(),
-            id_incoming.clone(),
-            outgoing_result_for_service,
-            outgoing_data_for_user,
-            Metrics::noop(),
-        ));
-        MockPrelims {
-            id_incoming,
-            pen_incoming,
-            id_outgoing,
-            pen_outgoing,
-            incoming_handle,
-            outgoing_handle,
-            data_from_incoming,
-            data_from_outgoing: Some(data_from_outgoing),
-            result_from_incoming,
-            result_from_outgoing,
-            authorization_requests,
-        }
-    }
-
-    fn handle_authorization<PK: Send + 'static>(
-        mut authorization_requests: mpsc::UnboundedReceiver<(PK, oneshot::Sender<bool>)>,
-        handler: impl FnOnce(PK) -> bool + Send + 'static,
-    ) -> impl Future<Output = Result<(), ()>> {
-        tokio::spawn(async move {
-            let (public_key, response_sender) = authorization_requests
-                .next()
-                .await
-                .expect("We should recieve at least one authorization request.");
-            let authorization_result = handler(public_key);
-            response_sender
-                .send(authorization_result)
-                .expect("We should be able to send back an authorization response.");
-            Result::<(), ()>::Ok(())
-        })
-        .map(|result| match result {
-            Ok(ok) => ok,
-            Err(_) => Err(()),
-        })
+        MockPrelims::new(Protocol::V1)
     }
 
     fn all_pass_authorization_handler<PK: Send + 'static>(
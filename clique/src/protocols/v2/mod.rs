@@ -0,0 +1,301 @@
+use futures::{
+    channel::{mpsc, oneshot},
+    StreamExt,
+};
+use log::{debug, info, trace};
+use parity_scale_codec::Encode;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    time::timeout,
+};
+
+use crate::{
+    metrics::{Event, Metrics},
+    protocols::{
+        encryption::{EncryptedReceiver, EncryptedSender},
+        handshake::{v2_handshake_incoming, v2_handshake_outgoing},
+        v1::{check_authorization, Message, HEARTBEAT_TIMEOUT, MAX_MISSED_HEARTBEATS},
+        ProtocolError, ResultForService,
+    },
+    rate_limiting::{PeerRateLimiters, PeerTrafficLimiter},
+    Data, PublicKey, SecretKey, Splittable, LOG_TARGET,
+};
+
+async fn sending<PK: PublicKey, D: Data, S: AsyncWrite + Unpin + Send>(
+    mut sender: EncryptedSender<S>,
+    mut data_from_user: mpsc::UnboundedReceiver<D>,
+    mut rate_limiter: PeerTrafficLimiter,
+) -> Result<(), ProtocolError<PK>> {
+    use Message::*;
+    loop {
+        let to_send = match timeout(HEARTBEAT_TIMEOUT, data_from_user.next()).await {
+            Ok(maybe_data) => match maybe_data {
+                Some(data) => Data(data),
+                // We have been closed by the parent service, all good.
+                None => return Ok(()),
+            },
+            _ => Heartbeat,
+        };
+        rate_limiter = rate_limiter.account(to_send.encoded_size()).await;
+        sender = timeout(
+            MAX_MISSED_HEARTBEATS * HEARTBEAT_TIMEOUT,
+            sender.send_data(to_send),
+        )
+        .await
+        .map_err(|_| ProtocolError::SendTimeout)??;
+    }
+}
+
+async fn receiving<PK: PublicKey, D: Data, R: AsyncRead + Unpin + Send>(
+    mut receiver: EncryptedReceiver<R>,
+    data_for_user: mpsc::UnboundedSender<D>,
+    mut rate_limiter: PeerTrafficLimiter,
+) -> Result<(), ProtocolError<PK>> {
+    use Message::*;
+    loop {
+        let (old_receiver, message) = timeout(
+            MAX_MISSED_HEARTBEATS * HEARTBEAT_TIMEOUT,
+            receiver.receive_data(),
+        )
+        .await
+        .map_err(|_| ProtocolError::CardiacArrest)??;
+        receiver = old_receiver;
+        let message_size = message.encoded_size();
+        match message {
+            Data(data) => data_for_user
+                .unbounded_send(data)
+                .map_err(|_| ProtocolError::NoUserConnection)?,
+            Heartbeat => (),
+        }
+        rate_limiter = rate_limiter.account(message_size).await;
+    }
+}
+
+async fn manage_connection<
+    PK: PublicKey,
+    D: Data,
+    S: AsyncWrite + Unpin + Send,
+    R: AsyncRead + Unpin + Send,
+>(
+    sender: EncryptedSender<S>,
+    receiver: EncryptedReceiver<R>,
+    data_from_user: mpsc::UnboundedReceiver<D>,
+    data_for_user: mpsc::UnboundedSender<D>,
+    (received_limiter, sent_limiter): (PeerTrafficLimiter, PeerTrafficLimiter),
+) -> Result<(), ProtocolError<PK>> {
+    let sending = sending(sender, data_from_user, sent_limiter);
+    let receiving = receiving(receiver, data_for_user, received_limiter);
+    tokio::select! {
+        result = receiving => result,
+        result = sending => result,
+    }
+}
+
+/// Performs the outgoing encrypted handshake, and then manages a connection sending and receiving
+/// encrypted data. Exits on parent request, or in case of broken or dead network connection.
+pub async fn outgoing<SK: SecretKey, D: Data, S: Splittable>(
+    stream: S,
+    secret_key: SK,
+    public_key: SK::PublicKey,
+    result_for_parent: mpsc::UnboundedSender<ResultForService<SK::PublicKey, D>>,
+    data_for_user: mpsc::UnboundedSender<D>,
+    metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
+) -> Result<(), ProtocolError<SK::PublicKey>> {
+    use Event::*;
+    trace!(target: LOG_TARGET, "Extending hand to {}.", public_key);
+    let (sender, receiver) = v2_handshake_outgoing(stream, secret_key, public_key.clone()).await?;
+    info!(
+        target: LOG_TARGET,
+        "Outgoing encrypted handshake with {} finished successfully.", public_key
+    );
+    let (data_for_network, data_from_user) = mpsc::unbounded();
+    result_for_parent
+        .unbounded_send((public_key.clone(), Some(data_for_network)))
+        .map_err(|_| ProtocolError::NoParentConnection)?;
+    metrics.report_event(ConnectedOutgoing);
+
+    debug!(
+        target: LOG_TARGET,
+        "Starting worker for communicating with {}.", public_key
+    );
+    let peer_limiters = peer_rate_limiters.for_peer(&public_key, metrics.clone());
+    let result = manage_connection(
+        sender,
+        receiver,
+        data_from_user,
+        data_for_user,
+        peer_limiters,
+    )
+    .await;
+    metrics.report_event(DisconnectedOutgoing);
+    result
+}
+
+/// Performs the incoming encrypted handshake, and then manages a connection sending and receiving
+/// encrypted data. Exits on parent request (when the data source is dropped), or in case of broken
+/// or dead network connection.
+pub async fn incoming<SK: SecretKey, D: Data, S: Splittable>(
+    stream: S,
+    secret_key: SK,
+    authorization_requests_sender: mpsc::UnboundedSender<(SK::PublicKey, oneshot::Sender<bool>)>,
+    result_for_parent: mpsc::UnboundedSender<ResultForService<SK::PublicKey, D>>,
+    data_for_user: mpsc::UnboundedSender<D>,
+    metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
+) -> Result<(), ProtocolError<SK::PublicKey>> {
+    use Event::*;
+    trace!(target: LOG_TARGET, "Waiting for extended hand...");
+    let (sender, receiver, public_key) = v2_handshake_incoming(stream, secret_key).await?;
+    info!(
+        target: LOG_TARGET,
+        "Incoming encrypted handshake with {} finished successfully.", public_key
+    );
+
+    if !check_authorization::<SK>(authorization_requests_sender, public_key.clone()).await? {
+        return Err(ProtocolError::NotAuthorized);
+    }
+
+    let (data_for_network, data_from_user) = mpsc::unbounded();
+    result_for_parent
+        .unbounded_send((public_key.clone(), Some(data_for_network)))
+        .map_err(|_| ProtocolError::NoParentConnection)?;
+    metrics.report_event(ConnectedIncoming);
+    debug!(
+        target: LOG_TARGET,
+        "Starting worker for communicating with {}.", public_key
+    );
+    let peer_limiters = peer_rate_limiters.for_peer(&public_key, metrics.clone());
+    let result = manage_connection(
+        sender,
+        receiver,
+        data_from_user,
+        data_for_user,
+        peer_limiters,
+    )
+    .await;
+    metrics.report_event(DisconnectedIncoming);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{pin_mut, FutureExt, StreamExt};
+
+    use crate::{
+        mock::{handle_authorization, MockPrelims},
+        protocols::{Protocol, ProtocolError},
+        Data,
+    };
+
+    fn prepare<D: Data>() -> MockPrelims<D> {
+        MockPrelims::new(Protocol::V2)
+    }
+
+    #[tokio::test]
+    async fn send_data() {
+        let MockPrelims {
+            incoming_handle,
+            outgoing_handle,
+            mut data_from_incoming,
+            data_from_outgoing,
+            mut result_from_incoming,
+            mut result_from_outgoing,
+            authorization_requests,
+            ..
+        } = prepare::<Vec<i32>>();
+        let mut data_from_outgoing = data_from_outgoing.expect("No data from outgoing!");
+        let incoming_handle = incoming_handle.fuse();
+        let outgoing_handle = outgoing_handle.fuse();
+        pin_mut!(incoming_handle);
+        pin_mut!(outgoing_handle);
+        let _authorization_handle = handle_authorization(authorization_requests, |_| true);
+        let _data_for_outgoing = tokio::select! {
+            _ = &mut incoming_handle => panic!("incoming process unexpectedly finished"),
+            _ = &mut outgoing_handle => panic!("outgoing process unexpectedly finished"),
+            result = result_from_outgoing.next() => {
+                let (_, maybe_data_for_outgoing) = result.expect("the channel shouldn't be dropped");
+                let data_for_outgoing = maybe_data_for_outgoing.expect("successfully connected");
+                data_for_outgoing
+                    .unbounded_send(vec![4, 3, 43])
+                    .expect("should send");
+                data_for_outgoing
+            },
+        };
+        let _data_for_incoming = tokio::select! {
+            _ = &mut incoming_handle => panic!("incoming process unexpectedly finished"),
+            _ = &mut outgoing_handle => panic!("outgoing process unexpectedly finished"),
+            result = result_from_incoming.next() => {
+                let (_, maybe_data_for_incoming) = result.expect("the channel shouldn't be dropped");
+                let data_for_incoming = maybe_data_for_incoming.expect("successfully connected");
+                data_for_incoming
+                    .unbounded_send(vec![5, 4, 44])
+                    .expect("should send");
+                data_for_incoming
+            },
+        };
+        tokio::select! {
+            _ = &mut incoming_handle => panic!("incoming process unexpectedly finished"),
+            _ = &mut outgoing_handle => panic!("outgoing process unexpectedly finished"),
+            v = data_from_incoming.next() => {
+                assert_eq!(v, Some(vec![4, 3, 43]));
+            },
+        };
+        tokio::select! {
+            _ = &mut incoming_handle => panic!("incoming process unexpectedly finished"),
+            _ = &mut outgoing_handle => panic!("outgoing process unexpectedly finished"),
+            v = data_from_outgoing.next() => {
+                assert_eq!(v, Some(vec![5, 4, 44]));
+            },
+        };
+    }
+
+    #[tokio::test]
+    async fn sender_dead_before_handshake() {
+        let MockPrelims {
+            incoming_handle,
+            outgoing_handle,
+            data_from_incoming: _data_from_incoming,
+            data_from_outgoing: _data_from_outgoing,
+            result_from_incoming: _result_from_incoming,
+            result_from_outgoing: _result_from_outgoing,
+            authorization_requests,
+            ..
+        } = prepare::<Vec<i32>>();
+        let _authorization_handle = handle_authorization(authorization_requests, |_| true);
+        std::mem::drop(outgoing_handle);
+        match incoming_handle.await {
+            Err(ProtocolError::HandshakeError(_)) => (),
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("successfully finished when connection dead"),
+        };
+    }
+
+    #[tokio::test]
+    async fn do_not_call_sender_and_receiver_until_authorized() {
+        let MockPrelims {
+            incoming_handle,
+            outgoing_handle,
+            mut data_from_incoming,
+            mut result_from_incoming,
+            authorization_requests,
+            ..
+        } = prepare::<Vec<i32>>();
+
+        let authorization_handle = handle_authorization(authorization_requests, |_| false);
+
+        let (incoming_result, outgoing_result, authorization_result) =
+            tokio::join!(incoming_handle, outgoing_handle, authorization_handle);
+
+        assert!(incoming_result.is_err());
+        assert!(outgoing_result.is_err());
+        // this also verifies if it was called at all
+        assert!(authorization_result.is_ok());
+
+        let data_from_incoming = data_from_incoming.try_next();
+        assert!(data_from_incoming.ok().flatten().is_none());
+
+        let result_from_incoming = result_from_incoming.try_next();
+        assert!(result_from_incoming.ok().flatten().is_none());
+    }
+}
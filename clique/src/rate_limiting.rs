@@ -1,6 +1,15 @@
-use rate_limiter::{RateLimitedAsyncRead, SharedRateLimiter};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
-use crate::{ConnectionInfo, Data, Dialer, Listener, PeerAddressInfo, Splittable, Splitted};
+use futures::lock::Mutex as AsyncMutex;
+use rate_limiter::{RateLimitedAsyncRead, RateLimitedAsyncWrite, SharedRateLimiter};
+
+use crate::{
+    metrics::{Metrics, Traffic},
+    ConnectionInfo, Data, Dialer, Listener, PeerAddressInfo, PublicKey, Splittable, Splitted,
+};
 
 impl<Read> ConnectionInfo for RateLimitedAsyncRead<Read>
 where
@@ -11,10 +20,20 @@ where
     }
 }
 
+impl<Write> ConnectionInfo for RateLimitedAsyncWrite<Write>
+where
+    Write: ConnectionInfo,
+{
+    fn peer_address_info(&self) -> PeerAddressInfo {
+        self.inner().peer_address_info()
+    }
+}
+
 /// Implementation of the [Dialer] trait governing all returned [Dialer::Connection] instances by a rate-limiting wrapper.
 pub struct RateLimitingDialer<D> {
     dialer: D,
-    rate_limiter: SharedRateLimiter,
+    read_rate_limiter: SharedRateLimiter,
+    write_rate_limiter: SharedRateLimiter,
 }
 
 impl<D> Clone for RateLimitingDialer<D>
@@ -24,16 +43,22 @@ where
     fn clone(&self) -> Self {
         Self {
             dialer: self.dialer.clone(),
-            rate_limiter: self.rate_limiter.share(),
+            read_rate_limiter: self.read_rate_limiter.share(),
+            write_rate_limiter: self.write_rate_limiter.share(),
         }
     }
 }
 
 impl<D> RateLimitingDialer<D> {
-    pub fn new(dialer: D, rate_limiter: SharedRateLimiter) -> Self {
+    pub fn new(
+        dialer: D,
+        read_rate_limiter: SharedRateLimiter,
+        write_rate_limiter: SharedRateLimiter,
+    ) -> Self {
         Self {
             dialer,
-            rate_limiter,
+            read_rate_limiter,
+            write_rate_limiter,
         }
     }
 }
@@ -48,7 +73,7 @@ where
 {
     type Connection = Splitted<
         RateLimitedAsyncRead<<D::Connection as Splittable>::Receiver>,
-        <D::Connection as Splittable>::Sender,
+        RateLimitedAsyncWrite<<D::Connection as Splittable>::Sender>,
     >;
     type Error = D::Error;
 
@@ -56,8 +81,8 @@ where
         let connection = self.dialer.connect(address).await?;
         let (sender, receiver) = connection.split();
         Ok(Splitted(
-            RateLimitedAsyncRead::new(receiver, self.rate_limiter.share()),
-            sender,
+            RateLimitedAsyncRead::new(receiver, self.read_rate_limiter.share()),
+            RateLimitedAsyncWrite::new(sender, self.write_rate_limiter.share()),
         ))
     }
 }
@@ -65,14 +90,20 @@ where
 /// Implementation of the [Listener] trait governing all returned [Listener::Connection] instances by a rate-limiting wrapper.
 pub struct RateLimitingListener<L> {
     listener: L,
-    rate_limiter: SharedRateLimiter,
+    read_rate_limiter: SharedRateLimiter,
+    write_rate_limiter: SharedRateLimiter,
 }
 
 impl<L> RateLimitingListener<L> {
-    pub fn new(listener: L, rate_limiter: SharedRateLimiter) -> Self {
+    pub fn new(
+        listener: L,
+        read_rate_limiter: SharedRateLimiter,
+        write_rate_limiter: SharedRateLimiter,
+    ) -> Self {
         Self {
             listener,
-            rate_limiter,
+            read_rate_limiter,
+            write_rate_limiter,
         }
     }
 }
@@ -84,7 +115,7 @@ where
 {
     type Connection = Splitted<
         RateLimitedAsyncRead<<L::Connection as Splittable>::Receiver>,
-        <L::Connection as Splittable>::Sender,
+        RateLimitedAsyncWrite<<L::Connection as Splittable>::Sender>,
     >;
     type Error = L::Error;
 
@@ -92,8 +123,187 @@ where
         let connection = self.listener.accept().await?;
         let (sender, receiver) = connection.split();
         Ok(Splitted(
-            RateLimitedAsyncRead::new(receiver, self.rate_limiter.share()),
-            sender,
+            RateLimitedAsyncRead::new(receiver, self.read_rate_limiter.share()),
+            RateLimitedAsyncWrite::new(sender, self.write_rate_limiter.share()),
         ))
     }
 }
+
+/// Token buckets of a single peer, shared by all connections with that peer. They are taken out for the duration of
+/// rate-limiting, as [SharedRateLimiter::rate_limit] consumes them.
+type PeerBuckets = Arc<AsyncMutex<Option<SharedRateLimiter>>>;
+
+/// Rate-limiters of the traffic with authenticated peers, keyed by their public keys. The network-wide bit-rate, one for
+/// each direction, is split into equal shares between the peers which are actively exchanging data at the moment, and all
+/// connections with a peer draw from its single share. This way a peer cannot use more than its fair share of the
+/// bandwidth by opening multiple connections, while the bandwidth of idle peers is given to the active ones.
+pub struct PeerRateLimiters<PK> {
+    received_budget: SharedRateLimiter,
+    sent_budget: SharedRateLimiter,
+    limiters: Arc<Mutex<HashMap<PK, (PeerBuckets, PeerBuckets)>>>,
+}
+
+impl<PK> Clone for PeerRateLimiters<PK> {
+    fn clone(&self) -> Self {
+        Self {
+            received_budget: self.received_budget.share(),
+            sent_budget: self.sent_budget.share(),
+            limiters: self.limiters.clone(),
+        }
+    }
+}
+
+impl<PK: PublicKey> PeerRateLimiters<PK> {
+    /// Shares the bit-rates of `received_budget` and `sent_budget` between the active peers. They should not be shared
+    /// with any other consumers, otherwise these consumers count as additional peers.
+    pub fn new(received_budget: SharedRateLimiter, sent_budget: SharedRateLimiter) -> Self {
+        Self {
+            received_budget,
+            sent_budget,
+            limiters: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Does not limit the traffic with peers.
+    pub fn unlimited() -> Self {
+        Self::new(
+            SharedRateLimiter::unlimited(),
+            SharedRateLimiter::unlimited(),
+        )
+    }
+
+    /// Returns limiters for the received and sent traffic of a new connection with the peer.
+    pub fn for_peer(
+        &self,
+        public_key: &PK,
+        metrics: Metrics,
+    ) -> (PeerTrafficLimiter, PeerTrafficLimiter) {
+        let (received, sent) = self
+            .limiters
+            .lock()
+            .expect("mutex should not be poisoned")
+            .entry(public_key.clone())
+            .or_insert_with(|| {
+                (
+                    Arc::new(AsyncMutex::new(Some(self.received_budget.share()))),
+                    Arc::new(AsyncMutex::new(Some(self.sent_budget.share()))),
+                )
+            })
+            .clone();
+        let peer = public_key.to_string();
+        (
+            PeerTrafficLimiter {
+                buckets: received,
+                budget: self.received_budget.share(),
+                peer: peer.clone(),
+                traffic: Traffic::Received,
+                metrics: metrics.clone(),
+            },
+            PeerTrafficLimiter {
+                buckets: sent,
+                budget: self.sent_budget.share(),
+                peer,
+                traffic: Traffic::Sent,
+                metrics,
+            },
+        )
+    }
+
+    /// Forgets the token buckets of the peer, connections that are still alive keep using them.
+    pub fn remove_peer(&self, public_key: &PK) {
+        self.limiters
+            .lock()
+            .expect("mutex should not be poisoned")
+            .remove(public_key);
+    }
+}
+
+/// Limits and reports the traffic in one direction of a connection with an authenticated peer.
+pub struct PeerTrafficLimiter {
+    buckets: PeerBuckets,
+    budget: SharedRateLimiter,
+    peer: String,
+    traffic: Traffic,
+    metrics: Metrics,
+}
+
+impl PeerTrafficLimiter {
+    /// Accounts for `bytes` of traffic, waiting if the peer exceeded its share of the bit-rate.
+    pub async fn account(self, bytes: usize) -> Self {
+        self.metrics
+            .report_peer_traffic(&self.peer, self.traffic, bytes);
+        {
+            let mut buckets = self.buckets.lock().await;
+            // The buckets are missing only if accounting on another connection was cancelled while they were taken out.
+            let rate_limiter = buckets.take().unwrap_or_else(|| self.budget.share());
+            *buckets = Some(rate_limiter.rate_limit(bytes.saturating_mul(8)).await);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use futures::join;
+    use rate_limiter::{NonZeroRatePerSecond, SharedRateLimiter};
+
+    use super::PeerRateLimiters;
+    use crate::{
+        metrics::Metrics,
+        mock::{key, MockPublicKey},
+    };
+
+    fn peer_rate_limiters() -> PeerRateLimiters<MockPublicKey> {
+        // 100 bytes per second.
+        let rate = NonZeroRatePerSecond::try_from(800).expect("rate is not zero");
+        PeerRateLimiters::new(
+            SharedRateLimiter::new(rate.into()),
+            SharedRateLimiter::new(rate.into()),
+        )
+    }
+
+    #[tokio::test]
+    async fn connections_with_the_same_peer_share_its_bit_rate() {
+        let peer_rate_limiters = peer_rate_limiters();
+        let (peer, _) = key();
+        let (first_received, _) = peer_rate_limiters.for_peer(&peer, Metrics::noop());
+        let (second_received, _) = peer_rate_limiters.for_peer(&peer, Metrics::noop());
+
+        let start = Instant::now();
+        join!(first_received.account(20), second_received.account(20));
+
+        // Each of the connections gets only half of the bit-rate.
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn active_peers_share_bit_rate() {
+        let peer_rate_limiters = peer_rate_limiters();
+        let (first_peer, _) = key();
+        let (second_peer, _) = key();
+        let (first_received, _) = peer_rate_limiters.for_peer(&first_peer, Metrics::noop());
+        let (second_received, _) = peer_rate_limiters.for_peer(&second_peer, Metrics::noop());
+
+        let start = Instant::now();
+        join!(first_received.account(20), second_received.account(20));
+
+        // The bit-rate is split between the peers.
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn idle_peers_do_not_take_bit_rate() {
+        let peer_rate_limiters = peer_rate_limiters();
+        let (active_peer, _) = key();
+        let (idle_peer, _) = key();
+        let (active_received, _) = peer_rate_limiters.for_peer(&active_peer, Metrics::noop());
+        let (_idle_received, _) = peer_rate_limiters.for_peer(&idle_peer, Metrics::noop());
+
+        let start = Instant::now();
+        active_received.account(20).await;
+
+        assert!(start.elapsed() < Duration::from_millis(400));
+    }
+}
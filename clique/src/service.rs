@@ -12,6 +12,7 @@ use futures::{
     Future, StreamExt, TryFutureExt,
 };
 use log::{info, trace, warn};
+use rate_limiter::SharedRateLimiter;
 use substrate_prometheus_endpoint::Registry;
 use tokio::time;
 
@@ -21,6 +22,7 @@ use crate::{
     metrics::Metrics,
     outgoing::outgoing,
     protocols::ResultForService,
+    rate_limiting::PeerRateLimiters,
     Data, Dialer, Listener, Network, PeerId, PublicKey, SecretKey, LOG_TARGET,
 };
 
@@ -148,6 +150,7 @@ where
     spawn_handle: SH,
     secret_key: SK,
     metrics: Metrics,
+    peer_rate_limiters: PeerRateLimiters<SK::PublicKey>,
 }
 
 impl<SK: SecretKey, D: Data, A: Data + Debug, ND: Dialer<A>, NL: Listener, SH: SpawnHandleT>
@@ -156,11 +159,15 @@ where
     SK::PublicKey: PeerId,
 {
     /// Create a new clique network service plus an interface for interacting with it.
+    /// The bit-rates of `peer_read_rate_limiter` and `peer_write_rate_limiter` are shared between the authenticated peers
+    /// which are exchanging data at the moment.
     pub fn new(
         dialer: ND,
         listener: NL,
         secret_key: SK,
         spawn_handle: SH,
+        peer_read_rate_limiter: SharedRateLimiter,
+        peer_write_rate_limiter: SharedRateLimiter,
         metrics_registry: Option<Registry>,
     ) -> (Self, impl Network<SK::PublicKey, A, D>) {
         // Channel for sending commands between the service and interface
@@ -184,6 +191,10 @@ where
                 spawn_handle,
                 secret_key,
                 metrics,
+                peer_rate_limiters: PeerRateLimiters::new(
+                    peer_read_rate_limiter,
+                    peer_write_rate_limiter,
+                ),
             },
             ServiceInterface {
                 commands_for_service,
@@ -202,6 +213,7 @@ where
         let dialer = self.dialer.clone();
         let next_to_interface = self.next_to_interface.clone();
         let metrics = self.metrics.clone();
+        let peer_rate_limiters = self.peer_rate_limiters.clone();
         self.spawn_handle
             .spawn("aleph/clique_network_outgoing", async move {
                 outgoing(
@@ -212,6 +224,7 @@ where
                     result_for_parent,
                     next_to_interface,
                     metrics,
+                    peer_rate_limiters,
                 )
                 .await;
             });
@@ -229,6 +242,7 @@ where
         let secret_key = self.secret_key.clone();
         let next_to_interface = self.next_to_interface.clone();
         let metrics = self.metrics.clone();
+        let peer_rate_limiters = self.peer_rate_limiters.clone();
         self.spawn_handle
             .spawn("aleph/clique_network_incoming", async move {
                 incoming(
@@ -238,6 +252,7 @@ where
                     next_to_interface,
                     authorization_requests_sender,
                     metrics,
+                    peer_rate_limiters,
                 )
                 .await;
             });
@@ -275,6 +290,8 @@ where
             // remove the peer from the manager all workers will be killed automatically, due to closed channels
             DelConnection(public_key) => {
                 self.manager.remove_peer(&public_key);
+                self.peer_rate_limiters.remove_peer(&public_key);
+                self.metrics.remove_peer(&public_key.to_string());
             }
             // pass the data to the manager
             SendData(data, public_key) => match self.manager.send_to(&public_key, data) {
@@ -11,6 +11,7 @@ use futures::{
 };
 use log::info;
 use rand::{thread_rng, Rng};
+use rate_limiter::SharedRateLimiter;
 use tokio::time::{error::Elapsed, interval, timeout, Duration};
 
 use crate::{
@@ -45,7 +46,15 @@ fn spawn_peer(
     spawn_handle: Spawner,
 ) {
     let our_id = secret_key.public_key();
-    let (service, mut interface) = Service::new(dialer, listener, secret_key, spawn_handle, None);
+    let (service, mut interface) = Service::new(
+        dialer,
+        listener,
+        secret_key,
+        spawn_handle,
+        SharedRateLimiter::unlimited(),
+        SharedRateLimiter::unlimited(),
+        None,
+    );
     // run the service
     tokio::spawn(async {
         let (_exit, rx) = oneshot::channel();
@@ -37,6 +37,8 @@ DATABASE_ENGINE=${DATABASE_ENGINE:-}
 PRUNING_ENABLED=${PRUNING_ENABLED:-false}
 ALEPHBFT_NETWORK_BIT_RATE=${ALEPHBFT_NETWORK_BIT_RATE:-}
 SUBSTRATE_NETWORK_BIT_RATE=${SUBSTRATE_NETWORK_BIT_RATE:-}
+ALEPHBFT_NETWORK_UPLOAD_BIT_RATE=${ALEPHBFT_NETWORK_UPLOAD_BIT_RATE:-}
+SUBSTRATE_NETWORK_UPLOAD_BIT_RATE=${SUBSTRATE_NETWORK_UPLOAD_BIT_RATE:-}
 
 if [[ "true" == "$PURGE_BEFORE_START" ]]; then
   echo "Purging chain (${CHAIN}) at path ${BASE_PATH}"
@@ -151,4 +153,12 @@ if [[ -n "${SUBSTRATE_NETWORK_BIT_RATE}" ]]; then
     ARGS+=(--substrate-network-bit-rate ${SUBSTRATE_NETWORK_BIT_RATE})
 fi
 
+if [[ -n "${ALEPHBFT_NETWORK_UPLOAD_BIT_RATE}" ]]; then
+    ARGS+=(--alephbft-network-upload-bit-rate ${ALEPHBFT_NETWORK_UPLOAD_BIT_RATE})
+fi
+
+if [[ -n "${SUBSTRATE_NETWORK_UPLOAD_BIT_RATE}" ]]; then
+    ARGS+=(--substrate-network-upload-bit-rate ${SUBSTRATE_NETWORK_UPLOAD_BIT_RATE})
+fi
+
 echo "${CUSTOM_ARGS}" | xargs aleph-node "${ARGS[@]}"
@@ -82,8 +82,8 @@ pub async fn check_ban_config<C: CommitteeManagementApi>(
     expected_minimal_expected_performance: Perbill,
     expected_session_count_threshold: SessionCount,
     expected_clean_session_counter_delay: SessionCount,
-) -> ProductionBanConfig {
-    let ban_config = connection.get_ban_config(None).await;
+) -> anyhow::Result<ProductionBanConfig> {
+    let ban_config = connection.try_get_ban_config(None).await?;
 
     assert_eq!(
         ban_config.minimal_expected_performance.0,
@@ -98,17 +98,17 @@ pub async fn check_ban_config<C: CommitteeManagementApi>(
         expected_clean_session_counter_delay
     );
 
-    ban_config
+    Ok(ban_config)
 }
 
 pub async fn check_underperformed_validator_session_count<C: CommitteeManagementApi>(
     connection: &C,
     validator: &AccountId,
     expected_session_count: SessionCount,
-) -> SessionCount {
+) -> anyhow::Result<SessionCount> {
     let underperformed_validator_session_count = connection
-        .get_underperformed_validator_session_count(validator.clone(), None)
-        .await
+        .try_get_underperformed_validator_session_count(validator.clone(), None)
+        .await?
         .unwrap_or_default();
 
     assert_eq!(
@@ -116,34 +116,34 @@ pub async fn check_underperformed_validator_session_count<C: CommitteeManagement
         expected_session_count
     );
 
-    underperformed_validator_session_count
+    Ok(underperformed_validator_session_count)
 }
 
 pub async fn check_underperformed_validator_reason<C: CommitteeManagementApi>(
     connection: &C,
     validator: &AccountId,
     expected_info: Option<&BanInfo>,
-) -> Option<BanInfo> {
+) -> anyhow::Result<Option<BanInfo>> {
     let validator_ban_info = connection
-        .get_ban_info_for_validator(validator.clone(), None)
-        .await;
+        .try_get_ban_info_for_validator(validator.clone(), None)
+        .await?;
 
     assert_eq!(validator_ban_info.as_ref(), expected_info);
-    validator_ban_info
+    Ok(validator_ban_info)
 }
 
 pub async fn check_ban_info_for_validator<C: CommitteeManagementApi>(
     connection: &C,
     validator: &AccountId,
     expected_info: Option<&BanInfo>,
-) -> Option<BanInfo> {
+) -> anyhow::Result<Option<BanInfo>> {
     let validator_ban_info = connection
-        .get_ban_info_for_validator(validator.clone(), None)
-        .await;
+        .try_get_ban_info_for_validator(validator.clone(), None)
+        .await?;
 
     assert_eq!(validator_ban_info.as_ref(), expected_info);
 
-    validator_ban_info
+    Ok(validator_ban_info)
 }
 
 pub async fn check_ban_event<C: AlephWaiting>(
@@ -200,15 +200,15 @@ pub async fn check_underperformed_count_for_sessions<C: AsConnection + Sync>(
                 session, val
             );
             let session_underperformed_count = connection
-                .get_underperformed_validator_session_count(val.clone(), session_end_block_hash)
-                .await
+                .try_get_underperformed_validator_session_count(val.clone(), session_end_block_hash)
+                .await?
                 .unwrap_or_default();
             let previous_session_underperformed_count = connection
-                .get_underperformed_validator_session_count(
+                .try_get_underperformed_validator_session_count(
                     val.clone(),
                     previous_session_end_block_hash,
                 )
-                .await
+                .await?
                 .unwrap_or_default();
 
             let underperformed_diff =
@@ -22,8 +22,8 @@ pub async fn compute_session_committee<C: AsConnection + Sync>(
     let first_block_in_era = connection.first_block_of_session(first_session).await?;
 
     let validators = connection
-        .get_current_era_validators(first_block_in_era)
-        .await;
+        .try_get_current_era_validators(first_block_in_era)
+        .await?;
 
     let committee = connection
         .get_session_committee(session, first_block_in_era)
@@ -62,7 +62,11 @@ pub async fn get_and_test_members_for_session<C: AsConnection + Sync>(
 
     let members_active_set: HashSet<_> = members_active.iter().cloned().collect();
     let block = connection.first_block_of_session(session).await?;
-    let network_members: HashSet<_> = connection.get_validators(block).await.into_iter().collect();
+    let network_members: HashSet<_> = connection
+        .try_get_validators(block)
+        .await?
+        .into_iter()
+        .collect();
 
     debug!(
         "expected era validators for session {}: reserved - {:?}, non-reserved - {:?}",
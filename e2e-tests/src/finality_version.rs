@@ -6,7 +6,7 @@ pub async fn check_finality_version_at_block(
     connection: &Connection,
     block_number: BlockNumber,
     expected_version: Version,
-) {
+) -> anyhow::Result<()> {
     info!(
         "Checking current session finality version for block {}",
         block_number
@@ -15,15 +15,17 @@ pub async fn check_finality_version_at_block(
         .get_block_hash(block_number)
         .await
         .expect("Should have been able to get a block hash!");
-    let finality_version = connection.finality_version(block_hash).await;
+    let finality_version = connection.try_finality_version(block_hash).await?;
     assert_eq!(finality_version, expected_version);
+
+    Ok(())
 }
 
 pub async fn check_next_session_finality_version_at_block(
     connection: &Connection,
     block_number: BlockNumber,
     expected_version: Version,
-) {
+) -> anyhow::Result<()> {
     info!(
         "Checking next session finality version for block {}",
         block_number
@@ -32,6 +34,10 @@ pub async fn check_next_session_finality_version_at_block(
         .get_block_hash(block_number)
         .await
         .expect("Should have been able to get a block hash!");
-    let next_finality_version = connection.next_session_finality_version(block_hash).await;
+    let next_finality_version = connection
+        .try_next_session_finality_version(block_hash)
+        .await?;
     assert_eq!(next_finality_version, expected_version);
+
+    Ok(())
 }
@@ -82,10 +82,10 @@ pub async fn download_exposure<S: StakingApi>(
     era: EraIndex,
     account_id: &AccountId,
     beginning_of_session_block_hash: BlockHash,
-) -> Balance {
+) -> anyhow::Result<Balance> {
     let exposure = connection
-        .get_exposure(era, account_id, Some(beginning_of_session_block_hash))
-        .await;
+        .try_get_exposure(era, account_id, Some(beginning_of_session_block_hash))
+        .await?;
     info!(
         "Validator {} has own exposure of {} and total of {}.",
         account_id, exposure.own, exposure.total
@@ -96,7 +96,7 @@ pub async fn download_exposure<S: StakingApi>(
             account_id, individual_exposure.who, individual_exposure.value
         )
     });
-    exposure.total
+    Ok(exposure.total)
 }
 
 fn check_rewards(
@@ -137,10 +137,10 @@ async fn get_node_performance<S: ElectionsApi + CommitteeManagementApi>(
     account_id: &AccountId,
     before_end_of_session_block_hash: BlockHash,
     blocks_to_produce_per_session: u32,
-) -> f64 {
+) -> anyhow::Result<f64> {
     let block_count = connection
-        .get_validator_block_count(account_id.clone(), Some(before_end_of_session_block_hash))
-        .await
+        .try_get_validator_block_count(account_id.clone(), Some(before_end_of_session_block_hash))
+        .await?
         .unwrap_or_default();
 
     info!(
@@ -157,7 +157,7 @@ async fn get_node_performance<S: ElectionsApi + CommitteeManagementApi>(
         "Validator {}, lenient performance {:?}.",
         account_id, lenient_performance
     );
-    lenient_performance
+    Ok(lenient_performance)
 }
 
 pub async fn check_points<S: AsConnection + Sync>(
@@ -201,15 +201,15 @@ pub async fn check_points<S: AsConnection + Sync>(
 
     // get points stored by the Staking pallet
     let validator_reward_points_current_era = connection
-        .get_era_reward_points(era, end_of_session_block_hash)
-        .await
+        .try_get_era_reward_points(era, end_of_session_block_hash)
+        .await?
         .unwrap_or_default()
         .individual;
 
     let validator_reward_points_previous_session = HashMap::<AccountId, u32>::from_iter(
         connection
-            .get_era_reward_points(era, beginning_of_session_block_hash)
-            .await
+            .try_get_era_reward_points(era, beginning_of_session_block_hash)
+            .await?
             .unwrap_or_default()
             .individual,
     );
@@ -241,7 +241,7 @@ pub async fn check_points<S: AsConnection + Sync>(
                 before_end_of_session_block_hash.unwrap(),
                 blocks_to_produce_per_session,
             )
-            .await,
+            .await?,
         ));
     }
 
@@ -262,7 +262,7 @@ pub async fn check_points<S: AsConnection + Sync>(
             account_id,
             beginning_of_session_block_hash.unwrap(),
         )
-        .await;
+        .await?;
         *reward_points *= exposure as f64 / members_count;
     }
 
@@ -298,15 +298,15 @@ pub async fn setup_validators(
     let reserved_members = &members[0..reserved_size];
     let non_reserved_members = &members[reserved_size..];
 
-    let session = root_connection.get_session(None).await;
-    let network_validators = root_connection.get_current_era_validators(None).await;
+    let session = root_connection.try_get_session(None).await?;
+    let network_validators = root_connection.try_get_current_era_validators(None).await?;
     let first_block_in_session = root_connection
         .first_block_of_session(session)
         .await?
         .ok_or(anyhow!("First block of session {} is None!", session))?;
     let network_seats = root_connection
-        .get_committee_seats(Some(first_block_in_session))
-        .await;
+        .try_get_committee_seats(Some(first_block_in_session))
+        .await?;
 
     let era_validators = EraValidators {
         reserved: reserved_members.to_vec(),
@@ -329,19 +329,19 @@ pub async fn setup_validators(
         .await?;
 
     root_connection.wait_for_n_eras(2, BlockStatus::Best).await;
-    let session = root_connection.get_session(None).await;
+    let session = root_connection.try_get_session(None).await?;
 
     let first_block_in_session = root_connection.first_block_of_session(session).await?;
     let network_validators = root_connection
-        .get_current_era_validators(first_block_in_session)
-        .await;
+        .try_get_current_era_validators(first_block_in_session)
+        .await?;
     let reserved: HashSet<_> = era_validators.reserved.iter().cloned().collect();
     let network_reserved: HashSet<_> = network_validators.reserved.into_iter().collect();
     let non_reserved: HashSet<_> = era_validators.non_reserved.iter().cloned().collect();
     let network_non_reserved: HashSet<_> = network_validators.non_reserved.into_iter().collect();
     let network_seats = root_connection
-        .get_committee_seats(first_block_in_session)
-        .await;
+        .try_get_committee_seats(first_block_in_session)
+        .await?;
 
     assert_eq!(reserved, network_reserved);
     assert_eq!(non_reserved, network_non_reserved);
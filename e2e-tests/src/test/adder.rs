@@ -125,16 +125,16 @@ pub async fn adder_dry_run_failure() -> Result<()> {
     contract.add(&account.sign(&conn), 1).await?;
 
     let caller_balance_before = conn
-        .get_free_balance(account.account_id().clone(), None)
-        .await;
+        .try_get_free_balance(account.account_id().clone(), None)
+        .await?;
 
     // Should fail due to the overflow check in contract.
     let result = contract.add(&account.sign(&conn), u32::MAX).await;
     assert!(result.is_err());
 
     let caller_balance_after = conn
-        .get_free_balance(account.account_id().clone(), None)
-        .await;
+        .try_get_free_balance(account.account_id().clone(), None)
+        .await?;
 
     assert_eq!(caller_balance_before, caller_balance_after);
 
@@ -73,7 +73,7 @@ pub async fn ban_automatic() -> anyhow::Result<()> {
     check_validators(
         &reserved_validators,
         &non_reserved_validators,
-        root_connection.get_current_era_validators(None).await,
+        root_connection.try_get_current_era_validators(None).await?,
     );
 
     check_ban_config(
@@ -82,15 +82,15 @@ pub async fn ban_automatic() -> anyhow::Result<()> {
         DEFAULT_BAN_SESSION_COUNT_THRESHOLD,
         DEFAULT_CLEAN_SESSION_COUNTER_DELAY,
     )
-    .await;
+    .await?;
 
     let validator_to_disable =
         &non_reserved_validators[VALIDATOR_TO_DISABLE_NON_RESERVED_INDEX as usize];
 
     info!("Validator to disable: {}", validator_to_disable);
 
-    check_underperformed_validator_session_count(&root_connection, validator_to_disable, 0).await;
-    check_underperformed_validator_reason(&root_connection, validator_to_disable, None).await;
+    check_underperformed_validator_session_count(&root_connection, validator_to_disable, 0).await?;
+    check_underperformed_validator_reason(&root_connection, validator_to_disable, None).await?;
 
     disable_validator(NODE_TO_DISABLE_ADDRESS, VALIDATOR_TO_DISABLE_OVERALL_INDEX).await?;
 
@@ -100,10 +100,10 @@ pub async fn ban_automatic() -> anyhow::Result<()> {
 
     // The session count for underperforming validators is reset to 0 immediately on reaching the
     // threshold.
-    check_underperformed_validator_session_count(&root_connection, validator_to_disable, 0).await;
+    check_underperformed_validator_session_count(&root_connection, validator_to_disable, 0).await?;
 
     let reason = BanReason::InsufficientUptime(DEFAULT_BAN_SESSION_COUNT_THRESHOLD);
-    let start = root_connection.get_current_era(None).await + 1;
+    let start = root_connection.try_get_current_era(None).await? + 1;
     let expected_ban_info = BanInfo { reason, start };
 
     check_underperformed_validator_reason(
@@ -111,7 +111,7 @@ pub async fn ban_automatic() -> anyhow::Result<()> {
         validator_to_disable,
         Some(&expected_ban_info),
     )
-    .await;
+    .await?;
 
     let expected_non_reserved =
         &non_reserved_validators[(VALIDATOR_TO_DISABLE_NON_RESERVED_INDEX + 1) as usize..];
@@ -123,7 +123,7 @@ pub async fn ban_automatic() -> anyhow::Result<()> {
     check_validators(
         &reserved_validators,
         expected_non_reserved,
-        root_connection.get_current_era_validators(None).await,
+        root_connection.try_get_current_era_validators(None).await?,
     );
 
     Ok(())
@@ -142,7 +142,7 @@ pub async fn ban_manual() -> anyhow::Result<()> {
     check_validators(
         &reserved_validators,
         &non_reserved_validators,
-        root_connection.get_current_era_validators(None).await,
+        root_connection.try_get_current_era_validators(None).await?,
     );
 
     check_ban_config(
@@ -151,7 +151,7 @@ pub async fn ban_manual() -> anyhow::Result<()> {
         DEFAULT_BAN_SESSION_COUNT_THRESHOLD,
         DEFAULT_CLEAN_SESSION_COUNTER_DELAY,
     )
-    .await;
+    .await?;
 
     let validator_to_manually_ban =
         &non_reserved_validators[VALIDATOR_TO_MANUALLY_BAN_NON_RESERVED_INDEX as usize];
@@ -159,8 +159,8 @@ pub async fn ban_manual() -> anyhow::Result<()> {
     info!("Validator to manually ban: {}", validator_to_manually_ban);
 
     check_underperformed_validator_session_count(&root_connection, validator_to_manually_ban, 0)
-        .await;
-    check_ban_info_for_validator(&root_connection, validator_to_manually_ban, None).await;
+        .await?;
+    check_ban_info_for_validator(&root_connection, validator_to_manually_ban, None).await?;
 
     let bounded_reason = BoundedVec(MANUAL_BAN_REASON.as_bytes().to_vec());
 
@@ -172,15 +172,15 @@ pub async fn ban_manual() -> anyhow::Result<()> {
         )
         .await?;
 
-    let reason = BanReason::OtherReason(bounded_reason);
-    let start = root_connection.get_current_era(None).await + 1;
+    let reason = BanReason::Manual(bounded_reason);
+    let start = root_connection.try_get_current_era(None).await? + 1;
     let expected_ban_info = BanInfo { reason, start };
     check_ban_info_for_validator(
         &root_connection,
         validator_to_manually_ban,
         Some(&expected_ban_info),
     )
-    .await;
+    .await?;
 
     let expected_banned_validators = vec![(validator_to_manually_ban.clone(), expected_ban_info)];
 
@@ -196,7 +196,7 @@ pub async fn ban_manual() -> anyhow::Result<()> {
     check_validators(
         &reserved_validators,
         &expected_non_reserved,
-        root_connection.get_current_era_validators(None).await,
+        root_connection.try_get_current_era_validators(None).await?,
     );
 
     Ok(())
@@ -226,17 +226,19 @@ pub async fn clearing_session_count() -> anyhow::Result<()> {
         .await;
 
     let underperformed_validator_session_count = root_connection
-        .get_underperformed_validator_session_count(validator_to_disable.clone(), None)
-        .await
+        .try_get_underperformed_validator_session_count(validator_to_disable.clone(), None)
+        .await?
         .unwrap_or_default();
 
     // it only has to be ge than 0 and should be cleared before reaching values larger than 3.
     assert!(underperformed_validator_session_count <= 2);
 
-    let next_era_reserved_validators = root_connection.get_next_era_reserved_validators(None).await;
+    let next_era_reserved_validators = root_connection
+        .try_get_next_era_reserved_validators(None)
+        .await?;
     let next_era_non_reserved_validators = root_connection
-        .get_next_era_non_reserved_validators(None)
-        .await;
+        .try_get_next_era_non_reserved_validators(None)
+        .await?;
 
     // checks no one was banned
     assert_eq!(next_era_reserved_validators, reserved_validators);
@@ -294,8 +296,8 @@ pub async fn permissionless_ban() -> anyhow::Result<()> {
     let without_banned = HashSet::<_>::from_iter(non_reserved_without_banned);
     let non_reserved = HashSet::<_>::from_iter(
         root_connection
-            .get_current_era_validators(None)
-            .await
+            .try_get_current_era_validators(None)
+            .await?
             .non_reserved,
     );
     assert_eq!(without_banned, non_reserved);
@@ -309,8 +311,8 @@ pub async fn permissionless_ban() -> anyhow::Result<()> {
     let expected_non_reserved = HashSet::<_>::from_iter(non_reserved_validators);
     let non_reserved = HashSet::<_>::from_iter(
         root_connection
-            .get_current_era_validators(None)
-            .await
+            .try_get_current_era_validators(None)
+            .await?
             .non_reserved,
     );
 
@@ -332,7 +334,7 @@ pub async fn ban_threshold() -> anyhow::Result<()> {
     check_validators(
         &reserved_validators,
         &non_reserved_validators,
-        root_connection.get_current_era_validators(None).await,
+        root_connection.try_get_current_era_validators(None).await?,
     );
 
     check_ban_config(
@@ -341,7 +343,7 @@ pub async fn ban_threshold() -> anyhow::Result<()> {
         DEFAULT_BAN_SESSION_COUNT_THRESHOLD,
         DEFAULT_CLEAN_SESSION_COUNTER_DELAY,
     )
-    .await;
+    .await?;
 
     // Change ban config to require prohibitively high performance from all validators.
     root_connection
@@ -354,7 +356,7 @@ pub async fn ban_threshold() -> anyhow::Result<()> {
         )
         .await?;
 
-    let ban_config_change_session = root_connection.get_session(None).await;
+    let ban_config_change_session = root_connection.try_get_session(None).await?;
     let check_start_session = ban_config_change_session + 1;
     let check_end_session = check_start_session + SESSIONS_TO_CHECK - 1;
     root_connection
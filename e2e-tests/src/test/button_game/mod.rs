@@ -54,11 +54,11 @@ pub async fn wrapped_azero() -> Result<()> {
             == alephs(2)
     );
 
-    let balance_before = conn.get_free_balance(account_id.clone(), None).await;
+    let balance_before = conn.try_get_free_balance(account_id.clone(), None).await?;
     wazero.unwrap(account_conn, alephs(1)).await?;
 
     let event = assert_recv_id(&mut events, "Unwrapped").await;
-    let balance_after = conn.get_free_balance(account_id.clone(), None).await;
+    let balance_after = conn.try_get_free_balance(account_id.clone(), None).await?;
     let max_fee = alephs(1) / 100;
     assert!(balance_after - balance_before > alephs(1) - max_fee);
     let_assert!(Some(Value::Literal(acc_id)) = event.data.get("caller"));
@@ -84,13 +84,13 @@ async fn split_disable(validators: &[u32]) -> anyhow::Result<()> {
         validators
     );
     disable_validators(validators).await?;
-    let session_before = connection.get_session(None).await;
+    let session_before = connection.try_get_session(None).await?;
     let block_before = connection
         .get_best_block()
         .await?
         .expect("there should be some block");
     sleep(SLEEP_DURATION);
-    let session_after = connection.get_session(None).await;
+    let session_after = connection.try_get_session(None).await?;
     let block_after = connection
         .get_best_block()
         .await?
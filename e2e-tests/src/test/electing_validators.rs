@@ -87,14 +87,14 @@ async fn assert_validators_are_used_as_authorities<C: SessionApi + WaitingExt>(
     connection: &C,
     expected_authorities: &BTreeSet<AccountId>,
     min_num_sessions: u32,
-) {
+) -> anyhow::Result<()> {
     let mut authorities = BTreeSet::new();
 
     for _ in 0..min_num_sessions {
-        let current_session = connection.get_session(None).await;
+        let current_session = connection.try_get_session(None).await?;
 
         info!("Reading authorities in session {}", current_session);
-        let current_authorities = connection.get_validators(None).await;
+        let current_authorities = connection.try_get_validators(None).await?;
         for ca in current_authorities.into_iter() {
             authorities.insert(ca);
         }
@@ -106,14 +106,21 @@ async fn assert_validators_are_used_as_authorities<C: SessionApi + WaitingExt>(
         *expected_authorities, authorities,
         "Expected another set of authorities.\n\tExpected: {expected_authorities:?}\n\tActual: {authorities:?}"
     );
+
+    Ok(())
 }
 
-async fn assert_enough_validators<C: ConnectionApi>(connection: &C, min_validator_count: u32) {
-    let current_validator_count = connection.get_validators(None).await.len() as u32;
+async fn assert_enough_validators<C: ConnectionApi>(
+    connection: &C,
+    min_validator_count: u32,
+) -> anyhow::Result<()> {
+    let current_validator_count = connection.try_get_validators(None).await?.len() as u32;
     assert!(
         current_validator_count >= min_validator_count,
         "{current_validator_count} validators present. Staking enforces a minimum of {min_validator_count} validators."
     );
+
+    Ok(())
 }
 
 fn assert_enough_validators_left_after_chilling(
@@ -174,7 +181,9 @@ pub async fn authorities_are_staking() -> anyhow::Result<()> {
     const NON_RESERVED_SEATS_DEFAULT: u32 = 3;
 
     // `MinimumValidatorCount` from `pallet_staking`, set in chain spec.
-    let min_validator_count = root_connection.get_minimum_validator_count(None).await;
+    let min_validator_count = root_connection
+        .try_get_minimum_validator_count(None)
+        .await?;
 
     let reserved_seats = match config.test_case_params.reserved_seats {
         Some(seats) => seats,
@@ -189,7 +198,7 @@ pub async fn authorities_are_staking() -> anyhow::Result<()> {
     const RESERVED_TO_CHILL_COUNT: u32 = 1;
     const NON_RESERVED_TO_CHILL_COUNT: u32 = 1;
 
-    assert_enough_validators(&root_connection, min_validator_count).await;
+    assert_enough_validators(&root_connection, min_validator_count).await?;
 
     let desired_validator_count = reserved_seats + non_reserved_seats;
     let accounts = setup_accounts(desired_validator_count);
@@ -240,7 +249,7 @@ pub async fn authorities_are_staking() -> anyhow::Result<()> {
     // We need any signed connection.
     let connection = root_connection;
     connection.wait_for_n_eras(2, BlockStatus::Best).await;
-    let current_era = connection.get_current_era(None).await;
+    let current_era = connection.try_get_current_era(None).await?;
     info!("New validators are in force (era: {})", current_era);
 
     assert_validators_are_elected_stakers(&connection, current_era, accounts.get_stash_accounts())
@@ -254,12 +263,12 @@ pub async fn authorities_are_staking() -> anyhow::Result<()> {
         &BTreeSet::from_iter(accounts.get_stash_accounts().clone().into_iter()),
         min_num_sessions,
     )
-    .await;
+    .await?;
 
     chill_validators(node, vec![chilling_reserved, chilling_non_reserved]).await;
 
     connection.wait_for_n_eras(2, BlockStatus::Best).await;
-    let current_era = connection.get_current_era(None).await;
+    let current_era = connection.try_get_current_era(None).await?;
     info!(
         "Subset of validators should be in force (era: {})",
         current_era
@@ -275,7 +284,7 @@ pub async fn authorities_are_staking() -> anyhow::Result<()> {
         &BTreeSet::from_iter(left_stashes.into_iter()),
         min_num_sessions,
     )
-    .await;
+    .await?;
 
     Ok(())
 }
@@ -22,7 +22,7 @@ async fn set_emergency_finalizer_test() -> anyhow::Result<()> {
     let (finalizer, _seed) = AlephKeyPair::generate();
     let public = finalizer.public().0;
     let root = config.create_root_connection().await;
-    let current_finalizer = root.as_connection().emergency_finalizer(None).await;
+    let current_finalizer = root.as_connection().try_emergency_finalizer(None).await?;
 
     assert_ne!(current_finalizer, Some(public));
 
@@ -30,7 +30,7 @@ async fn set_emergency_finalizer_test() -> anyhow::Result<()> {
         .await?;
     root.wait_for_n_sessions(2, BlockStatus::Finalized).await;
 
-    let current_finalizer = root.as_connection().emergency_finalizer(None).await;
+    let current_finalizer = root.as_connection().try_emergency_finalizer(None).await?;
     assert_eq!(current_finalizer, Some(public));
     Ok(())
 }
@@ -122,10 +122,10 @@ async fn chain_dead_scenario() -> anyhow::Result<()> {
         last_best_block - last_best_block_before <= 20,
         "at most 20 blocks can be created after finalization stops. from {last_best_block_before} to {last_best_block}"
     );
-    let current_era = connection.get_active_era(Some(last_finalized)).await;
+    let current_era = connection.try_get_active_era(Some(last_finalized)).await?;
 
     // use finalizer to advance into the next era
-    while current_era == connection.get_active_era(Some(last_finalized)).await {
+    while current_era == connection.try_get_active_era(Some(last_finalized)).await? {
         let last_best_block = connection.get_best_block().await?.unwrap();
         let last_best_block = connection.get_block_hash(last_best_block).await?.unwrap();
         finalize_from_to(&connection, last_finalized, last_best_block, finalizer).await?;
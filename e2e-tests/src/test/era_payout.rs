@@ -7,6 +7,7 @@ use aleph_client::{
     waiting::{AlephWaiting, BlockStatus},
     TxStatus,
 };
+use anyhow::anyhow;
 use primitives::{
     EraIndex, DEFAULT_SESSIONS_PER_ERA, DEFAULT_SESSION_PERIOD, MILLISECS_PER_BLOCK, TOKEN,
 };
@@ -22,33 +23,32 @@ pub async fn era_payouts_calculated_correctly() -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn get_era_duration<C: TimestampApi + BlocksApi>(era: EraIndex, connection: &C) -> u64 {
+async fn get_era_duration<C: TimestampApi + BlocksApi>(
+    era: EraIndex,
+    connection: &C,
+) -> anyhow::Result<u64> {
     let current_era_first_block = era * DEFAULT_SESSIONS_PER_ERA * DEFAULT_SESSION_PERIOD;
     let next_era_first_block = (era + 1) * DEFAULT_SESSIONS_PER_ERA * DEFAULT_SESSION_PERIOD;
 
-    let current_era_first_block_hash = connection
-        .get_block_hash(current_era_first_block)
-        .await
-        .unwrap();
-    let next_era_first_block_hash = connection
-        .get_block_hash(next_era_first_block)
-        .await
-        .unwrap();
-
-    return connection
-        .get_timestamp(next_era_first_block_hash)
-        .await
-        .unwrap()
-        - connection
-            .get_timestamp(current_era_first_block_hash)
-            .await
-            .unwrap();
+    let current_era_first_block_hash = connection.get_block_hash(current_era_first_block).await?;
+    let next_era_first_block_hash = connection.get_block_hash(next_era_first_block).await?;
+
+    let current_era_start = connection
+        .try_get_timestamp(current_era_first_block_hash)
+        .await?
+        .ok_or_else(|| anyhow!("No timestamp of the first block of era {era}"))?;
+    let next_era_start = connection
+        .try_get_timestamp(next_era_first_block_hash)
+        .await?
+        .ok_or_else(|| anyhow!("No timestamp of the first block of era {}", era + 1))?;
+
+    Ok(next_era_start - current_era_start)
 }
 
 async fn force_era_payout(config: &Config) -> anyhow::Result<()> {
     let root_connection = config.create_root_connection().await;
     root_connection.wait_for_era(3, BlockStatus::Best).await;
-    let active_era = root_connection.get_active_era(None).await;
+    let active_era = root_connection.try_get_active_era(None).await?;
 
     let starting_session = active_era * DEFAULT_SESSIONS_PER_ERA;
     root_connection
@@ -61,8 +61,10 @@ async fn force_era_payout(config: &Config) -> anyhow::Result<()> {
         .wait_for_session(starting_session + 3, BlockStatus::Best)
         .await;
 
-    let actual_duration = get_era_duration(active_era - 1, &root_connection).await;
-    let payout = root_connection.get_payout_for_era(active_era, None).await;
+    let actual_duration = get_era_duration(active_era - 1, &root_connection).await?;
+    let payout = root_connection
+        .try_get_payout_for_era(active_era, None)
+        .await?;
 
     let expected_era_duration = (3 * DEFAULT_SESSION_PERIOD) as u64 * MILLISECS_PER_BLOCK;
 
@@ -87,13 +89,13 @@ async fn normal_era_payout(config: &Config) -> anyhow::Result<()> {
     let root_connection = config.create_root_connection().await;
 
     root_connection.wait_for_era(2, BlockStatus::Best).await;
-    let active_era = root_connection.get_active_era(None).await;
+    let active_era = root_connection.try_get_active_era(None).await?;
 
     let payout = root_connection
-        .get_payout_for_era(active_era - 1, None)
-        .await;
+        .try_get_payout_for_era(active_era - 1, None)
+        .await?;
 
-    let actual_duration = get_era_duration(active_era - 1, &root_connection).await;
+    let actual_duration = get_era_duration(active_era - 1, &root_connection).await?;
 
     let expected_era_duration =
         (DEFAULT_SESSIONS_PER_ERA * DEFAULT_SESSION_PERIOD) as u64 * MILLISECS_PER_BLOCK;
@@ -44,27 +44,34 @@ fn get_new_non_reserved_validators(config: &Config) -> Vec<KeyPair> {
 
 async fn get_current_and_next_era_reserved_validators<C: ElectionsApi>(
     connection: &C,
-) -> (HashSet<AccountId>, HashSet<AccountId>) {
-    let stored_reserved = connection.get_next_era_reserved_validators(None).await;
-    let current_reserved = connection.get_current_era_validators(None).await.reserved;
-    (
+) -> anyhow::Result<(HashSet<AccountId>, HashSet<AccountId>)> {
+    let stored_reserved = connection
+        .try_get_next_era_reserved_validators(None)
+        .await?;
+    let current_reserved = connection
+        .try_get_current_era_validators(None)
+        .await?
+        .reserved;
+    Ok((
         HashSet::from_iter(current_reserved),
         HashSet::from_iter(stored_reserved),
-    )
+    ))
 }
 
 async fn get_current_and_next_era_non_reserved_validators<C: ElectionsApi>(
     connection: &C,
-) -> (HashSet<AccountId>, HashSet<AccountId>) {
-    let stored_non_reserved = connection.get_next_era_non_reserved_validators(None).await;
+) -> anyhow::Result<(HashSet<AccountId>, HashSet<AccountId>)> {
+    let stored_non_reserved = connection
+        .try_get_next_era_non_reserved_validators(None)
+        .await?;
     let current_non_reserved = connection
-        .get_current_era_validators(None)
-        .await
+        .try_get_current_era_validators(None)
+        .await?
         .non_reserved;
-    (
+    Ok((
         HashSet::from_iter(current_non_reserved),
         HashSet::from_iter(stored_non_reserved),
-    )
+    ))
 }
 
 #[tokio::test]
@@ -124,9 +131,9 @@ pub async fn era_validators() -> anyhow::Result<()> {
         .wait_for_session(1, BlockStatus::Finalized)
         .await;
     let (eras_reserved, stored_reserved) =
-        get_current_and_next_era_reserved_validators(&connection).await;
+        get_current_and_next_era_reserved_validators(&connection).await?;
     let (eras_non_reserved, stored_non_reserved) =
-        get_current_and_next_era_non_reserved_validators(&connection).await;
+        get_current_and_next_era_non_reserved_validators(&connection).await?;
 
     assert_eq!(
         stored_reserved, new_reserved_validators,
@@ -149,9 +156,9 @@ pub async fn era_validators() -> anyhow::Result<()> {
     connection.wait_for_n_eras(1, BlockStatus::Finalized).await;
 
     let (eras_reserved, stored_reserved) =
-        get_current_and_next_era_reserved_validators(&connection).await;
+        get_current_and_next_era_reserved_validators(&connection).await?;
     let (eras_non_reserved, stored_non_reserved) =
-        get_current_and_next_era_non_reserved_validators(&connection).await;
+        get_current_and_next_era_non_reserved_validators(&connection).await?;
 
     assert_eq!(
         stored_reserved, new_reserved_validators,
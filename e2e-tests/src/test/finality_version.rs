@@ -32,7 +32,7 @@ pub async fn schedule_version_change() -> anyhow::Result<()> {
     let connection = config.create_root_connection().await;
     let test_case_params = config.test_case_params.clone();
 
-    let current_session = connection.get_session(None).await;
+    let current_session = connection.try_get_session(None).await?;
     let version_for_upgrade = test_case_params
         .upgrade_to_version
         .unwrap_or(UPGRADE_TO_VERSION);
@@ -74,7 +74,7 @@ pub async fn schedule_doomed_version_change_and_verify_finalization_stopped() ->
     let connection = config.create_root_connection().await;
     let test_case_params = config.test_case_params.clone();
 
-    let current_session = connection.get_session(None).await;
+    let current_session = connection.try_get_session(None).await?;
     let version_for_upgrade = test_case_params
         .upgrade_to_version
         .unwrap_or(UPGRADE_TO_VERSION);
@@ -127,7 +127,7 @@ pub async fn finality_version_change() -> anyhow::Result<()> {
     let root_connection = config.create_root_connection().await;
     let session_period = root_connection.get_session_period().await?;
 
-    let current_session = root_connection.get_session(None).await;
+    let current_session = root_connection.try_get_session(None).await?;
     let session_with_finality_version_change =
         current_session + SESSIONS_AHEAD_FOR_FINALITY_VERSION_CHANGE;
 
@@ -179,13 +179,13 @@ pub async fn finality_version_change() -> anyhow::Result<()> {
             block,
             LEGACY_FINALITY_VERSION as Version,
         )
-        .await;
+        .await?;
         check_next_session_finality_version_at_block(
             root_connection.as_connection(),
             block,
             LEGACY_FINALITY_VERSION as Version,
         )
-        .await;
+        .await?;
     }
 
     info!(
@@ -200,13 +200,13 @@ pub async fn finality_version_change() -> anyhow::Result<()> {
             block,
             LEGACY_FINALITY_VERSION as Version,
         )
-        .await;
+        .await?;
         check_next_session_finality_version_at_block(
             root_connection.as_connection(),
             block,
             first_incoming_finality_version,
         )
-        .await;
+        .await?;
     }
     info!(
         "Checking finality versions once the change has come into effect. Blocks {} to {}",
@@ -218,13 +218,13 @@ pub async fn finality_version_change() -> anyhow::Result<()> {
             block,
             first_incoming_finality_version,
         )
-        .await;
+        .await?;
         check_next_session_finality_version_at_block(
             root_connection.as_connection(),
             block,
             first_incoming_finality_version,
         )
-        .await;
+        .await?;
     }
 
     Ok(())
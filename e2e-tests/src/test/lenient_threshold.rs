@@ -17,7 +17,9 @@ pub async fn set_lenient_threshold_test() -> anyhow::Result<()> {
 
     assert_eq!(
         Some(Perquintill::from_percent(69)),
-        root_connection.get_lenient_threshold_percentage(None).await
+        root_connection
+            .try_get_lenient_threshold_percentage(None)
+            .await?
     );
 
     Ok(())
@@ -33,7 +33,7 @@ pub async fn points_basic() -> anyhow::Result<()> {
     let connection = config.get_first_signed_connection().await;
 
     connection.wait_for_n_eras(1, BlockStatus::Best).await;
-    let end_session = connection.get_session(None).await;
+    let end_session = connection.try_get_session(None).await?;
     let members_per_session = committee_size.reserved_seats + committee_size.non_reserved_seats;
 
     info!(
@@ -86,9 +86,9 @@ pub async fn points_stake_change() -> anyhow::Result<()> {
     .await;
 
     let connection = config.get_first_signed_connection().await;
-    let start_session = connection.get_session(None).await;
+    let start_session = connection.try_get_session(None).await?;
     connection.wait_for_n_eras(1, BlockStatus::Best).await;
-    let end_session = connection.get_session(None).await;
+    let end_session = connection.try_get_session(None).await?;
     let members_per_session = committee_size.reserved_seats + committee_size.non_reserved_seats;
 
     info!(
@@ -138,7 +138,7 @@ pub async fn disable_node() -> anyhow::Result<()> {
     reset_validator_keys(&controller_connection).await?;
 
     root_connection.wait_for_n_eras(1, BlockStatus::Best).await;
-    let end_session = root_connection.get_session(None).await;
+    let end_session = root_connection.try_get_session(None).await?;
     let members_per_session = committee_size.reserved_seats + committee_size.non_reserved_seats;
 
     info!(
@@ -190,8 +190,8 @@ pub async fn force_new_era() -> anyhow::Result<()> {
     connection
         .wait_for_session(start_session + 2, BlockStatus::Finalized)
         .await;
-    let active_era = connection.get_active_era(None).await;
-    let current_session = connection.get_session(None).await;
+    let active_era = connection.try_get_active_era(None).await?;
+    let current_session = connection.try_get_session(None).await?;
     info!(
         "After ForceNewEra | era: {}, session: {}",
         active_era, current_session
@@ -239,12 +239,12 @@ pub async fn change_stake_and_force_new_era() -> anyhow::Result<()> {
     .await;
 
     root_connection.force_new_era(TxStatus::Finalized).await?;
-    let start_session = root_connection.get_session(None).await;
+    let start_session = root_connection.try_get_session(None).await?;
     connection
         .wait_for_session(start_session + 2, BlockStatus::Finalized)
         .await;
-    let active_era = connection.get_active_era(None).await;
-    let current_session = connection.get_session(None).await;
+    let active_era = connection.try_get_active_era(None).await?;
+    let current_session = connection.try_get_session(None).await?;
     info!(
         "After ForceNewEra | era: {}, session: {}",
         active_era, current_session
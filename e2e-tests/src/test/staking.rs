@@ -74,7 +74,7 @@ pub async fn staking_era_payouts() -> anyhow::Result<()> {
     // All the above calls influence the next era, so we need to wait that it passes.
     // this test can be speeded up by forcing new era twice, and waiting 4 sessions in total instead of almost 10 sessions
     connection.wait_for_n_eras(2, BlockStatus::Finalized).await;
-    let current_era = connection.get_current_era(None).await;
+    let current_era = connection.try_get_current_era(None).await?;
     info!(
         "Era {} started, claiming rewards for era {}",
         current_era,
@@ -91,7 +91,7 @@ pub async fn staking_era_payouts() -> anyhow::Result<()> {
             &stash_account,
             current_era,
         )
-        .await;
+        .await?;
     }
 
     Ok(())
@@ -151,8 +151,8 @@ pub async fn staking_new_validator() -> anyhow::Result<()> {
         .await?;
 
     let bonded_controller_account = root_connection
-        .get_bonded(stash_account.clone(), None)
-        .await
+        .try_get_bonded(stash_account.clone(), None)
+        .await?
         .expect("should be bonded to smth");
     assert_eq!(
         bonded_controller_account, stash_account,
@@ -166,8 +166,8 @@ pub async fn staking_new_validator() -> anyhow::Result<()> {
         .await?;
     stash_connection.validate(10, TxStatus::InBlock).await?;
     let ledger = stash_connection
-        .get_ledger(stash_account.clone(), None)
-        .await;
+        .try_get_ledger(stash_account.clone(), None)
+        .await?;
     assert_eq!(
         ledger,
         StakingLedger {
@@ -196,7 +196,7 @@ pub async fn staking_new_validator() -> anyhow::Result<()> {
         .wait_for_n_sessions(2, BlockStatus::Best)
         .await;
     root_connection.wait_for_n_eras(2, BlockStatus::Best).await;
-    let current_era = root_connection.get_current_era(None).await;
+    let current_era = root_connection.try_get_current_era(None).await?;
     info!(
         "Era {} started, claiming rewards for era {}",
         current_era,
@@ -209,7 +209,7 @@ pub async fn staking_new_validator() -> anyhow::Result<()> {
         &stash_account,
         current_era,
     )
-    .await;
+    .await?;
 
     Ok(())
 }
@@ -226,17 +226,16 @@ async fn payout_stakers_and_assert_locked_balance<S: SignedConnectionApi + AsCon
     accounts_to_check_balance: &[AccountId],
     stash_account: &AccountId,
     era: BlockNumber,
-) {
+) -> anyhow::Result<()> {
     let locked_stash_balances_before_payout = stash_connection
-        .locks(accounts_to_check_balance, None)
-        .await;
+        .try_locks(accounts_to_check_balance, None)
+        .await?;
     stash_connection
         .payout_stakers(stash_account.clone(), era - 1, TxStatus::Finalized)
-        .await
-        .unwrap();
+        .await?;
     let locked_stash_balances_after_payout = stash_connection
-        .locks(accounts_to_check_balance, None)
-        .await;
+        .try_locks(accounts_to_check_balance, None)
+        .await?;
     locked_stash_balances_before_payout.iter()
         .zip(locked_stash_balances_after_payout.iter())
         .zip(accounts_to_check_balance.iter())
@@ -245,4 +244,6 @@ async fn payout_stakers_and_assert_locked_balance<S: SignedConnectionApi + AsCon
                     "Expected payout to be positive in locked balance for account {}. Balance before: {}, balance after: {}",
                     account_id, balances_before[0].amount, balances_after[0].amount);
         });
+
+    Ok(())
 }
@@ -326,7 +326,7 @@ pub async fn large_finalization_stall() -> anyhow::Result<()> {
         for (node_name, connection) in connections.iter() {
             let finalized = connection.get_finalized_block_hash().await?;
 
-            let session = connection.get_session(Some(finalized)).await;
+            let session = connection.try_get_session(Some(finalized)).await?;
             let first_block_of_session = connection
                 .first_block_of_session(session)
                 .await?
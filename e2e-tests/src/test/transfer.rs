@@ -11,7 +11,7 @@ pub async fn token_transfer() -> anyhow::Result<()> {
     let config = setup_test();
     let (connection, to) = setup_for_transfer(config).await;
 
-    let balance_before = connection.get_free_balance(to.clone(), None).await;
+    let balance_before = connection.try_get_free_balance(to.clone(), None).await?;
     info!("[+] Account {} balance before tx: {}", to, balance_before);
 
     let transfer_value = 1000;
@@ -19,7 +19,7 @@ pub async fn token_transfer() -> anyhow::Result<()> {
         .transfer_keep_alive(to.clone(), transfer_value, TxStatus::Finalized)
         .await?;
 
-    let balance_after = connection.get_free_balance(to.clone(), None).await;
+    let balance_after = connection.try_get_free_balance(to.clone(), None).await?;
     info!("[+] Account {} balance after tx: {}", to, balance_after);
 
     assert_eq!(
@@ -19,17 +19,19 @@ use crate::{accounts::get_validators_raw_keys, config::setup_test, transfer::set
 /// Returns current treasury free funds and total issuance.
 ///
 /// Takes two storage reads.
-async fn balance_info<C: ConnectionApi + AsConnection>(connection: &C) -> (Balance, Balance) {
+async fn balance_info<C: ConnectionApi + AsConnection>(
+    connection: &C,
+) -> anyhow::Result<(Balance, Balance)> {
     let treasury_balance = connection
-        .get_free_balance(connection.treasury_account().await, None)
-        .await;
-    let issuance = connection.total_issuance(None).await;
+        .try_get_free_balance(connection.treasury_account().await, None)
+        .await?;
+    let issuance = connection.try_total_issuance(None).await?;
     info!(
         "[+] Treasury balance: {}. Total issuance: {}.",
         treasury_balance, issuance
     );
 
-    (treasury_balance, issuance)
+    Ok((treasury_balance, issuance))
 }
 
 #[tokio::test]
@@ -42,19 +44,19 @@ pub async fn channeling_fee_and_tip() -> anyhow::Result<()> {
     // so that when we check the total issuance we don't have to take into account
     // the tokens minted when an era ends.
     // Therefore, let's wait for when the next era begins, and keep our fingers crossed.
-    let active_era = connection.get_active_era(None).await;
+    let active_era = connection.try_get_active_era(None).await?;
     connection
         .wait_for_era(active_era + 1, BlockStatus::Best)
         .await;
 
-    let (treasury_balance_before, issuance_before) = balance_info(&connection).await;
+    let (treasury_balance_before, issuance_before) = balance_info(&connection).await?;
 
     let transfer = connection
         .transfer_keep_alive_with_tip(to, transfer_amount, tip, TxStatus::Finalized)
         .await?;
     let fee = connection.get_tx_fee(transfer).await?;
 
-    let (treasury_balance_after, issuance_after) = balance_info(&connection).await;
+    let (treasury_balance_after, issuance_after) = balance_info(&connection).await?;
 
     check_issuance(issuance_before, issuance_after);
     check_treasury_balance(treasury_balance_before, treasury_balance_after, fee);
@@ -95,14 +97,20 @@ pub async fn treasury_access() -> anyhow::Result<()> {
     let beneficiary = account_from_keypair(proposer.signer());
     let connection = SignedConnection::new(&config.node, proposer).await;
 
-    let proposals_counter_before = connection.proposals_count(None).await.unwrap_or_default();
+    let proposals_counter_before = connection
+        .try_proposals_count(None)
+        .await?
+        .unwrap_or_default();
     connection
         .propose_spend(10, beneficiary.clone(), TxStatus::InBlock)
         .await?;
     connection
         .propose_spend(100, beneficiary.clone(), TxStatus::InBlock)
         .await?;
-    let proposals_counter_after = connection.proposals_count(None).await.unwrap_or_default();
+    let proposals_counter_after = connection
+        .try_proposals_count(None)
+        .await?
+        .unwrap_or_default();
 
     assert_eq!(
         proposals_counter_before + 2,
@@ -120,7 +128,7 @@ pub async fn treasury_access() -> anyhow::Result<()> {
 
 async fn approve_treasury_proposal(connection: &RootConnection, id: u32) -> anyhow::Result<()> {
     connection.approve(id, TxStatus::Finalized).await?;
-    let approvals = connection.approvals(None).await;
+    let approvals = connection.try_approvals(None).await?;
     assert!(approvals.contains(&id));
 
     Ok(())
@@ -21,9 +21,13 @@ pub async fn change_validators() -> anyhow::Result<()> {
     let accounts = get_validators_keys(config);
     let connection = config.create_root_connection().await;
 
-    let reserved_before = connection.get_next_era_reserved_validators(None).await;
-    let non_reserved_before = connection.get_next_era_non_reserved_validators(None).await;
-    let committee_size_before = connection.get_next_era_committee_seats(None).await;
+    let reserved_before = connection
+        .try_get_next_era_reserved_validators(None)
+        .await?;
+    let non_reserved_before = connection
+        .try_get_next_era_non_reserved_validators(None)
+        .await?;
+    let committee_size_before = connection.try_get_next_era_committee_seats(None).await?;
 
     info!(
         "[+] state before tx: reserved: {:#?}, non_reserved: {:#?}, committee_size: {:#?}",
@@ -60,9 +64,13 @@ pub async fn change_validators() -> anyhow::Result<()> {
         }
     }, BlockStatus::Best).await;
 
-    let reserved_after = connection.get_next_era_reserved_validators(None).await;
-    let non_reserved_after = connection.get_next_era_non_reserved_validators(None).await;
-    let committee_size_after = connection.get_next_era_committee_seats(None).await;
+    let reserved_after = connection
+        .try_get_next_era_reserved_validators(None)
+        .await?;
+    let non_reserved_after = connection
+        .try_get_next_era_non_reserved_validators(None)
+        .await?;
+    let committee_size_after = connection.try_get_next_era_committee_seats(None).await?;
 
     info!(
         "[+] state before tx: reserved: {:#?}, non_reserved: {:#?}, committee_size: {:#?}",
@@ -146,9 +154,13 @@ pub async fn fail_changing_validators() -> anyhow::Result<()> {
         )
         .await;
 
-    let reserved_after = connection.get_next_era_reserved_validators(None).await;
-    let non_reserved_after = connection.get_next_era_non_reserved_validators(None).await;
-    let committee_size_after = connection.get_next_era_committee_seats(None).await;
+    let reserved_after = connection
+        .try_get_next_era_reserved_validators(None)
+        .await?;
+    let non_reserved_after = connection
+        .try_get_next_era_non_reserved_validators(None)
+        .await?;
+    let committee_size_after = connection.try_get_next_era_committee_seats(None).await?;
 
     assert_eq!(new_validators[..2], reserved_after);
     assert_eq!(new_validators[2..], non_reserved_after);
@@ -44,7 +44,7 @@ pub async fn validators_rotate() -> anyhow::Result<()> {
     root_connection
         .wait_for_n_eras(2, BlockStatus::Finalized)
         .await;
-    let current_session = root_connection.get_session(None).await;
+    let current_session = root_connection.try_get_session(None).await?;
     root_connection
         .wait_for_n_sessions(TEST_LENGTH, BlockStatus::Finalized)
         .await;
@@ -53,8 +53,8 @@ pub async fn validators_rotate() -> anyhow::Result<()> {
 
     for session in current_session..current_session + TEST_LENGTH {
         let elected = connection
-            .get_validators(connection.first_block_of_session(session).await?)
-            .await;
+            .try_get_validators(connection.first_block_of_session(session).await?)
+            .await?;
 
         let (_, non_reserved) = compute_session_committee(&root_connection, session).await?;
 
@@ -1,8 +1,64 @@
+use std::{
+    cell::Cell,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+use current_aleph_bft::Round;
+use log::error;
+use parking_lot::Mutex;
+
 use crate::{
+    abft::{common::DelaySchedule, LOG_TARGET},
     crypto::{AuthorityPen, AuthorityVerifier, Signature},
-    NodeCount, NodeIndex, SignatureSet,
+    party::slashing_protection::{Error as SlashingProtectionError, SlashingProtection},
+    signer::{Error as SignerError, SignedUnit, SigningContext},
+    NodeCount, NodeIndex, SessionId, SignatureSet,
 };
 
+thread_local! {
+    /// Whether the member running on this thread just got the data for its next unit.
+    static UNIT_DATA_PROVIDED: Cell<bool> = Cell::new(false);
+}
+
+/// Should be called by the data provider of the AlephBFT member right before it hands out the
+/// data for a new unit. The member puts the data into the unit and signs it without yielding in
+/// between, so the next message signed on the same thread is that unit. Alerts about forks are
+/// signed by other tasks of the member, so they are never taken for units.
+pub fn note_unit_data_provided() {
+    UNIT_DATA_PROVIDED.with(|provided| provided.set(true));
+}
+
+/// Reasons for the keychain not to sign a message.
+#[derive(Debug)]
+pub enum Error {
+    /// Signing could be an equivocation, according to the slashing protection record.
+    SlashingProtection(SlashingProtectionError),
+    Signer(SignerError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        use Error::*;
+        match self {
+            SlashingProtection(e) => write!(f, "slashing protection refused: {e}"),
+            Signer(e) => write!(f, "signer failed: {e}"),
+        }
+    }
+}
+
+impl From<SlashingProtectionError> for Error {
+    fn from(e: SlashingProtectionError) -> Self {
+        Error::SlashingProtection(e)
+    }
+}
+
+impl From<SignerError> for Error {
+    fn from(e: SignerError) -> Self {
+        Error::Signer(e)
+    }
+}
+
 /// Keychain combines an AuthorityPen and AuthorityVerifier into one object implementing the AlephBFT
 /// MultiKeychain trait.
 #[derive(Clone)]
@@ -10,20 +66,82 @@ pub struct Keychain {
     id: NodeIndex,
     authority_pen: AuthorityPen,
     authority_verifier: AuthorityVerifier,
+    session_id: SessionId,
+    slashing_protection: Arc<SlashingProtection>,
+    next_unit_round: Option<Arc<Mutex<Option<Round>>>>,
 }
 
 impl Keychain {
     /// Constructs a new keychain from a signing contraption and verifier, with the specified node
-    /// index.
+    /// index. Every signature in the session is first cleared with the slashing protection, and
+    /// the round of every unit is recorded in it before the unit is signed.
     pub fn new(
         id: NodeIndex,
         authority_verifier: AuthorityVerifier,
         authority_pen: AuthorityPen,
+        session_id: SessionId,
+        slashing_protection: Arc<SlashingProtection>,
     ) -> Self {
         Keychain {
             id,
             authority_pen,
             authority_verifier,
+            session_id,
+            slashing_protection,
+            next_unit_round: None,
+        }
+    }
+
+    /// A copy of the keychain for the AlephBFT member, the only user of the keychain that signs
+    /// units. It learns the rounds of the units from the unit creation delay, see
+    /// [`Keychain::unit_creation_delay`], recognizes the units by the data provided for them, see
+    /// [`note_unit_data_provided`], and signs every unit in the context of its round.
+    pub fn for_member(&self) -> Self {
+        Keychain {
+            next_unit_round: Some(Arc::new(Mutex::new(None))),
+            ..self.clone()
+        }
+    }
+
+    /// Wraps the unit creation delay schedule of the member. The creator asks for the delay of
+    /// every round right before creating its unit of that round, so the next unit signed
+    /// afterwards is the unit of that round.
+    pub fn unit_creation_delay(&self, schedule: DelaySchedule) -> DelaySchedule {
+        let next_unit_round = self.next_unit_round.clone();
+        Arc::new(move |round| {
+            if let Some(next_unit_round) = &next_unit_round {
+                *next_unit_round.lock() = Round::try_from(round).ok();
+            }
+            schedule(round)
+        })
+    }
+
+    /// Forgets the round of the next unit, e.g. after the whole schedule was evaluated when
+    /// validating the config.
+    pub fn forget_unit_round(&self) {
+        if let Some(next_unit_round) = &self.next_unit_round {
+            *next_unit_round.lock() = None;
+        }
+    }
+
+    /// The context of signing the next message, which is the next unit if the data for it was
+    /// just provided on this thread.
+    fn context(&self) -> SigningContext {
+        let round = match &self.next_unit_round {
+            Some(next_unit_round)
+                if UNIT_DATA_PROVIDED.with(|provided| provided.replace(false)) =>
+            {
+                next_unit_round.lock().take()
+            }
+            _ => None,
+        };
+        match round {
+            Some(round) => SigningContext::Unit {
+                session: self.session_id,
+                creator: self.id,
+                round,
+            },
+            None => SigningContext::Other,
         }
     }
 
@@ -35,8 +153,20 @@ impl Keychain {
         self.authority_verifier.node_count()
     }
 
-    fn sign(&self, msg: &[u8]) -> Signature {
-        self.authority_pen.sign(msg)
+    fn sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        let context = self.context();
+        match &context {
+            SigningContext::Unit { round, .. } => self.slashing_protection.before_signing_unit(
+                self.session_id,
+                SignedUnit {
+                    creator: self.id,
+                    round: *round,
+                    hash: msg.to_vec(),
+                },
+            )?,
+            SigningContext::Other => self.slashing_protection.before_signing(self.session_id)?,
+        }
+        Ok(self.authority_pen.sign_in_context(&context, msg)?)
     }
 
     fn verify<I: Into<NodeIndex>>(&self, msg: &[u8], sgn: &Signature, index: I) -> bool {
@@ -63,8 +193,13 @@ impl legacy_aleph_bft::Keychain for Keychain {
         Keychain::node_count(self).into()
     }
 
+    // AlephBFT expects signing to always succeed. When we refuse to sign, whatever AlephBFT
+    // attaches the signature to gets rejected by other nodes instead.
     fn sign(&self, msg: &[u8]) -> Signature {
-        Keychain::sign(self, msg)
+        Keychain::sign(self, msg).unwrap_or_else(|e| {
+            error!(target: LOG_TARGET, "Failed to sign in session {:?}: {}.", self.session_id, e);
+            Signature::invalid()
+        })
     }
 
     fn verify(&self, msg: &[u8], sgn: &Signature, index: legacy_aleph_bft::NodeIndex) -> bool {
@@ -96,3 +231,91 @@ impl legacy_aleph_bft::MultiKeychain for Keychain {
         Keychain::is_complete(self, msg, partial)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use sc_keystore::LocalKeystore;
+    use sp_keystore::Keystore;
+
+    use super::{note_unit_data_provided, Keychain};
+    use crate::{
+        aleph_primitives::{AuthorityId, KEY_TYPE},
+        crypto::{AuthorityPen, AuthorityVerifier},
+        party::slashing_protection::SlashingProtection,
+        signer::SignedUnit,
+        NodeIndex, SessionId,
+    };
+
+    const SESSION: SessionId = SessionId(3);
+
+    fn member_keychain(slashing_protection: Arc<SlashingProtection>) -> Keychain {
+        let keystore = Arc::new(LocalKeystore::in_memory());
+        let authority_id = AuthorityId::from(
+            keystore
+                .ed25519_generate_new(KEY_TYPE, Some("//Alice"))
+                .expect("should generate a key"),
+        );
+        let authority_pen =
+            AuthorityPen::new(authority_id.clone(), keystore).expect("should create a pen");
+        Keychain::new(
+            NodeIndex(0),
+            AuthorityVerifier::new(vec![authority_id]),
+            authority_pen,
+            SESSION,
+            slashing_protection,
+        )
+        .for_member()
+    }
+
+    fn unit(round: u16, hash: &[u8]) -> SignedUnit {
+        SignedUnit {
+            creator: NodeIndex(0),
+            round,
+            hash: hash.to_vec(),
+        }
+    }
+
+    #[test]
+    fn signs_units_in_context_of_their_rounds() {
+        let slashing_protection = Arc::new(SlashingProtection::in_memory());
+        let keychain = member_keychain(slashing_protection.clone());
+        let delay = keychain.unit_creation_delay(Arc::new(|_| Duration::ZERO));
+
+        for (round, hash) in [(0, [0; 32]), (1, [1; 32])] {
+            delay(round as usize);
+            note_unit_data_provided();
+            assert!(keychain.sign(&hash).is_ok());
+        }
+
+        assert!(slashing_protection
+            .before_signing_unit(SESSION, unit(1, &[1; 32]))
+            .is_ok());
+        assert!(slashing_protection
+            .before_signing_unit(SESSION, unit(1, &[7; 32]))
+            .is_err());
+    }
+
+    #[test]
+    fn alert_signed_between_delay_and_unit_does_not_take_its_round() {
+        let slashing_protection = Arc::new(SlashingProtection::in_memory());
+        let keychain = member_keychain(slashing_protection.clone());
+        let delay = keychain.unit_creation_delay(Arc::new(|_| Duration::ZERO));
+        let alert = [1; 32];
+        let unit_hash = [2; 32];
+
+        delay(7);
+        assert!(keychain.sign(&alert).is_ok());
+        note_unit_data_provided();
+        assert!(keychain.sign(&unit_hash).is_ok());
+
+        // The unit took round 7, so only the very same unit can be signed in it again.
+        assert!(slashing_protection
+            .before_signing_unit(SESSION, unit(7, &unit_hash))
+            .is_ok());
+        assert!(slashing_protection
+            .before_signing_unit(SESSION, unit(7, &alert))
+            .is_err());
+    }
+}
@@ -11,8 +11,10 @@ use crate::{
         LOG_TARGET,
     },
     data_io::AlephData,
+    metrics::AbftScoreMetrics,
     party::manager::Runnable,
-    Hasher, UnverifiedHeader,
+    status::{AbftScoreStatus, StatusReporter},
+    Hasher, SessionId, UnverifiedHeader,
 };
 
 struct FinalizationWrapper<UH, FH>
@@ -64,6 +66,9 @@ where
 {
     batches_from_abft: mpsc::UnboundedReceiver<Batch<UH>>,
     scorer: Scorer,
+    session_id: SessionId,
+    metrics: AbftScoreMetrics,
+    status_reporter: StatusReporter,
 }
 
 impl<UH> Service<UH>
@@ -74,7 +79,10 @@ where
     /// ABFT. It will wrap the provided finalization handler and call it in the background.
     pub fn new<FH>(
         n_members: usize,
+        session_id: SessionId,
         finalization_handler: FH,
+        metrics: AbftScoreMetrics,
+        status_reporter: StatusReporter,
     ) -> (
         Self,
         impl current_aleph_bft::UnitFinalizationHandler<Data = AlephData<UH>, Hasher = Hasher>,
@@ -87,6 +95,9 @@ where
             Service {
                 batches_from_abft,
                 scorer: Scorer::new(NodeCount(n_members)),
+                session_id,
+                metrics,
+                status_reporter,
             },
             FinalizationWrapper::new(finalization_handler, batches_for_us),
         )
@@ -110,6 +121,11 @@ where
                         },
                     };
                     debug!(target: LOG_TARGET, "Received ABFT score: {:?}.", score);
+                    self.metrics.report_score(&score);
+                    self.status_reporter.report_abft_score(AbftScoreStatus {
+                        session: self.session_id,
+                        rounds_behind: score,
+                    });
                     // TODO(A0-4339): sometimes submit these scores to the chain.
                 }
                 _ = &mut exit => {
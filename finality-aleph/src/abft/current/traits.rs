@@ -1,5 +1,6 @@
 //! Implementations and definitions of traits used in current abft
 use crate::{
+    abft::crypto::note_unit_data_provided,
     block::{Header, HeaderVerifier, UnverifiedHeader},
     data_io::{AlephData, ChainInfoProvider, DataProvider, OrderedDataInterpreter},
 };
@@ -9,7 +10,9 @@ impl<UH: UnverifiedHeader> current_aleph_bft::DataProvider for DataProvider<UH>
     type Output = AlephData<UH>;
 
     async fn get_data(&mut self) -> Option<AlephData<UH>> {
-        DataProvider::get_data(self).await
+        let data = DataProvider::get_data(self).await;
+        note_unit_data_provided();
+        data
     }
 }
 
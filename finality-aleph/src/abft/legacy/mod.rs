@@ -80,11 +80,16 @@ pub fn create_aleph_config(
     node_id: NodeIndex,
     session_id: SessionId,
     unit_creation_delay: UnitCreationDelay,
+    keychain: &Keychain,
 ) -> Config {
     let mut delay_config = default_delay_config();
-    delay_config.unit_creation_delay = unit_creation_delay_fn(unit_creation_delay);
-    match create_config(n_members.into(), node_id.into(), session_id.0 as u64, MAX_ROUNDS, delay_config, Duration::from_millis(SESSION_LEN_LOWER_BOUND_MS as u64)) {
+    delay_config.unit_creation_delay =
+        keychain.unit_creation_delay(unit_creation_delay_fn(unit_creation_delay));
+    let config = match create_config(n_members.into(), node_id.into(), session_id.0 as u64, MAX_ROUNDS, delay_config, Duration::from_millis(SESSION_LEN_LOWER_BOUND_MS as u64)) {
         Ok(config) => config,
         Err(_) => panic!("Incorrect setting of delays. Make sure the total AlephBFT session time is at least {} ms.", SESSION_LEN_LOWER_BOUND_MS),
-    }
+    };
+    // Validating the config asks for the delays of all the rounds, no unit is created yet.
+    keychain.forget_unit_round();
+    config
 }
@@ -1,5 +1,6 @@
 //! Implementations and definitions of traits used in legacy abft
 use crate::{
+    abft::crypto::note_unit_data_provided,
     block::{Header, HeaderVerifier, UnverifiedHeader},
     data_io::{AlephData, ChainInfoProvider, DataProvider, OrderedDataInterpreter},
 };
@@ -7,7 +8,9 @@ use crate::{
 #[async_trait::async_trait]
 impl<UH: UnverifiedHeader> legacy_aleph_bft::DataProvider<AlephData<UH>> for DataProvider<UH> {
     async fn get_data(&mut self) -> Option<AlephData<UH>> {
-        DataProvider::get_data(self).await
+        let data = DataProvider::get_data(self).await;
+        note_unit_data_provided();
+        data
     }
 }
 
@@ -13,7 +13,7 @@ use crate::{
 mod backend;
 mod status_notifier;
 
-pub use backend::Backend;
+pub use backend::{Backend, EquivocationProof};
 
 impl BlockId {
     pub fn new_random(number: BlockNumber) -> Self {
@@ -77,6 +77,17 @@ impl MockHeader {
         self.id.random_child()
     }
 
+    /// A child with the given hash, for when the blocks have to be reproducible.
+    pub fn child_with_hash(&self, hash: BlockHash) -> Self {
+        MockHeader {
+            id: BlockId::new(hash, self.id.number + 1),
+            parent: Some(self.id.clone()),
+            valid: true,
+            equivocated: false,
+            own: false,
+        }
+    }
+
     pub fn random_branch(&self) -> impl Iterator<Item = Self> {
         self.id.random_branch()
     }
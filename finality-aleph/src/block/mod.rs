@@ -1,6 +1,7 @@
 use std::fmt::{Debug, Display, Error as FmtError, Formatter};
 
 use parity_scale_codec::{Codec, Decode, Encode};
+use serde::{Deserialize, Serialize};
 
 use crate::{BlockHash, BlockNumber};
 
@@ -9,7 +10,7 @@ pub mod mock;
 pub mod substrate;
 
 /// The identifier of a block, the least amount of knowledge we can have about a block.
-#[derive(PartialEq, Eq, Clone, Debug, Encode, Decode, Hash)]
+#[derive(PartialEq, Eq, Clone, Debug, Encode, Decode, Hash, Serialize, Deserialize)]
 pub struct BlockId {
     hash: BlockHash,
     number: BlockNumber,
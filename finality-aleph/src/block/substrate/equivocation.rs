@@ -0,0 +1,100 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use futures::{channel::mpsc, StreamExt};
+use log::{info, warn};
+use pallet_aleph_runtime_api::AlephSessionApi;
+use sc_client_api::{Backend, HeaderBackend};
+use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+
+use crate::{
+    aleph_primitives::Block,
+    block::substrate::{verification::EquivocationProof, LOG_TARGET},
+    ClientForAleph,
+};
+
+/// The first version of the runtime API that accepts equivocation reports.
+const EQUIVOCATION_REPORT_API_VERSION: u32 = 2;
+
+/// Submits equivocation proofs found by the sync service to the chain, so that the offenders
+/// get punished.
+pub struct EquivocationReporter<C, BE>
+where
+    C: ClientForAleph<Block, BE> + Send + Sync + 'static,
+    C::Api: AlephSessionApi<Block>,
+    BE: Backend<Block> + 'static,
+{
+    client: Arc<C>,
+    transaction_pool: OffchainTransactionPoolFactory<Block>,
+    proofs: mpsc::UnboundedReceiver<EquivocationProof>,
+    _phantom: PhantomData<BE>,
+}
+
+impl<C, BE> EquivocationReporter<C, BE>
+where
+    C: ClientForAleph<Block, BE> + Send + Sync + 'static,
+    C::Api: AlephSessionApi<Block>,
+    BE: Backend<Block> + 'static,
+{
+    /// Create a new reporter, returns also the sender for proofs that should be reported.
+    pub fn new(
+        client: Arc<C>,
+        transaction_pool: OffchainTransactionPoolFactory<Block>,
+    ) -> (Self, mpsc::UnboundedSender<EquivocationProof>) {
+        let (proofs_for_reporter, proofs) = mpsc::unbounded();
+        (
+            EquivocationReporter {
+                client,
+                transaction_pool,
+                proofs,
+                _phantom: PhantomData,
+            },
+            proofs_for_reporter,
+        )
+    }
+
+    fn report(&self, proof: EquivocationProof) {
+        let best_hash = self.client.info().best_hash;
+        let mut runtime_api = self.client.runtime_api();
+        match runtime_api.api_version::<dyn AlephSessionApi<Block>>(best_hash) {
+            Ok(Some(version)) if version >= EQUIVOCATION_REPORT_API_VERSION => {}
+            Ok(_) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Runtime does not accept equivocation reports yet, dropping one."
+                );
+                return;
+            }
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to check the runtime API version: {}.", e
+                );
+                return;
+            }
+        }
+        runtime_api.register_extension(self.transaction_pool.offchain_transaction_pool(best_hash));
+        match runtime_api.submit_production_equivocation_report(best_hash, proof.into()) {
+            Ok(Some(())) => info!(target: LOG_TARGET, "Submitted an equivocation report."),
+            Ok(None) => warn!(
+                target: LOG_TARGET,
+                "Equivocation report was rejected by the transaction pool."
+            ),
+            Err(e) => warn!(
+                target: LOG_TARGET,
+                "Failed to submit an equivocation report: {}.", e
+            ),
+        }
+    }
+
+    /// Run the reporter until the sync service stops sending proofs.
+    pub async fn run(mut self) {
+        while let Some(proof) = self.proofs.next().await {
+            self.report(proof);
+        }
+        warn!(
+            target: LOG_TARGET,
+            "Equivocation proofs stream ended, no more equivocations will be reported."
+        );
+    }
+}
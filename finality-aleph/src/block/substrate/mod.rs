@@ -1,26 +1,38 @@
 use primitives::BlockNumber;
-use sc_consensus::import_queue::{ImportQueueService, IncomingBlock};
+use sc_consensus::{
+    import_queue::{ImportQueueService, IncomingBlock},
+    ImportedState,
+};
 use sp_consensus::BlockOrigin;
-use sp_runtime::traits::{CheckedSub, Header as _, One};
+use sp_runtime::{
+    traits::{CheckedSub, Header as _, One},
+    Justification as SubstrateJustification, Justifications,
+};
+use sp_state_machine::KeyValueStates;
 
 use crate::{
     aleph_primitives::{Block, Header},
     block::{Block as BlockT, BlockId, BlockImport, Header as HeaderT, UnverifiedHeader},
+    justification::AlephJustification,
     metrics::TimingBlockMetrics,
 };
 
 mod chain_status;
+mod equivocation;
 mod finalizer;
 mod justification;
 mod status_notifier;
 mod verification;
 
 pub use chain_status::SubstrateChainStatus;
+pub use equivocation::EquivocationReporter;
 pub use justification::{
     InnerJustification, Justification, JustificationTranslator, TranslateError,
 };
 pub use status_notifier::SubstrateChainStatusNotifier;
-pub use verification::{SubstrateFinalizationInfo, VerifierCache};
+pub use verification::{
+    SessionVerificationError, SessionVerifier, SubstrateFinalizationInfo, VerifierCache,
+};
 
 use crate::{
     block::{BestBlockSelector, BlockchainEvents},
@@ -78,6 +90,34 @@ impl BlockImporter {
     pub fn attach_metrics(&mut self, metrics: TimingBlockMetrics) {
         self.metrics = metrics;
     }
+
+    /// Import the header of a block together with its whole state, without executing it.
+    /// The block becomes finalized with the provided justification, even though none of its
+    /// ancestors are present.
+    pub fn import_state(
+        &mut self,
+        header: Header,
+        justification: AlephJustification,
+        state: KeyValueStates,
+    ) {
+        let hash = header.hash();
+        let incoming_block = IncomingBlock::<Block> {
+            hash,
+            header: Some(header),
+            body: None,
+            indexed_body: None,
+            justifications: Some(Justifications::from(SubstrateJustification::from(
+                justification,
+            ))),
+            origin: None,
+            allow_missing_state: true,
+            skip_execution: true,
+            import_existing: true,
+            state: Some(ImportedState { block: hash, state }),
+        };
+        self.importer
+            .import_blocks(BlockOrigin::NetworkInitialSync, vec![incoming_block]);
+    }
 }
 
 impl BlockImport<Block> for BlockImporter {
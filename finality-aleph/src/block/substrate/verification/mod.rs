@@ -9,12 +9,11 @@ use sc_consensus_aura::standalone::{PreDigestLookupError, SealVerificationError}
 use sp_consensus_slots::Slot;
 
 use crate::{
-    aleph_primitives::{AccountId, AuraId, Block, BlockNumber, Header},
+    aleph_primitives::{
+        AccountId, AuraId, Block, BlockNumber, Header, ProductionEquivocationProof,
+    },
     block::{
-        substrate::{
-            verification::{cache::CacheError, verifier::SessionVerificationError},
-            FinalizationInfo,
-        },
+        substrate::{verification::cache::CacheError, FinalizationInfo},
         EquivocationProof as EquivocationProofT, Header as HeaderT,
     },
 };
@@ -23,6 +22,7 @@ mod cache;
 mod verifier;
 
 pub use cache::VerifierCache;
+pub use verifier::{SessionVerificationError, SessionVerifier};
 
 /// Substrate specific implementation of `FinalizationInfo`
 pub struct SubstrateFinalizationInfo<BE: HeaderBackend<Block>>(Arc<BE>);
@@ -98,6 +98,16 @@ impl EquivocationProofT for EquivocationProof {
     }
 }
 
+impl From<EquivocationProof> for ProductionEquivocationProof {
+    fn from(proof: EquivocationProof) -> Self {
+        ProductionEquivocationProof {
+            offender: proof.author,
+            first_header: proof.header_a,
+            second_header: proof.header_b,
+        }
+    }
+}
+
 impl Display for EquivocationProof {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match &self.account_id {
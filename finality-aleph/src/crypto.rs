@@ -1,12 +1,8 @@
-use std::{
-    fmt::{Display, Error as FmtError, Formatter},
-    sync::Arc,
-};
+use std::sync::Arc;
 
 use parity_scale_codec::{Decode, Encode};
-use sc_keystore::{Keystore, LocalKeystore};
-use sp_core::crypto::KeyTypeId;
-use sp_keystore::Error as KeystoreError;
+use sc_keystore::LocalKeystore;
+use sp_core::{crypto::KeyTypeId, ed25519};
 use sp_runtime::RuntimeAppPublic;
 
 use crate::{
@@ -15,28 +11,9 @@ use crate::{
         crypto::AuthorityVerifier as PrimitivesAuthorityVerifier, AuthorityId, AuthoritySignature,
         KEY_TYPE,
     },
+    signer::{Error, LocalSigner, Signer, SigningContext},
 };
 
-#[derive(Debug)]
-pub enum Error {
-    KeyMissing(AuthorityId),
-    Keystore(KeystoreError),
-}
-
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
-        use Error::*;
-        match self {
-            KeyMissing(auth_id) => {
-                write!(f, "no key for authority {auth_id:?}")
-            }
-            Keystore(err) => {
-                write!(f, "keystore error: {err}")
-            }
-        }
-    }
-}
-
 #[derive(PartialEq, Eq, Clone, Debug, Hash, Decode, Encode)]
 pub struct Signature(pub AuthoritySignature);
 
@@ -46,18 +23,26 @@ impl From<AuthoritySignature> for Signature {
     }
 }
 
+impl Signature {
+    /// A signature that does not verify for any message. Used where a signature has to be
+    /// provided, but the signer refused to make one, so that whatever it is attached to gets
+    /// rejected by other nodes.
+    pub fn invalid() -> Signature {
+        Signature(ed25519::Signature::from_raw([0; 64]).into())
+    }
+}
+
 /// Verify the signature given an authority id.
 pub fn verify(authority: &AuthorityId, message: &[u8], signature: &Signature) -> bool {
     authority.verify(&message, &signature.0)
 }
 
-/// Ties an authority identification and a cryptography keystore together for use in
-/// signing that requires an authority.
+/// Ties an authority identification and a signer together for use in signing that requires an
+/// authority.
 #[derive(Clone)]
 pub struct AuthorityPen {
-    key_type_id: KeyTypeId,
     authority_id: AuthorityId,
-    keystore: Arc<LocalKeystore>,
+    signer: Arc<dyn Signer>,
 }
 
 impl AuthorityPen {
@@ -70,16 +55,20 @@ impl AuthorityPen {
         keystore: Arc<LocalKeystore>,
         key_type: KeyTypeId,
     ) -> Result<Self, Error> {
+        Self::with_signer(
+            authority_id,
+            Arc::new(LocalSigner::with_key_type(keystore, key_type)),
+        )
+    }
+
+    /// Constructs a new authority pen signing with the given signer.
+    /// Will attempt to sign a test message to verify that signing works.
+    pub fn with_signer(authority_id: AuthorityId, signer: Arc<dyn Signer>) -> Result<Self, Error> {
         // Check whether this signing setup works
-        let _: AuthoritySignature = keystore
-            .ed25519_sign(key_type, &authority_id.clone().into(), b"test")
-            .map_err(Error::Keystore)?
-            .ok_or_else(|| Error::KeyMissing(authority_id.clone()))?
-            .into();
+        let _: AuthoritySignature = signer.sign(&authority_id, &SigningContext::Other, b"test")?;
         Ok(AuthorityPen {
-            key_type_id: key_type,
             authority_id,
-            keystore,
+            signer,
         })
     }
 
@@ -91,15 +80,21 @@ impl AuthorityPen {
         Self::new_with_key_type(authority_id, keystore, KEY_TYPE)
     }
 
-    /// Cryptographically signs the message.
-    pub fn sign(&self, msg: &[u8]) -> Signature {
-        Signature(
-            self.keystore
-                .ed25519_sign(self.key_type_id, &self.authority_id.clone().into(), msg)
-                .expect("the keystore works")
-                .expect("we have the required key")
-                .into(),
-        )
+    /// Cryptographically signs the message, which cannot lead to equivocations.
+    pub fn sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        self.sign_in_context(&SigningContext::Other, msg)
+    }
+
+    /// Cryptographically signs the message in the given context. Fails if the signer fails, in
+    /// particular if a remote signer refuses to sign because it would equivocate.
+    pub fn sign_in_context(
+        &self,
+        context: &SigningContext,
+        msg: &[u8],
+    ) -> Result<Signature, Error> {
+        self.signer
+            .sign(&self.authority_id, context, msg)
+            .map(Signature)
     }
 
     /// Return the associated AuthorityId.
@@ -191,7 +186,7 @@ mod tests {
         let (pens, verifier) = prepare_test();
         let msg = b"test";
         for (i, pen) in pens.into_iter().enumerate() {
-            let signature = pen.sign(msg);
+            let signature = pen.sign(msg).expect("signing works");
             assert!(verifier.verify(msg, &signature, NodeIndex(i)));
         }
     }
@@ -201,7 +196,7 @@ mod tests {
         let (pens, verifier) = prepare_test();
         let msg = b"test";
         for pen in &pens[1..] {
-            let signature = pen.sign(msg);
+            let signature = pen.sign(msg).expect("signing works");
             assert!(!verifier.verify(msg, &signature, NodeIndex(0)));
         }
     }
@@ -211,7 +206,7 @@ mod tests {
         let (pens, verifier) = prepare_test();
         let msg = b"test";
         for pen in &pens {
-            let signature = pen.sign(msg);
+            let signature = pen.sign(msg).expect("signing works");
             assert!(!verifier.verify(msg, &signature, NodeIndex(pens.len())));
         }
     }
@@ -222,7 +217,7 @@ mod tests {
         let msg = b"test";
         let not_msg = b"not test";
         for (i, pen) in pens.into_iter().enumerate() {
-            let signature = pen.sign(msg);
+            let signature = pen.sign(msg).expect("signing works");
             assert!(!verifier.verify(not_msg, &signature, NodeIndex(i)));
         }
     }
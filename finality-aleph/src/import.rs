@@ -1,10 +1,12 @@
 use std::{
     error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
+    sync::Arc,
 };
 
 use futures::channel::mpsc::{self, TrySendError, UnboundedReceiver, UnboundedSender};
 use log::{debug, warn};
+use parking_lot::Mutex;
 use sc_consensus::{
     BlockCheckParams, BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult,
     JustificationImport,
@@ -19,20 +21,48 @@ use crate::{
     BlockId,
 };
 
+/// The block whose justification warp sync has verified, and which can thus be imported
+/// together with its state and finalized immediately. Blocks imported with state are treated
+/// like any other block otherwise.
+#[derive(Clone, Default)]
+pub struct StateImportTarget(Arc<Mutex<Option<BlockHash>>>);
+
+impl StateImportTarget {
+    /// Allow importing the state of the given block, replacing any previous target.
+    pub fn set(&self, hash: BlockHash) {
+        *self.0.lock() = Some(hash);
+    }
+
+    fn is_target(&self, block: &BlockImportParams<Block>) -> bool {
+        block.with_state() && *self.0.lock() == Some(block.post_hash())
+    }
+
+    fn clear(&self) {
+        *self.0.lock() = None;
+    }
+}
+
 /// Constructs block import specific for aleph consensus.
 pub fn get_aleph_block_import<I, SC>(
     inner: I,
     justification_tx: UnboundedSender<Justification>,
     translator: JustificationTranslator,
     select_chain: SC,
+    state_import_target: StateImportTarget,
 ) -> impl BlockImport<Block, Error = I::Error> + JustificationImport<Block, Error = ConsensusError> + Clone
 where
     I: BlockImport<Block> + Send + Sync + Clone,
     SC: SelectChain<Block> + Send + Sync,
 {
-    let favourite_marker_import = FavouriteMarkerBlockImport::new(inner, select_chain);
-
-    AlephBlockImport::new(favourite_marker_import, justification_tx, translator)
+    let favourite_marker_import =
+        FavouriteMarkerBlockImport::new(inner, select_chain, state_import_target.clone());
+
+    AlephBlockImport::new(
+        favourite_marker_import,
+        justification_tx,
+        translator,
+        state_import_target,
+    )
 }
 
 /// A wrapper around a block import that also checks if the newly imported block is potentially
@@ -45,6 +75,7 @@ where
 {
     inner: I,
     select_chain: SC,
+    state_import_target: StateImportTarget,
 }
 
 impl<I, SC> FavouriteMarkerBlockImport<I, SC>
@@ -52,10 +83,11 @@ where
     I: BlockImport<Block> + Send + Sync,
     SC: SelectChain<Block> + Send + Sync,
 {
-    pub fn new(inner: I, select_chain: SC) -> Self {
+    pub fn new(inner: I, select_chain: SC, state_import_target: StateImportTarget) -> Self {
         Self {
             inner,
             select_chain,
+            state_import_target,
         }
     }
 }
@@ -79,6 +111,11 @@ where
         &mut self,
         mut block: BlockImportParams<Block>,
     ) -> Result<ImportResult, Self::Error> {
+        // The warp sync target has no ancestors, it becomes the best block regardless.
+        if self.state_import_target.is_target(&block) {
+            return self.inner.import_block(block).await;
+        }
+
         if let Ok(best) = self.select_chain.best_chain().await {
             block.fork_choice = Some(ForkChoiceStrategy::Custom(
                 best.hash() == *block.header.parent_hash(),
@@ -99,6 +136,7 @@ where
     inner: I,
     justification_tx: UnboundedSender<Justification>,
     translator: JustificationTranslator,
+    state_import_target: StateImportTarget,
 }
 
 #[derive(Debug)]
@@ -123,11 +161,13 @@ where
         inner: I,
         justification_tx: UnboundedSender<Justification>,
         translator: JustificationTranslator,
+        state_import_target: StateImportTarget,
     ) -> AlephBlockImport<I> {
         AlephBlockImport {
             inner,
             justification_tx,
             translator,
+            state_import_target,
         }
     }
 
@@ -176,6 +216,16 @@ where
         let number = *block.header.number();
         let post_hash = block.post_hash();
 
+        // Warp sync has already verified the justification of its target, so we finalize it
+        // immediately, storing the justification.
+        if self.state_import_target.is_target(&block) {
+            debug!(target: "aleph-justification", "Importing state of block {:?} {:?}", number, post_hash);
+            block.finalized = true;
+            let result = self.inner.import_block(block).await;
+            self.state_import_target.clear();
+            return result;
+        }
+
         let justifications = block.justifications.take();
 
         debug!(target: "aleph-justification", "Importing block {:?} {:?} {:?}", number, block.header.hash(), block.post_hash());
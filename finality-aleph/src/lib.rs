@@ -32,7 +32,7 @@ use crate::{
     block::UnverifiedHeader,
     compatibility::{Version, Versioned},
     network::data::split::Split,
-    session::{SessionBoundaries, SessionBoundaryInfo, SessionId},
+    session::{SessionBoundaries, SessionBoundaryInfo},
     VersionedTryFromError::{ExpectedNewGotOld, ExpectedOldGotNew},
 };
 
@@ -53,25 +53,36 @@ mod party;
 mod runtime_api;
 mod session;
 mod session_map;
+mod signer;
+mod status;
 mod sync;
 mod sync_oracle;
 #[cfg(test)]
 pub mod testing;
+mod warp_sync;
 
 pub use crate::{
     block::{
         substrate::{BlockImporter, Justification, JustificationTranslator, SubstrateChainStatus},
         BlockId,
     },
-    import::{get_aleph_block_import, AlephBlockImport, RedirectingBlockImport},
-    justification::AlephJustification,
+    import::{get_aleph_block_import, AlephBlockImport, RedirectingBlockImport, StateImportTarget},
+    justification::{backwards_compatible_decode, versioned_encode, AlephJustification},
     network::{
         address_cache::{ValidatorAddressCache, ValidatorAddressingInfo},
         build_network, BuildNetworkOutput, ProtocolNetwork, SubstrateNetworkConfig,
         SubstratePeerId,
     },
     nodes::run_validator_node,
-    session::SessionPeriod,
+    session::{SessionId, SessionPeriod},
+    signer::{
+        DoubleSignGuard, Error as SignerError, LocalSigner, RemoteSigner, RemoteSignerServer,
+        Signer, SigningContext,
+    },
+    status::{
+        status_channel, AbftScoreStatus, AbftStatus, CommitteeMemberStatus, CommitteeStatus,
+        JustificationInfo, NodeStatus, StatusReporter, SyncStatus,
+    },
     sync::FavouriteSelectChainProvider,
     sync_oracle::SyncOracle,
 };
@@ -253,19 +264,27 @@ type Hasher = abft::HashWrapper<BlakeTwo256>;
 pub struct RateLimiterConfig {
     /// Maximum bit-rate in bits per second of the alephbft validator network.
     pub alephbft_network_bit_rate: u64,
+    /// Maximum upload bit-rate in bits per second of the alephbft validator network, unlimited if `None`.
+    pub alephbft_network_upload_bit_rate: Option<u64>,
     /// Maximum bit-rate in bits per second of the substrate network (shared by sync, gossip, etc.).
     pub substrate_network_bit_rate: u64,
+    /// Maximum upload bit-rate in bits per second of the substrate network, unlimited if `None`.
+    pub substrate_network_upload_bit_rate: Option<u64>,
 }
 
 pub struct AlephConfig<C, T> {
     pub authentication_network: ProtocolNetwork,
     pub block_sync_network: ProtocolNetwork,
+    pub warp_sync_network: ProtocolNetwork,
     pub client: Arc<C>,
     pub chain_status: SubstrateChainStatus,
     pub import_queue_handle: BlockImporter,
     pub select_chain_provider: FavouriteSelectChainProvider<AlephBlock>,
     pub spawn_handle: SpawnHandle,
+    /// Only used for the keys of the validator network, which are generated on every start.
     pub keystore: Arc<LocalKeystore>,
+    /// Signs with the Aleph session keys, which do not have to be kept in the keystore.
+    pub signer: Arc<dyn Signer>,
     pub justification_channel_provider: ChannelProvider<Justification>,
     pub block_rx: mpsc::UnboundedReceiver<AlephBlock>,
     pub registry: Option<Registry>,
@@ -273,10 +292,19 @@ pub struct AlephConfig<C, T> {
     pub millisecs_per_block: MillisecsPerBlock,
     pub unit_creation_delay: UnitCreationDelay,
     pub backup_saving_path: Option<PathBuf>,
+    /// Number of sessions preceding the current one for which backups are kept.
+    pub backup_retained_sessions: u32,
+    /// Where the record of sessions in which we signed AlephBFT units is kept. Unlike backups,
+    /// it cannot be turned off, since it prevents equivocating after a restart without backup.
+    pub slashing_protection_path: PathBuf,
     pub external_addresses: Vec<String>,
     pub validator_port: u16,
     pub rate_limiter_config: RateLimiterConfig,
     pub sync_oracle: SyncOracle,
     pub validator_address_cache: Option<ValidatorAddressCache>,
+    pub status_reporter: StatusReporter,
     pub transaction_pool: Arc<T>,
+    pub warp_sync: bool,
+    /// Shared with the block import, so that it finalizes the state imported by warp sync.
+    pub state_import_target: StateImportTarget,
 }
@@ -0,0 +1,41 @@
+use substrate_prometheus_endpoint::{register, GaugeVec, Opts, PrometheusError, Registry, U64};
+
+use crate::aleph_primitives::RawScore;
+
+#[derive(Clone)]
+pub enum AbftScoreMetrics {
+    Prometheus { rounds_behind: GaugeVec<U64> },
+    Noop,
+}
+
+impl AbftScoreMetrics {
+    pub fn new(registry: Option<&Registry>) -> Result<Self, PrometheusError> {
+        let registry = match registry {
+            Some(registry) => registry,
+            None => return Ok(Self::Noop),
+        };
+
+        Ok(Self::Prometheus {
+            rounds_behind: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "aleph_abft_rounds_behind",
+                        "Number of rounds the newest ordered unit of a committee member is behind the newest ordered unit",
+                    ),
+                    &["member"],
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    pub fn report_score(&self, score: &RawScore) {
+        if let Self::Prometheus { rounds_behind } = self {
+            for (member, rounds) in score.iter().enumerate() {
+                rounds_behind
+                    .with_label_values(&[&member.to_string()])
+                    .set(*rounds as u64);
+            }
+        }
+    }
+}
@@ -12,6 +12,7 @@ use crate::{
         best_block::BestBlockMetrics, timing::Checkpoint, transaction_pool::TransactionPoolMetrics,
         TimingBlockMetrics, LOG_TARGET,
     },
+    sync::SloReporter,
     BlockId, SubstrateChainStatus,
 };
 
@@ -90,8 +91,10 @@ impl SloMetrics {
     pub fn report_transaction_in_pool(&self, hash: TxHash) {
         self.transaction_metrics.report_in_pool(hash);
     }
+}
 
-    pub fn report_block_imported(&mut self, block_id: BlockId, is_new_best: bool, own: bool) {
+impl SloReporter for SloMetrics {
+    fn report_block_imported(&mut self, block_id: BlockId, is_new_best: bool, own: bool) {
         self.timing_metrics
             .report_block(block_id.hash(), Checkpoint::Imported);
         if own {
@@ -111,7 +114,7 @@ impl SloMetrics {
         }
     }
 
-    pub fn report_block_finalized(&self, block_id: BlockId) {
+    fn report_block_finalized(&self, block_id: BlockId) {
         self.timing_metrics
             .report_block(block_id.hash(), Checkpoint::Finalized);
         self.finality_rate_metrics
@@ -43,6 +43,7 @@ pub struct NetworkOutput<TP: TransactionPool + 'static> {
     pub network: Arc<NetworkService<TP::Block, TP::Hash>>,
     pub authentication_network: ProtocolNetwork,
     pub block_sync_network: ProtocolNetwork,
+    pub warp_sync_network: ProtocolNetwork,
     // names chosen for compatibility with SpawnTaskParams, get better ones if we ever stop using that
     pub sync_service: Arc<SyncingService<TP::Block>>,
     pub tx_handler_controller: TransactionsHandlerController<TP::Hash>,
@@ -52,6 +53,8 @@ pub struct NetworkOutput<TP: TransactionPool + 'static> {
 pub struct SubstrateNetworkConfig {
     /// Maximum bit-rate in bits per second of the substrate network (shared by sync, gossip, etc.).
     pub substrate_network_bit_rate: u64,
+    /// Maximum upload bit-rate in bits per second of the substrate network, unlimited if `None`.
+    pub substrate_network_upload_bit_rate: Option<u64>,
     /// Configuration of the network service.
     pub network_config: NetworkConfiguration,
 }
@@ -83,14 +86,21 @@ where
         setup_base_protocol::<TP::Block>(genesis_hash);
 
     let network_rate_limit = network_config.substrate_network_bit_rate;
-    let rate_limiter = SharedRateLimiter::new(network_rate_limit.into());
-    let transport_builder = |config| transport::build_transport(rate_limiter, config);
+    let read_rate_limiter = SharedRateLimiter::new(network_rate_limit.into());
+    let write_rate_limiter = network_config
+        .substrate_network_upload_bit_rate
+        .map_or_else(SharedRateLimiter::unlimited, |rate| {
+            SharedRateLimiter::new(rate.into())
+        });
+    let transport_builder =
+        |config| transport::build_transport(read_rate_limiter, write_rate_limiter, config);
 
     let (
         network,
         Networks {
             block_sync_network,
             authentication_network,
+            warp_sync_network,
         },
         transaction_prototype,
     ) = base_network(
@@ -102,7 +112,11 @@ where
         base_protocol_config,
         metrics_registry.clone(),
     )?;
-    let protocol_names = vec![authentication_network.name(), block_sync_network.name()];
+    let protocol_names = vec![
+        authentication_network.name(),
+        block_sync_network.name(),
+        warp_sync_network.name(),
+    ];
     let (base_service, syncing_service) = BaseProtocolService::new(
         major_sync,
         genesis_hash,
@@ -135,6 +149,7 @@ where
         network,
         block_sync_network,
         authentication_network,
+        warp_sync_network,
         sync_service: syncing_service,
         tx_handler_controller: transaction_interface,
         system_rpc_tx: rpc_interface,
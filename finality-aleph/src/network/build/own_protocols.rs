@@ -5,6 +5,7 @@ use crate::{
         session::MAX_MESSAGE_SIZE as MAX_AUTHENTICATION_MESSAGE_SIZE, substrate::ProtocolNetwork,
     },
     sync::MAX_MESSAGE_SIZE as MAX_BLOCK_SYNC_MESSAGE_SIZE,
+    warp_sync::MAX_MESSAGE_SIZE as MAX_WARP_SYNC_MESSAGE_SIZE,
     BlockHash,
 };
 
@@ -15,12 +16,17 @@ const AUTHENTICATION_PROTOCOL_NAME: &str = "/auth/0";
 /// Name of the network protocol used by Aleph Zero to synchronize the block state.
 const BLOCK_SYNC_PROTOCOL_NAME: &str = "/sync/0";
 
-/// Struct containing networks for our two protocols.
+/// Name of the network protocol used by Aleph Zero to warp sync new nodes.
+const WARP_SYNC_PROTOCOL_NAME: &str = "/warp/0";
+
+/// Struct containing networks for our three protocols.
 pub struct Networks {
     /// Authentication network.
     pub authentication_network: ProtocolNetwork,
     /// Block sync network.
     pub block_sync_network: ProtocolNetwork,
+    /// Warp sync network.
+    pub warp_sync_network: ProtocolNetwork,
 }
 
 impl Networks {
@@ -58,10 +64,17 @@ impl Networks {
             MAX_BLOCK_SYNC_MESSAGE_SIZE,
             net_config,
         );
+        let warp_sync_network = Self::add_protocol(
+            genesis_hash,
+            WARP_SYNC_PROTOCOL_NAME,
+            MAX_WARP_SYNC_MESSAGE_SIZE,
+            net_config,
+        );
 
         Self {
             authentication_network,
             block_sync_network,
+            warp_sync_network,
         }
     }
 }
@@ -2,14 +2,20 @@ use libp2p::{core::muxing::StreamMuxer, PeerId, Transport};
 use rate_limiter::{FuturesRateLimitedAsyncReadWrite, SharedRateLimiter};
 
 struct RateLimitedStreamMuxer<SM> {
-    rate_limiter: SharedRateLimiter,
+    read_rate_limiter: SharedRateLimiter,
+    write_rate_limiter: SharedRateLimiter,
     stream_muxer: SM,
 }
 
 impl<SM> RateLimitedStreamMuxer<SM> {
-    pub fn new(stream_muxer: SM, rate_limiter: SharedRateLimiter) -> Self {
+    pub fn new(
+        stream_muxer: SM,
+        read_rate_limiter: SharedRateLimiter,
+        write_rate_limiter: SharedRateLimiter,
+    ) -> Self {
         Self {
-            rate_limiter,
+            read_rate_limiter,
+            write_rate_limiter,
             stream_muxer,
         }
     }
@@ -36,9 +42,16 @@ where
         self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Result<Self::Substream, Self::Error>> {
-        let rate_limiter = self.rate_limiter.share();
+        let read_rate_limiter = self.read_rate_limiter.share();
+        let write_rate_limiter = self.write_rate_limiter.share();
         self.inner().poll_inbound(cx).map(|result| {
-            result.map(|substream| FuturesRateLimitedAsyncReadWrite::new(substream, rate_limiter))
+            result.map(|substream| {
+                FuturesRateLimitedAsyncReadWrite::new(
+                    substream,
+                    read_rate_limiter,
+                    write_rate_limiter,
+                )
+            })
         })
     }
 
@@ -46,9 +59,16 @@ where
         self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Result<Self::Substream, Self::Error>> {
-        let rate_limiter = self.rate_limiter.share();
+        let read_rate_limiter = self.read_rate_limiter.share();
+        let write_rate_limiter = self.write_rate_limiter.share();
         self.inner().poll_outbound(cx).map(|result| {
-            result.map(|substream| FuturesRateLimitedAsyncReadWrite::new(substream, rate_limiter))
+            result.map(|substream| {
+                FuturesRateLimitedAsyncReadWrite::new(
+                    substream,
+                    read_rate_limiter,
+                    write_rate_limiter,
+                )
+            })
         })
     }
 
@@ -67,11 +87,13 @@ where
     }
 }
 
-/// Builds a rate-limited implementation of [libp2p::Transport].
+/// Builds a rate-limited implementation of [libp2p::Transport]. Downloads are governed by `read_rate_limiter`, uploads by
+/// `write_rate_limiter`.
 /// Note: all of the `Send` constraints in the return type are put in order to satisfy constraints of the constructor of
 /// [sc_network::NetworkWorker].
 pub fn build_transport(
-    rate_limiter: SharedRateLimiter,
+    read_rate_limiter: SharedRateLimiter,
+    write_rate_limiter: SharedRateLimiter,
     config: sc_network::transport::NetworkConfig,
 ) -> impl Transport<
     Output = (
@@ -93,7 +115,8 @@ pub fn build_transport(
             Self(self.share())
         }
     }
-    let rate_limiter = ClonableSharedRateLimiter(rate_limiter);
+    let read_rate_limiter = ClonableSharedRateLimiter(read_rate_limiter);
+    let write_rate_limiter = ClonableSharedRateLimiter(write_rate_limiter);
 
     sc_network::transport::build_transport(
         config.keypair,
@@ -104,7 +127,11 @@ pub fn build_transport(
     .map(move |(peer_id, stream_muxer), _| {
         (
             peer_id,
-            RateLimitedStreamMuxer::new(stream_muxer, rate_limiter.share()),
+            RateLimitedStreamMuxer::new(
+                stream_muxer,
+                read_rate_limiter.share(),
+                write_rate_limiter.share(),
+            ),
         )
     })
 }
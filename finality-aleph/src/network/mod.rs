@@ -55,6 +55,13 @@ pub trait GossipNetwork<D: Data>: Send + 'static {
     /// returned, retry appropriately.
     fn broadcast(&mut self, data: D) -> Result<(), Self::Error>;
 
+    /// Stop exchanging data with the peer: ignore anything it sends and never send anything to
+    /// it, including random sends and broadcasts. Lasts until the peer gets unbanned.
+    fn ban(&mut self, peer_id: Self::PeerId);
+
+    /// Resume exchanging data with a previously banned peer.
+    fn unban(&mut self, peer_id: Self::PeerId);
+
     /// Receive some data from the network, including information about who sent it.
     /// This method's implementation must be cancellation safe.
     async fn next(&mut self) -> Result<(D, Self::PeerId), Self::Error>;
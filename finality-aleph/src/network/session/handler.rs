@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use log::error;
 use parity_scale_codec::Encode;
 
 use crate::{
@@ -60,9 +61,17 @@ fn construct_session_info<A: AddressingInformation>(
                 node_id: *node_index,
                 session_id,
             };
-            let signature = authority_pen.sign(&auth_data.encode());
-            let authentications = Authentication(auth_data, signature);
-            (SessionInfo::OwnAuthentication(authentications), peer_id)
+            match authority_pen.sign(&auth_data.encode()) {
+                Ok(signature) => {
+                    let authentications = Authentication(auth_data, signature);
+                    (SessionInfo::OwnAuthentication(authentications), peer_id)
+                }
+                // Without an authentication other nodes will not connect to us in the session.
+                Err(e) => {
+                    error!(target: "aleph-network", "Failed to authenticate in session {:?}: {}.", session_id, e);
+                    (SessionInfo::SessionId(session_id), peer_id)
+                }
+            }
         }
         None => (SessionInfo::SessionId(session_id), peer_id),
     }
@@ -89,7 +98,8 @@ impl<A: AddressingInformation> Handler<A> {
         }
     }
 
-    fn index(&self) -> Option<NodeIndex> {
+    /// Returns the index of the node in the session, if it is a validator.
+    pub fn index(&self) -> Option<NodeIndex> {
         match self.authority_index_and_pen {
             Some((index, _)) => Some(index),
             _ => None,
@@ -18,6 +18,7 @@ use crate::{
         },
         AddressingInformation, Data, NetworkIdentity, PeerId,
     },
+    status::{CommitteeMemberStatus, CommitteeStatus},
     NodeIndex, SessionId,
 };
 
@@ -373,6 +374,36 @@ impl<NI: NetworkIdentity, D: Data, VCU: ValidatorAddressCacheUpdater> Manager<NI
         }
     }
 
+    /// The committees of all the sessions we take part in as validators, with the peers we know
+    /// the addresses of.
+    pub fn committee_status(&self) -> Vec<CommitteeStatus> {
+        let mut committees: Vec<_> = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| session.handler.is_validator())
+            .map(|(session_id, session)| {
+                let own_index = session.handler.index();
+                let peers = session.handler.peers();
+                let members = (0..session.handler.node_count().0)
+                    .map(NodeIndex)
+                    .filter(|node_id| Some(*node_id) != own_index)
+                    .map(|node_id| CommitteeMemberStatus {
+                        node_index: node_id.0 as u32,
+                        peer_id: peers.get(&node_id).map(|peer_id| peer_id.to_string()),
+                    })
+                    .collect();
+                CommitteeStatus {
+                    session: *session_id,
+                    node_count: session.handler.node_count().0 as u32,
+                    own_index: own_index.map(|node_id| node_id.0 as u32),
+                    members,
+                }
+            })
+            .collect();
+        committees.sort_by_key(|committee| committee.session.0);
+        committees
+    }
+
     pub fn status_report(&self) {
         let mut status = String::from("Connection Manager status report: ");
 
@@ -468,6 +499,7 @@ mod tests {
             address_cache::{test::noop_updater, ValidatorAddressCacheUpdater},
             mock::crypto_basics,
             session::data::DataInSession,
+            AddressingInformation,
         },
         Recipient, SessionId,
     };
@@ -602,6 +634,55 @@ mod tests {
         assert!(maybe_message.is_some());
     }
 
+    #[test]
+    fn reports_committee_status() {
+        let mut manager = build();
+        let (validator_data, verifier) = crypto_basics(NUM_NODES);
+        let (node_id, pen) = validator_data[0].clone();
+        let session_id = SessionId(43);
+        manager
+            .update_validator_session(PreValidatorSession {
+                session_id,
+                verifier: verifier.clone(),
+                node_id,
+                pen,
+            })
+            .unwrap();
+        manager
+            .update_nonvalidator_session(PreNonvalidatorSession {
+                session_id: SessionId(44),
+                verifier: verifier.clone(),
+            })
+            .unwrap();
+        let mut other_manager = build();
+        let (other_node_id, pen) = validator_data[1].clone();
+        let (ManagerActions { maybe_message, .. }, _) = other_manager
+            .update_validator_session(PreValidatorSession {
+                session_id,
+                verifier,
+                node_id: other_node_id,
+                pen,
+            })
+            .unwrap();
+        let message = maybe_message.expect("there should be a discovery message");
+        let peer_id = message.0.address().peer_id();
+        manager.on_discovery_message(message);
+
+        let committees = manager.committee_status();
+        assert_eq!(committees.len(), 1);
+        let committee = &committees[0];
+        assert_eq!(committee.session, session_id);
+        assert_eq!(committee.node_count, NUM_NODES as u32);
+        assert_eq!(committee.own_index, Some(node_id.0 as u32));
+        assert_eq!(committee.members.len(), NUM_NODES - 1);
+        for member in &committee.members {
+            match member.node_index == other_node_id.0 as u32 {
+                true => assert_eq!(member.peer_id, Some(peer_id.to_string())),
+                false => assert_eq!(member.peer_id, None),
+            }
+        }
+    }
+
     #[test]
     fn sends_user_data() {
         let mut manager = build();
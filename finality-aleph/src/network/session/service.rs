@@ -27,6 +27,7 @@ use crate::{
         },
         AddressingInformation, Data, GossipNetwork, NetworkIdentity,
     },
+    status::StatusReporter,
     MillisecsPerBlock, NodeIndex, SessionId, SessionPeriod, STATUS_REPORT_INTERVAL,
 };
 
@@ -186,6 +187,7 @@ pub struct Service<
     messages_from_user: mpsc::UnboundedReceiver<(D, SessionId, Recipient)>,
     validator_network: CN,
     gossip_network: GN,
+    status_reporter: StatusReporter,
     maintenance_period: Duration,
     initial_delay: Duration,
 }
@@ -226,6 +228,7 @@ where
         validator_network: CN,
         gossip_network: GN,
         validator_address_cache_updater: VCU,
+        status_reporter: StatusReporter,
         config: Config,
     ) -> (
         Service<D, NI, CN, GN, VCU>,
@@ -250,6 +253,7 @@ where
                 messages_from_user,
                 validator_network,
                 gossip_network,
+                status_reporter,
                 maintenance_period,
                 initial_delay,
             },
@@ -346,6 +350,11 @@ where
         }
     }
 
+    fn report_status(&self) {
+        self.status_reporter
+            .report_committees(self.manager.committee_status());
+    }
+
     /// Run the connection manager service.
     pub async fn run(mut self) -> Result<(), Error<GN::Error>> {
         // Initial delay is needed so that Network is fully set up and we received some first discovery broadcasts from other nodes.
@@ -364,6 +373,7 @@ where
                         Ok(to_send) => self.handle_manager_actions(to_send)?,
                         Err(e) => warn!(target: "aleph-network", "Failed to update handler: {:?}", e),
                     }
+                    self.report_status();
                 },
                 maybe_message = self.messages_from_user.next() => {
                     trace!(target: "aleph-network", "Manager received a message from user");
@@ -388,7 +398,8 @@ where
                     match authentication.try_into() {
                         Ok(message) => {
                             let manager_actions = self.manager.on_discovery_message(message);
-                            self.handle_manager_actions(manager_actions)?
+                            self.handle_manager_actions(manager_actions)?;
+                            self.report_status();
                         },
                         Err(e) => debug!(target: "aleph-network", "Could not cast versioned authentication in discovery message: {:?}", e),
                     }
@@ -27,6 +27,7 @@ pub type BoxedNotificationService = Box<dyn sc_network::config::NotificationServ
 pub struct ProtocolNetwork {
     service: BoxedNotificationService,
     connected_peers: HashSet<PeerId>,
+    banned_peers: HashSet<PeerId>,
     last_status_report: time::Instant,
 }
 
@@ -47,6 +48,7 @@ impl ProtocolNetwork {
         Self {
             service,
             connected_peers: HashSet::new(),
+            banned_peers: HashSet::new(),
             last_status_report: time::Instant::now(),
         }
     }
@@ -55,22 +57,30 @@ impl ProtocolNetwork {
         self.service.protocol().clone()
     }
 
+    fn available_peers(&self) -> impl Iterator<Item = &PeerId> {
+        self.connected_peers.difference(&self.banned_peers)
+    }
+
     fn random_peer<'a>(&'a self, peer_ids: &'a HashSet<PeerId>) -> Option<&'a PeerId> {
-        peer_ids
-            .intersection(&self.connected_peers)
+        self.available_peers()
+            .filter(|peer_id| peer_ids.contains(peer_id))
             .choose(&mut thread_rng())
-            .or_else(|| self.connected_peers.iter().choose(&mut thread_rng()))
+            .or_else(|| self.available_peers().choose(&mut thread_rng()))
     }
 
     fn handle_network_event(&mut self, event: SubstrateEvent) -> Option<(Vec<u8>, PeerId)> {
         use SubstrateEvent::*;
         match event {
             ValidateInboundSubstream {
-                peer: _,
+                peer,
                 handshake: _,
                 result_tx,
             } => {
-                let _ = result_tx.send(ValidationResult::Accept);
+                let result = match self.banned_peers.contains(&peer) {
+                    true => ValidationResult::Reject,
+                    false => ValidationResult::Accept,
+                };
+                let _ = result_tx.send(result);
                 None
             }
             NotificationStreamOpened { peer, .. } => {
@@ -81,16 +91,22 @@ impl ProtocolNetwork {
                 self.connected_peers.remove(&peer);
                 None
             }
-            NotificationReceived { peer, notification } => Some((notification, peer)),
+            NotificationReceived { peer, notification } => {
+                match self.banned_peers.contains(&peer) {
+                    true => None,
+                    false => Some((notification, peer)),
+                }
+            }
         }
     }
 
     fn status_report(&self) {
         let mut status = String::from("Network status report: ");
         status.push_str(&format!(
-            "{} connected peers - {:?}; ",
+            "{} connected peers - {:?}, banned peers - {:?}; ",
             self.service.protocol(),
-            self.connected_peers.len()
+            self.connected_peers.len(),
+            self.banned_peers.len(),
         ));
         info!(target: LOG_TARGET, "{}", status);
     }
@@ -117,6 +133,14 @@ impl<D: Data> GossipNetwork<D> for ProtocolNetwork {
     type PeerId = PeerId;
 
     fn send_to(&mut self, data: D, peer_id: PeerId) -> Result<(), Self::Error> {
+        if self.banned_peers.contains(&peer_id) {
+            trace!(
+                target: LOG_TARGET,
+                "Not sending data to banned peer {:?}.",
+                peer_id,
+            );
+            return Ok(());
+        }
         trace!(
             target: LOG_TARGET,
             "Sending block sync data to peer {:?}.",
@@ -146,13 +170,24 @@ impl<D: Data> GossipNetwork<D> for ProtocolNetwork {
     }
 
     fn broadcast(&mut self, data: D) -> Result<(), Self::Error> {
-        for peer in self.connected_peers.clone() {
+        let peers: Vec<_> = self.available_peers().copied().collect();
+        for peer in peers {
             // in the current version send_to never returns an error
             let _ = self.send_to(data.clone(), peer);
         }
         Ok(())
     }
 
+    fn ban(&mut self, peer_id: PeerId) {
+        debug!(target: LOG_TARGET, "Banning peer {:?}.", peer_id);
+        self.banned_peers.insert(peer_id);
+    }
+
+    fn unban(&mut self, peer_id: PeerId) {
+        debug!(target: LOG_TARGET, "Unbanning peer {:?}.", peer_id);
+        self.banned_peers.remove(&peer_id);
+    }
+
     async fn next(&mut self) -> Result<(D, PeerId), Self::Error> {
         let mut status_ticker = time::interval_at(
             self.last_status_report
@@ -6,7 +6,7 @@ use std::{
 };
 
 use derive_more::{AsRef, Display};
-use log::info;
+use log::{info, warn};
 use network_clique::{Dialer, Listener, PeerId, PublicKey, SecretKey};
 use parity_scale_codec::{Decode, Encode};
 use sp_core::crypto::KeyTypeId;
@@ -16,6 +16,7 @@ use crate::{
     aleph_primitives::AuthorityId,
     crypto::{verify, AuthorityPen, Signature},
     network::{AddressingInformation, NetworkIdentity},
+    SignerError,
 };
 
 const LOG_TARGET: &str = "tcp-network";
@@ -47,8 +48,13 @@ impl SecretKey for AuthorityPen {
     type Signature = Signature;
     type PublicKey = AuthorityIdWrapper;
 
+    // The handshake expects signing to always succeed. When we fail to sign, the other side
+    // rejects the handshake instead.
     fn sign(&self, message: &[u8]) -> Self::Signature {
-        AuthorityPen::sign(self, message)
+        AuthorityPen::sign(self, message).unwrap_or_else(|e| {
+            warn!(target: LOG_TARGET, "Failed to sign the handshake: {}.", e);
+            Signature::invalid()
+        })
     }
 
     fn public_key(&self) -> Self::PublicKey {
@@ -130,10 +136,10 @@ impl SignedTcpAddressingInformation {
     fn new(
         addresses: Vec<String>,
         authority_pen: &AuthorityPen,
-    ) -> Result<SignedTcpAddressingInformation, AddressingInformationError> {
+    ) -> Result<SignedTcpAddressingInformation, Error> {
         let peer_id = authority_pen.authority_id();
         let addressing_information = TcpAddressingInformation::new(addresses, peer_id)?;
-        let signature = authority_pen.sign(&addressing_information.encode());
+        let signature = authority_pen.sign(&addressing_information.encode())?;
         Ok(SignedTcpAddressingInformation {
             addressing_information,
             signature,
@@ -180,6 +186,7 @@ impl Dialer<SignedTcpAddressingInformation> for TcpDialer {
 pub enum Error {
     Io(IoError),
     AddressingInformation(AddressingInformationError),
+    Signing(SignerError),
 }
 
 impl FmtDisplay for Error {
@@ -192,6 +199,9 @@ impl FmtDisplay for Error {
             AddressingInformation(err) => {
                 write!(f, "problem with addressing information: {err:?}")
             }
+            Signing(err) => {
+                write!(f, "addressing information could not be signed: {err}")
+            }
         }
     }
 }
@@ -208,6 +218,12 @@ impl From<AddressingInformationError> for Error {
     }
 }
 
+impl From<SignerError> for Error {
+    fn from(e: SignerError) -> Self {
+        Error::Signing(e)
+    }
+}
+
 /// Create a new tcp network, including an identity that can be used for constructing
 /// authentications for other peers.
 pub async fn new_tcp_network<A: ToSocketAddrs>(
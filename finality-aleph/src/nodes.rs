@@ -2,41 +2,48 @@ use std::{marker::PhantomData, sync::Arc};
 
 use bip39::{Language, Mnemonic, MnemonicType};
 use futures::channel::oneshot;
-use log::{debug, error};
+use log::{debug, error, warn};
 use network_clique::{RateLimitingDialer, RateLimitingListener, Service, SpawnHandleT};
 use pallet_aleph_runtime_api::AlephSessionApi;
 use primitives::TransactionHash;
 use rate_limiter::SharedRateLimiter;
 use sc_client_api::Backend;
 use sc_keystore::{Keystore, LocalKeystore};
-use sc_transaction_pool_api::TransactionPool;
+use sc_transaction_pool_api::{
+    LocalTransactionPool, OffchainTransactionPoolFactory, TransactionPool,
+};
 use sp_consensus_aura::AuraApi;
+use tokio::time;
 
 use crate::{
     aleph_primitives::{AuraId, Block},
     block::{
-        substrate::{JustificationTranslator, SubstrateFinalizationInfo, VerifierCache},
-        BlockchainEvents, ChainStatus, FinalizationStatus, Justification,
+        substrate::{
+            EquivocationReporter, JustificationTranslator, SubstrateFinalizationInfo, VerifierCache,
+        },
+        BlockchainEvents, ChainStatus, FinalizationStatus, HeaderBackend, Justification,
     },
     crypto::AuthorityPen,
     finalization::AlephFinalizer,
     idx_to_account::ValidatorIndexToAccountIdConverterImpl,
-    metrics::{run_metrics_service, SloMetrics},
+    metrics::{run_metrics_service, AbftScoreMetrics, SloMetrics},
     network::{
         address_cache::validator_address_cache_updater,
         session::{ConnectionManager, ConnectionManagerConfig},
         tcp::{new_tcp_network, KEY_TYPE},
     },
     party::{
-        impls::ChainStateImpl, manager::NodeSessionManagerImpl, ConsensusParty,
-        ConsensusPartyParams,
+        backup::backup_storage, impls::ChainStateImpl, manager::NodeSessionManagerImpl,
+        slashing_protection::SlashingProtection, ConsensusParty, ConsensusPartyParams,
     },
     runtime_api::RuntimeApiImpl,
     session::SessionBoundaryInfo,
     session_map::{
-        AuthorityProviderImpl, FinalityNotifierImpl, FinalizedBlockProviderImpl, SessionMapUpdater,
+        AuthorityProvider, AuthorityProviderImpl, FinalityNotifierImpl, FinalizedBlockProviderImpl,
+        SessionMapUpdater,
     },
     sync::{DatabaseIO as SyncDatabaseIO, Service as SyncService, IO as SyncIO},
+    warp_sync::{Service as WarpSyncService, WARP_SYNC_TIMEOUT},
     AlephConfig,
 };
 
@@ -60,17 +67,21 @@ where
     C: crate::ClientForAleph<Block, BE> + Send + Sync + 'static,
     C::Api: AlephSessionApi<Block> + AuraApi<Block, AuraId>,
     BE: Backend<Block> + 'static,
-    TP: TransactionPool<Block = Block, Hash = TransactionHash> + 'static,
+    TP: TransactionPool<Block = Block, Hash = TransactionHash>
+        + LocalTransactionPool<Block = Block>
+        + 'static,
 {
     let AlephConfig {
         authentication_network,
         block_sync_network,
+        warp_sync_network,
         client,
         chain_status,
         mut import_queue_handle,
         select_chain_provider,
         spawn_handle,
         keystore,
+        signer,
         registry,
         unit_creation_delay,
         session_period,
@@ -78,26 +89,78 @@ where
         justification_channel_provider,
         block_rx,
         backup_saving_path,
+        backup_retained_sessions,
+        slashing_protection_path,
         external_addresses,
         validator_port,
         rate_limiter_config,
         sync_oracle,
         validator_address_cache,
+        status_reporter,
         transaction_pool,
+        warp_sync,
+        state_import_target,
     } = aleph_config;
 
+    let session_info = SessionBoundaryInfo::new(session_period);
+    let slashing_protection = Arc::new(
+        SlashingProtection::open(slashing_protection_path)
+            .expect("the slashing protection record should be readable"),
+    );
+    // Warp sync only makes sense if we have nothing but the genesis block.
+    let genesis_authorities = match warp_sync && chain_status.top_finalized_id().number() == 0 {
+        true => Some(
+            AuthorityProviderImpl::new(client.clone(), RuntimeApiImpl::new(client.clone()))
+                .authority_data(0)
+                .expect("authorities of the first session should be available"),
+        ),
+        false => None,
+    };
+    let (warp_sync_service, warp_sync_target) = WarpSyncService::new(
+        warp_sync_network,
+        client.clone(),
+        chain_status.clone(),
+        session_info.clone(),
+        genesis_authorities,
+    );
+    spawn_handle.spawn("aleph/warp_sync", async move {
+        if let Err(err) = warp_sync_service.run().await {
+            error!(
+                target: LOG_TARGET,
+                "Warp sync service finished with error: {err}."
+            );
+        }
+    });
+    match time::timeout(WARP_SYNC_TIMEOUT, warp_sync_target).await {
+        Ok(Ok(Some(target))) => {
+            target
+                .import(
+                    &mut import_queue_handle,
+                    &chain_status,
+                    &state_import_target,
+                )
+                .await
+        }
+        Ok(_) => {}
+        Err(_) => warn!(
+            target: LOG_TARGET,
+            "Warp sync did not finish in {:?}, falling back to full sync.", WARP_SYNC_TIMEOUT
+        ),
+    }
+
     // We generate the phrase manually to only save the key in RAM, we don't want to have these
     // relatively low-importance keys getting spammed around the absolutely crucial Aleph keys.
     // The interface of `ed25519_generate_new` only allows to save in RAM by providing a mnemonic.
     let network_authority_pen = new_pen(
         Mnemonic::new(MnemonicType::Words12, Language::English).phrase(),
-        keystore.clone(),
+        keystore,
     );
 
     debug!(
         target: LOG_TARGET,
-        "Initializing rate-limiter for the validator-network with {} byte(s) per second.",
-        rate_limiter_config.alephbft_network_bit_rate
+        "Initializing rate-limiter for the validator-network with {} bit(s) per second for downloads and {:?} bit(s) per second for uploads.",
+        rate_limiter_config.alephbft_network_bit_rate,
+        rate_limiter_config.alephbft_network_upload_bit_rate,
     );
 
     let (dialer, listener, network_identity) = new_tcp_network(
@@ -108,16 +171,35 @@ where
     .await
     .expect("we should have working networking");
 
-    let alephbft_rate_limiter =
+    let alephbft_read_rate_limiter =
         SharedRateLimiter::new(rate_limiter_config.alephbft_network_bit_rate.into());
-    let dialer = RateLimitingDialer::new(dialer, alephbft_rate_limiter.share());
-    let listener = RateLimitingListener::new(listener, alephbft_rate_limiter);
+    let alephbft_write_rate_limiter = rate_limiter_config
+        .alephbft_network_upload_bit_rate
+        .map_or_else(SharedRateLimiter::unlimited, |rate| {
+            SharedRateLimiter::new(rate.into())
+        });
+    let dialer = RateLimitingDialer::new(
+        dialer,
+        alephbft_read_rate_limiter.share(),
+        alephbft_write_rate_limiter.share(),
+    );
+    let listener = RateLimitingListener::new(
+        listener,
+        alephbft_read_rate_limiter,
+        alephbft_write_rate_limiter,
+    );
 
     let (validator_network_service, validator_network) = Service::new(
         dialer,
         listener,
         network_authority_pen,
         spawn_handle.clone(),
+        SharedRateLimiter::new(rate_limiter_config.alephbft_network_bit_rate.into()),
+        rate_limiter_config
+            .alephbft_network_upload_bit_rate
+            .map_or_else(SharedRateLimiter::unlimited, |rate| {
+                SharedRateLimiter::new(rate.into())
+            }),
         registry.clone(),
     );
     let (_validator_network_exit, exit) = oneshot::channel();
@@ -148,9 +230,17 @@ where
 
     let slo_metrics = SloMetrics::new(registry.as_ref(), chain_status.clone());
     let timing_metrics = slo_metrics.timing_metrics().clone();
+    let abft_score_metrics = AbftScoreMetrics::new(registry.as_ref()).unwrap_or_else(|e| {
+        warn!(
+            target: LOG_TARGET,
+            "Failed to register Prometheus ABFT score metrics: {:?}.", e
+        );
+        AbftScoreMetrics::Noop
+    });
 
     spawn_handle.spawn("aleph/slo-metrics", {
         let slo_metrics = slo_metrics.clone();
+        let transaction_pool = transaction_pool.clone();
         async move {
             run_metrics_service(
                 &slo_metrics,
@@ -160,7 +250,6 @@ where
         }
     });
 
-    let session_info = SessionBoundaryInfo::new(session_period);
     let genesis_header = match chain_status.finalized_at(0) {
         Ok(FinalizationStatus::FinalizedWithJustification(justification)) => {
             justification.header().clone()
@@ -195,6 +284,10 @@ where
     );
     let select_chain = select_chain_provider.select_chain();
     let favourite_block_user_requests = select_chain_provider.favourite_block_user_requests();
+    let (equivocation_reporter, equivocation_reports) = EquivocationReporter::new(
+        client.clone(),
+        OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+    );
     let (sync_service, request_block) = match SyncService::new(
         verifier.clone(),
         session_info.clone(),
@@ -202,6 +295,8 @@ where
         registry.clone(),
         slo_metrics,
         favourite_block_user_requests,
+        equivocation_reports,
+        status_reporter.clone(),
     ) {
         Ok(x) => x,
         Err(e) => panic!("Failed to initialize Sync service: {e}"),
@@ -229,6 +324,7 @@ where
         validator_network,
         authentication_network,
         validator_address_cache_updater,
+        status_reporter.clone(),
         ConnectionManagerConfig::with_session_period(&session_period, &millisecs_per_block),
     );
 
@@ -241,13 +337,18 @@ where
     spawn_handle.spawn("aleph/sync", sync_task);
     debug!(target: LOG_TARGET, "Sync has started.");
 
+    spawn_handle.spawn("aleph/equivocation_reporter", equivocation_reporter.run());
+    debug!(target: LOG_TARGET, "Equivocation reporter has started.");
+
     spawn_handle.spawn("aleph/connection_manager", connection_manager_task);
     debug!(target: LOG_TARGET, "Sync network has started.");
 
     let party = ConsensusParty::new(ConsensusPartyParams {
         session_authorities,
         sync_oracle,
-        backup_saving_path,
+        backup_storage: backup_storage(backup_saving_path, backup_retained_sessions),
+        slashing_protection: slashing_protection.clone(),
+        status_reporter: status_reporter.clone(),
         chain_state: ChainStateImpl {
             client: client.clone(),
             _phantom: PhantomData,
@@ -263,9 +364,12 @@ where
             JustificationTranslator::new(chain_status.clone()),
             request_block,
             timing_metrics,
+            abft_score_metrics,
+            status_reporter,
             spawn_handle,
             connection_manager,
-            keystore,
+            signer,
+            slashing_protection,
         ),
         session_info,
     });
@@ -1,16 +1,26 @@
 use std::{
     fmt, fs,
     fs::File,
-    io::{Error as IoError, Read, Result as IoResult},
+    io::{Error as IoError, ErrorKind, Result as IoResult, Write},
     path::{Path, PathBuf},
     pin::Pin,
     str::FromStr,
+    sync::Arc,
 };
 
 use futures::io::{empty, sink, AllowStdIo, AsyncRead, AsyncWrite, Cursor};
-use log::debug;
+use log::{debug, warn};
+use sp_core::twox_64;
 
 const BACKUP_FILE_EXTENSION: &str = ".abfts";
+/// Marks backup files consisting of checksummed records, older files contain raw data only.
+const BACKUP_FILE_MAGIC: &[u8] = b"ABFTS\x00v1";
+/// Compacted backup that is still being written, should be discarded after a crash.
+const COMPACTION_IN_PROGRESS_FILE: &str = "compacted.abfts.tmp";
+/// Complete compacted backup, should replace all other backup files of the session.
+const COMPACTION_DONE_FILE: &str = "compacted.abfts.done";
+const RECORD_LENGTH_SIZE: usize = 4;
+const RECORD_CHECKSUM_SIZE: usize = 8;
 
 #[derive(Debug)]
 pub enum BackupLoadError {
@@ -46,98 +56,290 @@ pub type Saver = Pin<Box<dyn AsyncWrite + Send + Sync + Unpin>>;
 pub type Loader = Pin<Box<dyn AsyncRead + Send + Sync + Unpin>>;
 pub type ABFTBackup = (Saver, Loader);
 
-/// Find all `*.abfts` files at `session_path` and return their indexes sorted, if all are present.
-fn get_session_backup_idxs(session_path: &Path) -> Result<Vec<usize>, BackupLoadError> {
-    fs::create_dir_all(session_path)?;
+/// Storage of the AlephBFT backups of sessions.
+pub trait BackupStorage: Send + Sync + 'static {
+    /// Loads the existing backup of the session, and prepares a saver for the new backup data.
+    fn rotate(&self, session_id: u32) -> Result<ABFTBackup, BackupLoadError>;
+
+    /// Whether a backup of the session was ever started, so that all the units saved by
+    /// earlier runs in the session can be restored.
+    fn has_backup(&self, session_id: u32) -> bool;
+
+    /// Removes backups of sessions that are too old to be needed when the session
+    /// `current_session` is running.
+    fn remove_old_backups(&self, current_session: u32) -> IoResult<()>;
+}
+
+/// Returns the default backup storage, keeping the backups in the filesystem at `backup_path`,
+/// if provided, and not keeping any backups otherwise.
+pub fn backup_storage(
+    backup_path: Option<PathBuf>,
+    retained_sessions: u32,
+) -> Arc<dyn BackupStorage> {
+    match backup_path {
+        Some(path) => Arc::new(FilesystemBackupStorage::new(path, retained_sessions)),
+        None => Arc::new(NoBackupStorage),
+    }
+}
+
+/// Does not keep any backups.
+pub struct NoBackupStorage;
+
+impl BackupStorage for NoBackupStorage {
+    fn rotate(&self, session_id: u32) -> Result<ABFTBackup, BackupLoadError> {
+        debug!(target: "aleph-party", "Passing empty backup for session {:?} as no backup argument was provided", session_id);
+        Ok((Box::pin(sink()), Box::pin(empty())))
+    }
+
+    fn has_backup(&self, _session_id: u32) -> bool {
+        false
+    }
+
+    fn remove_old_backups(&self, _current_session: u32) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// Keeps the backups in the filesystem.
+///
+/// Current directory structure (this is an implementation detail, not part of the public API):
+///   backup-stash/      - the main directory, backup_path/--backup-saving-path
+///   `-- 18723/         - subdirectory for the current session
+///       |-- 0.abfts    - files containing data
+///       |-- 1.abfts    - each restart after a crash will cause another one to be created
+///       |-- 2.abfts    - these numbers count up sequentially
+///       `-- 3.abfts
+///
+/// Every restart compacts the existing files into `0.abfts` and continues with `1.abfts`.
+/// Files consist of checksummed records, one for every flush of the saver, so that a record
+/// torn by a crash is detected and dropped.
+pub struct FilesystemBackupStorage {
+    path: PathBuf,
+    retained_sessions: u32,
+}
+
+impl FilesystemBackupStorage {
+    /// Keeps the backups at `path`, together with the backups of `retained_sessions` sessions
+    /// preceding the current one.
+    pub fn new(path: PathBuf, retained_sessions: u32) -> Self {
+        FilesystemBackupStorage {
+            path,
+            retained_sessions,
+        }
+    }
+}
+
+/// Writes data flushed since the previous flush as a single checksummed record.
+struct RecordWriter<W: Write> {
+    inner: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> RecordWriter<W> {
+    fn new(mut inner: W) -> IoResult<Self> {
+        inner.write_all(BACKUP_FILE_MAGIC)?;
+        inner.flush()?;
+        Ok(RecordWriter {
+            inner,
+            buffer: Vec::new(),
+        })
+    }
+
+    fn write_record(&mut self, data: &[u8]) -> IoResult<()> {
+        let length = u32::try_from(data.len())
+            .map_err(|_| IoError::new(ErrorKind::InvalidInput, "backup record too long"))?;
+        let mut record = Vec::with_capacity(RECORD_LENGTH_SIZE + RECORD_CHECKSUM_SIZE + data.len());
+        record.extend_from_slice(&length.to_le_bytes());
+        record.extend_from_slice(&twox_64(data));
+        record.extend_from_slice(data);
+        self.inner.write_all(&record)
+    }
+}
+
+impl<W: Write> Write for RecordWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        if !self.buffer.is_empty() {
+            let data = std::mem::take(&mut self.buffer);
+            self.write_record(&data)?;
+        }
+        self.inner.flush()
+    }
+}
+
+/// Decodes the contents of a backup file. Returns the records up to the first invalid one,
+/// together with an indication whether all of the contents were valid.
+fn decode_records(contents: &[u8]) -> (Vec<&[u8]>, bool) {
+    if BACKUP_FILE_MAGIC.starts_with(contents) {
+        // The file was torn before the marker was fully written.
+        return (Vec::new(), contents.is_empty());
+    }
+    let Some(mut remaining) = contents.strip_prefix(BACKUP_FILE_MAGIC) else {
+        // A file written before records were introduced, its integrity cannot be checked.
+        return (vec![contents], true);
+    };
+    let mut records = Vec::new();
+    while !remaining.is_empty() {
+        if remaining.len() < RECORD_LENGTH_SIZE + RECORD_CHECKSUM_SIZE {
+            return (records, false);
+        }
+        let (length, rest) = remaining.split_at(RECORD_LENGTH_SIZE);
+        let (checksum, rest) = rest.split_at(RECORD_CHECKSUM_SIZE);
+        let length =
+            u32::from_le_bytes(length.try_into().expect("we split the right size")) as usize;
+        if rest.len() < length {
+            return (records, false);
+        }
+        let (data, rest) = rest.split_at(length);
+        if twox_64(data)[..] != checksum[..] {
+            return (records, false);
+        }
+        records.push(data);
+        remaining = rest;
+    }
+    (records, true)
+}
+
+/// Find all `*.abfts` files at `session_path` and return their indexes sorted.
+fn get_backup_idxs(session_path: &Path) -> IoResult<Vec<usize>> {
     let mut session_backups: Vec<_> = fs::read_dir(session_path)?
         .filter_map(|r| r.ok())
         .filter_map(|x| x.file_name().into_string().ok())
         .filter_map(|s| usize::from_str(s.strip_suffix(BACKUP_FILE_EXTENSION)?).ok())
         .collect();
     session_backups.sort_unstable();
+    Ok(session_backups)
+}
+
+/// Find all `*.abfts` files at `session_path` and return their indexes sorted, if all are present.
+fn get_session_backup_idxs(session_path: &Path) -> Result<Vec<usize>, BackupLoadError> {
+    let session_backups = get_backup_idxs(session_path)?;
     if !session_backups.iter().cloned().eq(0..session_backups.len()) {
         return Err(BackupLoadError::BackupIncomplete(session_backups));
     }
     Ok(session_backups)
 }
 
-/// Load session backup at path `session_path` from all `session_idxs`.
-fn load_backup(session_path: &Path, session_idxs: &[usize]) -> Result<Loader, BackupLoadError> {
-    let mut buffer = Vec::new();
+fn backup_file_path(session_path: &Path, index: usize) -> PathBuf {
+    session_path.join(format!("{index}{BACKUP_FILE_EXTENSION}"))
+}
+
+/// Load the valid records of the session backup at path `session_path` from all `session_idxs`.
+/// Returns them together with an indication whether any invalid data was encountered.
+fn load_records(
+    session_path: &Path,
+    session_idxs: &[usize],
+) -> Result<(Vec<Vec<u8>>, bool), BackupLoadError> {
+    let mut records = Vec::new();
+    let mut all_valid = true;
     for index in session_idxs.iter() {
-        let load_path = session_path.join(format!("{index}{BACKUP_FILE_EXTENSION}"));
-        File::open(load_path)?.read_to_end(&mut buffer)?;
+        let load_path = backup_file_path(session_path, *index);
+        let contents = fs::read(&load_path)?;
+        let (file_records, valid) = decode_records(&contents);
+        if !valid {
+            warn!(target: "aleph-party", "Backup file {:?} ends with a torn or corrupted record, dropping the data after the last valid one", load_path);
+            all_valid = false;
+        }
+        records.extend(file_records.into_iter().map(|record| record.to_vec()));
     }
-    Ok(Box::pin(Cursor::new(buffer)))
+    Ok((records, all_valid))
 }
 
-/// Get path of next backup file in session.
-fn get_next_path(session_path: &Path, session_idxs: &[usize]) -> PathBuf {
-    session_path.join(format!(
-        "{}{}",
-        session_idxs.last().map_or(0, |i| i + 1),
-        BACKUP_FILE_EXTENSION,
-    ))
+/// Replaces all backup files of the session with a compacted one. Once the compacted file is
+/// complete, the procedure can be finished by [finish_compaction] even after a crash.
+fn compact(session_path: &Path, records: &[Vec<u8>]) -> IoResult<()> {
+    let in_progress_path = session_path.join(COMPACTION_IN_PROGRESS_FILE);
+    let mut writer = RecordWriter::new(File::create(&in_progress_path)?)?;
+    for record in records {
+        writer.write_record(record)?;
+    }
+    writer.inner.sync_all()?;
+    fs::rename(in_progress_path, session_path.join(COMPACTION_DONE_FILE))?;
+    finish_compaction(session_path)
 }
 
-/// Loads the existing backups, and opens a new backup file to write to.
-///
-/// `backup_path` is the path to the backup directory (i.e. the argument to `--backup-saving-path`).
-///
-/// Returns the newly-created file (opened for writing), and the concatenation of the contents of
-/// all existing files.
-///
-/// Current directory structure (this is an implementation detail, not part of the public API):
-///   backup-stash/      - the main directory, backup_path/--backup-saving-path
-///   `-- 18723/         - subdirectory for the current session
-///       |-- 0.abfts    - files containing data
-///       |-- 1.abfts    - each restart after a crash will cause another one to be created
-///       |-- 2.abfts    - these numbers count up sequentially
-///       `-- 3.abfts
-pub fn rotate(
-    backup_path: Option<PathBuf>,
-    session_id: u32,
-) -> Result<ABFTBackup, BackupLoadError> {
-    debug!(target: "aleph-party", "Loading AlephBFT backup for session {:?}", session_id);
-    let session_path = if let Some(path) = backup_path {
-        path.join(format!("{session_id}"))
-    } else {
-        debug!(target: "aleph-party", "Passing empty backup for session {:?} as no backup argument was provided", session_id);
-        return Ok((Box::pin(sink()), Box::pin(empty())));
-    };
-    debug!(target: "aleph-party", "Loading backup for session {:?} at path {:?}", session_id, session_path);
+/// Finishes a compaction interrupted by a crash, or discards it if the compacted file might be
+/// incomplete.
+fn finish_compaction(session_path: &Path) -> IoResult<()> {
+    let in_progress_path = session_path.join(COMPACTION_IN_PROGRESS_FILE);
+    if in_progress_path.exists() {
+        fs::remove_file(in_progress_path)?;
+    }
+    let done_path = session_path.join(COMPACTION_DONE_FILE);
+    if done_path.exists() {
+        for index in get_backup_idxs(session_path)? {
+            fs::remove_file(backup_file_path(session_path, index))?;
+        }
+        fs::rename(done_path, backup_file_path(session_path, 0))?;
+    }
+    Ok(())
+}
 
-    let session_backup_idxs = get_session_backup_idxs(&session_path)?;
+impl BackupStorage for FilesystemBackupStorage {
+    /// Loads the existing backups, and opens a new backup file to write to.
+    ///
+    /// Returns the newly-created file (opened for writing), and the concatenation of the valid
+    /// contents of all existing files.
+    fn rotate(&self, session_id: u32) -> Result<ABFTBackup, BackupLoadError> {
+        let session_path = self.path.join(format!("{session_id}"));
+        debug!(target: "aleph-party", "Loading backup for session {:?} at path {:?}", session_id, session_path);
 
-    let backup_loader = load_backup(&session_path, &session_backup_idxs)?;
+        fs::create_dir_all(&session_path)?;
+        finish_compaction(&session_path)?;
+        let session_backup_idxs = get_session_backup_idxs(&session_path)?;
+        let (records, all_valid) = load_records(&session_path, &session_backup_idxs)?;
 
-    let next_backup_path = get_next_path(&session_path, &session_backup_idxs);
-    debug!(target: "aleph-party", "Loaded backup for session {:?}. Creating new backup file at {:?}", session_id, next_backup_path);
-    let backup_saver = Box::pin(AllowStdIo::new(File::create(next_backup_path)?));
+        let next_backup_idx = match session_backup_idxs.len() {
+            0 => 0,
+            1 if all_valid => 1,
+            _ => {
+                debug!(target: "aleph-party", "Compacting {:?} backup files for session {:?}", session_backup_idxs.len(), session_id);
+                compact(&session_path, &records)?;
+                1
+            }
+        };
+        let backup_loader = Box::pin(Cursor::new(records.concat()));
 
-    debug!(target: "aleph-party", "Backup rotation done for session {:?}", session_id);
-    Ok((backup_saver, backup_loader))
-}
+        let next_backup_path = backup_file_path(&session_path, next_backup_idx);
+        debug!(target: "aleph-party", "Loaded backup for session {:?}. Creating new backup file at {:?}", session_id, next_backup_path);
+        let backup_saver = Box::pin(AllowStdIo::new(RecordWriter::new(File::create(
+            next_backup_path,
+        )?)?));
 
-/// Removes the backup directory for all old sessions except the current session.
-///
-/// `backup_path` is the path to the backup directory (i.e. the argument to `--backup-saving-path`).
-/// If it is `None`, nothing is done.
-///
-/// Any filesystem errors are returned.
-///
-/// This should be done at the beginning of the new session.
-pub fn remove_old_backups(path: Option<PathBuf>, current_session: u32) -> IoResult<()> {
-    if let Some(path) = path {
-        if !path.exists() {
+        debug!(target: "aleph-party", "Backup rotation done for session {:?}", session_id);
+        Ok((backup_saver, backup_loader))
+    }
+
+    /// Checks whether there are any backup files of the session, including a compacted one
+    /// that was not moved into place yet.
+    fn has_backup(&self, session_id: u32) -> bool {
+        let session_path = self.path.join(format!("{session_id}"));
+        session_path.join(COMPACTION_DONE_FILE).exists()
+            || get_backup_idxs(&session_path)
+                .map(|idxs| !idxs.is_empty())
+                .unwrap_or(false)
+    }
+
+    /// Removes the backup directories of all sessions older than the current session by more
+    /// than the number of retained sessions.
+    ///
+    /// Any filesystem errors are returned.
+    ///
+    /// This should be done at the beginning of the new session.
+    fn remove_old_backups(&self, current_session: u32) -> IoResult<()> {
+        if !self.path.exists() {
             return Ok(());
         }
-        for read_dir in fs::read_dir(path)? {
+        for read_dir in fs::read_dir(&self.path)? {
             let item = read_dir?;
             match item.file_name().to_str() {
                 Some(name) => match name.parse::<u32>() {
                     Ok(session_id) => {
-                        if session_id < current_session {
+                        if session_id.saturating_add(self.retained_sessions) < current_session {
                             fs::remove_dir_all(item.path())?;
                         }
                     }
@@ -148,6 +350,150 @@ pub fn remove_old_backups(path: Option<PathBuf>, current_session: u32) -> IoResu
                 None => debug!(target: "aleph-party", "backup directory contains unexpected data."),
             };
         }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use futures::{AsyncReadExt, AsyncWriteExt};
+    use rand::random;
+
+    use super::{backup_file_path, BackupStorage, FilesystemBackupStorage, COMPACTION_DONE_FILE};
+
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("aleph-backup-test-{}", random::<u64>()));
+            fs::create_dir_all(&path).expect("should create the test directory");
+            TestDir(path)
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    async fn save(storage: &FilesystemBackupStorage, session_id: u32, units: &[&[u8]]) {
+        let (mut saver, _) = storage.rotate(session_id).expect("rotation should work");
+        for unit in units {
+            saver.write_all(unit).await.expect("should write");
+            saver.flush().await.expect("should flush");
+        }
+    }
+
+    async fn load(storage: &FilesystemBackupStorage, session_id: u32) -> Vec<u8> {
+        let (_, mut loader) = storage.rotate(session_id).expect("rotation should work");
+        let mut loaded = Vec::new();
+        loader.read_to_end(&mut loaded).await.expect("should read");
+        loaded
+    }
+
+    #[tokio::test]
+    async fn loads_data_saved_before_restarts() {
+        let dir = TestDir::new();
+        let storage = FilesystemBackupStorage::new(dir.0.clone(), 0);
+
+        save(&storage, 7, &[b"first", b"second"]).await;
+        save(&storage, 7, &[b"third"]).await;
+
+        assert_eq!(load(&storage, 7).await, b"firstsecondthird".to_vec());
+    }
+
+    #[tokio::test]
+    async fn compacts_backup_files_on_rotation() {
+        let dir = TestDir::new();
+        let storage = FilesystemBackupStorage::new(dir.0.clone(), 0);
+        let session_path = dir.0.join("7");
+
+        save(&storage, 7, &[b"first"]).await;
+        save(&storage, 7, &[b"second"]).await;
+        save(&storage, 7, &[b"third"]).await;
+
+        // The last rotation compacted the first two files into one.
+        assert!(backup_file_path(&session_path, 0).exists());
+        assert!(backup_file_path(&session_path, 1).exists());
+        assert!(!backup_file_path(&session_path, 2).exists());
+        assert_eq!(load(&storage, 7).await, b"firstsecondthird".to_vec());
+    }
+
+    #[tokio::test]
+    async fn drops_torn_records() {
+        let dir = TestDir::new();
+        let storage = FilesystemBackupStorage::new(dir.0.clone(), 0);
+        let backup_path = backup_file_path(&dir.0.join("7"), 0);
+
+        save(&storage, 7, &[b"first", b"second"]).await;
+        let contents = fs::read(&backup_path).expect("should read");
+        fs::write(&backup_path, &contents[..contents.len() - 2]).expect("should write");
+
+        assert_eq!(load(&storage, 7).await, b"first".to_vec());
+        // The torn record is gone for good, not only skipped.
+        assert_eq!(load(&storage, 7).await, b"first".to_vec());
+    }
+
+    #[tokio::test]
+    async fn loads_backups_without_records() {
+        let dir = TestDir::new();
+        let storage = FilesystemBackupStorage::new(dir.0.clone(), 0);
+        let session_path = dir.0.join("7");
+        fs::create_dir_all(&session_path).expect("should create");
+        fs::write(backup_file_path(&session_path, 0), b"legacy").expect("should write");
+
+        save(&storage, 7, &[b"new"]).await;
+
+        assert_eq!(load(&storage, 7).await, b"legacynew".to_vec());
+    }
+
+    #[tokio::test]
+    async fn finishes_interrupted_compaction() {
+        let dir = TestDir::new();
+        let storage = FilesystemBackupStorage::new(dir.0.clone(), 0);
+        let session_path = dir.0.join("7");
+
+        save(&storage, 7, &[b"first"]).await;
+        save(&storage, 7, &[b"second"]).await;
+        // Pretend we crashed after writing the compacted file, but before removing the old ones.
+        fs::copy(
+            backup_file_path(&session_path, 1),
+            session_path.join(COMPACTION_DONE_FILE),
+        )
+        .expect("should copy");
+
+        assert_eq!(load(&storage, 7).await, b"second".to_vec());
+    }
+
+    #[test]
+    fn has_backup_only_after_rotation() {
+        let dir = TestDir::new();
+        let storage = FilesystemBackupStorage::new(dir.0.clone(), 0);
+
+        assert!(!storage.has_backup(7));
+        storage.rotate(7).expect("rotation should work");
+        assert!(storage.has_backup(7));
+        assert!(!storage.has_backup(8));
+    }
+
+    #[test]
+    fn removes_sessions_beyond_horizon() {
+        let dir = TestDir::new();
+        let storage = FilesystemBackupStorage::new(dir.0.clone(), 2);
+        for session_id in 0..6 {
+            storage.rotate(session_id).expect("rotation should work");
+        }
+
+        storage.remove_old_backups(5).expect("should remove");
+
+        for session_id in 0..3 {
+            assert!(!dir.0.join(format!("{session_id}")).exists());
+        }
+        for session_id in 3..6 {
+            assert!(dir.0.join(format!("{session_id}")).exists());
+        }
     }
-    Ok(())
 }
@@ -5,8 +5,6 @@ use futures::channel::oneshot;
 use log::{debug, info, trace, warn};
 use network_clique::SpawnHandleExt;
 use pallet_aleph_runtime_api::AlephSessionApi;
-use sc_keystore::{Keystore, LocalKeystore};
-use sp_application_crypto::RuntimeAppPublic;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 
 use crate::{
@@ -14,14 +12,14 @@ use crate::{
         current_create_aleph_config, legacy_create_aleph_config, run_current_member,
         run_legacy_member, CurrentPerformanceService, SpawnHandle,
     },
-    aleph_primitives::{BlockHash, BlockNumber, KEY_TYPE},
+    aleph_primitives::{BlockHash, BlockNumber},
     block::{
         substrate::{Justification, JustificationTranslator},
         BestBlockSelector, Block, Header, HeaderVerifier, UnverifiedHeader,
     },
     crypto::{AuthorityPen, AuthorityVerifier},
     data_io::{ChainTracker, DataStore, OrderedDataInterpreter, SubstrateChainInfoProvider},
-    metrics::TimingBlockMetrics,
+    metrics::{AbftScoreMetrics, TimingBlockMetrics},
     mpsc,
     network::{
         data::{
@@ -31,8 +29,11 @@ use crate::{
         session::{SessionManager, SessionSender},
     },
     party::{
-        backup::ABFTBackup, manager::aggregator::AggregatorVersion, traits::NodeSessionManager,
+        backup::ABFTBackup, manager::aggregator::AggregatorVersion,
+        slashing_protection::SlashingProtection, traits::NodeSessionManager,
     },
+    signer::Signer,
+    status::StatusReporter,
     sync::JustificationSubmissions,
     AuthorityId, BlockId, CurrentRmcNetworkData, Keychain, LegacyRmcNetworkData, NodeIndex,
     ProvideRuntimeApi, SessionBoundaries, SessionBoundaryInfo, SessionId, SessionPeriod,
@@ -111,9 +112,12 @@ where
     justification_translator: JustificationTranslator,
     block_requester: RB,
     metrics: TimingBlockMetrics,
+    abft_score_metrics: AbftScoreMetrics,
+    status_reporter: StatusReporter,
     spawn_handle: SpawnHandle,
     session_manager: SM,
-    keystore: Arc<LocalKeystore>,
+    signer: Arc<dyn Signer>,
+    slashing_protection: Arc<SlashingProtection>,
     _phantom: PhantomData<(B, H)>,
 }
 
@@ -143,9 +147,12 @@ where
         justification_translator: JustificationTranslator,
         block_requester: RB,
         metrics: TimingBlockMetrics,
+        abft_score_metrics: AbftScoreMetrics,
+        status_reporter: StatusReporter,
         spawn_handle: SpawnHandle,
         session_manager: SM,
-        keystore: Arc<LocalKeystore>,
+        signer: Arc<dyn Signer>,
+        slashing_protection: Arc<SlashingProtection>,
     ) -> Self {
         Self {
             client,
@@ -158,9 +165,12 @@ where
             justification_translator,
             block_requester,
             metrics,
+            abft_score_metrics,
+            status_reporter,
             spawn_handle,
             session_manager,
-            keystore,
+            signer,
+            slashing_protection,
             _phantom: PhantomData,
         }
     }
@@ -197,8 +207,14 @@ where
             self.verifier.clone(),
             session_boundaries.clone(),
         );
-        let consensus_config =
-            legacy_create_aleph_config(n_members, node_id, session_id, self.unit_creation_delay);
+        let member_keychain = multikeychain.for_member();
+        let consensus_config = legacy_create_aleph_config(
+            n_members,
+            node_id,
+            session_id,
+            self.unit_creation_delay,
+            &member_keychain,
+        );
         let data_network = data_network.map();
 
         let (unfiltered_aleph_network, rmc_network) =
@@ -216,7 +232,7 @@ where
             exit_rx,
             run_legacy_member(
                 subtask_common.clone(),
-                multikeychain.clone(),
+                member_keychain,
                 consensus_config,
                 aleph_network.into(),
                 data_provider,
@@ -274,10 +290,21 @@ where
             self.verifier.clone(),
             session_boundaries.clone(),
         );
-        let (abft_performance, abft_batch_handler) =
-            CurrentPerformanceService::new(n_members, ordered_data_interpreter);
-        let consensus_config =
-            current_create_aleph_config(n_members, node_id, session_id, self.unit_creation_delay);
+        let (abft_performance, abft_batch_handler) = CurrentPerformanceService::new(
+            n_members,
+            session_id,
+            ordered_data_interpreter,
+            self.abft_score_metrics.clone(),
+            self.status_reporter.clone(),
+        );
+        let member_keychain = multikeychain.for_member();
+        let consensus_config = current_create_aleph_config(
+            n_members,
+            node_id,
+            session_id,
+            self.unit_creation_delay,
+            &member_keychain,
+        );
         let data_network = data_network.map();
 
         let (unfiltered_aleph_network, rmc_network) =
@@ -295,7 +322,7 @@ where
             exit_rx,
             run_current_member(
                 subtask_common.clone(),
-                multikeychain.clone(),
+                member_keychain,
                 consensus_config,
                 aleph_network.into(),
                 data_provider,
@@ -329,10 +356,15 @@ where
 
         let authority_verifier = AuthorityVerifier::new(authorities.to_vec());
         let authority_pen =
-            AuthorityPen::new(authorities[node_id.0].clone(), self.keystore.clone())
+            AuthorityPen::with_signer(authorities[node_id.0].clone(), self.signer.clone())
                 .expect("The keys should sign successfully");
-        let multikeychain =
-            Keychain::new(node_id, authority_verifier.clone(), authority_pen.clone());
+        let multikeychain = Keychain::new(
+            node_id,
+            authority_verifier.clone(),
+            authority_pen.clone(),
+            session_id,
+            self.slashing_protection.clone(),
+        );
 
         let session_boundaries = self.session_info.boundaries_for_session(session_id);
         let (blocks_for_aggregator, blocks_from_interpreter) = mpsc::unbounded();
@@ -477,7 +509,7 @@ where
     ) -> Result<(), Self::Error> {
         let authority_verifier = AuthorityVerifier::new(authorities.to_vec());
         let authority_pen =
-            AuthorityPen::new(authorities[node_id.0].clone(), self.keystore.clone())
+            AuthorityPen::with_signer(authorities[node_id.0].clone(), self.signer.clone())
                 .expect("The keys should sign successfully");
         self.session_manager.early_start_validator_session(
             session,
@@ -503,17 +535,17 @@ where
     }
 
     fn node_idx(&self, authorities: &[AuthorityId]) -> Option<NodeIndex> {
-        let our_consensus_keys: HashSet<_> = match self.keystore.keys(KEY_TYPE) {
+        let our_consensus_keys: HashSet<_> = match self.signer.public_keys() {
             Ok(keys) => keys.into_iter().collect(),
             Err(e) => {
-                warn!(target: "aleph-data-store", "Error accessing keystore: {}", e);
+                warn!(target: "aleph-data-store", "Error accessing keys: {}", e);
                 return None;
             }
         };
-        trace!(target: "aleph-data-store", "Found {:?} consensus keys in our local keystore {:?}", our_consensus_keys.len(), our_consensus_keys);
+        trace!(target: "aleph-data-store", "Found {:?} consensus keys {:?}", our_consensus_keys.len(), our_consensus_keys);
         authorities
             .iter()
-            .position(|pkey| our_consensus_keys.contains(&pkey.to_raw_vec()))
+            .position(|pkey| our_consensus_keys.contains(pkey))
             .map(|id| id.into())
     }
 }
@@ -1,4 +1,4 @@
-use std::{default::Default, path::PathBuf, time::Duration};
+use std::{default::Default, sync::Arc, time::Duration};
 
 use futures::FutureExt;
 use futures_timer::Delay;
@@ -8,17 +8,21 @@ use tokio::{task::spawn_blocking, time::sleep};
 
 use crate::{
     party::{
+        backup::BackupStorage,
         manager::{Handle, Task, TaskCommon as AuthoritySubtaskCommon},
+        slashing_protection::SlashingProtection,
         traits::{ChainState, NodeSessionManager},
     },
     session::SessionBoundaryInfo,
     session_map::ReadOnlySessionMap,
+    status::{AbftStatus, StatusReporter},
     SessionId, SyncOracle,
 };
 
 pub(crate) mod backup;
 pub mod impls;
 pub mod manager;
+pub(crate) mod slashing_protection;
 pub mod traits;
 
 #[cfg(test)]
@@ -28,7 +32,9 @@ pub(crate) struct ConsensusPartyParams<CS, NSM> {
     pub session_authorities: ReadOnlySessionMap,
     pub chain_state: CS,
     pub sync_oracle: SyncOracle,
-    pub backup_saving_path: Option<PathBuf>,
+    pub backup_storage: Arc<dyn BackupStorage>,
+    pub slashing_protection: Arc<SlashingProtection>,
+    pub status_reporter: StatusReporter,
     pub session_manager: NSM,
     pub session_info: SessionBoundaryInfo,
 }
@@ -41,7 +47,9 @@ where
     session_authorities: ReadOnlySessionMap,
     chain_state: CS,
     sync_oracle: SyncOracle,
-    backup_saving_path: Option<PathBuf>,
+    backup_storage: Arc<dyn BackupStorage>,
+    slashing_protection: Arc<SlashingProtection>,
+    status_reporter: StatusReporter,
     session_manager: NSM,
     session_info: SessionBoundaryInfo,
 }
@@ -57,7 +65,9 @@ where
         let ConsensusPartyParams {
             session_authorities,
             sync_oracle,
-            backup_saving_path,
+            backup_storage,
+            slashing_protection,
+            status_reporter,
             chain_state,
             session_manager,
             session_info,
@@ -66,7 +76,9 @@ where
         Self {
             sync_oracle,
             session_authorities,
-            backup_saving_path,
+            backup_storage,
+            slashing_protection,
+            status_reporter,
             chain_state,
             session_manager,
             session_info,
@@ -99,12 +111,32 @@ where
         }
     }
 
+    /// Checks whether we can run the session as an authority without equivocating, i.e. we
+    /// either did not sign anything in it before the restart, or we can restore what we signed
+    /// from the backup.
+    fn can_sign(&self, session_id: SessionId) -> bool {
+        let record = match self.slashing_protection.earlier_record(session_id) {
+            Some(record) => record,
+            None => return true,
+        };
+        if self.backup_storage.has_backup(session_id.0) {
+            self.slashing_protection.unlock(session_id);
+            return true;
+        }
+        error!(
+            target: "aleph-party",
+            "Units were signed in session {:?} before the restart, up to round {:?}, but there is no backup to restore them from. Running the session as non-authority to avoid equivocating.",
+            session_id, record.newest_unit.map(|unit| unit.round)
+        );
+        false
+    }
+
     async fn run_session(&mut self, session_id: SessionId) {
         let last_block = self.session_info.last_block_of_session(session_id);
         if session_id.0.checked_sub(1).is_some() {
-            let backup_saving_path = self.backup_saving_path.clone();
+            let backup_storage = self.backup_storage.clone();
             spawn_blocking(move || {
-                if let Err(e) = backup::remove_old_backups(backup_saving_path, session_id.0) {
+                if let Err(e) = backup_storage.remove_old_backups(session_id.0) {
                     warn!(target: "aleph-party", "Error when clearing old backups: {}", e);
                 }
             });
@@ -143,10 +175,12 @@ where
         let authorities = authority_data.authorities();
 
         trace!(target: "aleph-party", "Authority data for session {:?}: {:?}", session_id, authorities);
-        let mut maybe_authority_task = if let Some(node_id) =
-            self.session_manager.node_idx(authorities)
-        {
-            match backup::rotate(self.backup_saving_path.clone(), session_id.0) {
+        let maybe_node_id = self
+            .session_manager
+            .node_idx(authorities)
+            .filter(|_| self.can_sign(session_id));
+        let mut maybe_authority_task = if let Some(node_id) = maybe_node_id {
+            match self.backup_storage.rotate(session_id.0) {
                 Ok(backup) => {
                     debug!(target: "aleph-party", "Running session {:?} as authority id {:?}", session_id, node_id);
                     Some(
@@ -179,6 +213,10 @@ where
             }
             None
         };
+        self.status_reporter.report_abft(AbftStatus {
+            session: session_id,
+            node_index: maybe_node_id.map(|node_id| node_id.0 as u32),
+        });
         let mut check_session_status = Delay::new(SESSION_STATUS_CHECK_PERIOD);
         let next_session_id = SessionId(session_id.0 + 1);
         let mut start_next_session_network = self
@@ -266,11 +304,14 @@ mod tests {
     use crate::{
         aleph_primitives::{AuthorityId, SessionAuthorityData},
         party::{
+            backup::NoBackupStorage,
             mocks::{MockChainState, MockNodeSessionManager},
+            slashing_protection::SlashingProtection,
             ConsensusParty, ConsensusPartyParams, SESSION_STATUS_CHECK_PERIOD,
         },
         session::SessionBoundaryInfo,
         session_map::SharedSessionMap,
+        status::status_channel,
         SessionId, SessionPeriod, SyncOracle,
     };
 
@@ -513,7 +554,9 @@ mod tests {
             session_authorities: readonly_session_authorities,
             chain_state,
             sync_oracle,
-            backup_saving_path: None,
+            backup_storage: Arc::new(NoBackupStorage),
+            slashing_protection: Arc::new(SlashingProtection::in_memory()),
+            status_reporter: status_channel().0,
             session_manager,
             session_info,
         };
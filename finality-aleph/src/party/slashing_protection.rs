@@ -0,0 +1,308 @@
+use std::{collections::BTreeSet, fmt, io::Result as IoResult, path::PathBuf, sync::Mutex};
+
+use log::{debug, info};
+
+use crate::{
+    signer::{RecordError, SessionRecord, SignedUnit, SignedUnitsRecord},
+    SessionId,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    /// An earlier run of the node signed units in the session, and they could not be restored.
+    SignedEarlier(SessionId, SessionRecord),
+    Record(RecordError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::SignedEarlier(session, record) => write!(
+                f,
+                "units were signed in session {:?} before the restart, up to round {:?}",
+                session.0,
+                record.newest_unit.as_ref().map(|unit| unit.round)
+            ),
+            Error::Record(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<RecordError> for Error {
+    fn from(err: RecordError) -> Self {
+        Self::Record(err)
+    }
+}
+
+impl std::error::Error for Error {}
+
+struct State {
+    /// The node signs with a single key, so the record does not need to tell keys apart.
+    record: SignedUnitsRecord<()>,
+    /// Sessions in which an earlier run of the node signed, and which were not unlocked.
+    locked: BTreeSet<u32>,
+}
+
+/// A record of the sessions in which this node signed AlephBFT units, kept independently of
+/// the backups. A node that lost its backup would not know which units it already created, so
+/// it must not sign in such sessions again, or it would equivocate.
+///
+/// Sessions signed in by an earlier run of the node are locked, until the party confirms their
+/// backup was restored with [`SlashingProtection::unlock`].
+pub struct SlashingProtection {
+    state: Mutex<State>,
+}
+
+impl SlashingProtection {
+    /// Keeps the record in the file at `path`, loading the record left by earlier runs.
+    pub fn open(path: PathBuf) -> IoResult<Self> {
+        let record = SignedUnitsRecord::open(path)?;
+        let locked: BTreeSet<_> = record.sessions(&()).map(|session| session.0).collect();
+        debug!(target: "aleph-party", "Loaded slashing protection record for sessions {:?}", locked);
+        Ok(SlashingProtection {
+            state: Mutex::new(State { record, locked }),
+        })
+    }
+
+    /// Keeps the record in memory only, so it does not protect against anything after a restart.
+    pub fn in_memory() -> Self {
+        SlashingProtection {
+            state: Mutex::new(State {
+                record: SignedUnitsRecord::in_memory(),
+                locked: BTreeSet::new(),
+            }),
+        }
+    }
+
+    /// The record of the session, if an earlier run of the node signed in it and the session
+    /// was not unlocked.
+    pub fn earlier_record(&self, session: SessionId) -> Option<SessionRecord> {
+        let state = self.state.lock().expect("the lock is not poisoned");
+        match state.locked.contains(&session.0) {
+            true => state.record.session(&(), session).cloned(),
+            false => None,
+        }
+    }
+
+    /// Allows signing in the session again, should only be called when all the units signed
+    /// in it were restored from the backup.
+    pub fn unlock(&self, session: SessionId) {
+        let mut state = self.state.lock().expect("the lock is not poisoned");
+        if state.locked.remove(&session.0) {
+            info!(target: "aleph-party", "Signing in session {:?} unlocked, as its backup is available.", session.0);
+        }
+    }
+
+    /// Should be called before every signature in the session. Fails if the session is locked,
+    /// otherwise makes sure the session is recorded before anything is signed in it.
+    pub fn before_signing(&self, session: SessionId) -> Result<(), Error> {
+        let mut state = self.state.lock().expect("the lock is not poisoned");
+        Self::check_unlocked(&state, session)?;
+        Ok(state.record.note_session(&(), session)?)
+    }
+
+    /// Should be called before signing our unit in the session. Fails if the session is locked,
+    /// or signing the unit could be an equivocation, see [`SignedUnitsRecord`]. Otherwise records
+    /// the unit before it is signed.
+    pub fn before_signing_unit(&self, session: SessionId, unit: SignedUnit) -> Result<(), Error> {
+        let mut state = self.state.lock().expect("the lock is not poisoned");
+        Self::check_unlocked(&state, session)?;
+        Ok(state.record.note_unit(&(), session, unit)?)
+    }
+
+    fn check_unlocked(state: &State, session: SessionId) -> Result<(), Error> {
+        match state.locked.contains(&session.0) {
+            true => Err(Error::SignedEarlier(
+                session,
+                state
+                    .record
+                    .session(&(), session)
+                    .cloned()
+                    .unwrap_or_default(),
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use rand::random;
+
+    use super::{Error, SlashingProtection};
+    use crate::{
+        abft::NodeIndex,
+        signer::{record::REMEMBERED_SESSIONS, RecordError, SessionRecord, SignedUnit},
+        SessionId,
+    };
+
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "aleph-slashing-protection-test-{}",
+                random::<u64>()
+            ));
+            fs::create_dir_all(&path).expect("should create the test directory");
+            TestDir(path)
+        }
+
+        fn record_path(&self) -> PathBuf {
+            self.0.join("slashing-protection")
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn unit(round: u16, hash: &[u8]) -> SignedUnit {
+        SignedUnit {
+            creator: NodeIndex(1),
+            round,
+            hash: hash.to_vec(),
+        }
+    }
+
+    #[test]
+    fn allows_signing_repeatedly_in_one_run() {
+        let dir = TestDir::new();
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+
+        assert!(protection.before_signing(SessionId(3)).is_ok());
+        assert!(protection.before_signing(SessionId(3)).is_ok());
+        assert!(protection.earlier_record(SessionId(3)).is_none());
+    }
+
+    #[test]
+    fn locks_sessions_signed_in_before_restart() {
+        let dir = TestDir::new();
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+        protection
+            .before_signing_unit(SessionId(3), unit(17, b"unit"))
+            .expect("should sign");
+        drop(protection);
+
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+        let record = SessionRecord {
+            newest_unit: Some(unit(17, b"unit")),
+        };
+        assert_eq!(protection.earlier_record(SessionId(3)), Some(record));
+        assert!(matches!(
+            protection.before_signing(SessionId(3)),
+            Err(Error::SignedEarlier(SessionId(3), locked_record)) if locked_record == record
+        ));
+        assert!(matches!(
+            protection.before_signing_unit(SessionId(3), unit(18, b"next unit")),
+            Err(Error::SignedEarlier(SessionId(3), _))
+        ));
+        assert!(protection.before_signing(SessionId(4)).is_ok());
+    }
+
+    #[test]
+    fn unlocked_sessions_can_be_signed_in() {
+        let dir = TestDir::new();
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+        protection
+            .before_signing(SessionId(3))
+            .expect("should sign");
+        drop(protection);
+
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+        protection.unlock(SessionId(3));
+        assert!(protection.earlier_record(SessionId(3)).is_none());
+        assert!(protection.before_signing(SessionId(3)).is_ok());
+    }
+
+    #[test]
+    fn refuses_units_of_signed_rounds() {
+        let protection = SlashingProtection::in_memory();
+        assert!(protection
+            .before_signing_unit(SessionId(3), unit(0, b"first unit"))
+            .is_ok());
+        assert!(protection
+            .before_signing_unit(SessionId(3), unit(17, b"unit"))
+            .is_ok());
+        assert!(protection
+            .before_signing_unit(SessionId(3), unit(17, b"unit"))
+            .is_ok());
+        assert!(matches!(
+            protection.before_signing_unit(SessionId(3), unit(17, b"other unit")),
+            Err(Error::Record(RecordError::RoundAlreadySigned(
+                SessionId(3),
+                17,
+                17
+            )))
+        ));
+        assert!(matches!(
+            protection.before_signing_unit(SessionId(3), unit(12, b"older unit")),
+            Err(Error::Record(RecordError::RoundAlreadySigned(
+                SessionId(3),
+                12,
+                17
+            )))
+        ));
+        assert!(protection.before_signing(SessionId(3)).is_ok());
+        assert!(protection
+            .before_signing_unit(SessionId(3), unit(18, b"next unit"))
+            .is_ok());
+        assert!(protection
+            .before_signing_unit(SessionId(4), unit(0, b"unit"))
+            .is_ok());
+    }
+
+    #[test]
+    fn remembers_signed_rounds_of_unlocked_sessions() {
+        let dir = TestDir::new();
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+        protection
+            .before_signing_unit(SessionId(3), unit(17, b"unit"))
+            .expect("should sign");
+        drop(protection);
+
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+        protection.unlock(SessionId(3));
+        assert!(matches!(
+            protection.before_signing_unit(SessionId(3), unit(17, b"other unit")),
+            Err(Error::Record(RecordError::RoundAlreadySigned(
+                SessionId(3),
+                17,
+                17
+            )))
+        ));
+        assert!(protection
+            .before_signing_unit(SessionId(3), unit(18, b"next unit"))
+            .is_ok());
+    }
+
+    #[test]
+    fn forgets_old_sessions() {
+        let dir = TestDir::new();
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+        protection
+            .before_signing(SessionId(3))
+            .expect("should sign");
+        protection
+            .before_signing(SessionId(4 + REMEMBERED_SESSIONS))
+            .expect("should sign");
+        drop(protection);
+
+        let protection = SlashingProtection::open(dir.record_path()).expect("should open");
+        assert!(protection.earlier_record(SessionId(3)).is_none());
+        assert!(protection
+            .earlier_record(SessionId(4 + REMEMBERED_SESSIONS))
+            .is_some());
+    }
+
+    #[test]
+    fn refuses_corrupted_record() {
+        let dir = TestDir::new();
+        fs::write(dir.record_path(), [7, 7, 7]).expect("should write");
+        assert!(SlashingProtection::open(dir.record_path()).is_err());
+    }
+}
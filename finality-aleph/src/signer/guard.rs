@@ -0,0 +1,180 @@
+use std::{io::Result as IoResult, path::PathBuf};
+
+use crate::{
+    aleph_primitives::AuthorityId,
+    signer::{SignedUnit, SignedUnitsRecord, SigningContext},
+};
+
+/// Makes sure that a key never signs two different units of one round, nor as two different
+/// members of an AlephBFT committee. Either would be an equivocation, e.g. by two nodes running
+/// the same member, or by a node that restarted and lost its backup.
+///
+/// Units have to be signed in increasing rounds, only the newest one can be signed again, see
+/// [`SignedUnitsRecord`]. The record of signed units is kept in a file, so that it survives
+/// restarts.
+pub struct DoubleSignGuard {
+    record: SignedUnitsRecord<AuthorityId>,
+}
+
+impl DoubleSignGuard {
+    /// Keeps the record in the file at `path`, loading the record left by earlier runs.
+    pub fn open(path: PathBuf) -> IoResult<Self> {
+        Ok(DoubleSignGuard {
+            record: SignedUnitsRecord::open(path)?,
+        })
+    }
+
+    /// Keeps the record in memory only, so it does not protect against anything after a restart.
+    pub fn in_memory() -> Self {
+        DoubleSignGuard {
+            record: SignedUnitsRecord::in_memory(),
+        }
+    }
+
+    /// Checks whether the key may sign the message in the given context, and records the unit
+    /// before it is signed.
+    pub fn check(
+        &mut self,
+        key: &AuthorityId,
+        context: &SigningContext,
+        message: &[u8],
+    ) -> Result<(), String> {
+        match context {
+            SigningContext::Unit {
+                session,
+                creator,
+                round,
+            } => self
+                .record
+                .note_unit(
+                    key,
+                    *session,
+                    SignedUnit {
+                        creator: *creator,
+                        round: *round,
+                        hash: message.to_vec(),
+                    },
+                )
+                .map_err(|e| e.to_string()),
+            SigningContext::Other => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use rand::random;
+    use sp_core::{ed25519, Pair};
+
+    use super::DoubleSignGuard;
+    use crate::{
+        abft::NodeIndex,
+        aleph_primitives::AuthorityId,
+        signer::{record::REMEMBERED_SESSIONS, SigningContext},
+        SessionId,
+    };
+
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new() -> Self {
+            let path =
+                std::env::temp_dir().join(format!("aleph-signer-guard-test-{}", random::<u64>()));
+            fs::create_dir_all(&path).expect("should create the test directory");
+            TestDir(path)
+        }
+
+        fn record_path(&self) -> PathBuf {
+            self.0.join("signed-units")
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn key() -> AuthorityId {
+        ed25519::Pair::from_string("//Alice", None)
+            .expect("the seed is correct")
+            .public()
+            .into()
+    }
+
+    fn unit(session: u32, creator: usize, round: u16) -> SigningContext {
+        SigningContext::Unit {
+            session: SessionId(session),
+            creator: NodeIndex(creator),
+            round,
+        }
+    }
+
+    #[test]
+    fn allows_signing_units_of_increasing_rounds() {
+        let mut guard = DoubleSignGuard::in_memory();
+        assert!(guard.check(&key(), &unit(3, 1, 0), b"first").is_ok());
+        assert!(guard.check(&key(), &unit(3, 1, 1), b"second").is_ok());
+        assert!(guard.check(&key(), &unit(3, 1, 5), b"third").is_ok());
+        assert!(guard
+            .check(&key(), &unit(4, 2, 0), b"other session")
+            .is_ok());
+    }
+
+    #[test]
+    fn allows_signing_the_newest_unit_again() {
+        let mut guard = DoubleSignGuard::in_memory();
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"unit").is_ok());
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"unit").is_ok());
+    }
+
+    #[test]
+    fn refuses_different_unit_of_signed_round() {
+        let mut guard = DoubleSignGuard::in_memory();
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"unit").is_ok());
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"other unit").is_err());
+        assert!(guard.check(&key(), &unit(3, 1, 6), b"older unit").is_err());
+        assert!(guard
+            .check(&key(), &SigningContext::Other, b"other unit")
+            .is_ok());
+    }
+
+    #[test]
+    fn refuses_signing_as_two_members() {
+        let mut guard = DoubleSignGuard::in_memory();
+        assert!(guard.check(&key(), &unit(3, 1, 0), b"unit").is_ok());
+        assert!(guard.check(&key(), &unit(3, 2, 1), b"other unit").is_err());
+    }
+
+    #[test]
+    fn remembers_signed_units_after_restart() {
+        let dir = TestDir::new();
+        let mut guard = DoubleSignGuard::open(dir.record_path()).expect("should open");
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"unit").is_ok());
+        drop(guard);
+
+        let mut guard = DoubleSignGuard::open(dir.record_path()).expect("should open");
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"unit").is_ok());
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"other unit").is_err());
+        assert!(guard.check(&key(), &unit(3, 1, 8), b"next unit").is_ok());
+    }
+
+    #[test]
+    fn forgets_old_sessions() {
+        let mut guard = DoubleSignGuard::in_memory();
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"unit").is_ok());
+        assert!(guard
+            .check(&key(), &unit(4 + REMEMBERED_SESSIONS, 1, 0), b"new unit")
+            .is_ok());
+        assert!(guard.check(&key(), &unit(3, 1, 7), b"other unit").is_ok());
+    }
+
+    #[test]
+    fn refuses_corrupted_record() {
+        let dir = TestDir::new();
+        fs::write(dir.record_path(), [7, 7, 7]).expect("should write");
+        assert!(DoubleSignGuard::open(dir.record_path()).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    fmt::{Display, Error as FmtError, Formatter},
+    io::Error as IoError,
+    sync::Arc,
+};
+
+use current_aleph_bft::Round;
+use parity_scale_codec::{Decode, Encode, Error as CodecError};
+use sc_keystore::{Keystore, LocalKeystore};
+use sp_core::crypto::KeyTypeId;
+use sp_keystore::Error as KeystoreError;
+
+use crate::{
+    abft::NodeIndex,
+    aleph_primitives::{AuthorityId, AuthoritySignature, KEY_TYPE},
+    SessionId,
+};
+
+mod guard;
+pub(crate) mod record;
+mod remote;
+
+pub use guard::DoubleSignGuard;
+pub use record::{Error as RecordError, SessionRecord, SignedUnit, SignedUnitsRecord};
+pub use remote::{RemoteSigner, RemoteSignerServer};
+
+#[derive(Debug)]
+pub enum Error {
+    KeyMissing(AuthorityId),
+    Keystore(KeystoreError),
+    Io(IoError),
+    Codec(CodecError),
+    AuthenticationFailed,
+    Rejected(String),
+    UnexpectedResponse(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        use Error::*;
+        match self {
+            KeyMissing(auth_id) => {
+                write!(f, "no key for authority {auth_id:?}")
+            }
+            Keystore(err) => {
+                write!(f, "keystore error: {err}")
+            }
+            Io(err) => write!(f, "connection with the remote signer failed: {err}"),
+            Codec(err) => write!(f, "malformed message from the remote signer: {err}"),
+            AuthenticationFailed => write!(f, "the remote signer could not be authenticated"),
+            Rejected(reason) => write!(f, "the remote signer refused to sign: {reason}"),
+            UnexpectedResponse(response) => {
+                write!(f, "unexpected response from the remote signer: {response}")
+            }
+        }
+    }
+}
+
+impl From<IoError> for Error {
+    fn from(err: IoError) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<CodecError> for Error {
+    fn from(err: CodecError) -> Self {
+        Error::Codec(err)
+    }
+}
+
+/// What a signature is requested for.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum SigningContext {
+    /// Signing the hash of a unit of the given round, created as the given member of the
+    /// AlephBFT committee of the session. A key never signs two different units of one round,
+    /// nor as two members of one committee, see [`DoubleSignGuard`].
+    Unit {
+        session: SessionId,
+        creator: NodeIndex,
+        round: Round,
+    },
+    /// Anything that cannot lead to equivocations, e.g. authenticating in the validator network.
+    Other,
+}
+
+/// Signs messages with Aleph session keys, wherever they are kept.
+pub trait Signer: Send + Sync {
+    /// The keys this signer can sign with.
+    fn public_keys(&self) -> Result<Vec<AuthorityId>, Error>;
+
+    /// Signs the message with the given key.
+    fn sign(
+        &self,
+        key: &AuthorityId,
+        context: &SigningContext,
+        message: &[u8],
+    ) -> Result<AuthoritySignature, Error>;
+}
+
+/// Signs with the keys kept in the keystore of the node.
+#[derive(Clone)]
+pub struct LocalSigner {
+    keystore: Arc<LocalKeystore>,
+    key_type: KeyTypeId,
+}
+
+impl LocalSigner {
+    /// Signs with the keys of the aleph key type.
+    pub fn new(keystore: Arc<LocalKeystore>) -> Self {
+        Self::with_key_type(keystore, KEY_TYPE)
+    }
+
+    pub fn with_key_type(keystore: Arc<LocalKeystore>, key_type: KeyTypeId) -> Self {
+        LocalSigner { keystore, key_type }
+    }
+}
+
+impl Signer for LocalSigner {
+    fn public_keys(&self) -> Result<Vec<AuthorityId>, Error> {
+        Ok(self
+            .keystore
+            .ed25519_public_keys(self.key_type)
+            .into_iter()
+            .map(AuthorityId::from)
+            .collect())
+    }
+
+    fn sign(
+        &self,
+        key: &AuthorityId,
+        _context: &SigningContext,
+        message: &[u8],
+    ) -> Result<AuthoritySignature, Error> {
+        Ok(self
+            .keystore
+            .ed25519_sign(self.key_type, &key.clone().into(), message)
+            .map_err(Error::Keystore)?
+            .ok_or_else(|| Error::KeyMissing(key.clone()))?
+            .into())
+    }
+}
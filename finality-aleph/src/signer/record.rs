@@ -0,0 +1,190 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Error as FmtError, Formatter},
+    fs,
+    fs::File,
+    io::{Error as IoError, ErrorKind, Result as IoResult, Write},
+    path::PathBuf,
+};
+
+use current_aleph_bft::Round;
+use parity_scale_codec::{Decode, Encode};
+
+use crate::{abft::NodeIndex, SessionId};
+
+/// How many sessions before the newest recorded one the signed units are remembered for.
+pub const REMEMBERED_SESSIONS: u32 = 10;
+const TMP_FILE_EXTENSION: &str = "tmp";
+
+/// A unit signed in a session. It is recorded before it is signed, so it might not have been
+/// signed in the end.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct SignedUnit {
+    pub creator: NodeIndex,
+    pub round: Round,
+    pub hash: Vec<u8>,
+}
+
+/// What is known about the signatures made with a key in a session.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+pub struct SessionRecord {
+    /// The newest unit signed in the session, if any.
+    pub newest_unit: Option<SignedUnit>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The key already signed as the second member of the committee of the session, not as the
+    /// first one.
+    OtherMember(SessionId, NodeIndex, NodeIndex),
+    /// A different unit of the second round, at least as high as the first one, was already
+    /// signed in the session.
+    RoundAlreadySigned(SessionId, Round, Round),
+    IOError(IoError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        use Error::*;
+        match self {
+            OtherMember(session, creator, signed_creator) => write!(
+                f,
+                "refusing to sign as member {} of session {}, the key already signed as member {}",
+                creator.0, session.0, signed_creator.0
+            ),
+            RoundAlreadySigned(session, round, signed_round) => write!(
+                f,
+                "refusing to sign a unit of round {} in session {}, a unit of round {} was already signed",
+                round, session.0, signed_round
+            ),
+            IOError(err) => write!(f, "the record of signed units could not be saved: {err}"),
+        }
+    }
+}
+
+impl From<IoError> for Error {
+    fn from(err: IoError) -> Self {
+        Error::IOError(err)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A record of the sessions of the recent past in which every key signed, and the newest unit
+/// it signed in each of them, kept in a file so that it survives restarts.
+///
+/// A key signs units of a session as a single member of the committee, in increasing rounds,
+/// and only the newest unit can be signed again. Anything else could be an equivocation.
+pub struct SignedUnitsRecord<K> {
+    path: Option<PathBuf>,
+    sessions: BTreeMap<(K, u32), SessionRecord>,
+}
+
+impl<K: Clone + Ord + Encode + Decode> SignedUnitsRecord<K> {
+    /// Keeps the record in the file at `path`, loading the record left by earlier runs.
+    pub fn open(path: PathBuf) -> IoResult<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let sessions = match fs::read(&path) {
+            Ok(data) => Decode::decode(&mut &data[..]).map_err(|e| {
+                IoError::new(
+                    ErrorKind::InvalidData,
+                    format!("corrupted record of signed units at {path:?}: {e}"),
+                )
+            })?,
+            Err(e) if e.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(SignedUnitsRecord {
+            path: Some(path),
+            sessions,
+        })
+    }
+
+    /// Keeps the record in memory only, so it does not protect against anything after a restart.
+    pub fn in_memory() -> Self {
+        SignedUnitsRecord {
+            path: None,
+            sessions: BTreeMap::new(),
+        }
+    }
+
+    /// The sessions in which the key signed anything.
+    pub fn sessions<'a>(&'a self, key: &'a K) -> impl Iterator<Item = SessionId> + 'a {
+        self.sessions
+            .keys()
+            .filter(move |(signer, _)| signer == key)
+            .map(|(_, session)| SessionId(*session))
+    }
+
+    /// The record of the session, if the key signed anything in it.
+    pub fn session(&self, key: &K, session: SessionId) -> Option<&SessionRecord> {
+        self.sessions.get(&(key.clone(), session.0))
+    }
+
+    /// Records that the key signs something in the session.
+    pub fn note_session(&mut self, key: &K, session: SessionId) -> Result<(), Error> {
+        if self.session(key, session).is_some() {
+            return Ok(());
+        }
+        self.sessions
+            .insert((key.clone(), session.0), SessionRecord::default());
+        self.forget_old_and_save(session)
+    }
+
+    /// Records the unit before the key signs it, unless signing it could be an equivocation.
+    pub fn note_unit(
+        &mut self,
+        key: &K,
+        session: SessionId,
+        unit: SignedUnit,
+    ) -> Result<(), Error> {
+        if let Some(newest) = self
+            .session(key, session)
+            .and_then(|record| record.newest_unit.as_ref())
+        {
+            if newest.creator != unit.creator {
+                return Err(Error::OtherMember(session, unit.creator, newest.creator));
+            }
+            if newest.round > unit.round || (newest.round == unit.round && newest.hash != unit.hash)
+            {
+                return Err(Error::RoundAlreadySigned(session, unit.round, newest.round));
+            }
+            if newest.round == unit.round {
+                return Ok(());
+            }
+        }
+        self.sessions.insert(
+            (key.clone(), session.0),
+            SessionRecord {
+                newest_unit: Some(unit),
+            },
+        );
+        self.forget_old_and_save(session)
+    }
+
+    fn forget_old_and_save(&mut self, session: SessionId) -> Result<(), Error> {
+        self.sessions
+            .retain(|(_, recorded), _| recorded.saturating_add(REMEMBERED_SESSIONS) >= session.0);
+        self.save()?;
+        Ok(())
+    }
+
+    fn save(&self) -> IoResult<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        // Write to a temporary file first, so that a crash never leaves a torn record behind.
+        let tmp_path = path.with_extension(TMP_FILE_EXTENSION);
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&self.sessions.encode())?;
+        file.sync_all()?;
+        fs::rename(tmp_path, path)?;
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,397 @@
+use std::{
+    io::{Error as IoError, ErrorKind, Read, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use log::{debug, info, warn};
+use parity_scale_codec::{Decode, DecodeAll, Encode};
+use parking_lot::Mutex;
+use sp_core::hashing::blake2_256;
+use tokio::{
+    runtime::{Handle, RuntimeFlavor},
+    task::block_in_place,
+};
+
+use crate::{
+    aleph_primitives::{AuthorityId, AuthoritySignature},
+    signer::{DoubleSignGuard, Error, Signer, SigningContext},
+};
+
+const LOG_TARGET: &str = "aleph-signer";
+/// Messages are prefixed with their length, no honest message is longer than this.
+const MAX_MESSAGE_SIZE: u32 = 64 * 1024;
+/// How long we wait for the remote signer before giving up on a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Separates the proofs of this protocol from hashes of anything else.
+const PROOF_DOMAIN: &[u8] = b"aleph-remote-signer";
+
+type Nonce = [u8; 32];
+type Proof = [u8; 32];
+/// Identifies a node across its connections to the remote signer.
+type SignerId = u64;
+
+#[derive(Clone, Copy, Debug, Encode, Decode)]
+enum Role {
+    Node,
+    Signer,
+}
+
+/// Proves the knowledge of the shared secret, bound to the nonce chosen by the other side.
+fn proof(secret: &[u8], role: Role, nonce: &Nonce, instance: SignerId) -> Proof {
+    blake2_256(&(PROOF_DOMAIN, secret, role, nonce, instance).encode())
+}
+
+fn proofs_equal(left: &Proof, right: &Proof) -> bool {
+    left.iter()
+        .zip(right.iter())
+        .fold(0, |acc, (l, r)| acc | (l ^ r))
+        == 0
+}
+
+/// Opens the connection, `instance` identifies the node across its connections.
+#[derive(Encode, Decode)]
+struct NodeHello {
+    instance: SignerId,
+    nonce: Nonce,
+}
+
+/// Answers the `NodeHello`, after which the node sends its own `Proof`.
+#[derive(Encode, Decode)]
+struct SignerHello {
+    nonce: Nonce,
+    proof: Proof,
+}
+
+#[derive(Debug, Encode, Decode)]
+enum Request {
+    PublicKeys,
+    Sign {
+        key: AuthorityId,
+        context: SigningContext,
+        message: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Encode, Decode)]
+enum Response {
+    PublicKeys(Vec<AuthorityId>),
+    Signature(AuthoritySignature),
+    Rejected(String),
+}
+
+fn send<M: Encode>(stream: &mut UnixStream, message: &M) -> Result<(), Error> {
+    let bytes = message.encode();
+    stream.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    stream.write_all(&bytes)?;
+    Ok(())
+}
+
+fn receive<M: Decode>(stream: &mut UnixStream) -> Result<M, Error> {
+    let mut length = [0; 4];
+    stream.read_exact(&mut length)?;
+    let length = u32::from_le_bytes(length);
+    if length > MAX_MESSAGE_SIZE {
+        return Err(IoError::new(ErrorKind::InvalidData, "message too long").into());
+    }
+    let mut bytes = vec![0; length as usize];
+    stream.read_exact(&mut bytes)?;
+    Ok(M::decode_all(&mut bytes.as_slice())?)
+}
+
+/// Runs `f`, which blocks on the socket. Signatures are requested from within async tasks, so
+/// the executor thread we are on is told to hand its other tasks over first, if it can.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => block_in_place(f),
+        _ => f(),
+    }
+}
+
+/// Signs with keys kept by a separate process, listening on a Unix socket. Both sides prove
+/// that they know a shared secret before any signatures are requested.
+pub struct RemoteSigner {
+    path: PathBuf,
+    secret: Vec<u8>,
+    instance: SignerId,
+    connection: Mutex<Option<UnixStream>>,
+}
+
+impl RemoteSigner {
+    /// Connects to the signer listening at `path`, failing if it cannot be authenticated.
+    pub fn connect(path: PathBuf, secret: Vec<u8>) -> Result<Self, Error> {
+        let instance = rand::random();
+        let connection = Self::open(&path, &secret, instance)?;
+        info!(target: LOG_TARGET, "Connected to the remote signer at {:?}.", path);
+        Ok(RemoteSigner {
+            path,
+            secret,
+            instance,
+            connection: Mutex::new(Some(connection)),
+        })
+    }
+
+    fn open(path: &Path, secret: &[u8], instance: SignerId) -> Result<UnixStream, Error> {
+        let mut stream = UnixStream::connect(path)?;
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+        stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+        let nonce = rand::random();
+        send(&mut stream, &NodeHello { instance, nonce })?;
+        let hello: SignerHello = receive(&mut stream)?;
+        if !proofs_equal(&hello.proof, &proof(secret, Role::Signer, &nonce, instance)) {
+            return Err(Error::AuthenticationFailed);
+        }
+        send(
+            &mut stream,
+            &proof(secret, Role::Node, &hello.nonce, instance),
+        )?;
+        Ok(stream)
+    }
+
+    fn exchange(
+        &self,
+        connection: &mut Option<UnixStream>,
+        request: &Request,
+    ) -> Result<Response, Error> {
+        if connection.is_none() {
+            *connection = Some(Self::open(&self.path, &self.secret, self.instance)?);
+        }
+        let stream = connection.as_mut().expect("we just connected");
+        let result = send(stream, request).and_then(|_| receive(stream));
+        if result.is_err() {
+            *connection = None;
+        }
+        result
+    }
+
+    fn request(&self, request: Request) -> Result<Response, Error> {
+        blocking(|| {
+            let mut connection = self.connection.lock();
+            match self.exchange(&mut connection, &request) {
+                // The signer might have restarted, so we try again with a new connection.
+                Err(Error::Io(e)) => {
+                    warn!(target: LOG_TARGET, "Lost connection to the remote signer: {}, reconnecting.", e);
+                    self.exchange(&mut connection, &request)
+                }
+                result => result,
+            }
+        })
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn public_keys(&self) -> Result<Vec<AuthorityId>, Error> {
+        match self.request(Request::PublicKeys)? {
+            Response::PublicKeys(keys) => Ok(keys),
+            Response::Rejected(reason) => Err(Error::Rejected(reason)),
+            response => Err(Error::UnexpectedResponse(format!("{response:?}"))),
+        }
+    }
+
+    fn sign(
+        &self,
+        key: &AuthorityId,
+        context: &SigningContext,
+        message: &[u8],
+    ) -> Result<AuthoritySignature, Error> {
+        let request = Request::Sign {
+            key: key.clone(),
+            context: context.clone(),
+            message: message.to_vec(),
+        };
+        match self.request(request)? {
+            Response::Signature(signature) => Ok(signature),
+            Response::Rejected(reason) => Err(Error::Rejected(reason)),
+            response => Err(Error::UnexpectedResponse(format!("{response:?}"))),
+        }
+    }
+}
+
+/// The other side of [`RemoteSigner`], signing with the given signer for the nodes that know
+/// the shared secret, guarded by a [`DoubleSignGuard`]. The guard is shared by all the nodes,
+/// and never forgets a unit because the node that signed it disconnected.
+pub struct RemoteSignerServer<S: Signer> {
+    signer: S,
+    secret: Vec<u8>,
+    guard: Mutex<DoubleSignGuard>,
+}
+
+impl<S: Signer + 'static> RemoteSignerServer<S> {
+    pub fn new(signer: S, secret: Vec<u8>, guard: DoubleSignGuard) -> Self {
+        RemoteSignerServer {
+            signer,
+            secret,
+            guard: Mutex::new(guard),
+        }
+    }
+
+    /// Serves every connection to the listener in a separate thread. Only returns on errors of
+    /// the listener itself.
+    pub fn run(self: Arc<Self>, listener: UnixListener) -> Result<(), Error> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let server = self.clone();
+            thread::spawn(move || {
+                if let Err(e) = server.serve(stream) {
+                    debug!(target: LOG_TARGET, "Connection with a node failed: {}.", e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Serves a single connection until the node closes it.
+    pub fn serve(&self, mut stream: UnixStream) -> Result<(), Error> {
+        self.authenticate(&mut stream)?;
+        self.handle_requests(&mut stream)
+    }
+
+    fn authenticate(&self, stream: &mut UnixStream) -> Result<(), Error> {
+        let hello: NodeHello = receive(stream)?;
+        let nonce = rand::random();
+        send(
+            stream,
+            &SignerHello {
+                nonce,
+                proof: proof(&self.secret, Role::Signer, &hello.nonce, hello.instance),
+            },
+        )?;
+        let node_proof: Proof = receive(stream)?;
+        match proofs_equal(
+            &node_proof,
+            &proof(&self.secret, Role::Node, &nonce, hello.instance),
+        ) {
+            true => Ok(()),
+            false => Err(Error::AuthenticationFailed),
+        }
+    }
+
+    fn handle_requests(&self, stream: &mut UnixStream) -> Result<(), Error> {
+        loop {
+            let request = match receive(stream) {
+                Ok(request) => request,
+                Err(Error::Io(e)) if e.kind() == ErrorKind::UnexpectedEof => return Ok(()),
+                Err(e) => return Err(e),
+            };
+            send(stream, &self.handle(request))?;
+        }
+    }
+
+    fn handle(&self, request: Request) -> Response {
+        match request {
+            Request::PublicKeys => match self.signer.public_keys() {
+                Ok(keys) => Response::PublicKeys(keys),
+                Err(e) => Response::Rejected(e.to_string()),
+            },
+            Request::Sign {
+                key,
+                context,
+                message,
+            } => {
+                if let Err(reason) = self.guard.lock().check(&key, &context, &message) {
+                    warn!(target: LOG_TARGET, "Refused to sign with {:?}: {}.", key, reason);
+                    return Response::Rejected(reason);
+                }
+                match self.signer.sign(&key, &context, &message) {
+                    Ok(signature) => Response::Signature(signature),
+                    Err(e) => Response::Rejected(e.to_string()),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{os::unix::net::UnixListener, path::PathBuf, sync::Arc, thread, time::Duration};
+
+    use sc_keystore::LocalKeystore;
+    use sp_keystore::Keystore;
+    use sp_runtime::RuntimeAppPublic;
+
+    use super::{RemoteSigner, RemoteSignerServer};
+    use crate::{
+        abft::NodeIndex,
+        aleph_primitives::{AuthorityId, KEY_TYPE},
+        signer::{DoubleSignGuard, Error, LocalSigner, Signer, SigningContext},
+        SessionId,
+    };
+
+    const SECRET: &[u8] = b"very secret";
+
+    fn start_server() -> (PathBuf, AuthorityId) {
+        let keystore = Arc::new(LocalKeystore::in_memory());
+        let key = keystore
+            .ed25519_generate_new(KEY_TYPE, Some("//Alice"))
+            .expect("generating a key works")
+            .into();
+        let path =
+            std::env::temp_dir().join(format!("aleph-signer-{}.sock", rand::random::<u64>()));
+        let listener = UnixListener::bind(&path).expect("binding the socket works");
+        let server = Arc::new(RemoteSignerServer::new(
+            LocalSigner::new(keystore),
+            SECRET.to_vec(),
+            DoubleSignGuard::in_memory(),
+        ));
+        thread::spawn(move || server.run(listener));
+        (path, key)
+    }
+
+    fn unit_context() -> SigningContext {
+        SigningContext::Unit {
+            session: SessionId(7),
+            creator: NodeIndex(2),
+            round: 3,
+        }
+    }
+
+    #[test]
+    fn signs_with_remote_keys() {
+        let (path, key) = start_server();
+        let signer = RemoteSigner::connect(path, SECRET.to_vec()).expect("the secret is right");
+        assert_eq!(
+            signer.public_keys().expect("the signer works"),
+            vec![key.clone()]
+        );
+
+        let message = b"message";
+        let signature = signer
+            .sign(&key, &unit_context(), message)
+            .expect("the signer has the key");
+        assert!(key.verify(&message, &signature));
+    }
+
+    #[test]
+    fn does_not_connect_with_wrong_secret() {
+        let (path, _) = start_server();
+        assert!(matches!(
+            RemoteSigner::connect(path, b"wrong secret".to_vec()),
+            Err(Error::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn refuses_to_run_member_for_two_nodes() {
+        let (path, key) = start_server();
+        let first = RemoteSigner::connect(path.clone(), SECRET.to_vec()).expect("connects");
+        let second = RemoteSigner::connect(path.clone(), SECRET.to_vec()).expect("connects");
+        assert!(first.sign(&key, &unit_context(), b"unit").is_ok());
+        assert!(matches!(
+            second.sign(&key, &unit_context(), b"other unit"),
+            Err(Error::Rejected(_))
+        ));
+        assert!(second.sign(&key, &SigningContext::Other, b"other").is_ok());
+
+        drop(first);
+        // The unit stays claimed after the node that signed it disconnects.
+        thread::sleep(Duration::from_millis(100));
+        assert!(matches!(
+            second.sign(&key, &unit_context(), b"other unit"),
+            Err(Error::Rejected(_))
+        ));
+        assert!(second.sign(&key, &unit_context(), b"unit").is_ok());
+    }
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+use crate::{block::BlockId, justification::AlephJustification, SessionId};
+
+/// The AlephBFT session the node is currently running.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbftStatus {
+    pub session: SessionId,
+    /// Index of the node in the committee, `None` if it is not a member and only follows the
+    /// session.
+    pub node_index: Option<u32>,
+}
+
+/// The performance of the members of the AlephBFT committee, as seen by this node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbftScoreStatus {
+    pub session: SessionId,
+    /// For every member, ordered by their indices, by how many rounds its newest ordered unit is
+    /// behind the newest ordered unit overall.
+    pub rounds_behind: Vec<u16>,
+}
+
+/// What the validator network knows about a single member of a committee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitteeMemberStatus {
+    pub node_index: u32,
+    /// The validator network peer id of the member, if we received its authentication.
+    pub peer_id: Option<String>,
+}
+
+/// A committee of a session the validator network currently maintains.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitteeStatus {
+    pub session: SessionId,
+    pub node_count: u32,
+    /// Index of the node in the committee, `None` if it is not a member.
+    pub own_index: Option<u32>,
+    /// The other members of the committee, ordered by their indices.
+    pub members: Vec<CommitteeMemberStatus>,
+}
+
+/// The state of the block sync.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub finalized: BlockId,
+    pub imported: BlockId,
+    pub favourite: BlockId,
+    pub major_sync: bool,
+}
+
+/// A justification with the signatures replaced by the indices of the signers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum JustificationInfo {
+    CommitteeMultisignature {
+        committee_size: u32,
+        signers: Vec<u32>,
+    },
+    EmergencySignature,
+}
+
+impl From<AlephJustification> for JustificationInfo {
+    fn from(justification: AlephJustification) -> Self {
+        match justification {
+            AlephJustification::CommitteeMultisignature(signatures) => {
+                let mut signers: Vec<_> =
+                    signatures.iter().map(|(index, _)| index.0 as u32).collect();
+                signers.sort_unstable();
+                JustificationInfo::CommitteeMultisignature {
+                    committee_size: signatures.size().0 as u32,
+                    signers,
+                }
+            }
+            AlephJustification::EmergencySignature(_) => JustificationInfo::EmergencySignature,
+        }
+    }
+}
+
+/// Used by the finality components to publish their status.
+#[derive(Clone)]
+pub struct StatusReporter {
+    abft: Arc<watch::Sender<Option<AbftStatus>>>,
+    abft_score: Arc<watch::Sender<Option<AbftScoreStatus>>>,
+    committees: Arc<watch::Sender<Vec<CommitteeStatus>>>,
+    sync: Arc<watch::Sender<Option<SyncStatus>>>,
+}
+
+impl StatusReporter {
+    pub fn report_abft(&self, status: AbftStatus) {
+        self.abft.send_replace(Some(status));
+    }
+
+    pub fn report_abft_score(&self, status: AbftScoreStatus) {
+        self.abft_score.send_replace(Some(status));
+    }
+
+    pub fn report_committees(&self, status: Vec<CommitteeStatus>) {
+        self.committees.send_replace(status);
+    }
+
+    pub fn report_sync(&self, status: SyncStatus) {
+        self.sync.send_replace(Some(status));
+    }
+}
+
+/// The most recent status published by the finality components.
+#[derive(Clone)]
+pub struct NodeStatus {
+    abft: watch::Receiver<Option<AbftStatus>>,
+    abft_score: watch::Receiver<Option<AbftScoreStatus>>,
+    committees: watch::Receiver<Vec<CommitteeStatus>>,
+    sync: watch::Receiver<Option<SyncStatus>>,
+}
+
+impl NodeStatus {
+    pub fn abft(&self) -> Option<AbftStatus> {
+        self.abft.borrow().clone()
+    }
+
+    pub fn abft_score(&self) -> Option<AbftScoreStatus> {
+        self.abft_score.borrow().clone()
+    }
+
+    pub fn committees(&self) -> Vec<CommitteeStatus> {
+        self.committees.borrow().clone()
+    }
+
+    pub fn sync(&self) -> Option<SyncStatus> {
+        self.sync.borrow().clone()
+    }
+}
+
+/// Creates a channel through which the finality components report their status. Nothing is
+/// reported until the components start.
+pub fn status_channel() -> (StatusReporter, NodeStatus) {
+    let (abft_sender, abft) = watch::channel(None);
+    let (abft_score_sender, abft_score) = watch::channel(None);
+    let (committees_sender, committees) = watch::channel(Vec::new());
+    let (sync_sender, sync) = watch::channel(None);
+    (
+        StatusReporter {
+            abft: Arc::new(abft_sender),
+            abft_score: Arc::new(abft_score_sender),
+            committees: Arc::new(committees_sender),
+            sync: Arc::new(sync_sender),
+        },
+        NodeStatus {
+            abft,
+            abft_score,
+            committees,
+            sync,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use primitives::BlockHash;
+
+    use super::{status_channel, AbftScoreStatus, AbftStatus, CommitteeStatus, SyncStatus};
+    use crate::{BlockId, SessionId};
+
+    #[test]
+    fn reports_latest_status() {
+        let (reporter, status) = status_channel();
+        assert_eq!(status.abft(), None);
+        assert!(status.committees().is_empty());
+
+        reporter.report_abft(AbftStatus {
+            session: SessionId(1),
+            node_index: None,
+        });
+        reporter.report_abft(AbftStatus {
+            session: SessionId(2),
+            node_index: Some(3),
+        });
+        let committee = CommitteeStatus {
+            session: SessionId(2),
+            node_count: 4,
+            own_index: Some(3),
+            members: Vec::new(),
+        };
+        reporter.report_committees(vec![committee.clone()]);
+        let score = AbftScoreStatus {
+            session: SessionId(2),
+            rounds_behind: vec![0, 1, 0, 3],
+        };
+        reporter.report_abft_score(score.clone());
+
+        assert_eq!(
+            status.abft(),
+            Some(AbftStatus {
+                session: SessionId(2),
+                node_index: Some(3),
+            })
+        );
+        assert_eq!(status.committees(), vec![committee]);
+        assert_eq!(status.abft_score(), Some(score));
+        assert_eq!(status.sync(), None);
+
+        let sync = SyncStatus {
+            finalized: BlockId::new(BlockHash::repeat_byte(1), 10),
+            imported: BlockId::new(BlockHash::repeat_byte(2), 12),
+            favourite: BlockId::new(BlockHash::repeat_byte(2), 12),
+            major_sync: false,
+        };
+        reporter.report_sync(sync.clone());
+        assert_eq!(status.sync(), Some(sync));
+    }
+}
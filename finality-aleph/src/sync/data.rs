@@ -285,6 +285,9 @@ where
     V4(NetworkData<B, J>),
 }
 
+// The version of the oldest data we can interpret.
+const OLDEST_SUPPORTED_VERSION: u16 = 3;
+
 // We need 32 bits, since blocks can be quite sizeable.
 type ByteCount = u32;
 
@@ -379,6 +382,7 @@ where
     B: Block<UnverifiedHeader = UnverifiedHeaderFor<J>>,
 {
     inner: N,
+    malformed_data_senders: Vec<N::PeerId>,
     _phantom: PhantomData<(B, J)>,
 }
 
@@ -392,9 +396,15 @@ where
     pub fn new(inner: N) -> Self {
         VersionWrapper {
             inner,
+            malformed_data_senders: Vec::new(),
             _phantom: PhantomData,
         }
     }
+
+    /// The peers that sent us data which could not be interpreted since the last call.
+    pub fn take_malformed_data_senders(&mut self) -> Vec<N::PeerId> {
+        std::mem::take(&mut self.malformed_data_senders)
+    }
 }
 
 #[async_trait::async_trait]
@@ -438,6 +448,14 @@ where
         self.inner.broadcast(VersionedNetworkData::V4(data))
     }
 
+    fn ban(&mut self, peer_id: Self::PeerId) {
+        self.inner.ban(peer_id)
+    }
+
+    fn unban(&mut self, peer_id: Self::PeerId) {
+        self.inner.unban(peer_id)
+    }
+
     /// Retrieves next message from the network.
     ///
     /// # Cancel safety
@@ -446,18 +464,26 @@ where
     async fn next(&mut self) -> Result<(NetworkData<B, J>, Self::PeerId), Self::Error> {
         loop {
             match self.inner.next().await? {
-                (VersionedNetworkData::Other(version, _), _) => {
+                (VersionedNetworkData::Other(version, _), peer_id) => {
                     debug!(
                         target: LOG_TARGET,
                         "Received sync data of unsupported version {:?}.", version
-                    )
+                    );
+                    // Newer versions might come from upgraded honest nodes, but nobody should
+                    // be sending the ones we stopped supporting long ago.
+                    if version.0 < OLDEST_SUPPORTED_VERSION {
+                        self.malformed_data_senders.push(peer_id);
+                    }
                 }
                 (VersionedNetworkData::V3(data), peer_id) => match data.try_into() {
                     Ok(data) => return Ok((data, peer_id)),
-                    Err(()) => warn!(
-                        target: LOG_TARGET,
-                        "Received request with no header in target, this should never happen.",
-                    ),
+                    Err(()) => {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Received request with no header in target, this should never happen.",
+                        );
+                        self.malformed_data_senders.push(peer_id);
+                    }
                 },
                 (VersionedNetworkData::V4(data), peer_id) => return Ok((data, peer_id)),
             }
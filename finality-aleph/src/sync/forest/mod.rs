@@ -11,6 +11,7 @@ use static_assertions::const_assert;
 use crate::{
     aleph_primitives::DEFAULT_SESSION_PERIOD,
     block::{Block, ChainStatus, Header, Justification, UnverifiedHeaderFor},
+    status::SyncStatus,
     sync::{data::BranchKnowledge, BlockId, PeerId},
     BlockNumber,
 };
@@ -153,6 +154,22 @@ pub struct Status {
     favourite: BlockId,
 }
 
+impl Status {
+    pub fn into_sync_status(self, major_sync: bool) -> SyncStatus {
+        let Status {
+            finalized,
+            imported,
+            favourite,
+        } = self;
+        SyncStatus {
+            finalized,
+            imported,
+            favourite,
+            major_sync,
+        }
+    }
+}
+
 impl Display for Status {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         write!(
@@ -14,6 +14,7 @@ mod forest;
 mod handler;
 mod message_limiter;
 mod metrics;
+mod reputation;
 mod select_chain;
 mod service;
 mod task_queue;
@@ -52,5 +53,20 @@ pub trait RequestBlocks<UH: UnverifiedHeader>: Clone + Send + Sync + 'static {
     fn request_block(&self, header: UH) -> Result<(), Self::Error>;
 }
 
+/// An interface for reporting the blocks seen by the block sync to the SLO metrics.
+pub trait SloReporter: Send + 'static {
+    /// Report an imported block, and whether it is our new favourite block and our own.
+    fn report_block_imported(&mut self, block_id: BlockId, is_new_best: bool, own: bool);
+
+    /// Report a finalized block.
+    fn report_block_finalized(&self, block_id: BlockId);
+}
+
 #[cfg(test)]
 pub type MockPeerId = u32;
+
+#[cfg(test)]
+pub type MockNetworkData = data::VersionedNetworkData<
+    crate::block::mock::MockBlock,
+    crate::block::mock::MockJustification,
+>;
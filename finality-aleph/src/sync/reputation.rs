@@ -0,0 +1,261 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Error as FmtError, Formatter},
+};
+
+use crate::sync::PeerId;
+
+type Score = i32;
+
+/// The score every peer starts with, and to which scores return over time.
+const NEUTRAL_SCORE: Score = 0;
+/// The highest score a peer can earn with useful responses.
+const MAX_SCORE: Score = 50;
+/// Peers with scores below this are only contacted if there is nobody better.
+const POOR_SCORE: Score = -30;
+/// Peers with scores at or below this get banned.
+const BAN_SCORE: Score = -100;
+/// How much the scores move towards neutral on every tick.
+const RECOVERY_PER_TICK: Score = 1;
+/// How many ticks a ban lasts.
+const BAN_TICKS: u32 = 360;
+/// How much a useful response raises the score.
+const USEFUL_RESPONSE_REWARD: Score = 2;
+
+/// Ways in which a peer can waste our time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Misbehaviour {
+    /// Sent a header that failed verification.
+    InvalidHeader,
+    /// Sent a justification that failed verification.
+    BadJustification,
+    /// Sent a response larger than any honest node would send in one message.
+    OversizedResponse,
+    /// Sent a response with nothing in it.
+    EmptyResponse,
+    /// Sent data that could not be interpreted.
+    MalformedData,
+}
+
+impl Misbehaviour {
+    fn penalty(&self) -> Score {
+        use Misbehaviour::*;
+        match self {
+            // Honest peers might be ahead of us, or on a fork we do not know about, and send
+            // headers or justifications we cannot verify yet, so these are not punished too
+            // harshly. Headers are affected by every fork race, so even less.
+            InvalidHeader => 5,
+            BadJustification => 20,
+            OversizedResponse => 50,
+            EmptyResponse => 5,
+            MalformedData => 50,
+        }
+    }
+}
+
+impl Display for Misbehaviour {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        use Misbehaviour::*;
+        match self {
+            InvalidHeader => write!(f, "invalid header"),
+            BadJustification => write!(f, "bad justification"),
+            OversizedResponse => write!(f, "oversized response"),
+            EmptyResponse => write!(f, "empty response"),
+            MalformedData => write!(f, "malformed data"),
+        }
+    }
+}
+
+/// Changes to the set of banned peers resulting from updating the reputation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BanUpdate<I: PeerId> {
+    pub banned: Vec<I>,
+    pub unbanned: Vec<I>,
+}
+
+impl<I: PeerId> BanUpdate<I> {
+    fn new() -> Self {
+        BanUpdate {
+            banned: Vec::new(),
+            unbanned: Vec::new(),
+        }
+    }
+}
+
+/// Keeps track of how useful the peers we sync with are. Peers sending bad data are asked for
+/// data only if nobody else can be, and eventually get banned for a while. Not responding is not
+/// punished, as honest peers do not respond to requests they have nothing new for.
+pub struct Reputation<I: PeerId> {
+    scores: HashMap<I, Score>,
+    banned: HashMap<I, u32>,
+}
+
+impl<I: PeerId> Reputation<I> {
+    pub fn new() -> Self {
+        Reputation {
+            scores: HashMap::new(),
+            banned: HashMap::new(),
+        }
+    }
+
+    fn score(&self, peer: &I) -> Score {
+        self.scores.get(peer).copied().unwrap_or(NEUTRAL_SCORE)
+    }
+
+    /// Whether the peer is currently banned.
+    pub fn is_banned(&self, peer: &I) -> bool {
+        self.banned.contains_key(peer)
+    }
+
+    fn penalize(&mut self, peer: I, penalty: Score) -> Option<I> {
+        if self.is_banned(&peer) {
+            return None;
+        }
+        let score = self.score(&peer).saturating_sub(penalty);
+        match score <= BAN_SCORE {
+            true => {
+                self.scores.remove(&peer);
+                self.banned.insert(peer.clone(), BAN_TICKS);
+                Some(peer)
+            }
+            false => {
+                self.scores.insert(peer, score);
+                None
+            }
+        }
+    }
+
+    /// Lowers the score of the peer, returning it if it should be banned as a result.
+    pub fn report_misbehaviour(&mut self, peer: I, misbehaviour: Misbehaviour) -> Option<I> {
+        self.penalize(peer, misbehaviour.penalty())
+    }
+
+    /// Notes that the peer sent us a response, rewarding it if the response was useful.
+    pub fn report_response(&mut self, peer: I, useful: bool) {
+        if useful && !self.is_banned(&peer) {
+            let score = (self.score(&peer) + USEFUL_RESPONSE_REWARD).min(MAX_SCORE);
+            self.scores.insert(peer, score);
+        }
+    }
+
+    /// Restricts the peers to the ones we would prefer to send requests to, i.e. the ones that
+    /// are not banned and, if there are any, the ones with a decent score.
+    pub fn preferred(&self, peers: HashSet<I>) -> HashSet<I> {
+        let (decent, poor): (HashSet<_>, HashSet<_>) = peers
+            .into_iter()
+            .filter(|peer| !self.is_banned(peer))
+            .partition(|peer| self.score(peer) >= POOR_SCORE);
+        match decent.is_empty() {
+            true => poor,
+            false => decent,
+        }
+    }
+
+    /// Should be called periodically. Moves the scores towards neutral and lifts expired bans.
+    pub fn tick(&mut self) -> BanUpdate<I> {
+        let mut update = BanUpdate::new();
+        for score in self.scores.values_mut() {
+            *score = match *score < NEUTRAL_SCORE {
+                true => (*score + RECOVERY_PER_TICK).min(NEUTRAL_SCORE),
+                false => (*score - RECOVERY_PER_TICK).max(NEUTRAL_SCORE),
+            };
+        }
+        self.scores.retain(|_, score| *score != NEUTRAL_SCORE);
+
+        self.banned.retain(|peer, ticks_left| {
+            *ticks_left = ticks_left.saturating_sub(1);
+            if *ticks_left == 0 {
+                update.unbanned.push(peer.clone());
+            }
+            *ticks_left > 0
+        });
+        update
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{Misbehaviour, Reputation, BAN_TICKS};
+    use crate::sync::MockPeerId;
+
+    #[test]
+    fn bans_persistently_bad_peers() {
+        let mut reputation = Reputation::<MockPeerId>::new();
+        let mut banned = None;
+        for _ in 0..100 {
+            if let Some(peer) = reputation.report_misbehaviour(7, Misbehaviour::MalformedData) {
+                banned = Some(peer);
+                break;
+            }
+        }
+        assert_eq!(banned, Some(7));
+        assert!(reputation.is_banned(&7));
+        assert!(!reputation.is_banned(&3));
+    }
+
+    #[test]
+    fn lifts_bans_after_a_while() {
+        let mut reputation = Reputation::<MockPeerId>::new();
+        while reputation
+            .report_misbehaviour(7, Misbehaviour::MalformedData)
+            .is_none()
+        {}
+        for _ in 1..BAN_TICKS {
+            assert!(reputation.tick().unbanned.is_empty());
+        }
+        assert_eq!(reputation.tick().unbanned, vec![7]);
+        assert!(!reputation.is_banned(&7));
+    }
+
+    #[test]
+    fn prefers_peers_with_decent_scores() {
+        let mut reputation = Reputation::<MockPeerId>::new();
+        reputation.report_misbehaviour(1, Misbehaviour::MalformedData);
+        let peers = HashSet::from([1, 2]);
+        assert_eq!(reputation.preferred(peers), HashSet::from([2]));
+        assert_eq!(reputation.preferred(HashSet::from([1])), HashSet::from([1]));
+    }
+
+    #[test]
+    fn never_prefers_banned_peers() {
+        let mut reputation = Reputation::<MockPeerId>::new();
+        while reputation
+            .report_misbehaviour(1, Misbehaviour::MalformedData)
+            .is_none()
+        {}
+        assert!(reputation.preferred(HashSet::from([1])).is_empty());
+    }
+
+    #[test]
+    fn rewards_useful_responses() {
+        let mut reputation = Reputation::<MockPeerId>::new();
+        reputation.report_response(1, true);
+        reputation.report_response(2, false);
+        assert!(reputation.score(&1) > reputation.score(&2));
+    }
+
+    #[test]
+    fn survives_fork_races() {
+        let mut reputation = Reputation::<MockPeerId>::new();
+        for _ in 0..10 {
+            assert_eq!(
+                reputation.report_misbehaviour(1, Misbehaviour::InvalidHeader),
+                None
+            );
+        }
+        assert!(!reputation.is_banned(&1));
+    }
+
+    #[test]
+    fn scores_recover() {
+        let mut reputation = Reputation::<MockPeerId>::new();
+        reputation.report_misbehaviour(1, Misbehaviour::MalformedData);
+        assert_eq!(reputation.preferred(HashSet::from([1, 2])).len(), 1);
+        for _ in 0..50 {
+            reputation.tick();
+        }
+        assert_eq!(reputation.preferred(HashSet::from([1, 2])).len(), 2);
+    }
+}
@@ -6,6 +6,7 @@ use futures::{
     StreamExt,
 };
 use log::{debug, error, info, trace, warn};
+use parity_scale_codec::Encode;
 use substrate_prometheus_endpoint::Registry;
 use tokio::time;
 
@@ -15,22 +16,23 @@ use crate::{
         EquivocationProof, Finalizer, Header, HeaderVerifier, Justification, JustificationVerifier,
         UnverifiedHeader, UnverifiedHeaderFor,
     },
-    metrics::SloMetrics,
     network::GossipNetwork,
     session::SessionBoundaryInfo,
+    status::StatusReporter,
     sync::{
         data::{
             NetworkData, PreRequest, Request, ResponseItem, ResponseItems, State, VersionWrapper,
-            VersionedNetworkData,
+            VersionedNetworkData, MAX_SYNC_MESSAGE_SIZE,
         },
         forest::ExtensionRequest,
         handler::{Action, DatabaseIO, Error as HandlerError, HandleStateAction, Handler},
         message_limiter::{Error as MsgLimiterError, MsgLimiter},
         metrics::{Event, Metrics},
+        reputation::{BanUpdate, Misbehaviour, Reputation},
         task_queue::TaskQueue,
         tasks::{Action as TaskAction, RequestTask},
         ticker::Ticker,
-        BlockId, JustificationSubmissions, RequestBlocks, LOG_TARGET,
+        BlockId, JustificationSubmissions, RequestBlocks, SloReporter, LOG_TARGET,
     },
     SyncOracle, STATUS_REPORT_INTERVAL,
 };
@@ -38,6 +40,7 @@ use crate::{
 const BROADCAST_COOLDOWN: Duration = Duration::from_millis(600);
 const CHAIN_EXTENSION_COOLDOWN: Duration = Duration::from_millis(300);
 const TICK_PERIOD: Duration = Duration::from_secs(5);
+const REPUTATION_TICK_PERIOD: Duration = Duration::from_secs(10);
 
 pub struct IO<B, J, N, CE, CS, F, BI>
 where
@@ -122,7 +125,7 @@ where
 }
 
 /// A service synchronizing the knowledge about the chain between the nodes.
-pub struct Service<B, J, N, CE, CS, V, F, BI>
+pub struct Service<B, J, N, CE, CS, V, F, BI, SM>
 where
     J: Justification,
     B: Block<UnverifiedHeader = UnverifiedHeaderFor<J>>,
@@ -132,6 +135,7 @@ where
     V: JustificationVerifier<J> + HeaderVerifier<J::Header>,
     F: Finalizer<J>,
     BI: BlockImport<B>,
+    SM: SloReporter,
 {
     network: VersionWrapper<B, J, N>,
     handler: Handler<B, N::PeerId, J, CS, V, F, BI>,
@@ -144,8 +148,11 @@ where
     blocks_from_creator: mpsc::UnboundedReceiver<B>,
     major_sync_last_status: bool,
     metrics: Metrics,
-    slo_metrics: SloMetrics,
+    slo_metrics: SM,
     favourite_block_request: mpsc::UnboundedReceiver<oneshot::Sender<J::Header>>,
+    equivocation_reports: mpsc::UnboundedSender<V::EquivocationProof>,
+    status_reporter: StatusReporter,
+    reputation: Reputation<N::PeerId>,
 }
 
 impl<J: Justification> JustificationSubmissions<J> for mpsc::UnboundedSender<J::Unverified> {
@@ -164,7 +171,7 @@ impl<UH: UnverifiedHeader> RequestBlocks<UH> for mpsc::UnboundedSender<UH> {
     }
 }
 
-impl<B, J, N, CE, CS, V, F, BI> Service<B, J, N, CE, CS, V, F, BI>
+impl<B, J, N, CE, CS, V, F, BI, SM> Service<B, J, N, CE, CS, V, F, BI, SM>
 where
     J: Justification,
     B: Block<UnverifiedHeader = UnverifiedHeaderFor<J>>,
@@ -174,16 +181,20 @@ where
     V: JustificationVerifier<J> + HeaderVerifier<J::Header>,
     F: Finalizer<J>,
     BI: BlockImport<B>,
+    SM: SloReporter,
 {
     /// Create a new service using the provided network for communication.
+    /// Detected equivocations get sent to `equivocation_reports`.
     /// Also returns an interface for requesting blocks.
     pub fn new(
         verifier: V,
         session_info: SessionBoundaryInfo,
         io: IO<B, J, N, CE, CS, F, BI>,
         metrics_registry: Option<Registry>,
-        slo_metrics: SloMetrics,
+        slo_metrics: SM,
         favourite_block_request: mpsc::UnboundedReceiver<oneshot::Sender<J::Header>>,
+        equivocation_reports: mpsc::UnboundedSender<V::EquivocationProof>,
+        status_reporter: StatusReporter,
     ) -> Result<(Self, impl RequestBlocks<B::UnverifiedHeader>), HandlerError<B, J, CS, V, F>> {
         let IO {
             network,
@@ -219,6 +230,9 @@ where
                 metrics,
                 slo_metrics,
                 favourite_block_request,
+                equivocation_reports,
+                status_reporter,
+                reputation: Reputation::new(),
             },
             block_requests_for_sync,
         ))
@@ -269,6 +283,7 @@ where
                 return;
             }
         };
+        let know_most = self.reputation.preferred(know_most);
         match self.network.send_to_random(data, know_most) {
             Ok(()) => self.chain_extension_ticker.reset(),
             Err(e) => {
@@ -328,12 +343,60 @@ where
         trace!(target: LOG_TARGET, "Sending a request: {:?}", request);
         let data = NetworkData::Request(request);
 
+        let peers = self.reputation.preferred(peers);
         if let Err(e) = self.network.send_to_random(data, peers) {
             self.metrics.report_event_error(Event::SendRequest);
             warn!(target: LOG_TARGET, "Error sending request: {}.", e);
         }
     }
 
+    fn apply_ban_update(&mut self, update: BanUpdate<N::PeerId>) {
+        let BanUpdate { banned, unbanned } = update;
+        for peer in banned {
+            warn!(
+                target: LOG_TARGET,
+                "Banning peer {:?} for persistently sending bad data.", peer
+            );
+            self.network.ban(peer);
+        }
+        for peer in unbanned {
+            info!(target: LOG_TARGET, "Lifting the ban on peer {:?}.", peer);
+            self.network.unban(peer);
+        }
+    }
+
+    fn report_misbehaviour(&mut self, peer: N::PeerId, misbehaviour: Misbehaviour) {
+        debug!(
+            target: LOG_TARGET,
+            "Peer {:?} misbehaved: {}.", peer, misbehaviour
+        );
+        if let Some(peer) = self.reputation.report_misbehaviour(peer, misbehaviour) {
+            self.apply_ban_update(BanUpdate {
+                banned: vec![peer],
+                unbanned: Vec::new(),
+            });
+        }
+    }
+
+    fn report_handler_error(&mut self, error: &HandlerError<B, J, CS, V, F>, peer: N::PeerId) {
+        match error {
+            HandlerError::JustificationVerifier(_) => {
+                self.report_misbehaviour(peer, Misbehaviour::BadJustification)
+            }
+            HandlerError::HeaderVerifier(_) => {
+                self.report_misbehaviour(peer, Misbehaviour::InvalidHeader)
+            }
+            // Other errors are either our own problems, or can be caused by honest peers
+            // seeing a slightly different state of the chain.
+            _ => (),
+        }
+    }
+
+    fn handle_reputation_tick(&mut self) {
+        let update = self.reputation.tick();
+        self.apply_ban_update(update);
+    }
+
     fn send_to(&mut self, data: NetworkData<B, J>, peer: N::PeerId) {
         self.metrics.report_event(Event::SendTo);
         trace!(
@@ -354,6 +417,12 @@ where
             if proof.are_we_equivocating() {
                 panic!("We are equivocating, which is ILLEGAL - shutting down the node. This is probably caused by running two instances of the node with the same set of credentials. Make sure that you are running ONLY ONE instance of the node. If the problem persists, contact the Aleph Zero developers on Discord.");
             }
+            if let Err(e) = self.equivocation_reports.unbounded_send(proof) {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to pass equivocation proof for reporting: {}.", e
+                );
+            }
         }
     }
 
@@ -377,6 +446,7 @@ where
             }
             Err(e) => {
                 self.metrics.report_event_error(Event::HandleState);
+                self.report_handler_error(&e, peer.clone());
                 match e {
                     HandlerError::JustificationVerifier(e) => debug!(
                         target: LOG_TARGET,
@@ -412,6 +482,9 @@ where
         let (new_info, maybe_error) =
             self.handler
                 .handle_state_response(justification, maybe_justification, peer.clone());
+        if let Some(e) = &maybe_error {
+            self.report_handler_error(e, peer.clone());
+        }
         match maybe_error {
             Some(HandlerError::JustificationVerifier(e)) => debug!(
                 target: LOG_TARGET,
@@ -476,9 +549,21 @@ where
             response_items,
         );
         self.metrics.report_event(Event::HandleRequestResponse);
+        if response_items.is_empty() {
+            self.report_misbehaviour(peer, Misbehaviour::EmptyResponse);
+            return;
+        }
+        if response_items.encoded_size() > MAX_SYNC_MESSAGE_SIZE as usize {
+            self.report_misbehaviour(peer, Misbehaviour::OversizedResponse);
+            return;
+        }
         let (new_info, equivocation_proofs, maybe_error) = self
             .handler
             .handle_request_response(response_items, peer.clone());
+        match &maybe_error {
+            Some(e) => self.report_handler_error(e, peer.clone()),
+            None => self.reputation.report_response(peer.clone(), new_info),
+        }
         match maybe_error {
             Some(HandlerError::JustificationVerifier(e)) => {
                 debug!(
@@ -678,6 +763,7 @@ where
             Err(e) => {
                 self.metrics
                     .report_event_error(Event::HandleExtensionRequest);
+                self.report_handler_error(&e, peer.clone());
                 match e {
                     HandlerError::JustificationVerifier(e) => debug!(
                         target: LOG_TARGET,
@@ -748,6 +834,14 @@ where
         self.major_sync_last_status = new_status;
     }
 
+    fn report_status(&self) {
+        self.status_reporter.report_sync(
+            self.handler
+                .status()
+                .into_sync_status(self.major_sync_last_status),
+        );
+    }
+
     /// Stay synchronized.
     pub async fn run(mut self) -> Result<(), Error<N::Error, CE::Error>> {
         if self.blocks_from_creator.is_terminated() {
@@ -755,12 +849,16 @@ where
         }
 
         let mut status_ticker = time::interval(STATUS_REPORT_INTERVAL);
+        let mut reputation_ticker = time::interval(REPUTATION_TICK_PERIOD);
         loop {
             self.report_sync_state_change();
 
             tokio::select! {
                 maybe_data = self.network.next() => {
                     let (data, peer) = maybe_data.map_err(Error::Network)?;
+                    for peer in self.network.take_malformed_data_senders() {
+                        self.report_misbehaviour(peer, Misbehaviour::MalformedData);
+                    }
                     self.handle_network_data(data, peer);
                 },
 
@@ -773,6 +871,7 @@ where
                 maybe_event = self.chain_events.next() => {
                     let chain_event = maybe_event.map_err(Error::ChainEvent)?;
                     self.handle_chain_event(chain_event);
+                    self.report_status();
                 },
 
                 maybe_justification = self.justifications_from_user.next() => {
@@ -799,8 +898,11 @@ where
                     self.send_favourite_block(favourite_block_sender);
                 }
 
+                _ = reputation_ticker.tick() => self.handle_reputation_tick(),
+
                 _ = status_ticker.tick() => {
                     info!(target: LOG_TARGET, "{}", self.handler.status());
+                    self.report_status();
                 },
             }
         }
@@ -1,3 +1,4 @@
 pub mod client_chain_builder;
 mod data_store;
 pub mod mocks;
+pub mod sync_simulation;
@@ -0,0 +1,214 @@
+//! A deterministic simulation of the block sync of many nodes running in a single process.
+//!
+//! Every node runs the real block sync on top of a mock backend, and the nodes communicate over
+//! a [`SimulatedNetwork`] that can delay, drop and corrupt messages, or partition the nodes. All
+//! the randomness comes from a single seed, and all the timers are tokio timers, so with the
+//! clock paused (`#[tokio::test(start_paused = true)]`) a simulation of many minutes takes
+//! milliseconds and runs the same way every time.
+//!
+//! Only the block sync is under test. AlephBFT and the aggregator are not run, justifications
+//! come from an oracle with a global view of all the nodes instead, see
+//! [`SyncSimulation::finalize_by_oracle`]. So the simulation shows how the sync spreads blocks
+//! and justifications, but says nothing about whether the consensus would finalize them.
+//!
+//! This is not the full-node simulation asked for originally. The session manager, the AlephBFT
+//! party and the aggregator depend on the substrate client, runtime API and task manager, and
+//! running them here would first need them to be made generic over those. Until then, stalls
+//! caused by the consensus or by session changes cannot be simulated, only by the sync.
+
+use std::time::Duration;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::time::{sleep, Instant};
+
+use crate::{
+    block::{mock::MockHeader, Header},
+    session::SessionBoundaryInfo,
+    sync::{MockNetworkData, MockPeerId},
+    BlockHash, BlockNumber, SessionPeriod,
+};
+
+mod network;
+mod node;
+mod tests;
+
+pub use network::{Behaviour, LinkConfig, SimulatedNetwork};
+pub use node::Node;
+
+const POLL_PERIOD: Duration = Duration::from_millis(100);
+
+/// The setup of a block sync simulation.
+#[derive(Clone, Debug)]
+pub struct SyncSimulationConfig {
+    pub nodes: usize,
+    pub seed: u64,
+    pub session_period: SessionPeriod,
+    pub link: LinkConfig,
+}
+
+impl Default for SyncSimulationConfig {
+    fn default() -> Self {
+        SyncSimulationConfig {
+            nodes: 4,
+            seed: 0,
+            session_period: SessionPeriod(20),
+            link: LinkConfig::default(),
+        }
+    }
+}
+
+pub struct SyncSimulation {
+    nodes: Vec<Node>,
+    network: SimulatedNetwork<MockNetworkData>,
+    rng: StdRng,
+    session_info: SessionBoundaryInfo,
+}
+
+impl SyncSimulation {
+    /// Starts all the nodes, has to be called within a tokio runtime.
+    pub fn new(config: SyncSimulationConfig) -> Self {
+        let SyncSimulationConfig {
+            nodes,
+            seed,
+            session_period,
+            link,
+        } = config;
+        let session_info = SessionBoundaryInfo::new(session_period);
+        let network = SimulatedNetwork::new(seed, link);
+        let nodes = (0..nodes as MockPeerId)
+            .map(|id| Node::spawn(id, session_info.clone(), &network))
+            .collect();
+        SyncSimulation {
+            nodes,
+            network,
+            // Offset the seed, so that block hashes are not correlated with the network.
+            rng: StdRng::seed_from_u64(seed.wrapping_add(1)),
+            session_info,
+        }
+    }
+
+    pub fn node(&self, id: MockPeerId) -> &Node {
+        &self.nodes[id as usize]
+    }
+
+    pub fn network(&self) -> &SimulatedNetwork<MockNetworkData> {
+        &self.network
+    }
+
+    /// Lets the simulation run for the given time.
+    pub async fn run_for(&self, duration: Duration) {
+        sleep(duration).await;
+    }
+
+    /// Makes the node produce `count` blocks on top of its favourite block.
+    pub async fn produce_blocks(&mut self, id: MockPeerId, count: usize) -> Vec<MockHeader> {
+        let mut parent = self.node(id).favourite_block().await;
+        let mut result = Vec::with_capacity(count);
+        for _ in 0..count {
+            let hash = BlockHash::from(self.rng.gen::<[u8; 32]>());
+            parent = self.node(id).produce_block(&parent, hash);
+            result.push(parent.clone());
+        }
+        result
+    }
+
+    /// Nodes that count towards finalization, i.e. running nodes that are honest.
+    fn honest_nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter().filter(|node| {
+            node.is_running() && self.network.behaviour(node.id()) == Behaviour::Honest
+        })
+    }
+
+    /// Finalizes blocks by looking at all the nodes at once, standing in for AlephBFT and the
+    /// aggregator. The highest block that more than two thirds of all the nodes have on the chain
+    /// of their favourite block is finalized, if it is higher than what was finalized already.
+    /// Only running honest nodes count, and they have to be able to communicate with each other.
+    /// The justifications of the block and of the last blocks of the sessions it closes are
+    /// submitted to all the nodes that count.
+    ///
+    /// Returns the finalized block, if any.
+    pub async fn finalize_by_oracle(&self) -> Option<MockHeader> {
+        let quorum = 2 * self.nodes.len() / 3 + 1;
+        let mut favourites = Vec::new();
+        for node in self.honest_nodes() {
+            favourites.push((node, node.favourite_block().await));
+        }
+        let top_finalized = favourites
+            .iter()
+            .map(|(node, _)| node.top_finalized().id().number())
+            .max()?;
+        let mut best: Option<(MockHeader, Vec<&Node>)> = None;
+        for (candidate_node, favourite) in &favourites {
+            let mut candidate = favourite.clone();
+            while candidate.id().number() > top_finalized {
+                if best.as_ref().map_or(false, |(best, _)| {
+                    best.id().number() >= candidate.id().number()
+                }) {
+                    break;
+                }
+                let supporters: Vec<_> = favourites
+                    .iter()
+                    .filter(|(node, favourite)| node.is_ancestor(&candidate.id(), favourite))
+                    .map(|(node, _)| *node)
+                    .collect();
+                if supporters.len() >= quorum && self.connected(&supporters) {
+                    best = Some((candidate, supporters));
+                    break;
+                }
+                candidate = match candidate_node.parent_of(&candidate) {
+                    Some(parent) => parent,
+                    None => break,
+                };
+            }
+        }
+        let (block, supporters) = best?;
+        let mut justified = vec![block.clone()];
+        let mut header = block.clone();
+        while header.id().number() > top_finalized + 1 {
+            header = supporters[0]
+                .parent_of(&header)
+                .expect("supporters have the whole chain");
+            if self.is_last_of_session(header.id().number()) {
+                justified.push(header.clone());
+            }
+        }
+        for node in supporters {
+            for header in justified.iter().rev() {
+                node.submit_justification(header.clone());
+            }
+        }
+        Some(block)
+    }
+
+    /// Waits until all the running nodes finalize the block with the given number, or the time
+    /// runs out. Returns whether they did.
+    pub async fn wait_for_finalized(&self, number: BlockNumber, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if self
+                .nodes
+                .iter()
+                .filter(|node| node.is_running())
+                .all(|node| node.top_finalized().id().number() >= number)
+            {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            sleep(POLL_PERIOD).await;
+        }
+    }
+
+    fn connected(&self, nodes: &[&Node]) -> bool {
+        let ids: Vec<_> = nodes.iter().map(|node| node.id()).collect();
+        ids.iter()
+            .all(|id| self.network.can_communicate(ids[0], *id))
+    }
+
+    fn is_last_of_session(&self, number: BlockNumber) -> bool {
+        self.session_info
+            .last_block_of_session(self.session_info.session_id_from_block_num(number))
+            == number
+    }
+}
@@ -0,0 +1,422 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    StreamExt,
+};
+use parity_scale_codec::{Decode, Encode};
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, seq::IteratorRandom, Rng, SeedableRng};
+use tokio::time::sleep;
+
+use crate::{
+    network::{Data, GossipNetwork},
+    sync::MockPeerId,
+};
+
+/// How many of its recent messages a replaying peer remembers.
+const REPLAY_BUFFER_SIZE: usize = 16;
+
+/// Properties of all the links between the nodes.
+#[derive(Clone, Copy, Debug)]
+pub struct LinkConfig {
+    pub min_latency: Duration,
+    pub max_latency: Duration,
+    /// The probability of a message getting lost.
+    pub drop_rate: f64,
+}
+
+impl Default for LinkConfig {
+    fn default() -> Self {
+        LinkConfig {
+            min_latency: Duration::from_millis(10),
+            max_latency: Duration::from_millis(100),
+            drop_rate: 0.0,
+        }
+    }
+}
+
+/// How a node treats the messages it sends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Behaviour {
+    #[default]
+    Honest,
+    /// Never sends anything.
+    Silent,
+    /// Sends a random one of its earlier messages together with every message.
+    Replaying,
+    /// Flips a random bit in every message, drops the ones that no longer decode.
+    Corrupting,
+}
+
+struct State<D: Data> {
+    rng: StdRng,
+    link: LinkConfig,
+    /// The group of every node, nodes in different groups cannot communicate.
+    groups: HashMap<MockPeerId, usize>,
+    behaviours: HashMap<MockPeerId, Behaviour>,
+    inboxes: HashMap<MockPeerId, UnboundedSender<(D, MockPeerId)>>,
+    sent: HashMap<MockPeerId, VecDeque<D>>,
+    /// The peers every node currently bans.
+    bans: HashMap<MockPeerId, HashSet<MockPeerId>>,
+}
+
+/// An in-memory network between the nodes of a simulation. All the randomness, i.e. latencies,
+/// drops, random recipients and Byzantine behaviour, comes from a single seeded generator.
+/// Delays are measured with the tokio clock, so they take no time with the clock paused.
+pub struct SimulatedNetwork<D: Data> {
+    state: Arc<Mutex<State<D>>>,
+}
+
+impl<D: Data> Clone for SimulatedNetwork<D> {
+    fn clone(&self) -> Self {
+        SimulatedNetwork {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<D: Data> SimulatedNetwork<D> {
+    pub fn new(seed: u64, link: LinkConfig) -> Self {
+        SimulatedNetwork {
+            state: Arc::new(Mutex::new(State {
+                rng: StdRng::seed_from_u64(seed),
+                link,
+                groups: HashMap::new(),
+                behaviours: HashMap::new(),
+                inboxes: HashMap::new(),
+                sent: HashMap::new(),
+                bans: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Connects a node to the network, replacing its earlier connection if there was one.
+    pub fn connect(&self, peer_id: MockPeerId) -> NodeNetwork<D> {
+        let (inbox, messages) = mpsc::unbounded();
+        self.state.lock().inboxes.insert(peer_id, inbox);
+        NodeNetwork {
+            peer_id,
+            network: self.clone(),
+            messages,
+        }
+    }
+
+    /// Disconnects a node, messages to it are lost until it connects again.
+    pub fn disconnect(&self, peer_id: MockPeerId) {
+        self.state.lock().inboxes.remove(&peer_id);
+    }
+
+    pub fn set_link(&self, link: LinkConfig) {
+        self.state.lock().link = link;
+    }
+
+    pub fn set_behaviour(&self, peer_id: MockPeerId, behaviour: Behaviour) {
+        self.state.lock().behaviours.insert(peer_id, behaviour);
+    }
+
+    pub fn behaviour(&self, peer_id: MockPeerId) -> Behaviour {
+        self.state
+            .lock()
+            .behaviours
+            .get(&peer_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Splits the network, so that only nodes within the same group can communicate. Nodes not
+    /// in any of the groups can communicate only with each other.
+    pub fn partition(&self, groups: &[&[MockPeerId]]) {
+        let mut state = self.state.lock();
+        state.groups = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(group, peers)| peers.iter().map(move |peer| (*peer, group + 1)))
+            .collect();
+    }
+
+    /// Removes all the partitions.
+    pub fn heal(&self) {
+        self.state.lock().groups.clear();
+    }
+
+    /// The peers the node currently bans.
+    pub fn banned_by(&self, peer_id: MockPeerId) -> HashSet<MockPeerId> {
+        self.state
+            .lock()
+            .bans
+            .get(&peer_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn is_banned(&self, by: MockPeerId, peer_id: MockPeerId) -> bool {
+        self.state
+            .lock()
+            .bans
+            .get(&by)
+            .map_or(false, |bans| bans.contains(&peer_id))
+    }
+
+    /// Whether the nodes are not separated by a partition.
+    pub fn can_communicate(&self, first: MockPeerId, second: MockPeerId) -> bool {
+        let state = self.state.lock();
+        state.groups.get(&first) == state.groups.get(&second)
+    }
+
+    fn peers(&self) -> Vec<MockPeerId> {
+        let mut peers: Vec<_> = self.state.lock().inboxes.keys().copied().collect();
+        peers.sort_unstable();
+        peers
+    }
+
+    fn random_peer(&self, candidates: impl Iterator<Item = MockPeerId>) -> Option<MockPeerId> {
+        let mut candidates: Vec<_> = candidates.collect();
+        candidates.sort_unstable();
+        candidates.into_iter().choose(&mut self.state.lock().rng)
+    }
+
+    fn send(&self, from: MockPeerId, to: MockPeerId, data: D) {
+        if from == to {
+            return;
+        }
+        let mut state = self.state.lock();
+        let state = &mut *state;
+        let mut messages = Vec::new();
+        match state.behaviours.get(&from).copied().unwrap_or_default() {
+            Behaviour::Honest => messages.push(data),
+            Behaviour::Silent => (),
+            Behaviour::Replaying => {
+                let sent = state.sent.entry(from).or_default();
+                sent.push_back(data.clone());
+                if sent.len() > REPLAY_BUFFER_SIZE {
+                    sent.pop_front();
+                }
+                let index = state.rng.gen_range(0..sent.len());
+                messages.push(sent[index].clone());
+                messages.push(data);
+            }
+            Behaviour::Corrupting => {
+                let mut encoded = data.encode();
+                if !encoded.is_empty() {
+                    let index = state.rng.gen_range(0..encoded.len());
+                    encoded[index] ^= 1 << state.rng.gen_range(0..8u32);
+                }
+                if let Ok(corrupted) = D::decode(&mut &encoded[..]) {
+                    messages.push(corrupted);
+                }
+            }
+        }
+        if state.groups.get(&from) != state.groups.get(&to) {
+            return;
+        }
+        for message in messages {
+            let link = state.link;
+            if state.rng.gen_bool(link.drop_rate) {
+                continue;
+            }
+            let latency = state.rng.gen_range(link.min_latency..=link.max_latency);
+            let network = self.clone();
+            tokio::spawn(async move {
+                sleep(latency).await;
+                if let Some(inbox) = network.state.lock().inboxes.get(&to) {
+                    let _ = inbox.unbounded_send((message, from));
+                }
+            });
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NetworkClosed;
+
+impl Display for NetworkClosed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "the simulated network was closed")
+    }
+}
+
+/// The connection of a single node to the simulated network.
+pub struct NodeNetwork<D: Data> {
+    peer_id: MockPeerId,
+    network: SimulatedNetwork<D>,
+    messages: UnboundedReceiver<(D, MockPeerId)>,
+}
+
+impl<D: Data> NodeNetwork<D> {
+    fn is_banned(&self, peer_id: MockPeerId) -> bool {
+        self.network.is_banned(self.peer_id, peer_id)
+    }
+}
+
+#[async_trait::async_trait]
+impl<D: Data> GossipNetwork<D> for NodeNetwork<D> {
+    type Error = NetworkClosed;
+    type PeerId = MockPeerId;
+
+    fn send_to(&mut self, data: D, peer_id: Self::PeerId) -> Result<(), Self::Error> {
+        if !self.is_banned(peer_id) {
+            self.network.send(self.peer_id, peer_id, data);
+        }
+        Ok(())
+    }
+
+    fn send_to_random(
+        &mut self,
+        data: D,
+        peer_ids: HashSet<Self::PeerId>,
+    ) -> Result<(), Self::Error> {
+        let allowed = |peer_id: &MockPeerId| *peer_id != self.peer_id && !self.is_banned(*peer_id);
+        let recipient = match self
+            .network
+            .random_peer(peer_ids.into_iter().filter(allowed))
+        {
+            Some(peer_id) => Some(peer_id),
+            None => self
+                .network
+                .random_peer(self.network.peers().into_iter().filter(allowed)),
+        };
+        if let Some(peer_id) = recipient {
+            self.network.send(self.peer_id, peer_id, data);
+        }
+        Ok(())
+    }
+
+    fn broadcast(&mut self, data: D) -> Result<(), Self::Error> {
+        for peer_id in self.network.peers() {
+            if !self.is_banned(peer_id) {
+                self.network.send(self.peer_id, peer_id, data.clone());
+            }
+        }
+        Ok(())
+    }
+
+    fn ban(&mut self, peer_id: Self::PeerId) {
+        let mut state = self.network.state.lock();
+        state.bans.entry(self.peer_id).or_default().insert(peer_id);
+    }
+
+    fn unban(&mut self, peer_id: Self::PeerId) {
+        let mut state = self.network.state.lock();
+        if let Some(bans) = state.bans.get_mut(&self.peer_id) {
+            bans.remove(&peer_id);
+        }
+    }
+
+    async fn next(&mut self) -> Result<(D, Self::PeerId), Self::Error> {
+        loop {
+            let (data, peer_id) = self.messages.next().await.ok_or(NetworkClosed)?;
+            if !self.is_banned(peer_id) {
+                return Ok((data, peer_id));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, time::Duration};
+
+    use tokio::time::{timeout, Instant};
+
+    use super::{Behaviour, LinkConfig, NodeNetwork, SimulatedNetwork};
+    use crate::network::GossipNetwork;
+
+    const WAIT: Duration = Duration::from_secs(1);
+
+    fn setup(seed: u64, link: LinkConfig) -> (SimulatedNetwork<u64>, Vec<NodeNetwork<u64>>) {
+        let network = SimulatedNetwork::new(seed, link);
+        let nodes = (0..3).map(|peer_id| network.connect(peer_id)).collect();
+        (network, nodes)
+    }
+
+    async fn received(node: &mut NodeNetwork<u64>) -> Vec<(u64, u32)> {
+        let mut result = Vec::new();
+        while let Ok(Ok(message)) = timeout(WAIT, node.next()).await {
+            result.push(message);
+        }
+        result
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delivers_broadcasts_within_latency() {
+        let (_network, mut nodes) = setup(7, LinkConfig::default());
+        let start = Instant::now();
+        nodes[0].broadcast(43).expect("should send");
+
+        let (data, sender) = nodes[1].next().await.expect("should receive");
+        assert_eq!((data, sender), (43, 0));
+        assert!(start.elapsed() >= LinkConfig::default().min_latency);
+        assert!(start.elapsed() <= LinkConfig::default().max_latency);
+        assert_eq!(received(&mut nodes[2]).await, vec![(43, 0)]);
+        assert!(received(&mut nodes[0]).await.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn partitions_cut_communication_until_healed() {
+        let (network, mut nodes) = setup(7, LinkConfig::default());
+        network.partition(&[&[0, 1], &[2]]);
+        nodes[0].broadcast(43).expect("should send");
+        assert_eq!(received(&mut nodes[1]).await, vec![(43, 0)]);
+        assert!(received(&mut nodes[2]).await.is_empty());
+
+        network.heal();
+        nodes[0].send_to(44, 2).expect("should send");
+        assert_eq!(received(&mut nodes[2]).await, vec![(44, 0)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn silent_and_banned_peers_are_not_heard() {
+        let (network, mut nodes) = setup(7, LinkConfig::default());
+        network.set_behaviour(0, Behaviour::Silent);
+        nodes[0].broadcast(43).expect("should send");
+        nodes[2].ban(1);
+        nodes[1].broadcast(44).expect("should send");
+
+        assert!(received(&mut nodes[2]).await.is_empty());
+        assert_eq!(network.banned_by(2), HashSet::from([1]));
+        nodes[2].unban(1);
+        assert!(network.banned_by(2).is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sends_to_random_peers_from_the_list() {
+        let (_network, mut nodes) = setup(7, LinkConfig::default());
+        nodes[0]
+            .send_to_random(43, HashSet::from([2]))
+            .expect("should send");
+
+        assert!(received(&mut nodes[1]).await.is_empty());
+        assert_eq!(received(&mut nodes[2]).await, vec![(43, 0)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn same_seed_gives_same_delivery() {
+        let link = LinkConfig {
+            drop_rate: 0.3,
+            ..LinkConfig::default()
+        };
+        let mut deliveries = Vec::new();
+        for _ in 0..2 {
+            let (network, mut nodes) = setup(43, link);
+            network.set_behaviour(0, Behaviour::Corrupting);
+            let start = Instant::now();
+            for data in 0..20 {
+                nodes[0].send_to(data, 1).expect("should send");
+            }
+            let mut delivery = Vec::new();
+            while let Ok(Ok((data, _))) = timeout(WAIT, nodes[1].next()).await {
+                delivery.push((data, start.elapsed()));
+            }
+            deliveries.push(delivery);
+        }
+
+        assert!(!deliveries[0].is_empty());
+        assert_eq!(deliveries[0], deliveries[1]);
+    }
+}
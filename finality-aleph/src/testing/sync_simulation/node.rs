@@ -0,0 +1,154 @@
+use futures::channel::{mpsc, oneshot};
+use log::warn;
+use tokio::task::JoinHandle;
+
+use crate::{
+    block::{
+        mock::{Backend, EquivocationProof, MockBlock, MockHeader, MockJustification},
+        Block, ChainStatus, Header, Justification,
+    },
+    session::SessionBoundaryInfo,
+    status::status_channel,
+    sync::{DatabaseIO, MockNetworkData, MockPeerId, Service, SloReporter, IO},
+    testing::sync_simulation::network::SimulatedNetwork,
+    BlockHash, BlockId, SyncOracle,
+};
+
+/// Discards the SLO metrics, as there is no Substrate backend for them to inspect.
+struct NoSloMetrics;
+
+impl SloReporter for NoSloMetrics {
+    fn report_block_imported(&mut self, _block_id: BlockId, _is_new_best: bool, _own: bool) {}
+
+    fn report_block_finalized(&self, _block_id: BlockId) {}
+}
+
+/// A node of the simulation, running the block sync on top of a mock backend.
+pub struct Node {
+    id: MockPeerId,
+    backend: Backend,
+    justifications: mpsc::UnboundedSender<MockJustification>,
+    own_blocks: mpsc::UnboundedSender<MockBlock>,
+    favourite_requests: mpsc::UnboundedSender<oneshot::Sender<MockHeader>>,
+    _equivocation_reports: mpsc::UnboundedReceiver<EquivocationProof>,
+    sync: JoinHandle<()>,
+}
+
+impl Node {
+    /// Starts the node and connects it to the network.
+    pub fn spawn(
+        id: MockPeerId,
+        session_info: SessionBoundaryInfo,
+        network: &SimulatedNetwork<MockNetworkData>,
+    ) -> Self {
+        let (backend, chain_events) = Backend::setup(session_info.clone());
+        let (justifications, justifications_from_user) = mpsc::unbounded();
+        let (own_blocks, blocks_from_creator) = mpsc::unbounded();
+        let (favourite_requests, favourite_block_request) = mpsc::unbounded();
+        let (equivocation_reports, _equivocation_reports) = mpsc::unbounded();
+        let database_io = DatabaseIO::new(backend.clone(), backend.clone(), backend.clone());
+        let io = IO::new(
+            database_io,
+            network.connect(id),
+            chain_events,
+            SyncOracle::new().0,
+            justifications_from_user,
+            blocks_from_creator,
+        );
+        let (service, _) = Service::new(
+            backend.clone(),
+            session_info,
+            io,
+            None,
+            NoSloMetrics,
+            favourite_block_request,
+            equivocation_reports,
+            status_channel().0,
+        )
+        .expect("mock backend works");
+        let sync = tokio::spawn(async move {
+            if let Err(e) = service.run().await {
+                warn!(target: "aleph-sync-simulation", "Sync of node {} stopped: {}", id, e);
+            }
+        });
+        Node {
+            id,
+            backend,
+            justifications,
+            own_blocks,
+            favourite_requests,
+            _equivocation_reports,
+            sync,
+        }
+    }
+
+    pub fn id(&self) -> MockPeerId {
+        self.id
+    }
+
+    /// Whether the node is still running, i.e. it did not crash or get stopped.
+    pub fn is_running(&self) -> bool {
+        !self.sync.is_finished()
+    }
+
+    /// Stops the node abruptly, as if it crashed.
+    pub fn stop(&self) {
+        self.sync.abort();
+    }
+
+    pub fn top_finalized(&self) -> MockHeader {
+        self.backend
+            .top_finalized()
+            .expect("mock backend works")
+            .header()
+            .clone()
+    }
+
+    /// The block the node would build on.
+    pub async fn favourite_block(&self) -> MockHeader {
+        let (sender, receiver) = oneshot::channel();
+        self.favourite_requests
+            .unbounded_send(sender)
+            .expect("the sync is running");
+        receiver.await.expect("the sync is running")
+    }
+
+    /// The parent of the block, if the node imported it.
+    pub fn parent_of(&self, header: &MockHeader) -> Option<MockHeader> {
+        self.backend
+            .block(header.parent_id()?)
+            .expect("mock backend works")
+            .map(|block| block.header().clone())
+    }
+
+    /// Whether the block is an ancestor of, or equal to, `descendant` on the chain of the node.
+    pub fn is_ancestor(&self, id: &BlockId, descendant: &MockHeader) -> bool {
+        let mut header = descendant.clone();
+        while header.id().number() > id.number() {
+            header = match self.parent_of(&header) {
+                Some(parent) => parent,
+                None => return false,
+            };
+        }
+        &header.id() == id
+    }
+
+    /// Makes the node produce a block on top of `parent`, as if it was its own.
+    pub fn produce_block(&self, parent: &MockHeader, hash: BlockHash) -> MockHeader {
+        let header = parent.child_with_hash(hash);
+        self.own_blocks
+            .unbounded_send(MockBlock::new(header.clone(), true))
+            .expect("the sync is running");
+        header
+    }
+
+    /// Passes a justification to the node, as if it came from its own AlephBFT instance.
+    pub fn submit_justification(&self, header: MockHeader) {
+        if let Err(e) = self
+            .justifications
+            .unbounded_send(MockJustification::for_header(header))
+        {
+            warn!(target: "aleph-sync-simulation", "Node {} did not accept a justification: {}", self.id, e);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::{collections::HashSet, time::Duration};
+
+use crate::{
+    block::Header,
+    testing::sync_simulation::{Behaviour, LinkConfig, SyncSimulation, SyncSimulationConfig},
+    SessionPeriod,
+};
+
+const SYNC_TIME: Duration = Duration::from_secs(5);
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+#[tokio::test(start_paused = true)]
+async fn finalizes_blocks_produced_by_one_node() {
+    let mut simulation = SyncSimulation::new(SyncSimulationConfig::default());
+    let blocks = simulation.produce_blocks(0, 10).await;
+    simulation.run_for(SYNC_TIME).await;
+
+    assert_eq!(
+        simulation.finalize_by_oracle().await,
+        Some(blocks[9].clone())
+    );
+    assert!(simulation.wait_for_finalized(10, TIMEOUT).await);
+}
+
+#[tokio::test(start_paused = true)]
+async fn partition_stalls_finalization_until_healed() {
+    let mut simulation = SyncSimulation::new(SyncSimulationConfig::default());
+    simulation.network().partition(&[&[0, 1], &[2, 3]]);
+    let blocks = simulation.produce_blocks(0, 5).await;
+    simulation.run_for(SYNC_TIME).await;
+
+    assert_eq!(simulation.finalize_by_oracle().await, None);
+    assert!(!simulation.wait_for_finalized(1, SYNC_TIME).await);
+
+    simulation.network().heal();
+    simulation.run_for(SYNC_TIME).await;
+    assert_eq!(
+        simulation.finalize_by_oracle().await,
+        Some(blocks[4].clone())
+    );
+    assert!(simulation.wait_for_finalized(5, TIMEOUT).await);
+}
+
+#[tokio::test(start_paused = true)]
+async fn minority_fork_gets_reorged() {
+    let mut simulation = SyncSimulation::new(SyncSimulationConfig::default());
+    simulation.network().partition(&[&[0, 1, 2], &[3]]);
+    let main = simulation.produce_blocks(0, 5).await;
+    let fork = simulation.produce_blocks(3, 8).await;
+    simulation.run_for(SYNC_TIME).await;
+
+    assert_eq!(simulation.finalize_by_oracle().await, Some(main[4].clone()));
+    simulation.network().heal();
+    assert!(simulation.wait_for_finalized(5, TIMEOUT).await);
+    assert_eq!(simulation.node(3).top_finalized(), main[4]);
+
+    simulation.run_for(SYNC_TIME).await;
+    let favourite = simulation.node(3).favourite_block().await;
+    assert!(simulation.node(3).is_ancestor(&main[4].id(), &favourite));
+    assert!(!simulation.node(3).is_ancestor(&fork[4].id(), &favourite));
+}
+
+#[tokio::test(start_paused = true)]
+async fn lagging_node_catches_up_across_sessions() {
+    let mut simulation = SyncSimulation::new(SyncSimulationConfig {
+        session_period: SessionPeriod(5),
+        ..SyncSimulationConfig::default()
+    });
+    simulation.network().partition(&[&[0, 1, 2], &[3]]);
+    // Blocks of sessions too far ahead of the finalized one are not accepted, so produce and
+    // finalize a bit at a time.
+    for _ in 0..4 {
+        let blocks = simulation.produce_blocks(0, 6).await;
+        simulation.run_for(SYNC_TIME).await;
+        assert_eq!(
+            simulation.finalize_by_oracle().await,
+            blocks.last().cloned()
+        );
+        simulation.run_for(SYNC_TIME).await;
+    }
+    assert!(!simulation.wait_for_finalized(24, SYNC_TIME).await);
+
+    simulation.network().heal();
+    assert!(simulation.wait_for_finalized(24, TIMEOUT).await);
+}
+
+#[tokio::test(start_paused = true)]
+async fn crashed_nodes_do_not_stop_finalization() {
+    let mut simulation = SyncSimulation::new(SyncSimulationConfig::default());
+    simulation.node(3).stop();
+    let blocks = simulation.produce_blocks(0, 5).await;
+    simulation.run_for(SYNC_TIME).await;
+
+    assert_eq!(
+        simulation.finalize_by_oracle().await,
+        Some(blocks[4].clone())
+    );
+    assert!(simulation.wait_for_finalized(5, TIMEOUT).await);
+}
+
+#[tokio::test(start_paused = true)]
+async fn finalizes_despite_byzantine_peers_and_lossy_links() {
+    let mut simulation = SyncSimulation::new(SyncSimulationConfig {
+        nodes: 7,
+        seed: 43,
+        link: LinkConfig {
+            drop_rate: 0.1,
+            ..LinkConfig::default()
+        },
+        ..SyncSimulationConfig::default()
+    });
+    simulation.network().set_behaviour(5, Behaviour::Corrupting);
+    simulation.network().set_behaviour(6, Behaviour::Replaying);
+    let blocks = simulation.produce_blocks(0, 10).await;
+    simulation.run_for(SYNC_TIME).await;
+
+    assert_eq!(
+        simulation.finalize_by_oracle().await,
+        Some(blocks[9].clone())
+    );
+    assert!(simulation.wait_for_finalized(10, TIMEOUT).await);
+
+    let more_blocks = simulation.produce_blocks(1, 10).await;
+    simulation.run_for(SYNC_TIME).await;
+    assert_eq!(
+        simulation.finalize_by_oracle().await,
+        Some(more_blocks[9].clone())
+    );
+    assert!(simulation.wait_for_finalized(20, TIMEOUT).await);
+}
+
+#[tokio::test(start_paused = true)]
+async fn same_seed_produces_same_blocks() {
+    let mut first = SyncSimulation::new(SyncSimulationConfig::default());
+    let mut second = SyncSimulation::new(SyncSimulationConfig::default());
+
+    assert_eq!(
+        first.produce_blocks(0, 5).await,
+        second.produce_blocks(0, 5).await
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn honest_peers_are_not_banned() {
+    let mut simulation = SyncSimulation::new(SyncSimulationConfig {
+        session_period: SessionPeriod(5),
+        ..SyncSimulationConfig::default()
+    });
+    // Forks and lagging nodes make the peers send each other headers that cannot be imported
+    // and leave requests unanswered, which should not be mistaken for misbehaviour.
+    simulation.network().partition(&[&[0, 1, 2], &[3]]);
+    for _ in 0..4 {
+        let blocks = simulation.produce_blocks(0, 6).await;
+        simulation.produce_blocks(3, 6).await;
+        simulation.run_for(SYNC_TIME).await;
+        assert_eq!(
+            simulation.finalize_by_oracle().await,
+            blocks.last().cloned()
+        );
+        simulation.run_for(SYNC_TIME).await;
+    }
+    simulation.network().heal();
+    assert!(simulation.wait_for_finalized(24, TIMEOUT).await);
+    // Long enough for many rounds of requests and reputation updates.
+    simulation.run_for(10 * TIMEOUT).await;
+
+    for id in 0..4 {
+        assert!(simulation.network().banned_by(id).is_empty());
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn only_misbehaving_peers_get_banned() {
+    let mut simulation = SyncSimulation::new(SyncSimulationConfig::default());
+    simulation.network().set_behaviour(3, Behaviour::Corrupting);
+    for _ in 0..10 {
+        simulation.produce_blocks(0, 5).await;
+        simulation.produce_blocks(3, 5).await;
+        simulation.run_for(SYNC_TIME).await;
+    }
+
+    for id in 0..3 {
+        assert!(simulation
+            .network()
+            .banned_by(id)
+            .is_subset(&HashSet::from([3])));
+    }
+}
@@ -0,0 +1,35 @@
+use parity_scale_codec::{Decode, Encode};
+use sp_trie::CompactProof;
+
+use crate::{session::SessionId, warp_sync::proof::WarpSyncProof, BlockHash};
+
+/// A request for a part of the state of a block.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct StateRequest {
+    /// The block whose state we want.
+    pub block: BlockHash,
+    /// The keys after which the response should start, one per trie level.
+    pub start: Vec<Vec<u8>>,
+}
+
+/// A response containing a part of the state of a block, in the form of a range proof.
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct StateResponse {
+    /// The request this is a response to.
+    pub request: StateRequest,
+    /// Proof of the key-value pairs following the requested start keys.
+    pub proof: CompactProof,
+}
+
+/// Data sent between nodes performing warp sync.
+#[derive(Clone, Debug, Encode, Decode)]
+pub enum WarpSyncMessage {
+    /// Request for session boundary justifications starting with the given session.
+    ProofRequest(SessionId),
+    /// Session boundary justifications, with proofs of the authorities of subsequent sessions.
+    ProofResponse(WarpSyncProof),
+    /// Request for a part of the state of a block.
+    StateRequest(StateRequest),
+    /// A part of the state of a block.
+    StateResponse(StateResponse),
+}
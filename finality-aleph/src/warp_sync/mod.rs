@@ -0,0 +1,16 @@
+mod data;
+mod proof;
+mod service;
+mod state;
+
+pub use service::{Service, WarpSyncTarget};
+use tokio::time::Duration;
+
+const LOG_TARGET: &str = "aleph-warp-sync";
+
+/// How long we wait for warp sync to download the state before giving up and syncing all blocks
+/// instead.
+pub const WARP_SYNC_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// The maximal size of a message accepted by the warp sync protocol.
+pub const MAX_MESSAGE_SIZE: u64 = 16 * 1024 * 1024;
@@ -0,0 +1,438 @@
+use std::{
+    fmt::{Display, Error as FmtError, Formatter},
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use parity_scale_codec::{Decode, Encode};
+use sc_client_api::Backend;
+use sp_runtime::traits::{BlakeTwo256, Header as _};
+use sp_state_machine::read_proof_check;
+use sp_trie::StorageProof;
+
+use crate::{
+    aleph_primitives::{
+        next_authority_data_storage_keys, AuthorityId, Block, Header, SessionAuthorityData,
+    },
+    block::{
+        substrate::{
+            InnerJustification, Justification, SessionVerificationError, SessionVerifier,
+            SubstrateChainStatus,
+        },
+        ChainStatus, FinalizationStatus, HeaderBackend,
+    },
+    justification::AlephJustification,
+    session::{SessionBoundaryInfo, SessionId},
+    BlockHash, BlockNumber, ClientForAleph,
+};
+
+/// The soft limit on the size of a single proof, the last fragment might exceed it.
+/// Kept small, so that a response passes through the rate limited network in a few seconds.
+pub const MAX_PROOF_SIZE: usize = 2 * 1024 * 1024;
+
+/// The justification of the last block of a session, together with a proof of the authorities
+/// of the next session against the state of that block.
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct WarpSyncFragment {
+    pub justification: Justification,
+    pub next_authorities_proof: StorageProof,
+}
+
+/// A sequence of fragments for consecutive sessions.
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct WarpSyncProof {
+    pub fragments: Vec<WarpSyncFragment>,
+    /// Whether the fragments reach the last session the prover knows about.
+    pub is_finished: bool,
+}
+
+/// Errors that can occur when generating a proof.
+#[derive(Debug)]
+pub enum ProofError {
+    ChainStatus(<SubstrateChainStatus as ChainStatus<Block, Justification>>::Error),
+    MissingJustification(BlockNumber),
+    Storage(sp_blockchain::Error),
+}
+
+impl Display for ProofError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        use ProofError::*;
+        match self {
+            ChainStatus(e) => write!(f, "chain status error: {e}"),
+            MissingJustification(number) => {
+                write!(
+                    f,
+                    "missing justification for session boundary block #{number}"
+                )
+            }
+            Storage(e) => write!(f, "failed to prove storage: {e}"),
+        }
+    }
+}
+
+/// Generates warp sync proofs from the local database.
+pub struct Prover<C, BE>
+where
+    C: ClientForAleph<Block, BE>,
+    BE: Backend<Block>,
+{
+    client: Arc<C>,
+    chain_status: SubstrateChainStatus,
+    session_info: SessionBoundaryInfo,
+    _phantom: PhantomData<BE>,
+}
+
+impl<C, BE> Prover<C, BE>
+where
+    C: ClientForAleph<Block, BE>,
+    BE: Backend<Block>,
+{
+    pub fn new(
+        client: Arc<C>,
+        chain_status: SubstrateChainStatus,
+        session_info: SessionBoundaryInfo,
+    ) -> Self {
+        Prover {
+            client,
+            chain_status,
+            session_info,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn fragment(&self, session: SessionId) -> Result<Option<WarpSyncFragment>, ProofError> {
+        let last_block = self.session_info.last_block_of_session(session);
+        let justification = match self
+            .chain_status
+            .finalized_at(last_block)
+            .map_err(ProofError::ChainStatus)?
+        {
+            FinalizationStatus::FinalizedWithJustification(justification) => justification,
+            FinalizationStatus::FinalizedByDescendant(_) => {
+                return Err(ProofError::MissingJustification(last_block))
+            }
+            FinalizationStatus::NotFinalized => return Ok(None),
+        };
+        let keys = next_authority_data_storage_keys();
+        let next_authorities_proof = self
+            .client
+            .read_proof(
+                justification.header.hash(),
+                &mut keys.iter().map(|key| key.as_slice()),
+            )
+            .map_err(ProofError::Storage)?;
+        Ok(Some(WarpSyncFragment {
+            justification,
+            next_authorities_proof,
+        }))
+    }
+
+    /// Generate a proof starting with the given session, it might not reach the top finalized
+    /// block if it would be too large.
+    pub fn generate(&self, from: SessionId) -> Result<WarpSyncProof, ProofError> {
+        let top_finalized = self.chain_status.top_finalized_id().number();
+        let mut fragments = Vec::new();
+        let mut size = 0;
+        let mut session = from;
+        while self.session_info.last_block_of_session(session) <= top_finalized {
+            let fragment = match self.fragment(session)? {
+                Some(fragment) => fragment,
+                None => break,
+            };
+            size += fragment.encoded_size();
+            fragments.push(fragment);
+            if size > MAX_PROOF_SIZE {
+                return Ok(WarpSyncProof {
+                    fragments,
+                    is_finished: false,
+                });
+            }
+            session = session.next();
+        }
+        Ok(WarpSyncProof {
+            fragments,
+            is_finished: true,
+        })
+    }
+}
+
+/// Errors that can occur when verifying a proof.
+#[derive(Debug)]
+pub enum VerificationError {
+    UnexpectedBlock(BlockNumber, BlockNumber),
+    GenesisJustification,
+    Justification(SessionVerificationError),
+    StorageProof(String),
+    Decode(parity_scale_codec::Error),
+    MissingAuthorities(SessionId),
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        use VerificationError::*;
+        match self {
+            UnexpectedBlock(expected, got) => write!(
+                f,
+                "expected a justification of block #{expected}, got one of #{got}"
+            ),
+            GenesisJustification => write!(f, "got a virtual genesis justification"),
+            Justification(e) => write!(f, "incorrect justification: {e}"),
+            StorageProof(e) => write!(f, "incorrect storage proof: {e}"),
+            Decode(e) => write!(f, "failed to decode authorities: {e}"),
+            MissingAuthorities(session) => {
+                write!(f, "proof shows no authorities for session {}", session.0)
+            }
+        }
+    }
+}
+
+/// Verifies consecutive fragments of warp sync proofs, keeping track of the authorities of the
+/// next session.
+pub struct Verifier {
+    session_info: SessionBoundaryInfo,
+    next_session: SessionId,
+    session_verifier: SessionVerifier,
+}
+
+impl Verifier {
+    /// Create a verifier starting with the first session, using the authorities from genesis.
+    pub fn new(
+        session_info: SessionBoundaryInfo,
+        genesis_authorities: SessionAuthorityData,
+    ) -> Self {
+        Verifier {
+            session_info,
+            next_session: SessionId(0),
+            session_verifier: genesis_authorities.into(),
+        }
+    }
+
+    /// The session the next fragment should be for.
+    pub fn next_session(&self) -> SessionId {
+        self.next_session
+    }
+
+    fn next_authority_data(
+        &self,
+        state_root: BlockHash,
+        proof: StorageProof,
+    ) -> Result<SessionAuthorityData, VerificationError> {
+        use VerificationError::*;
+        let [authorities_key, emergency_finalizer_key] = next_authority_data_storage_keys();
+        let mut values = read_proof_check::<BlakeTwo256, _>(
+            state_root,
+            proof,
+            [&authorities_key, &emergency_finalizer_key],
+        )
+        .map_err(|e| StorageProof(e.to_string()))?;
+        let authorities = match values.remove(&authorities_key).flatten() {
+            Some(encoded) => Vec::<AuthorityId>::decode(&mut encoded.as_slice()).map_err(Decode)?,
+            None => Vec::new(),
+        };
+        if authorities.is_empty() {
+            return Err(MissingAuthorities(self.next_session.next()));
+        }
+        let emergency_finalizer = match values.remove(&emergency_finalizer_key).flatten() {
+            Some(encoded) => Some(AuthorityId::decode(&mut encoded.as_slice()).map_err(Decode)?),
+            None => None,
+        };
+        Ok(SessionAuthorityData::new(authorities, emergency_finalizer))
+    }
+
+    /// Verify the fragment for the next session, returning the justified header if correct.
+    pub fn verify(
+        &mut self,
+        fragment: WarpSyncFragment,
+    ) -> Result<(Header, AlephJustification), VerificationError> {
+        let WarpSyncFragment {
+            justification:
+                Justification {
+                    header,
+                    inner_justification,
+                },
+            next_authorities_proof,
+        } = fragment;
+        let expected = self.session_info.last_block_of_session(self.next_session);
+        let number = *header.number();
+        if number != expected {
+            return Err(VerificationError::UnexpectedBlock(expected, number));
+        }
+        let aleph_justification = match inner_justification {
+            InnerJustification::AlephJustification(aleph_justification) => aleph_justification,
+            InnerJustification::Genesis => return Err(VerificationError::GenesisJustification),
+        };
+        self.session_verifier
+            .verify_bytes(&aleph_justification, header.hash().encode())
+            .map_err(VerificationError::Justification)?;
+        let next_authority_data =
+            self.next_authority_data(*header.state_root(), next_authorities_proof)?;
+        self.session_verifier = next_authority_data.into();
+        self.next_session = self.next_session.next();
+        Ok((header, aleph_justification))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use parity_scale_codec::Encode;
+    use primitives::{next_authority_data_storage_keys, Header};
+    use sp_runtime::{
+        traits::{BlakeTwo256, Header as _},
+        Digest, StateVersion,
+    };
+    use sp_state_machine::{prove_read, InMemoryBackend};
+    use sp_trie::StorageProof;
+
+    use super::{VerificationError, Verifier, WarpSyncFragment};
+    use crate::{
+        abft::SignatureSet,
+        aleph_primitives::SessionAuthorityData,
+        block::substrate::{Justification, SessionVerificationError},
+        crypto::AuthorityPen,
+        justification::AlephJustification,
+        network::mock::crypto_basics,
+        session::{testing::authority_data, SessionBoundaryInfo},
+        NodeCount, NodeIndex, SessionId, SessionPeriod,
+    };
+
+    const SESSION_PERIOD: u32 = 30;
+    const COMMITTEE_SIZE: usize = 4;
+
+    type Pens = Vec<(NodeIndex, AuthorityPen)>;
+
+    fn session_info() -> SessionBoundaryInfo {
+        SessionBoundaryInfo::new(SessionPeriod(SESSION_PERIOD))
+    }
+
+    fn verifier() -> Verifier {
+        Verifier::new(session_info(), authority_data(0, 4))
+    }
+
+    fn committee() -> (Pens, SessionAuthorityData) {
+        let (pens, _) = crypto_basics(COMMITTEE_SIZE);
+        let authorities = pens.iter().map(|(_, pen)| pen.authority_id()).collect();
+        (pens, SessionAuthorityData::new(authorities, None))
+    }
+
+    fn header(number: u32, state_root: crate::BlockHash) -> Header {
+        Header::new(
+            number,
+            Default::default(),
+            state_root,
+            Default::default(),
+            Digest::default(),
+        )
+    }
+
+    fn unsigned_fragment(number: u32) -> WarpSyncFragment {
+        WarpSyncFragment {
+            justification: Justification::genesis_justification(header(number, Default::default())),
+            next_authorities_proof: StorageProof::empty(),
+        }
+    }
+
+    /// A fragment for the given session signed by `pens`, proving that `next` are the
+    /// authorities of the following session.
+    fn fragment(session: u32, pens: &Pens, next: &SessionAuthorityData) -> WarpSyncFragment {
+        let [authorities_key, emergency_finalizer_key] = next_authority_data_storage_keys();
+        let mut state = BTreeMap::new();
+        state.insert(authorities_key, next.authorities().encode());
+        if let Some(emergency_finalizer) = next.emergency_finalizer() {
+            state.insert(emergency_finalizer_key, emergency_finalizer.encode());
+        }
+        let backend = InMemoryBackend::<BlakeTwo256>::from((state, StateVersion::V1));
+        let header = header(
+            session_info().last_block_of_session(SessionId(session)),
+            *backend.root(),
+        );
+        let next_authorities_proof = prove_read(backend, next_authority_data_storage_keys())
+            .expect("the keys should be provable");
+
+        let message = header.hash().encode();
+        let signatures = pens.iter().fold(
+            SignatureSet::with_size(NodeCount(pens.len())),
+            |signatures, (index, pen)| {
+                signatures.add_signature(&pen.sign(&message).expect("signing works"), *index)
+            },
+        );
+        WarpSyncFragment {
+            justification: Justification::aleph_justification(
+                header,
+                AlephJustification::CommitteeMultisignature(signatures),
+            ),
+            next_authorities_proof,
+        }
+    }
+
+    #[test]
+    fn verifies_consecutive_sessions() {
+        let committees: Vec<_> = (0..4).map(|_| committee()).collect();
+        let mut verifier = Verifier::new(session_info(), committees[0].1.clone());
+        for session in 0..3 {
+            let (pens, _) = &committees[session];
+            let (_, next) = &committees[session + 1];
+            let (header, _) = verifier
+                .verify(fragment(session as u32, pens, next))
+                .expect("the fragment should be correct");
+            assert_eq!(
+                *header.number(),
+                session_info().last_block_of_session(SessionId(session as u32))
+            );
+        }
+        assert_eq!(verifier.next_session().0, 3);
+    }
+
+    #[test]
+    fn rejects_fragment_signed_by_previous_committee() {
+        let (first_pens, first) = committee();
+        let (_, second) = committee();
+        let mut verifier = Verifier::new(session_info(), first.clone());
+        verifier
+            .verify(fragment(0, &first_pens, &second))
+            .expect("the fragment should be correct");
+        match verifier.verify(fragment(1, &first_pens, &first)) {
+            Err(VerificationError::Justification(SessionVerificationError::BadMultisignature)) => {}
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+        assert_eq!(verifier.next_session().0, 1);
+    }
+
+    #[test]
+    fn rejects_authorities_proof_for_other_state() {
+        let (pens, authorities) = committee();
+        let (_, next) = committee();
+        let mut other_state = fragment(0, &pens, &authorities);
+        other_state.next_authorities_proof = fragment(0, &pens, &next).next_authorities_proof;
+        let mut verifier = Verifier::new(session_info(), authorities);
+        match verifier.verify(other_state) {
+            Err(VerificationError::StorageProof(_)) => (),
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+        assert_eq!(verifier.next_session().0, 0);
+    }
+
+    #[test]
+    fn rejects_fragment_for_wrong_block() {
+        let mut verifier = verifier();
+        match verifier.verify(unsigned_fragment(SESSION_PERIOD)) {
+            Err(VerificationError::UnexpectedBlock(expected, got)) => {
+                assert_eq!(expected, SESSION_PERIOD - 1);
+                assert_eq!(got, SESSION_PERIOD);
+            }
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+        assert_eq!(verifier.next_session().0, 0);
+    }
+
+    #[test]
+    fn rejects_genesis_justification() {
+        let mut verifier = verifier();
+        match verifier.verify(unsigned_fragment(SESSION_PERIOD - 1)) {
+            Err(VerificationError::GenesisJustification) => (),
+            other => panic!("unexpected verification result: {other:?}"),
+        }
+        assert_eq!(verifier.next_session().0, 0);
+    }
+}
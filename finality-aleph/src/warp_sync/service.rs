@@ -0,0 +1,464 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    sync::Arc,
+};
+
+use futures::channel::oneshot;
+use log::{debug, info, warn};
+use sc_client_api::Backend;
+use sp_runtime::traits::Header as _;
+use sp_state_machine::KeyValueStates;
+use static_assertions::const_assert;
+use tokio::time::{self, Duration, Instant};
+
+use crate::{
+    aleph_primitives::{Block, Header, SessionAuthorityData},
+    block::{
+        substrate::{BlockImporter, SubstrateChainStatus},
+        HeaderBackend,
+    },
+    import::StateImportTarget,
+    justification::AlephJustification,
+    network::GossipNetwork,
+    session::{SessionBoundaryInfo, SessionId},
+    warp_sync::{
+        data::{StateRequest, StateResponse, WarpSyncMessage},
+        proof::{Prover, Verifier, WarpSyncProof, MAX_PROOF_SIZE},
+        state::StateDownload,
+        LOG_TARGET, MAX_MESSAGE_SIZE,
+    },
+    ClientForAleph,
+};
+
+/// How often we resend our request if we get no response.
+const REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+/// How large a part of the state we send in a single response.
+const MAX_STATE_RESPONSE_SIZE: usize = 2 * 1024 * 1024;
+/// How long we wait for the imported state to become finalized.
+const IMPORT_TIMEOUT: Duration = Duration::from_secs(120);
+const IMPORT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How many requests of a single peer we answer per `RESPONSE_LIMIT_PERIOD`. Peers performing
+/// warp sync send one request at a time, so this only limits misbehaving ones.
+const MAX_PEER_RESPONSES: usize = 4;
+/// How many requests of all peers together we answer per `RESPONSE_LIMIT_PERIOD`.
+const MAX_RESPONSES: usize = 32;
+const RESPONSE_LIMIT_PERIOD: Duration = Duration::from_secs(1);
+
+// The last fragment or state entry might go over the limit, leave some space for it.
+const_assert!(MAX_MESSAGE_SIZE as usize > 4 * MAX_PROOF_SIZE);
+const_assert!(MAX_MESSAGE_SIZE as usize > 4 * MAX_STATE_RESPONSE_SIZE);
+
+/// A finalized block together with its whole state, the result of warp sync.
+pub struct WarpSyncTarget {
+    header: Header,
+    justification: AlephJustification,
+    state: KeyValueStates,
+}
+
+impl WarpSyncTarget {
+    /// Import the target and wait until it becomes finalized.
+    pub async fn import(
+        self,
+        importer: &mut BlockImporter,
+        chain_status: &SubstrateChainStatus,
+        state_import_target: &StateImportTarget,
+    ) {
+        let WarpSyncTarget {
+            header,
+            justification,
+            state,
+        } = self;
+        let number = *header.number();
+        let entries: usize = state.0.iter().map(|level| level.key_values.len()).sum();
+        info!(
+            target: LOG_TARGET,
+            "Importing state of block #{}, {} entries.", number, entries
+        );
+        state_import_target.set(header.hash());
+        importer.import_state(header, justification, state);
+        let import = async {
+            while chain_status.top_finalized_id().number() < number {
+                time::sleep(IMPORT_POLL_INTERVAL).await;
+            }
+        };
+        match time::timeout(IMPORT_TIMEOUT, import).await {
+            Ok(()) => info!(target: LOG_TARGET, "Warp sync finished at block #{}.", number),
+            Err(_) => warn!(
+                target: LOG_TARGET,
+                "Imported state of block #{} did not get finalized, falling back to full sync.",
+                number
+            ),
+        }
+    }
+}
+
+enum Stage {
+    /// Verifying justifications of consecutive session boundaries, remembering the last one.
+    Proofs {
+        verifier: Verifier,
+        target: Option<(Header, AlephJustification)>,
+    },
+    /// Downloading the state of the last verified session boundary.
+    State {
+        header: Header,
+        justification: AlephJustification,
+        download: StateDownload,
+    },
+}
+
+/// Limits how many requests we answer, as generating the responses is expensive.
+struct ResponseLimiter<P: Eq + Hash> {
+    responses: VecDeque<Instant>,
+    peer_responses: HashMap<P, VecDeque<Instant>>,
+}
+
+impl<P: Eq + Hash> ResponseLimiter<P> {
+    fn new() -> Self {
+        ResponseLimiter {
+            responses: VecDeque::new(),
+            peer_responses: HashMap::new(),
+        }
+    }
+
+    fn forget_old(responses: &mut VecDeque<Instant>, now: Instant) {
+        while let Some(&oldest) = responses.front() {
+            if now.duration_since(oldest) < RESPONSE_LIMIT_PERIOD {
+                break;
+            }
+            responses.pop_front();
+        }
+    }
+
+    /// Whether we should answer a request of the peer, if so the response is counted.
+    fn try_respond(&mut self, peer: P) -> bool {
+        let now = Instant::now();
+        Self::forget_old(&mut self.responses, now);
+        self.peer_responses.retain(|_, responses| {
+            Self::forget_old(responses, now);
+            !responses.is_empty()
+        });
+        let peer_responses = self.peer_responses.entry(peer).or_default();
+        if self.responses.len() >= MAX_RESPONSES || peer_responses.len() >= MAX_PEER_RESPONSES {
+            return false;
+        }
+        peer_responses.push_back(now);
+        self.responses.push_back(now);
+        true
+    }
+}
+
+/// Our own warp sync in progress.
+struct Task {
+    stage: Stage,
+    result: oneshot::Sender<Option<WarpSyncTarget>>,
+}
+
+/// A service responding to warp sync requests from other nodes and, if requested, performing
+/// warp sync of our own.
+///
+/// Warp sync verifies the justifications of the last blocks of all sessions, starting with the
+/// authorities from genesis and learning the authorities of every following session from a
+/// storage proof against the state of the justified block. Afterwards it downloads the whole
+/// state of the last verified block. Nothing older than that block gets downloaded.
+pub struct Service<N, C, BE>
+where
+    N: GossipNetwork<WarpSyncMessage>,
+    C: ClientForAleph<Block, BE>,
+    BE: Backend<Block>,
+{
+    network: N,
+    client: Arc<C>,
+    prover: Prover<C, BE>,
+    response_limiter: ResponseLimiter<N::PeerId>,
+    task: Option<Task>,
+}
+
+impl<N, C, BE> Service<N, C, BE>
+where
+    N: GossipNetwork<WarpSyncMessage>,
+    C: ClientForAleph<Block, BE>,
+    BE: Backend<Block>,
+{
+    /// Create a new service, which will perform warp sync if the genesis authorities are
+    /// provided. The returned receiver gets the state to import once that is done, or `None`
+    /// immediately if no warp sync was requested.
+    pub fn new(
+        network: N,
+        client: Arc<C>,
+        chain_status: SubstrateChainStatus,
+        session_info: SessionBoundaryInfo,
+        genesis_authorities: Option<SessionAuthorityData>,
+    ) -> (Self, oneshot::Receiver<Option<WarpSyncTarget>>) {
+        let (result, target) = oneshot::channel();
+        let task = match genesis_authorities {
+            Some(genesis_authorities) => Some(Task {
+                stage: Stage::Proofs {
+                    verifier: Verifier::new(session_info.clone(), genesis_authorities),
+                    target: None,
+                },
+                result,
+            }),
+            None => {
+                let _ = result.send(None);
+                None
+            }
+        };
+        let prover = Prover::new(client.clone(), chain_status, session_info);
+        (
+            Service {
+                network,
+                client,
+                prover,
+                response_limiter: ResponseLimiter::new(),
+                task,
+            },
+            target,
+        )
+    }
+
+    fn request(&self) -> Option<WarpSyncMessage> {
+        match &self.task.as_ref()?.stage {
+            Stage::Proofs { verifier, .. } => {
+                Some(WarpSyncMessage::ProofRequest(verifier.next_session()))
+            }
+            Stage::State {
+                header, download, ..
+            } => Some(WarpSyncMessage::StateRequest(StateRequest {
+                block: header.hash(),
+                start: download.start(),
+            })),
+        }
+    }
+
+    fn send_request(&mut self, peer: Option<N::PeerId>) {
+        let request = match self.request() {
+            Some(request) => request,
+            None => return,
+        };
+        let result = match peer {
+            Some(peer) => self.network.send_to(request, peer),
+            None => self.network.send_to_random(request, HashSet::new()),
+        };
+        if let Err(e) = result {
+            warn!(target: LOG_TARGET, "Failed to send warp sync request: {}.", e);
+        }
+    }
+
+    fn send_response(&mut self, response: WarpSyncMessage, peer: N::PeerId) {
+        if let Err(e) = self.network.send_to(response, peer) {
+            warn!(target: LOG_TARGET, "Failed to send warp sync response: {}.", e);
+        }
+    }
+
+    fn report(result: oneshot::Sender<Option<WarpSyncTarget>>, target: Option<WarpSyncTarget>) {
+        if result.send(target).is_err() {
+            warn!(target: LOG_TARGET, "Nobody is waiting for warp sync to finish.");
+        }
+    }
+
+    fn handle_proof_request(&mut self, session: SessionId, peer: N::PeerId) {
+        match self.prover.generate(session) {
+            Ok(proof) => self.send_response(WarpSyncMessage::ProofResponse(proof), peer),
+            Err(e) => warn!(
+                target: LOG_TARGET,
+                "Failed to generate warp sync proof from session {}: {}.", session.0, e
+            ),
+        }
+    }
+
+    fn handle_state_request(&mut self, request: StateRequest, peer: N::PeerId) {
+        match self.client.read_proof_collection(
+            request.block,
+            &request.start,
+            MAX_STATE_RESPONSE_SIZE,
+        ) {
+            Ok((proof, _)) => self.send_response(
+                WarpSyncMessage::StateResponse(StateResponse { request, proof }),
+                peer,
+            ),
+            Err(e) => debug!(
+                target: LOG_TARGET,
+                "Failed to prove state of block {}: {}.", request.block, e
+            ),
+        }
+    }
+
+    fn handle_proof(&mut self, proof: WarpSyncProof, peer: N::PeerId) {
+        let task = match self.task.as_mut() {
+            Some(task) => task,
+            None => return,
+        };
+        let (verifier, target) = match &mut task.stage {
+            Stage::Proofs { verifier, target } => (verifier, target),
+            Stage::State { .. } => return,
+        };
+        let WarpSyncProof {
+            fragments,
+            is_finished,
+        } = proof;
+        // A peer that has nothing to offer might just be syncing itself, so we only accept such
+        // responses once we have something to download. Because of this warp sync waits for
+        // the first session to end on young chains.
+        if fragments.is_empty() && target.is_none() {
+            return;
+        }
+        for fragment in fragments {
+            match verifier.verify(fragment) {
+                Ok(justified) => *target = Some(justified),
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Bad warp sync proof from {:?}: {}.", peer, e);
+                    return;
+                }
+            }
+        }
+        debug!(
+            target: LOG_TARGET,
+            "Verified session boundaries up to session {}.",
+            verifier.next_session().0
+        );
+        if is_finished {
+            if let Some((header, justification)) = target.take() {
+                info!(
+                    target: LOG_TARGET,
+                    "Downloading state of block #{}.",
+                    header.number()
+                );
+                task.stage = Stage::State {
+                    header,
+                    justification,
+                    download: StateDownload::new(),
+                };
+            }
+        }
+        self.send_request(Some(peer));
+    }
+
+    fn handle_state(&mut self, response: StateResponse, peer: N::PeerId) {
+        let task = match self.task.as_mut() {
+            Some(task) => task,
+            None => return,
+        };
+        let (header, download) = match &mut task.stage {
+            Stage::State {
+                header, download, ..
+            } => (header, download),
+            Stage::Proofs { .. } => return,
+        };
+        let StateResponse { request, proof } = response;
+        if request.block != header.hash() || request.start != download.start() {
+            // A response to an old request.
+            return;
+        }
+        let (values, stopped_at) =
+            match self
+                .client
+                .verify_range_proof(*header.state_root(), proof, &request.start)
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Bad state proof from {:?}: {}.", peer, e);
+                    return;
+                }
+            };
+        download.import(values, stopped_at);
+        if !download.is_complete() {
+            return self.send_request(Some(peer));
+        }
+        if let Some(Task {
+            stage:
+                Stage::State {
+                    header,
+                    justification,
+                    download,
+                },
+            result,
+        }) = self.task.take()
+        {
+            Self::report(
+                result,
+                Some(WarpSyncTarget {
+                    header,
+                    justification,
+                    state: download.into_state(),
+                }),
+            );
+        }
+    }
+
+    fn handle_network_data(&mut self, data: WarpSyncMessage, peer: N::PeerId) {
+        use WarpSyncMessage::*;
+        if matches!(data, ProofRequest(_) | StateRequest(_))
+            && !self.response_limiter.try_respond(peer.clone())
+        {
+            debug!(
+                target: LOG_TARGET,
+                "Ignoring warp sync request from {:?}, too many requests.", peer
+            );
+            return;
+        }
+        match data {
+            ProofRequest(session) => self.handle_proof_request(session, peer),
+            ProofResponse(proof) => self.handle_proof(proof, peer),
+            StateRequest(request) => self.handle_state_request(request, peer),
+            StateResponse(response) => self.handle_state(response, peer),
+        }
+    }
+
+    fn on_request_tick(&mut self) {
+        // Nobody waits for the result anymore, most likely warp sync timed out.
+        if let Some(true) = self.task.as_ref().map(|task| task.result.is_canceled()) {
+            warn!(target: LOG_TARGET, "Warp sync abandoned.");
+            self.task = None;
+            return;
+        }
+        self.send_request(None);
+    }
+
+    /// Run the service, it keeps responding to requests after our own warp sync finishes.
+    pub async fn run(mut self) -> Result<(), N::Error> {
+        let mut request_ticker = time::interval(REQUEST_INTERVAL);
+        loop {
+            tokio::select! {
+                maybe_data = self.network.next() => {
+                    let (data, peer) = maybe_data?;
+                    self.handle_network_data(data, peer);
+                },
+
+                _ = request_ticker.tick(), if self.task.is_some() => self.on_request_tick(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time;
+
+    use super::{ResponseLimiter, MAX_PEER_RESPONSES, MAX_RESPONSES, RESPONSE_LIMIT_PERIOD};
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_responses_per_peer() {
+        let mut limiter = ResponseLimiter::new();
+        for _ in 0..MAX_PEER_RESPONSES {
+            assert!(limiter.try_respond(0));
+        }
+        assert!(!limiter.try_respond(0));
+        assert!(limiter.try_respond(1));
+
+        time::advance(RESPONSE_LIMIT_PERIOD).await;
+        assert!(limiter.try_respond(0));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_responses_of_all_peers() {
+        let mut limiter = ResponseLimiter::new();
+        for peer in 0..MAX_RESPONSES {
+            assert!(limiter.try_respond(peer));
+        }
+        assert!(!limiter.try_respond(MAX_RESPONSES));
+
+        time::advance(RESPONSE_LIMIT_PERIOD).await;
+        assert!(limiter.try_respond(MAX_RESPONSES));
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use smallvec::SmallVec;
+use sp_core::storage::well_known_keys::is_child_storage_key;
+use sp_state_machine::{KeyValueStates, KeyValueStorageLevel};
+
+type KeyValues = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Accumulates the state of a block downloaded in parts, keeping track of where the next part
+/// should start.
+pub struct StateDownload {
+    last_key: SmallVec<[Vec<u8>; 2]>,
+    // Key values per trie root, together with the keys of the child trie roots in the parent.
+    // The top trie uses an empty root.
+    state: HashMap<Vec<u8>, (KeyValues, Vec<Vec<u8>>)>,
+    complete: bool,
+}
+
+impl StateDownload {
+    pub fn new() -> Self {
+        StateDownload {
+            last_key: SmallVec::new(),
+            state: HashMap::new(),
+            complete: false,
+        }
+    }
+
+    /// The keys after which the next part of the state should start.
+    pub fn start(&self) -> Vec<Vec<u8>> {
+        self.last_key.to_vec()
+    }
+
+    /// Whether the whole state has been downloaded.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Import verified values, `stopped_at` is the trie depth at which the proof ended, with zero
+    /// meaning the whole state has been proven.
+    pub fn import(&mut self, values: KeyValueStates, stopped_at: usize) {
+        if stopped_at == 0 {
+            self.complete = true;
+        } else if !values.update_last_key(stopped_at, &mut self.last_key) {
+            // The values are correct, so this can only happen if the proof contained nothing new,
+            // which will result in requesting the same part again.
+            return;
+        }
+        for KeyValueStorageLevel {
+            state_root,
+            key_values,
+            ..
+        } in values.0
+        {
+            let key_values = match state_root.is_empty() {
+                true => key_values
+                    .into_iter()
+                    .filter(|(key, value)| {
+                        if is_child_storage_key(key) {
+                            self.state
+                                .entry(value.clone())
+                                .or_default()
+                                .1
+                                .push(key.clone());
+                            false
+                        } else {
+                            true
+                        }
+                    })
+                    .collect(),
+                false => key_values,
+            };
+            self.state
+                .entry(state_root)
+                .or_default()
+                .0
+                .extend(key_values);
+        }
+    }
+
+    /// The whole downloaded state, in a form suitable for importing.
+    pub fn into_state(self) -> KeyValueStates {
+        KeyValueStates(
+            self.state
+                .into_iter()
+                .map(
+                    |(state_root, (key_values, parent_storage_keys))| KeyValueStorageLevel {
+                        state_root,
+                        parent_storage_keys,
+                        key_values,
+                    },
+                )
+                .collect(),
+        )
+    }
+}
+
+impl Default for StateDownload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp_core::storage::well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX;
+    use sp_state_machine::{KeyValueStates, KeyValueStorageLevel};
+
+    use super::StateDownload;
+
+    fn top_level(key_values: Vec<(Vec<u8>, Vec<u8>)>) -> KeyValueStates {
+        KeyValueStates(vec![KeyValueStorageLevel {
+            state_root: Vec::new(),
+            parent_storage_keys: Vec::new(),
+            key_values,
+        }])
+    }
+
+    #[test]
+    fn continues_after_last_key() {
+        let mut download = StateDownload::new();
+        assert!(download.start().is_empty());
+        download.import(top_level(vec![(vec![1], vec![43]), (vec![2], vec![44])]), 1);
+        assert!(!download.is_complete());
+        assert_eq!(download.start(), vec![vec![2]]);
+        download.import(top_level(vec![(vec![3], vec![45])]), 0);
+        assert!(download.is_complete());
+        let state = download.into_state();
+        assert_eq!(state.0.len(), 1);
+        assert_eq!(
+            state.0[0].key_values,
+            vec![
+                (vec![1], vec![43]),
+                (vec![2], vec![44]),
+                (vec![3], vec![45])
+            ]
+        );
+    }
+
+    #[test]
+    fn tracks_child_tries() {
+        let mut download = StateDownload::new();
+        let child_key = [DEFAULT_CHILD_STORAGE_KEY_PREFIX, b"child"].concat();
+        let child_root = vec![7; 32];
+        download.import(
+            KeyValueStates(vec![
+                KeyValueStorageLevel {
+                    state_root: Vec::new(),
+                    parent_storage_keys: Vec::new(),
+                    key_values: vec![(vec![1], vec![43]), (child_key.clone(), child_root.clone())],
+                },
+                KeyValueStorageLevel {
+                    state_root: child_root.clone(),
+                    parent_storage_keys: Vec::new(),
+                    key_values: vec![(vec![2], vec![44])],
+                },
+            ]),
+            0,
+        );
+        assert!(download.is_complete());
+        let state = download.into_state();
+        let child = state
+            .0
+            .iter()
+            .find(|level| level.state_root == child_root)
+            .expect("child trie should be present");
+        assert_eq!(child.parent_storage_keys, vec![child_key]);
+        assert_eq!(child.key_values, vec![(vec![2], vec![44])]);
+        let top = state
+            .0
+            .iter()
+            .find(|level| level.state_root.is_empty())
+            .expect("top trie should be present");
+        assert_eq!(top.key_values, vec![(vec![1], vec![43])]);
+    }
+}
@@ -261,12 +261,10 @@ async fn compute_stats(
     let mut block_times = vec![];
 
     let timestamp = |number| async move {
-        anyhow::Ok(
-            connection
-                .get_timestamp(connection.get_block_hash(number).await?)
-                .await
-                .unwrap(),
-        )
+        connection
+            .try_get_timestamp(connection.get_block_hash(number).await?)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("No timestamp in block {}.", number))
     };
 
     for number in start_block..=end_block {
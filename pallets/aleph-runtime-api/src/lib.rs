@@ -2,13 +2,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use primitives::{
-    crypto::SignatureSet, AccountId, ApiError, AuthorityId, AuthoritySignature, Balance, Perbill,
-    Score, SessionAuthorityData, SessionCommittee, SessionIndex, SessionValidatorError, Version,
+    crypto::SignatureSet, AccountId, ApiError, AuthorityId, AuthoritySignature, Balance, BanInfo,
+    ElectionPreview, Perbill, ProductionEquivocationProof, Score, SessionAuthorityData,
+    SessionCommittee, SessionIndex, SessionValidatorError, Version,
 };
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+    #[api_version(5)]
     pub trait AlephSessionApi {
         fn next_session_authorities() -> Result<Vec<AuthorityId>, ApiError>;
         fn authorities() -> Vec<AuthorityId>;
@@ -39,5 +41,19 @@ sp_api::decl_runtime_apis! {
         fn current_era_payout() -> (Balance, Balance);
         /// Submits score for a nonce in a session of performance of finality committee members.
         fn submit_abft_score(score: Score, signature: SignatureSet<AuthoritySignature>) -> Option<()>;
+        /// Submits a report of a block producer sealing two different blocks in the same slot.
+        #[api_version(2)]
+        fn submit_production_equivocation_report(proof: ProductionEquivocationProof) -> Option<()>;
+        /// Returns validators that would be elected for the next era if the elections were held
+        /// now, with their total backing. `None` if the elections would fail.
+        #[api_version(3)]
+        fn next_era_election_preview() -> Option<ElectionPreview<AccountId>>;
+        /// Returns the last ABFT score submitted in each of the recent sessions, ordered by session.
+        #[api_version(4)]
+        fn abft_score_history() -> Vec<Score>;
+        /// Returns the most recent bans of the validator, oldest first, including the ones that
+        /// have already expired.
+        #[api_version(5)]
+        fn ban_history(validator: AccountId) -> Vec<BanInfo>;
     }
 }
@@ -0,0 +1,83 @@
+use frame_benchmarking::{account, v2::*};
+use frame_system::RawOrigin;
+use parity_scale_codec::Decode;
+use primitives::{crypto::SignatureSet, Score};
+use sp_runtime::traits::TrailingZeroInput;
+use sp_std::{vec, vec::Vec};
+
+use crate::*;
+
+/// The largest committee we expect to see.
+const MAX_COMMITTEE_SIZE: u32 = 1000;
+
+fn authority<T: Config>() -> T::AuthorityId {
+    T::AuthorityId::decode(&mut TrailingZeroInput::zeroes())
+        .expect("infinite input is enough to decode any key")
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn set_emergency_finalizer() {
+        let emergency_finalizer = authority::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, emergency_finalizer);
+    }
+
+    #[benchmark]
+    fn schedule_finality_version_change() {
+        let session = Pallet::<T>::current_session() + 2;
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, DEFAULT_FINALITY_VERSION, session);
+
+        assert!(FinalityScheduledVersionChange::<T>::get().is_some());
+    }
+
+    #[benchmark]
+    fn set_inflation_parameters() {
+        let azero_cap = AzeroCap::<T>::get();
+        let horizon = ExponentialInflationHorizon::<T>::get();
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, Some(azero_cap), Some(horizon));
+    }
+
+    #[benchmark]
+    fn unsigned_submit_abft_score(n: Linear<0, MAX_COMMITTEE_SIZE>) {
+        let score = Score {
+            session_id: Pallet::<T>::current_session(),
+            nonce: 1,
+            points: vec![1; n as usize],
+        };
+        // The signature is only checked when validating the transaction.
+        let signature = SignatureSet(Vec::new());
+
+        #[extrinsic_call]
+        _(RawOrigin::None, score, signature);
+
+        assert_eq!(LastScoreNonce::<T>::get(), 1);
+    }
+
+    #[benchmark]
+    fn on_new_session(n: Linear<0, MAX_COMMITTEE_SIZE>) {
+        let accounts: Vec<T::AccountId> = (0..n).map(|i| account("validator", i, 0)).collect();
+        let queued: Vec<_> = accounts.iter().map(|a| (a, authority::<T>())).collect();
+        NextFinalityCommittee::<T>::put(accounts.clone());
+        QueuedEmergencyFinalizer::<T>::put(authority::<T>());
+        Pallet::<T>::set_next_emergency_finalizer(authority::<T>());
+
+        #[block]
+        {
+            Pallet::<T>::update_emergency_finalizer();
+            Pallet::<T>::update_authorities(queued);
+        }
+
+        assert_eq!(NextAuthorities::<T>::get().len(), n as usize);
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(&[]), crate::mock::Test);
+}
@@ -1,3 +1,4 @@
+use frame_support::traits::Get;
 use primitives::{AbftScoresProvider, FinalityCommitteeManager, Score, SessionIndex};
 use sp_std::vec::Vec;
 
@@ -25,6 +26,7 @@ where
     fn start_session(start_index: SessionIndex) {
         <T as Config>::SessionManager::start_session(start_index);
         Self::update_version_change_history();
+        Self::prune_abft_score_history(start_index);
     }
 }
 
@@ -52,6 +54,13 @@ where
             }
         }
     }
+
+    // Sessions start one by one, so removing the single score that got too old is enough.
+    fn prune_abft_score_history(session: SessionIndex) {
+        if let Some(expired) = session.checked_sub(T::AbftScoreHistoryDepth::get()) {
+            AbftScores::<T>::remove(expired);
+        }
+    }
 }
 
 impl<T: Config> FinalityCommitteeManager<T::AccountId> for Pallet<T> {
@@ -65,10 +74,6 @@ impl<T: Config> AbftScoresProvider for Pallet<T> {
         AbftScores::<T>::get(session_id)
     }
 
-    fn clear_scores() {
-        let _result = AbftScores::<T>::clear(u32::MAX, None);
-    }
-
     fn clear_nonce() {
         LastScoreNonce::<T>::kill();
     }
@@ -1,6 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -8,6 +10,7 @@ mod tests;
 
 mod impls;
 pub mod traits;
+pub mod weights;
 
 use frame_support::{
     sp_runtime::BoundToRuntimeAppPublic,
@@ -19,7 +22,6 @@ use primitives::{
     Balance, SessionIndex, Version, VersionChange, DEFAULT_FINALITY_VERSION,
     LEGACY_FINALITY_VERSION, TOKEN,
 };
-use sp_runtime::Perbill;
 use sp_std::prelude::*;
 
 /// The current storage version.
@@ -46,7 +48,7 @@ pub mod pallet {
     use sp_std::marker::PhantomData;
 
     use super::*;
-    use crate::traits::NextSessionAuthorityProvider;
+    use crate::{traits::NextSessionAuthorityProvider, weights::WeightInfo};
 
     #[pallet::config]
     pub trait Config:
@@ -58,6 +60,11 @@ pub mod pallet {
         type SessionManager: SessionManager<<Self as frame_system::Config>::AccountId>;
         type NextSessionAuthorityProvider: NextSessionAuthorityProvider<Self>;
         type TotalIssuanceProvider: TotalIssuanceProvider;
+        /// For how many sessions the ABFT scores are kept in [`AbftScores`].
+        #[pallet::constant]
+        type AbftScoreHistoryDepth: Get<SessionIndex>;
+        /// Weight information for extrinsics and session hooks in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     pub type Signature<T> = <<T as Config>::AuthorityId as RuntimeAppPublic>::Signature;
@@ -138,7 +145,8 @@ pub mod pallet {
     pub(super) type FinalityScheduledVersionChange<T: Config> =
         StorageValue<_, VersionChange, OptionQuery>;
 
-    // clear this storage on session end
+    /// The last ABFT score submitted in each of the recent sessions, kept for
+    /// [`Config::AbftScoreHistoryDepth`] sessions.
     #[pallet::storage]
     #[pallet::getter(fn abft_scores)]
     pub type AbftScores<T: Config> = StorageMap<_, Twox64Concat, SessionIndex, Score>;
@@ -352,6 +360,13 @@ pub mod pallet {
             Ok(())
         }
 
+        /// The scores kept in [`AbftScores`], ordered by session.
+        pub fn abft_score_history() -> Vec<Score> {
+            let mut scores: Vec<_> = AbftScores::<T>::iter_values().collect();
+            scores.sort_by_key(|score| score.session_id);
+            scores
+        }
+
         pub fn submit_abft_score(
             score: Score,
             signature: SignatureSet<Signature<T>>,
@@ -368,7 +383,7 @@ pub mod pallet {
         /// Sets the emergency finalization key. If called in session `N` the key can be used to
         /// finalize blocks from session `N+2` onwards, until it gets overridden.
         #[pallet::call_index(0)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((T::WeightInfo::set_emergency_finalizer(), DispatchClass::Operational))]
         pub fn set_emergency_finalizer(
             origin: OriginFor<T>,
             emergency_finalizer: T::AuthorityId,
@@ -386,7 +401,10 @@ pub mod pallet {
         /// In order to cancel a scheduled version change, a new version change should be scheduled
         /// with the same version as the current one.
         #[pallet::call_index(1)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((
+            T::WeightInfo::schedule_finality_version_change(),
+            DispatchClass::Operational
+        ))]
         pub fn schedule_finality_version_change(
             origin: OriginFor<T>,
             version_incoming: Version,
@@ -409,7 +427,7 @@ pub mod pallet {
 
         /// Sets the values of inflation parameters.
         #[pallet::call_index(2)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((T::WeightInfo::set_inflation_parameters(), DispatchClass::Operational))]
         pub fn set_inflation_parameters(
             origin: OriginFor<T>,
             azero_cap: Option<Balance>,
@@ -444,10 +462,9 @@ pub mod pallet {
             Ok(())
         }
 
-        // fix weight, take into account validate_unsigned
-        #[pallet::call_index(3)]
-        #[pallet::weight(T::BlockWeights::get().max_block * Perbill::from_percent(10))]
         /// Stores abft score
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::unsigned_submit_abft_score(score.points.len() as u32))]
         pub fn unsigned_submit_abft_score(
             origin: OriginFor<T>,
             score: Score,
@@ -504,7 +521,12 @@ pub mod pallet {
         {
             Self::update_emergency_finalizer();
             if changed {
-                Self::update_authorities(queued_validators.collect());
+                let queued_validators: Vec<_> = queued_validators.collect();
+                frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                    T::WeightInfo::on_new_session(queued_validators.len() as u32),
+                    DispatchClass::Mandatory,
+                );
+                Self::update_authorities(queued_validators);
             }
         }
 
@@ -163,6 +163,8 @@ impl Config for Test {
     type SessionManager = ();
     type NextSessionAuthorityProvider = Session;
     type TotalIssuanceProvider = TotalIssuanceProvider;
+    type AbftScoreHistoryDepth = ConstU32<3>;
+    type WeightInfo = ();
 }
 
 pub fn to_authority(id: &u64) -> AuthorityId {
@@ -1,5 +1,5 @@
 use frame_support::{storage_alias, traits::OneSessionHandler};
-use primitives::VersionChange;
+use primitives::{crypto::SignatureSet, Score, VersionChange};
 
 use crate::{mock::*, NextFinalityCommittee};
 
@@ -161,3 +161,37 @@ fn test_finality_version_scheduling() {
         assert!(scheduling_result.is_err());
     })
 }
+
+#[test]
+fn test_abft_score_history_is_pruned() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(1);
+
+        for session in 1..=5 {
+            let score = Score {
+                session_id: session,
+                nonce: session,
+                points: vec![session as u16; 2],
+            };
+            assert_eq!(
+                Aleph::unsigned_submit_abft_score(
+                    RuntimeOrigin::none(),
+                    score,
+                    SignatureSet(Vec::new())
+                )
+                .map(|_| ()),
+                Ok(())
+            );
+            run_session(session + 1);
+        }
+
+        let sessions: Vec<_> = Aleph::abft_score_history()
+            .into_iter()
+            .map(|score| score.session_id)
+            .collect();
+        assert_eq!(sessions, vec![4, 5]);
+        assert_eq!(Aleph::abft_scores(1), None);
+        assert_eq!(Aleph::abft_scores(5).map(|score| score.nonce), Some(5));
+    })
+}
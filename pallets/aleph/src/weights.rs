@@ -0,0 +1,113 @@
+//! Weights for pallet_aleph
+//!
+//! The storage accesses follow the benchmarks in `benchmarking.rs`, but the execution times are
+//! estimates that have not been measured yet. Replace them with the results measured on reference
+//! hardware by running `scripts/run_benchmarks.sh`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_aleph.
+pub trait WeightInfo {
+    fn set_emergency_finalizer() -> Weight;
+    fn schedule_finality_version_change() -> Weight;
+    fn set_inflation_parameters() -> Weight;
+    fn unsigned_submit_abft_score(n: u32, ) -> Weight;
+    fn on_new_session(n: u32, ) -> Weight;
+}
+
+/// Estimated weights for pallet_aleph, see the module documentation.
+pub struct AlephWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for AlephWeight<T> {
+    // Storage: `Aleph::NextEmergencyFinalizer` (r:0 w:1)
+    fn set_emergency_finalizer() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `Session::CurrentIndex` (r:1 w:0)
+    // Storage: `Aleph::FinalityScheduledVersionChange` (r:0 w:1)
+    fn schedule_finality_version_change() -> Weight {
+        Weight::from_parts(12_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `Aleph::AzeroCap` (r:1 w:1)
+    // Storage: `Aleph::ExponentialInflationHorizon` (r:1 w:1)
+    // Storage: `Balances::TotalIssuance` (r:1 w:0)
+    fn set_inflation_parameters() -> Weight {
+        Weight::from_parts(16_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    // Storage: `Aleph::LastScoreNonce` (r:0 w:1)
+    // Storage: `Aleph::AbftScores` (r:0 w:1)
+    /// The range of component `n` is `[0, 1000]`.
+    fn unsigned_submit_abft_score(n: u32, ) -> Weight {
+        Weight::from_parts(12_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(6_000_u64, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    // Storage: `Aleph::QueuedEmergencyFinalizer` (r:1 w:1)
+    // Storage: `Aleph::NextEmergencyFinalizer` (r:1 w:0)
+    // Storage: `Aleph::EmergencyFinalizer` (r:0 w:1)
+    // Storage: `Aleph::NextFinalityCommittee` (r:1 w:0)
+    // Storage: `Aleph::NextAuthorities` (r:1 w:1)
+    // Storage: `Aleph::Authorities` (r:0 w:1)
+    /// The range of component `n` is `[0, 1000]`.
+    fn on_new_session(n: u32, ) -> Weight {
+        Weight::from_parts(20_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(60_000_u64, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    // Storage: `Aleph::NextEmergencyFinalizer` (r:0 w:1)
+    fn set_emergency_finalizer() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `Session::CurrentIndex` (r:1 w:0)
+    // Storage: `Aleph::FinalityScheduledVersionChange` (r:0 w:1)
+    fn schedule_finality_version_change() -> Weight {
+        Weight::from_parts(12_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `Aleph::AzeroCap` (r:1 w:1)
+    // Storage: `Aleph::ExponentialInflationHorizon` (r:1 w:1)
+    // Storage: `Balances::TotalIssuance` (r:1 w:0)
+    fn set_inflation_parameters() -> Weight {
+        Weight::from_parts(16_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    // Storage: `Aleph::LastScoreNonce` (r:0 w:1)
+    // Storage: `Aleph::AbftScores` (r:0 w:1)
+    /// The range of component `n` is `[0, 1000]`.
+    fn unsigned_submit_abft_score(n: u32, ) -> Weight {
+        Weight::from_parts(12_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(6_000_u64, 0).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    // Storage: `Aleph::QueuedEmergencyFinalizer` (r:1 w:1)
+    // Storage: `Aleph::NextEmergencyFinalizer` (r:1 w:0)
+    // Storage: `Aleph::EmergencyFinalizer` (r:0 w:1)
+    // Storage: `Aleph::NextFinalityCommittee` (r:1 w:0)
+    // Storage: `Aleph::NextAuthorities` (r:1 w:1)
+    // Storage: `Aleph::Authorities` (r:0 w:1)
+    /// The range of component `n` is `[0, 1000]`.
+    fn on_new_session(n: u32, ) -> Weight {
+        Weight::from_parts(20_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(60_000_u64, 0).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+}
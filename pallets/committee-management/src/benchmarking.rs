@@ -0,0 +1,326 @@
+use frame_benchmarking::{account, v2::*};
+use frame_support::traits::{Currency, ReservableCurrency};
+use frame_system::RawOrigin;
+use pallet_elections::{CommitteeSize, CurrentEraValidators};
+use primitives::{
+    AuraId, BanInfo, BanReason, CommitteeSeats, CommitteeSelectionMode, EraValidators, Header,
+    HeaderT, ProductionEquivocationProof, SessionValidators, DEFAULT_BAN_REASON_LENGTH,
+};
+use sp_consensus_aura::{digests::CompatibleDigestItem, sr25519::AuthoritySignature, Slot};
+use sp_runtime::{Perbill, RuntimeAppPublic, Saturating};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+use crate::{
+    pallet::{
+        Banned, Call, Config, CurrentAndNextSessionValidatorsStorage, CurrentEraCommitteeSelection,
+        EquivocationSlashFraction, FinalityBanConfig, FinalityRewardCurve,
+        NextEraCommitteeSelection, Pallet, Probation, ProductionBanConfig, ReportedEquivocations,
+        SessionProducerKeys, UnbanConfig, UnderperformedFinalizerSessionCount,
+        UnderperformedValidatorSessionCount,
+    },
+    traits::EraInfoProvider,
+    BalanceOf, CurrentAndNextSessionValidators, ProbationInfo, ProductionBanConfigStruct,
+    UnbanConfig as UnbanConfigStruct, EQUIVOCATION_REPORT_SESSIONS,
+};
+
+/// The largest validator sets we expect to see.
+const MAX_RESERVED_VALIDATORS: u32 = 100;
+const MAX_VALIDATORS: u32 = 1000;
+
+fn accounts<T: Config>(name: &'static str, count: u32) -> Vec<T::AccountId> {
+    (0..count).map(|i| account(name, i, 0)).collect()
+}
+
+/// Makes `v` validators the validators of the current era, all of them in the committee.
+fn setup_era_validators<T: Config + pallet_elections::Config>(v: u32) -> Vec<T::AccountId> {
+    let reserved_count = v.min(MAX_RESERVED_VALIDATORS);
+    let reserved = accounts::<T>("reserved", reserved_count);
+    let non_reserved = accounts::<T>("non_reserved", v - reserved_count);
+    CommitteeSize::<T>::put(CommitteeSeats {
+        reserved_seats: reserved_count,
+        non_reserved_seats: v - reserved_count,
+        non_reserved_finality_seats: v - reserved_count,
+    });
+    CurrentEraValidators::<T>::put(EraValidators {
+        reserved: reserved.clone(),
+        non_reserved: non_reserved.clone(),
+    });
+
+    reserved.into_iter().chain(non_reserved).collect()
+}
+
+fn ban_all<T: Config>(validators: &[T::AccountId], start: u32) {
+    for validator in validators {
+        Banned::<T>::insert(
+            validator,
+            BanInfo {
+                reason: BanReason::InsufficientUptime(1),
+                start,
+            },
+        );
+    }
+}
+
+/// A bond comfortably above the existential deposit, and an account that can afford it.
+fn funded_bond<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+    let bond = T::Currency::minimum_balance().saturating_mul(10u32.into());
+    T::Currency::make_free_balance_be(who, bond.saturating_mul(10u32.into()));
+    bond
+}
+
+fn sealed_header(offender: &AuraId, slot: u64, parent: u8) -> Header {
+    let mut header = Header::new(
+        1,
+        Default::default(),
+        Default::default(),
+        [parent; 32].into(),
+        Default::default(),
+    );
+    header
+        .digest_mut()
+        .push(CompatibleDigestItem::<AuthoritySignature>::aura_pre_digest(
+            Slot::from(slot),
+        ));
+    let signature = offender
+        .sign(&header.hash())
+        .expect("the key was generated in the keystore");
+    header
+        .digest_mut()
+        .push(CompatibleDigestItem::<AuthoritySignature>::aura_seal(
+            signature,
+        ));
+    header
+}
+
+#[benchmarks(where T: pallet_elections::Config)]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn set_ban_config() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, Some(50), Some(3), Some(5), Some(10));
+
+        assert_eq!(ProductionBanConfig::<T>::get().ban_period, 10);
+    }
+
+    #[benchmark]
+    fn ban_from_committee() {
+        let banned: T::AccountId = account("banned", 0, 0);
+        let reason = vec![0; DEFAULT_BAN_REASON_LENGTH as usize];
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, banned.clone(), reason);
+
+        assert!(Banned::<T>::contains_key(banned));
+    }
+
+    #[benchmark]
+    fn cancel_ban() {
+        let banned: T::AccountId = account("banned", 0, 0);
+        ban_all::<T>(&[banned.clone()], 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, banned.clone());
+
+        assert!(!Banned::<T>::contains_key(banned));
+    }
+
+    #[benchmark]
+    fn set_lenient_threshold() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, 90);
+    }
+
+    #[benchmark]
+    fn set_finality_ban_config() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, Some(20), Some(3), Some(10));
+
+        assert_eq!(FinalityBanConfig::<T>::get().ban_period, 10);
+    }
+
+    #[benchmark]
+    fn report_production_equivocation() {
+        let offender_account: T::AccountId = account("offender", 0, 0);
+        let offender = AuraId::generate_pair(None);
+        SessionProducerKeys::<T>::insert(0, offender.clone(), offender_account.clone());
+        EquivocationSlashFraction::<T>::put(Perbill::from_percent(10));
+        let proof = ProductionEquivocationProof {
+            first_header: sealed_header(&offender, 7, 1),
+            second_header: sealed_header(&offender, 7, 2),
+            offender,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::None, Box::new(proof));
+
+        assert!(ReportedEquivocations::<T>::contains_key(
+            0,
+            (offender_account, 7)
+        ));
+    }
+
+    #[benchmark]
+    fn set_equivocation_slash_fraction() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, Perbill::from_percent(10));
+
+        assert_eq!(
+            EquivocationSlashFraction::<T>::get(),
+            Perbill::from_percent(10)
+        );
+    }
+
+    #[benchmark]
+    fn set_committee_selection_mode() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, CommitteeSelectionMode::StakeWeighted);
+
+        assert_eq!(
+            NextEraCommitteeSelection::<T>::get(),
+            CommitteeSelectionMode::StakeWeighted
+        );
+    }
+
+    #[benchmark]
+    fn set_finality_reward_curve() {
+        #[extrinsic_call]
+        _(
+            RawOrigin::Root,
+            Some(10),
+            Some(100),
+            Some(Perbill::from_percent(50)),
+        );
+
+        assert_eq!(
+            FinalityRewardCurve::<T>::get().minimal_reward_threshold,
+            100
+        );
+    }
+
+    #[benchmark]
+    fn request_unban() {
+        let validator: T::AccountId = account("banned", 0, 0);
+        let bond = funded_bond::<T>(&validator);
+        UnbanConfig::<T>::put(UnbanConfigStruct {
+            bond,
+            minimal_ban_period: 0,
+            probation_period: 1,
+        });
+        ban_all::<T>(&[validator.clone()], 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(validator.clone()));
+
+        assert!(Probation::<T>::contains_key(validator));
+    }
+
+    #[benchmark]
+    fn set_unban_config() {
+        let config = UnbanConfigStruct {
+            bond: T::Currency::minimum_balance(),
+            minimal_ban_period: 1,
+            probation_period: 10,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, Some(config.clone()));
+
+        assert_eq!(UnbanConfig::<T>::get(), Some(config));
+    }
+
+    // The era starting path: fresh bans are announced and the committee is drawn weighted by
+    // stake, which is the more expensive selection mode.
+    #[benchmark]
+    fn new_session(v: Linear<1, MAX_VALIDATORS>) {
+        setup_era_validators::<T>(v);
+        let fresh_ban_era = T::EraInfoProvider::active_era().unwrap_or(1) + 1;
+        ban_all::<T>(&accounts::<T>("banned", v), fresh_ban_era);
+        NextEraCommitteeSelection::<T>::put(CommitteeSelectionMode::StakeWeighted);
+
+        #[block]
+        {
+            Pallet::<T>::on_new_session(1, true);
+        }
+
+        assert_eq!(
+            CurrentEraCommitteeSelection::<T>::get(),
+            CommitteeSelectionMode::StakeWeighted
+        );
+        assert_eq!(
+            CurrentAndNextSessionValidatorsStorage::<T>::get()
+                .next
+                .producers
+                .len(),
+            v as usize
+        );
+    }
+
+    // Every producer missed all their blocks and gets banned.
+    #[benchmark]
+    fn end_session(v: Linear<1, MAX_VALIDATORS>) {
+        let validators = setup_era_validators::<T>(v);
+        ProductionBanConfig::<T>::put(ProductionBanConfigStruct {
+            underperformed_session_count_threshold: 1,
+            ..Default::default()
+        });
+        CurrentAndNextSessionValidatorsStorage::<T>::put(CurrentAndNextSessionValidators {
+            current: SessionValidators {
+                producers: validators.clone(),
+                finalizers: validators.clone(),
+                non_committee: Vec::new(),
+            },
+            next: SessionValidators::default(),
+        });
+
+        #[block]
+        {
+            Pallet::<T>::on_end_session(1);
+        }
+
+        assert!(Banned::<T>::contains_key(&validators[0]));
+    }
+
+    // The era starting path, with underperformance counters cleared, all bans expiring, all
+    // probations ending and the equivocation reports of an outdated session forgotten.
+    #[benchmark]
+    fn start_session(v: Linear<1, MAX_VALIDATORS>) {
+        let validators = setup_era_validators::<T>(v);
+        for validator in &validators {
+            let bond = funded_bond::<T>(validator);
+            T::Currency::reserve(validator, bond).expect("the account has enough funds");
+            Probation::<T>::insert(validator, ProbationInfo { bond, end: 1 });
+        }
+        ProductionBanConfig::<T>::put(ProductionBanConfigStruct {
+            clean_session_counter_delay: 1,
+            ban_period: 1,
+            ..Default::default()
+        });
+        for validator in &validators {
+            UnderperformedValidatorSessionCount::<T>::insert(validator, 1);
+            UnderperformedFinalizerSessionCount::<T>::insert(validator, 1);
+        }
+        ban_all::<T>(&accounts::<T>("banned", v), 0);
+        for (i, validator) in validators.iter().enumerate() {
+            let key = AuraId::generate_pair(None);
+            SessionProducerKeys::<T>::insert(0, key, validator.clone());
+            ReportedEquivocations::<T>::insert(0, (validator.clone(), i as u64), ());
+        }
+
+        #[block]
+        {
+            Pallet::<T>::on_start_session(EQUIVOCATION_REPORT_SESSIONS, Some(1));
+        }
+
+        assert_eq!(Banned::<T>::iter().count(), 0);
+        assert_eq!(Probation::<T>::iter().count(), 0);
+        assert_eq!(ReportedEquivocations::<T>::iter_prefix(0).count(), 0);
+    }
+
+    impl_benchmark_test_suite!(
+        Pallet,
+        crate::mock::new_benchmark_ext(),
+        crate::mock::TestRuntime
+    );
+}
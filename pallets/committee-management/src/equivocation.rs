@@ -0,0 +1,120 @@
+use frame_support::pallet_prelude::Get;
+use log::info;
+use primitives::{AuraId, BanReason, Header, HeaderT, ProductionEquivocationProof};
+use sp_consensus_aura::{digests::CompatibleDigestItem, sr25519::AuthoritySignature};
+use sp_runtime::{traits::Zero, RuntimeAppPublic};
+use sp_staking::SessionIndex;
+use sp_std::boxed::Box;
+
+use crate::{
+    pallet::{
+        Config, EquivocationSlashFraction, Error, Event, Pallet, ReportedEquivocations,
+        SessionProducerKeys,
+    },
+    traits::{EquivocationSlasher, ProducerKeysProvider},
+    EQUIVOCATION_REPORT_SESSIONS, LOG_TARGET,
+};
+
+/// Returns the slot the header was produced in, provided it was sealed by `author`.
+fn sealed_slot(header: &Header, author: &AuraId) -> Option<u64> {
+    let mut header = header.clone();
+    let seal = header.digest_mut().pop()?;
+    let signature = CompatibleDigestItem::<AuthoritySignature>::as_aura_seal(&seal)?;
+    let slot = header
+        .digest()
+        .logs()
+        .iter()
+        .find_map(CompatibleDigestItem::<AuthoritySignature>::as_aura_pre_digest)?;
+    let pre_hash = header.hash();
+    match author.verify(&pre_hash, &signature) {
+        true => Some(slot.into()),
+        false => None,
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Records the owners of the block production keys of the starting session, and forgets the
+    /// keys and reported equivocations of the session that falls out of the report window.
+    pub(crate) fn note_session_producer_keys(session: SessionIndex) {
+        for (validator, key) in T::ProducerKeysProvider::starting_session_keys() {
+            SessionProducerKeys::<T>::insert(session, key, validator);
+        }
+
+        if let Some(outdated) = session.checked_sub(EQUIVOCATION_REPORT_SESSIONS) {
+            let _ = SessionProducerKeys::<T>::clear_prefix(outdated, u32::MAX, None);
+            let _ = ReportedEquivocations::<T>::clear_prefix(outdated, u32::MAX, None);
+        }
+    }
+
+    /// Checks the proof, returning the account of the offender, and the session and the slot of
+    /// the equivocation.
+    pub(crate) fn check_equivocation_proof(
+        proof: &ProductionEquivocationProof,
+    ) -> Result<(T::AccountId, SessionIndex, u64), Error<T>> {
+        let ProductionEquivocationProof {
+            offender,
+            first_header,
+            second_header,
+        } = proof;
+        if first_header.hash() == second_header.hash() {
+            return Err(Error::<T>::InvalidEquivocationProof);
+        }
+        let slot =
+            sealed_slot(first_header, offender).ok_or(Error::<T>::InvalidEquivocationProof)?;
+        if sealed_slot(second_header, offender) != Some(slot) {
+            return Err(Error::<T>::InvalidEquivocationProof);
+        }
+
+        let session = first_header.number / T::SessionPeriod::get();
+        if second_header.number / T::SessionPeriod::get() != session {
+            return Err(Error::<T>::InvalidEquivocationProof);
+        }
+        // Keys are only kept for the sessions within the report window that have already started.
+        if SessionProducerKeys::<T>::iter_key_prefix(session)
+            .next()
+            .is_none()
+        {
+            return Err(Error::<T>::EquivocationOutsideReportWindow);
+        }
+        let account = SessionProducerKeys::<T>::get(session, offender)
+            .ok_or(Error::<T>::UnknownEquivocationOffender)?;
+        if ReportedEquivocations::<T>::contains_key(session, (&account, slot)) {
+            return Err(Error::<T>::EquivocationAlreadyReported);
+        }
+
+        Ok((account, session, slot))
+    }
+
+    /// Bans the offender and, if configured, slashes them for an equivocation in `slot` of
+    /// `session`.
+    pub(crate) fn punish_production_equivocation(
+        offender: T::AccountId,
+        session: SessionIndex,
+        slot: u64,
+    ) {
+        info!(
+            target: LOG_TARGET,
+            "Production equivocation of {:?} in slot {} of session {}", offender, slot, session
+        );
+        ReportedEquivocations::<T>::insert(session, (&offender, slot), ());
+        Self::ban_validator(&offender, BanReason::ProductionEquivocation(slot));
+
+        let fraction = EquivocationSlashFraction::<T>::get();
+        if !fraction.is_zero() {
+            T::EquivocationSlasher::slash(&offender, session, fraction);
+        }
+
+        Self::deposit_event(Event::ProductionEquivocationReported(offender, slot));
+    }
+
+    /// Submits an unsigned report of the equivocation to the transaction pool.
+    pub fn submit_production_equivocation_report(proof: ProductionEquivocationProof) -> Option<()> {
+        use frame_system::offchain::SubmitTransaction;
+
+        let call = crate::pallet::Call::report_production_equivocation {
+            proof: Box::new(proof),
+        };
+        SubmitTransaction::<T, crate::pallet::Call<T>>::submit_unsigned_transaction(call.into())
+            .ok()
+    }
+}
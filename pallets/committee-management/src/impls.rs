@@ -1,13 +1,18 @@
-use frame_support::pallet_prelude::Get;
+use frame_support::{
+    pallet_prelude::Get,
+    traits::{OnUnbalanced, ReservableCurrency},
+};
 use log::info;
 use parity_scale_codec::Encode;
 use primitives::{
     AbftScoresProvider, BanHandler, BanInfo, BanReason, BannedValidators, CommitteeSeats,
-    EraValidators, SessionCommittee, SessionValidatorError, SessionValidators, ValidatorProvider,
+    CommitteeSelectionMode, EraValidators, SessionCommittee, SessionValidatorError,
+    SessionValidators, ValidatorProvider,
 };
-use rand::{seq::SliceRandom, SeedableRng};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
 use rand_pcg::Pcg32;
-use sp_runtime::{Perbill, Perquintill};
+use sp_io::hashing::{blake2_128, blake2_256};
+use sp_runtime::{PerThing, Perbill, Perquintill, Saturating};
 use sp_staking::{EraIndex, SessionIndex};
 use sp_std::{
     collections::{btree_map::BTreeMap, btree_set::BTreeSet},
@@ -17,13 +22,14 @@ use sp_std::{
 
 use crate::{
     pallet::{
-        Banned, Config, CurrentAndNextSessionValidatorsStorage, Event, Pallet,
+        BanHistory, Banned, CommitteeRandomness, Config, CurrentAndNextSessionValidatorsStorage,
+        CurrentEraCommitteeSelection, Event, NextEraCommitteeSelection, Pallet, Probation,
         SessionValidatorBlockCount, UnderperformedFinalizerSessionCount,
         UnderperformedValidatorSessionCount, ValidatorEraTotalReward,
     },
     traits::{EraInfoProvider, ValidatorRewardsHandler},
-    CurrentAndNextSessionValidators, LenientThreshold, ProductionBanConfigStruct,
-    ValidatorExtractor, ValidatorTotalRewards, LOG_TARGET,
+    CurrentAndNextSessionValidators, FinalityRewardCurveStruct, LenientThreshold,
+    ProductionBanConfigStruct, ValidatorExtractor, ValidatorTotalRewards, LOG_TARGET,
 };
 
 const MAX_REWARD: u32 = 1_000_000_000;
@@ -129,6 +135,87 @@ fn select_committee_inner<AccountId: Clone + PartialEq>(
     })
 }
 
+/// Draws up to `count` distinct items, each time with probability proportional to its weight.
+fn choose_weighted<T: Clone, R: Rng>(
+    candidates: &[(T, u128)],
+    count: usize,
+    rng: &mut R,
+) -> Option<Vec<T>> {
+    if candidates.is_empty() || count == 0 {
+        return None;
+    }
+
+    // Candidates without any weight still get a tiny chance of being chosen.
+    let mut remaining: Vec<_> = candidates
+        .iter()
+        .map(|(candidate, weight)| (candidate.clone(), (*weight).max(1)))
+        .collect();
+    let mut chosen = Vec::new();
+
+    while chosen.len() < count && !remaining.is_empty() {
+        let total = remaining
+            .iter()
+            .fold(0u128, |total, (_, weight)| total.saturating_add(*weight));
+        let mut point = rng.gen_range(0..total);
+        let index = remaining
+            .iter()
+            .position(|(_, weight)| match point < *weight {
+                true => true,
+                false => {
+                    point -= weight;
+                    false
+                }
+            })
+            .unwrap_or(remaining.len() - 1);
+        chosen.push(remaining.swap_remove(index).0);
+    }
+
+    Some(chosen)
+}
+
+fn select_weighted_committee_inner<AccountId: Clone + PartialEq>(
+    current_session: SessionIndex,
+    randomness: [u8; 32],
+    reserved_seats: usize,
+    non_reserved_seats: usize,
+    non_reserved_finality_seats: usize,
+    reserved: &[AccountId],
+    non_reserved: &[(AccountId, u128)],
+) -> Option<SessionCommittee<AccountId>> {
+    // Reserved validators are chosen as in the round robin mode, but the non-reserved ones are
+    // drawn weighted by their exposure, using randomness unknown before the era is planned. The
+    // finality committee gets the first `non_reserved_finality_seats` of them, since the draw
+    // order is random anyway.
+    let mut rng = Pcg32::from_seed(blake2_128(&(randomness, current_session).encode()));
+
+    let reserved_committee = choose_for_session(reserved, reserved_seats, current_session as usize);
+    let non_reserved_committee = choose_weighted(non_reserved, non_reserved_seats, &mut rng);
+
+    let mut finalizers = reserved_committee.clone().unwrap_or_default();
+    finalizers.extend(
+        non_reserved_committee
+            .iter()
+            .flatten()
+            .take(non_reserved_finality_seats)
+            .cloned(),
+    );
+
+    let mut producers = match (reserved_committee, non_reserved_committee) {
+        (Some(rc), Some(nrc)) => Some(rc.into_iter().chain(nrc).collect()),
+        (Some(rc), _) => Some(rc),
+        (_, Some(nrc)) => Some(nrc),
+        _ => None,
+    }?;
+
+    producers.shuffle(&mut rng);
+    finalizers.shuffle(&mut rng);
+
+    Some(SessionCommittee {
+        producers,
+        finalizers,
+    })
+}
+
 fn calculate_adjusted_session_points(
     sessions_per_era: EraIndex,
     blocks_to_produce_per_session: u32,
@@ -151,6 +238,22 @@ fn calculate_adjusted_session_points(
     ) * total_possible_reward as u64) as u32
 }
 
+/// The fraction of the session reward a finalizer with the given ABFT score gets paid.
+fn calculate_finality_reward_fraction(curve: &FinalityRewardCurveStruct, score: u16) -> Perbill {
+    if score <= curve.full_reward_threshold {
+        return Perbill::one();
+    }
+    if score >= curve.minimal_reward_threshold {
+        return curve.minimal_reward;
+    }
+
+    let lag = Perbill::from_rational(
+        score - curve.full_reward_threshold,
+        curve.minimal_reward_threshold - curve.full_reward_threshold,
+    );
+    Perbill::one().saturating_sub(lag * curve.minimal_reward.left_from_one())
+}
+
 pub fn compute_validator_scaled_total_rewards<V>(
     validator_totals: Vec<(V, u128)>,
 ) -> Vec<(V, u32)> {
@@ -235,13 +338,36 @@ impl<T: Config> Pallet<T> {
             .saturating_div(T::ValidatorProvider::current_era_committee_size().size())
     }
 
-    pub fn adjust_rewards_for_session() {
+    /// Fractions of the session reward the finalizers earned with their ABFT scores in `session`.
+    /// Without any score submitted nobody's reward is reduced.
+    fn finality_reward_fractions(
+        session: SessionIndex,
+        finalizers: Vec<T::AccountId>,
+    ) -> BTreeMap<T::AccountId, Perbill> {
+        let curve = Self::finality_reward_curve();
+        T::AbftScoresProvider::scores_for_session(session)
+            .map(|score| {
+                finalizers
+                    .into_iter()
+                    .zip(score.points)
+                    .map(|(validator, points)| {
+                        (
+                            validator,
+                            calculate_finality_reward_fraction(&curve, points),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn adjust_rewards_for_session(session: SessionIndex) {
         let CurrentAndNextSessionValidators {
             current:
                 SessionValidators {
                     producers,
+                    finalizers,
                     non_committee,
-                    ..
                 },
             ..
         } = CurrentAndNextSessionValidatorsStorage::<T>::get();
@@ -252,6 +378,7 @@ impl<T: Config> Pallet<T> {
             .0;
 
         let lenient_threshold = LenientThreshold::<T>::get();
+        let finality_reward_fractions = Self::finality_reward_fractions(session, finalizers);
 
         let rewards = Self::reward_for_session_non_committee(
             non_committee,
@@ -267,7 +394,14 @@ impl<T: Config> Pallet<T> {
             blocks_per_session,
             &validator_total_rewards,
             lenient_threshold,
-        ));
+        ))
+        .map(|(validator, reward)| {
+            let fraction = finality_reward_fractions
+                .get(&validator)
+                .copied()
+                .unwrap_or(Perbill::one());
+            (validator, fraction * reward)
+        });
 
         T::ValidatorRewardsHandler::add_rewards(rewards);
     }
@@ -314,14 +448,46 @@ impl<T: Config> Pallet<T> {
             non_reserved_finality_seats,
         } = committee_seats;
 
-        select_committee_inner(
-            current_session,
-            reserved_seats as usize,
-            non_reserved_seats as usize,
-            non_reserved_finality_seats as usize,
-            reserved,
-            non_reserved,
-        )
+        match CurrentEraCommitteeSelection::<T>::get() {
+            CommitteeSelectionMode::RoundRobin => select_committee_inner(
+                current_session,
+                reserved_seats as usize,
+                non_reserved_seats as usize,
+                non_reserved_finality_seats as usize,
+                reserved,
+                non_reserved,
+            ),
+            CommitteeSelectionMode::StakeWeighted => {
+                let era = T::EraInfoProvider::current_era().unwrap_or(0);
+                let exposures: BTreeMap<_, _> = T::ValidatorRewardsHandler::validator_totals(era)
+                    .into_iter()
+                    .collect();
+                let non_reserved: Vec<_> = non_reserved
+                    .iter()
+                    .map(|v| (v.clone(), exposures.get(v).copied().unwrap_or(0)))
+                    .collect();
+
+                select_weighted_committee_inner(
+                    current_session,
+                    CommitteeRandomness::<T>::get(),
+                    reserved_seats as usize,
+                    non_reserved_seats as usize,
+                    non_reserved_finality_seats as usize,
+                    reserved,
+                    &non_reserved,
+                )
+            }
+        }
+    }
+
+    /// Fixes the committee selection mode for the era being planned, and mixes fresh randomness
+    /// in for drawing its committees.
+    pub(crate) fn plan_era_committee_selection(new_index: SessionIndex) {
+        CurrentEraCommitteeSelection::<T>::put(NextEraCommitteeSelection::<T>::get());
+        let parent_hash = frame_system::Pallet::<T>::parent_hash();
+        CommitteeRandomness::<T>::mutate(|randomness| {
+            *randomness = blake2_256(&(*randomness, parent_hash, new_index).encode())
+        });
     }
 
     pub(crate) fn rotate_committee(
@@ -426,7 +592,6 @@ impl<T: Config> Pallet<T> {
             );
             let _result = UnderperformedValidatorSessionCount::<T>::clear(u32::MAX, None);
             let _result = UnderperformedFinalizerSessionCount::<T>::clear(u32::MAX, None);
-            T::AbftScoresProvider::clear_scores();
         }
     }
 
@@ -448,8 +613,41 @@ impl<T: Config> Pallet<T> {
             .unwrap_or(0)
             .saturating_add(1);
         if T::BanHandler::can_ban(validator) {
-            Banned::<T>::insert(validator, BanInfo { reason, start });
+            let ban_info = BanInfo { reason, start };
+            Self::record_ban(validator, ban_info.clone());
+            Banned::<T>::insert(validator, ban_info);
             T::ValidatorExtractor::remove_validator(validator);
+            Self::slash_unban_bond(validator);
+        }
+    }
+
+    /// Appends the ban to the history of the validator, forgetting the oldest one if needed.
+    pub(crate) fn record_ban(validator: &T::AccountId, ban_info: BanInfo) {
+        BanHistory::<T>::mutate(validator, |history| {
+            if history.is_full() {
+                history.remove(0);
+            }
+            let _ = history.try_push(ban_info);
+        });
+    }
+
+    fn slash_unban_bond(validator: &T::AccountId) {
+        if let Some(probation) = Probation::<T>::take(validator) {
+            let (imbalance, _) = T::Currency::slash_reserved(validator, probation.bond);
+            T::UnbanBondSlash::on_unbalanced(imbalance);
+            Self::deposit_event(Event::UnbanBondSlashed(validator.clone(), probation.bond));
+        }
+    }
+
+    /// Returns the bonds of the reinstated validators whose probation is over.
+    pub(crate) fn end_probations(active_era: EraIndex) {
+        let ended: Vec<_> = Probation::<T>::iter()
+            .filter(|(_, probation)| probation.end <= active_era)
+            .collect();
+        for (validator, probation) in ended {
+            Probation::<T>::remove(&validator);
+            T::Currency::unreserve(&validator, probation.bond);
+            Self::deposit_event(Event::UnbanBondReturned(validator, probation.bond));
         }
     }
 
@@ -504,11 +702,17 @@ impl<T: Config> Pallet<T> {
 mod tests {
     use std::collections::{BTreeSet, VecDeque};
 
-    use sp_runtime::Perquintill;
-
-    use crate::impls::{
-        calculate_adjusted_session_points, compute_validator_scaled_total_rewards,
-        select_committee_inner, MAX_REWARD,
+    use rand::SeedableRng;
+    use rand_pcg::Pcg32;
+    use sp_runtime::{Perbill, Perquintill};
+
+    use crate::{
+        impls::{
+            calculate_adjusted_session_points, calculate_finality_reward_fraction, choose_weighted,
+            compute_validator_scaled_total_rewards, select_committee_inner,
+            select_weighted_committee_inner, MAX_REWARD,
+        },
+        FinalityRewardCurveStruct,
     };
 
     const THRESHOLD: Perquintill = Perquintill::from_percent(90);
@@ -567,6 +771,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finality_reward_fraction_follows_curve() {
+        let curve = FinalityRewardCurveStruct {
+            full_reward_threshold: 10,
+            minimal_reward_threshold: 30,
+            minimal_reward: Perbill::from_percent(20),
+        };
+        let fraction = |score| calculate_finality_reward_fraction(&curve, score);
+
+        assert_eq!(fraction(0), Perbill::one());
+        assert_eq!(fraction(10), Perbill::one());
+        assert_eq!(fraction(20), Perbill::from_percent(60));
+        assert_eq!(fraction(25), Perbill::from_percent(40));
+        assert_eq!(fraction(30), Perbill::from_percent(20));
+        assert_eq!(fraction(u16::MAX), Perbill::from_percent(20));
+    }
+
+    #[test]
+    fn default_finality_reward_curve_does_not_reduce_rewards() {
+        let curve = FinalityRewardCurveStruct::default();
+
+        assert_eq!(
+            calculate_finality_reward_fraction(&curve, 0),
+            Perbill::one()
+        );
+        assert_eq!(
+            calculate_finality_reward_fraction(&curve, u16::MAX),
+            Perbill::one()
+        );
+    }
+
     #[test]
     fn scale_points_correctly_when_under_u32() {
         assert_eq!(
@@ -653,4 +888,51 @@ mod tests {
             assert_eq!(expected_committee, committee,);
         }
     }
+
+    #[test]
+    fn weighted_choice_favours_heavier_candidates() {
+        let candidates = vec![(0, 1_000), (1, 1), (2, 1), (3, 1)];
+        let mut rng = Pcg32::seed_from_u64(0);
+        let heavy_first = (0..1000)
+            .filter(|_| choose_weighted(&candidates, 1, &mut rng) == Some(vec![0]))
+            .count();
+
+        assert!(heavy_first > 950);
+    }
+
+    #[test]
+    fn weighted_choice_picks_distinct_candidates() {
+        let candidates: Vec<_> = (0..10).map(|i| (i, i as u128)).collect();
+        let mut rng = Pcg32::seed_from_u64(0);
+
+        let chosen = choose_weighted(&candidates, 7, &mut rng).unwrap();
+        assert_eq!(chosen.iter().collect::<BTreeSet<_>>().len(), 7);
+
+        let chosen = choose_weighted(&candidates, 20, &mut rng).unwrap();
+        assert_eq!(chosen.into_iter().collect::<BTreeSet<_>>().len(), 10);
+
+        assert_eq!(choose_weighted(&candidates, 0, &mut rng), None);
+        assert_eq!(choose_weighted::<u32, _>(&[], 3, &mut rng), None);
+    }
+
+    #[test]
+    fn weighted_committee_depends_on_randomness() {
+        let reserved: Vec<_> = (0..4).collect();
+        let non_reserved: Vec<_> = (4..100).map(|i| (i, 100)).collect();
+        let committee = |randomness| {
+            select_weighted_committee_inner(7, randomness, 4, 10, 3, &reserved, &non_reserved)
+                .expect("Expected non-empty committee!")
+        };
+
+        let first = committee([0; 32]);
+        assert_eq!(first, committee([0; 32]));
+        assert_ne!(first, committee([1; 32]));
+
+        let producers: BTreeSet<_> = first.producers.iter().collect();
+        let finalizers: BTreeSet<_> = first.finalizers.iter().collect();
+        assert_eq!(producers.len(), 14);
+        assert_eq!(finalizers.len(), 7);
+        assert!(reserved.iter().all(|r| finalizers.contains(r)));
+        assert!(finalizers.is_subset(&producers));
+    }
 }
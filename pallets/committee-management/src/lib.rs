@@ -3,24 +3,34 @@
 
 extern crate core;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod equivocation;
 mod impls;
 mod manager;
+pub mod migration;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 mod traits;
+pub mod weights;
 
-use frame_support::{pallet_prelude::Get, traits::StorageVersion};
+use frame_support::{
+    pallet_prelude::Get,
+    traits::{Currency, StorageVersion},
+};
 pub use manager::SessionAndEraManager;
 pub use pallet::*;
 use parity_scale_codec::{Decode, Encode};
 use primitives::{
     BanInfo, FinalityBanConfig as FinalityBanConfigStruct,
+    FinalityRewardCurve as FinalityRewardCurveStruct,
     ProductionBanConfig as ProductionBanConfigStruct, SessionValidators, LENIENT_THRESHOLD,
 };
 use scale_info::TypeInfo;
 use sp_runtime::Perquintill;
+use sp_staking::{EraIndex, SessionIndex};
 use sp_std::{collections::btree_map::BTreeMap, default::Default};
 pub use traits::*;
 
@@ -43,6 +53,30 @@ impl<T> Default for CurrentAndNextSessionValidators<T> {
     }
 }
 
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+    <T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Terms on which banned validators can get reinstated before their bans expire.
+#[derive(Decode, Encode, TypeInfo, Debug, Clone, PartialEq, Eq)]
+pub struct UnbanConfig<Balance> {
+    /// Bond reserved from a validator requesting reinstatement.
+    pub bond: Balance,
+    /// How many eras a validator has to stay banned before requesting reinstatement.
+    pub minimal_ban_period: EraIndex,
+    /// For how many eras after reinstatement the bond is slashed if the validator gets banned again.
+    pub probation_period: EraIndex,
+}
+
+/// A reinstated validator, whose bond gets returned at the start of era `end`.
+#[derive(Decode, Encode, TypeInfo, Debug, Clone, PartialEq, Eq)]
+pub struct ProbationInfo<Balance> {
+    pub bond: Balance,
+    pub end: EraIndex,
+}
+
 pub struct DefaultLenientThreshold;
 
 impl Get<Perquintill> for DefaultLenientThreshold {
@@ -51,32 +85,55 @@ impl Get<Perquintill> for DefaultLenientThreshold {
     }
 }
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 pub(crate) const LOG_TARGET: &str = "pallet-committee-management";
+/// The largest meaningful ABFT score: 10min with 5 rounds per second.
+pub(crate) const MAX_FINALITY_PERFORMANCE: u16 = 10 * 60 * 5;
+/// For how many sessions, counting the current one, block production equivocations can be
+/// reported.
+pub(crate) const EQUIVOCATION_REPORT_SESSIONS: SessionIndex = 6;
 
 #[frame_support::pallet]
 #[pallet_doc("../README.md")]
 pub mod pallet {
     use frame_support::{
-        dispatch::DispatchResult, ensure, pallet_prelude::*, BoundedVec, Twox64Concat,
+        dispatch::DispatchResult,
+        ensure,
+        pallet_prelude::*,
+        traits::{OnUnbalanced, ReservableCurrency},
+        BoundedVec, Twox64Concat,
+    };
+    use frame_system::{
+        ensure_none, ensure_root, ensure_signed,
+        pallet_prelude::{BlockNumberFor, OriginFor},
     };
-    use frame_system::{ensure_root, pallet_prelude::OriginFor};
+    use pallets_support::StorageMigration;
     use primitives::{
-        AbftScoresProvider, BanHandler, BanReason, BlockCount, FinalityCommitteeManager,
-        SessionCount, SessionValidators, ValidatorProvider,
+        AbftScoresProvider, AuraId, BanHandler, BanReason, BlockCount, CommitteeSelectionMode,
+        FinalityCommitteeManager, ProductionEquivocationProof, SessionCount, SessionValidators,
+        ValidatorProvider,
     };
     use sp_runtime::{Perbill, Perquintill};
-    use sp_staking::EraIndex;
-    use sp_std::vec::Vec;
+    use sp_staking::{EraIndex, SessionIndex};
+    use sp_std::{boxed::Box, vec::Vec};
 
     use crate::{
-        traits::{EraInfoProvider, ValidatorRewardsHandler},
-        BanInfo, CurrentAndNextSessionValidators, DefaultLenientThreshold, FinalityBanConfigStruct,
-        ProductionBanConfigStruct, ValidatorExtractor, ValidatorTotalRewards, STORAGE_VERSION,
+        migration,
+        traits::{
+            EquivocationSlasher, EraInfoProvider, ProducerKeysProvider, StashProvider,
+            ValidatorRewardsHandler,
+        },
+        weights::WeightInfo,
+        BalanceOf, BanInfo, CurrentAndNextSessionValidators, DefaultLenientThreshold,
+        FinalityBanConfigStruct, FinalityRewardCurveStruct, NegativeImbalanceOf, ProbationInfo,
+        ProductionBanConfigStruct, UnbanConfig as UnbanConfigStruct, ValidatorExtractor,
+        ValidatorTotalRewards, MAX_FINALITY_PERFORMANCE, STORAGE_VERSION,
     };
 
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config:
+        frame_system::Config + frame_system::offchain::SendTransactionTypes<Call<Self>>
+    {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// Something that handles bans
         type BanHandler: BanHandler<AccountId = Self::AccountId>;
@@ -90,9 +147,24 @@ pub mod pallet {
         type ValidatorExtractor: ValidatorExtractor<AccountId = Self::AccountId>;
         type FinalityCommitteeManager: FinalityCommitteeManager<Self::AccountId>;
         type AbftScoresProvider: AbftScoresProvider;
+        /// Something that provides the block production keys of the validators of a session.
+        type ProducerKeysProvider: ProducerKeysProvider<AccountId = Self::AccountId>;
+        /// Something that slashes validators reported for equivocations.
+        type EquivocationSlasher: EquivocationSlasher<Self::AccountId>;
+        /// Something that maps controller accounts to the stashes they control.
+        type StashProvider: StashProvider<AccountId = Self::AccountId>;
+        /// The currency in which validators requesting reinstatement put up their bonds.
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Handler for the slashed bonds of reinstated validators that got banned again.
+        type UnbanBondSlash: OnUnbalanced<NegativeImbalanceOf<Self>>;
         /// Nr of blocks in the session.
         #[pallet::constant]
         type SessionPeriod: Get<u32>;
+        /// How many past bans of every validator are kept in [`BanHistory`].
+        #[pallet::constant]
+        type MaxBanHistory: Get<u32>;
+        /// Weight information for extrinsics and session hooks in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -129,6 +201,18 @@ pub mod pallet {
     #[pallet::storage]
     pub type Banned<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BanInfo>;
 
+    /// The most recent bans of every validator, oldest first. Unlike [`Banned`], entries are kept
+    /// after the bans expire or get lifted.
+    #[pallet::storage]
+    #[pallet::getter(fn ban_history)]
+    pub type BanHistory<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<BanInfo, T::MaxBanHistory>,
+        ValueQuery,
+    >;
+
     /// SessionValidators in the current session.
     #[pallet::storage]
     #[pallet::getter(fn current_session_validators)]
@@ -145,6 +229,59 @@ pub mod pallet {
     #[pallet::getter(fn finality_ban_config)]
     pub type FinalityBanConfig<T> = StorageValue<_, FinalityBanConfigStruct, ValueQuery>;
 
+    /// How the session rewards of finalizers are reduced based on their ABFT scores.
+    #[pallet::storage]
+    #[pallet::getter(fn finality_reward_curve)]
+    pub type FinalityRewardCurve<T> = StorageValue<_, FinalityRewardCurveStruct, ValueQuery>;
+
+    /// Owners of the block production keys in the sessions for which equivocations can still be
+    /// reported.
+    #[pallet::storage]
+    pub type SessionProducerKeys<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, SessionIndex, Blake2_128Concat, AuraId, T::AccountId>;
+
+    /// Block production equivocations that have already been punished, by session, offender and
+    /// slot. Pruned together with [`SessionProducerKeys`].
+    #[pallet::storage]
+    pub type ReportedEquivocations<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SessionIndex,
+        Twox64Concat,
+        (T::AccountId, u64),
+        (),
+        OptionQuery,
+    >;
+
+    /// Fraction of the stake slashed for a block production equivocation, zero disables slashing.
+    #[pallet::storage]
+    #[pallet::getter(fn equivocation_slash_fraction)]
+    pub type EquivocationSlashFraction<T> = StorageValue<_, Perbill, ValueQuery>;
+
+    /// How the non-reserved part of the committee will be chosen, starting from the next era.
+    #[pallet::storage]
+    pub type NextEraCommitteeSelection<T> = StorageValue<_, CommitteeSelectionMode, ValueQuery>;
+
+    /// How the non-reserved part of the committee is chosen in the current era.
+    #[pallet::storage]
+    #[pallet::getter(fn current_era_committee_selection)]
+    pub type CurrentEraCommitteeSelection<T> = StorageValue<_, CommitteeSelectionMode, ValueQuery>;
+
+    /// Terms of reinstating banned validators early, `None` if it is not possible.
+    #[pallet::storage]
+    #[pallet::getter(fn unban_config)]
+    pub type UnbanConfig<T: Config> = StorageValue<_, UnbanConfigStruct<BalanceOf<T>>, OptionQuery>;
+
+    /// Reinstated validators whose bonds are slashed if they get banned again.
+    #[pallet::storage]
+    pub type Probation<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, ProbationInfo<BalanceOf<T>>>;
+
+    /// Randomness used for drawing the committees of the current era, mixed with the parent hash
+    /// of the block planning every new era.
+    #[pallet::storage]
+    pub type CommitteeRandomness<T> = StorageValue<_, [u8; 32], ValueQuery>;
+
     #[pallet::error]
     pub enum Error<T> {
         /// Raised in any scenario [`ProductionBanConfig`] is invalid
@@ -159,6 +296,43 @@ pub mod pallet {
 
         /// Lenient threshold not in [0-100] range
         InvalidLenientThreshold,
+
+        /// Headers in the equivocation proof are not two different blocks sealed by the offender
+        /// in the same slot
+        InvalidEquivocationProof,
+
+        /// Block production key from the equivocation proof does not belong to any validator
+        /// of the session the headers are from
+        UnknownEquivocationOffender,
+
+        /// Headers in the equivocation proof are from a session that is too old or has not
+        /// started yet
+        EquivocationOutsideReportWindow,
+
+        /// This equivocation has already been punished
+        EquivocationAlreadyReported,
+
+        /// Raised in any scenario [`FinalityRewardCurve`] is invalid
+        /// * `full_reward_threshold` must not be greater than `minimal_reward_threshold`,
+        /// * both thresholds must be at most the largest allowed finality performance.
+        InvalidFinalityRewardCurve,
+
+        /// Banned validators cannot be reinstated early, as there is no [`UnbanConfig`]
+        UnbanDisabled,
+
+        /// Neither the caller nor the stash they control is banned
+        NotBanned,
+
+        /// The validator has not been banned for [`UnbanConfig`]'s `minimal_ban_period` yet
+        BanTooRecent,
+
+        /// Only bans for insufficient uptime or finalization can be lifted early, equivocation
+        /// and manual bans have to expire or be cancelled
+        BanNotLiftable,
+
+        /// Raised in any scenario [`UnbanConfig`] is invalid
+        /// * `probation_period` must be a positive number.
+        InvalidUnbanConfig,
     }
 
     #[pallet::event]
@@ -175,13 +349,37 @@ pub mod pallet {
 
         /// Validator is underperforimg in finality committee
         ValidatorUnderperforming(T::AccountId),
+
+        /// Validator produced two different blocks in the given slot
+        ProductionEquivocationReported(T::AccountId, u64),
+
+        /// Fraction of stake slashed for equivocations has changed
+        SetEquivocationSlashFraction(Perbill),
+
+        /// Committee selection mode for the next era has changed
+        SetCommitteeSelectionMode(CommitteeSelectionMode),
+
+        /// Curve scaling the rewards of finalizers has changed
+        SetFinalityRewardCurve(FinalityRewardCurveStruct),
+
+        /// Terms of reinstating banned validators have changed
+        SetUnbanConfig(Option<UnbanConfigStruct<BalanceOf<T>>>),
+
+        /// Banned validator has been reinstated, putting up the bond
+        ValidatorReinstated(T::AccountId, BalanceOf<T>),
+
+        /// Reinstated validator stayed unbanned through the probation, the bond was returned
+        UnbanBondReturned(T::AccountId, BalanceOf<T>),
+
+        /// Reinstated validator got banned again during the probation, the bond was slashed
+        UnbanBondSlashed(T::AccountId, BalanceOf<T>),
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Sets ban config, it has an immediate effect
         #[pallet::call_index(1)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((T::WeightInfo::set_ban_config(), DispatchClass::Operational))]
         pub fn set_ban_config(
             origin: OriginFor<T>,
             minimal_expected_performance: Option<u8>,
@@ -232,7 +430,7 @@ pub mod pallet {
 
         /// Schedule a non-reserved node to be banned out from the committee at the end of the era
         #[pallet::call_index(2)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((T::WeightInfo::ban_from_committee(), DispatchClass::Operational))]
         pub fn ban_from_committee(
             origin: OriginFor<T>,
             banned: T::AccountId,
@@ -243,7 +441,7 @@ pub mod pallet {
                 .try_into()
                 .map_err(|_| Error::<T>::BanReasonTooBig)?;
 
-            let reason = BanReason::OtherReason(bounded_description);
+            let reason = BanReason::Manual(bounded_description);
             Self::ban_validator(&banned, reason);
 
             Ok(())
@@ -251,7 +449,7 @@ pub mod pallet {
 
         /// Cancel the ban of the node
         #[pallet::call_index(3)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((T::WeightInfo::cancel_ban(), DispatchClass::Operational))]
         pub fn cancel_ban(origin: OriginFor<T>, banned: T::AccountId) -> DispatchResult {
             ensure_root(origin)?;
             Banned::<T>::remove(banned);
@@ -261,7 +459,7 @@ pub mod pallet {
 
         /// Set lenient threshold
         #[pallet::call_index(4)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((T::WeightInfo::set_lenient_threshold(), DispatchClass::Operational))]
         pub fn set_lenient_threshold(
             origin: OriginFor<T>,
             threshold_percent: u8,
@@ -279,7 +477,7 @@ pub mod pallet {
 
         /// Sets ban config, it has an immediate effect
         #[pallet::call_index(5)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((T::WeightInfo::set_finality_ban_config(), DispatchClass::Operational))]
         pub fn set_finality_ban_config(
             origin: OriginFor<T>,
             minimal_expected_performance: Option<u16>,
@@ -292,7 +490,7 @@ pub mod pallet {
 
             if let Some(minimal_expected_performance) = minimal_expected_performance {
                 ensure!(
-                    minimal_expected_performance <= 10 * 60 * 5, // 10min with 5 rounds per second
+                    minimal_expected_performance <= MAX_FINALITY_PERFORMANCE,
                     Error::<T>::InvalidBanConfig
                 );
                 current_committee_ban_config.minimal_expected_performance =
@@ -320,6 +518,178 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Reports a block producer that sealed two different blocks in the same slot. The
+        /// offender gets banned and slashed by [`EquivocationSlashFraction`] of their stake.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::report_production_equivocation())]
+        pub fn report_production_equivocation(
+            origin: OriginFor<T>,
+            proof: Box<ProductionEquivocationProof>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            let (offender, session, slot) = Self::check_equivocation_proof(&proof)?;
+            Self::punish_production_equivocation(offender, session, slot);
+
+            Ok(Pays::No.into())
+        }
+
+        /// Sets the fraction of stake slashed for block production equivocations
+        #[pallet::call_index(7)]
+        #[pallet::weight((T::WeightInfo::set_equivocation_slash_fraction(), DispatchClass::Operational))]
+        pub fn set_equivocation_slash_fraction(
+            origin: OriginFor<T>,
+            fraction: Perbill,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            EquivocationSlashFraction::<T>::put(fraction);
+            Self::deposit_event(Event::SetEquivocationSlashFraction(fraction));
+
+            Ok(())
+        }
+
+        /// Sets how the non-reserved part of the committee is chosen, starting from the next era
+        #[pallet::call_index(8)]
+        #[pallet::weight((T::WeightInfo::set_committee_selection_mode(), DispatchClass::Operational))]
+        pub fn set_committee_selection_mode(
+            origin: OriginFor<T>,
+            mode: CommitteeSelectionMode,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            NextEraCommitteeSelection::<T>::put(mode);
+            Self::deposit_event(Event::SetCommitteeSelectionMode(mode));
+
+            Ok(())
+        }
+
+        /// Sets the curve scaling the rewards of finalizers by their ABFT scores, it has an
+        /// immediate effect
+        #[pallet::call_index(9)]
+        #[pallet::weight((T::WeightInfo::set_finality_reward_curve(), DispatchClass::Operational))]
+        pub fn set_finality_reward_curve(
+            origin: OriginFor<T>,
+            full_reward_threshold: Option<u16>,
+            minimal_reward_threshold: Option<u16>,
+            minimal_reward: Option<Perbill>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let mut curve = Self::finality_reward_curve();
+            if let Some(full_reward_threshold) = full_reward_threshold {
+                curve.full_reward_threshold = full_reward_threshold;
+            }
+            if let Some(minimal_reward_threshold) = minimal_reward_threshold {
+                curve.minimal_reward_threshold = minimal_reward_threshold;
+            }
+            if let Some(minimal_reward) = minimal_reward {
+                curve.minimal_reward = minimal_reward;
+            }
+            ensure!(
+                curve.full_reward_threshold <= curve.minimal_reward_threshold
+                    && curve.minimal_reward_threshold <= MAX_FINALITY_PERFORMANCE,
+                Error::<T>::InvalidFinalityRewardCurve
+            );
+
+            FinalityRewardCurve::<T>::put(curve.clone());
+            Self::deposit_event(Event::SetFinalityRewardCurve(curve));
+
+            Ok(())
+        }
+
+        /// Lifts the ban of the validator whose stash or controller signs the call, once it has
+        /// lasted for [`UnbanConfig`]'s `minimal_ban_period`. Only bans for insufficient uptime
+        /// or finalization can be lifted. The bond gets reserved from the
+        /// stash, and is returned after the probation period, or slashed if the validator gets
+        /// banned again before then. The validator has to declare their intention to validate
+        /// again afterwards.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::request_unban())]
+        pub fn request_unban(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let config = Self::unban_config().ok_or(Error::<T>::UnbanDisabled)?;
+            let validator = match Banned::<T>::contains_key(&who) {
+                true => who,
+                false => T::StashProvider::stash_of(&who).ok_or(Error::<T>::NotBanned)?,
+            };
+            let ban_info = Banned::<T>::get(&validator).ok_or(Error::<T>::NotBanned)?;
+            ensure!(
+                matches!(
+                    ban_info.reason,
+                    BanReason::InsufficientUptime(_) | BanReason::InsufficientFinalization(_)
+                ),
+                Error::<T>::BanNotLiftable
+            );
+            let active_era = T::EraInfoProvider::active_era().unwrap_or(0);
+            ensure!(
+                active_era >= ban_info.start.saturating_add(config.minimal_ban_period),
+                Error::<T>::BanTooRecent
+            );
+
+            T::Currency::reserve(&validator, config.bond)?;
+            Banned::<T>::remove(&validator);
+            Probation::<T>::insert(
+                &validator,
+                ProbationInfo {
+                    bond: config.bond,
+                    end: active_era.saturating_add(config.probation_period),
+                },
+            );
+            Self::deposit_event(Event::ValidatorReinstated(validator, config.bond));
+
+            Ok(())
+        }
+
+        /// Sets the terms of reinstating banned validators early, `None` disables it. Validators
+        /// already on probation keep their terms.
+        #[pallet::call_index(11)]
+        #[pallet::weight((T::WeightInfo::set_unban_config(), DispatchClass::Operational))]
+        pub fn set_unban_config(
+            origin: OriginFor<T>,
+            config: Option<UnbanConfigStruct<BalanceOf<T>>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some(config) = &config {
+                ensure!(config.probation_period > 0, Error::<T>::InvalidUnbanConfig);
+            }
+
+            UnbanConfig::<T>::set(config.clone());
+            Self::deposit_event(Event::SetUnbanConfig(config));
+
+            Ok(())
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            match StorageVersion::get::<Pallet<T>>() == StorageVersion::new(1) {
+                true => migration::v2::Migration::<T>::migrate(),
+                false => T::DbWeight::get().reads(1),
+            }
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            if let Call::report_production_equivocation { proof } = call {
+                let (offender, session, slot) =
+                    Self::check_equivocation_proof(proof).map_err(|e| match e {
+                        Error::<T>::EquivocationAlreadyReported
+                        | Error::<T>::EquivocationOutsideReportWindow => InvalidTransaction::Stale,
+                        _ => InvalidTransaction::BadProof,
+                    })?;
+                ValidTransaction::with_tag_prefix("ProductionEquivocation")
+                    .priority(TransactionPriority::MAX)
+                    .and_provides((offender, session, slot))
+                    .propagate(true)
+                    .build()
+            } else {
+                InvalidTransaction::Call.into()
+            }
+        }
     }
 
     #[pallet::genesis_config]
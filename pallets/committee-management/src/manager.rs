@@ -1,13 +1,18 @@
+use frame_support::{dispatch::DispatchClass, weights::Weight};
 use frame_system::pallet_prelude::BlockNumberFor;
 use log::debug;
 use pallet_session::SessionManager;
-use primitives::{AbftScoresProvider, EraManager, FinalityCommitteeManager, SessionCommittee};
+use primitives::{
+    AbftScoresProvider, EraManager, EraValidators, FinalityCommitteeManager, SessionCommittee,
+    ValidatorProvider,
+};
 use sp_staking::{EraIndex, SessionIndex};
 use sp_std::{marker::PhantomData, vec::Vec};
 
 use crate::{
     pallet::{Config, Pallet, SessionValidatorBlockCount},
     traits::EraInfoProvider,
+    weights::WeightInfo,
     LOG_TARGET,
 };
 
@@ -16,13 +21,18 @@ use crate::{
 /// 1. Block `B` initialized
 /// 2. `end_session(S)` is called
 /// *  Based on block count we might mark the session for a given validator as underperformed
-/// *  We update rewards and clear block count for the session `S`.
+/// *  We update rewards, reduced for lagging finalizers based on their ABFT scores, and clear
+///    block count for the session `S`.
 /// 3. `start_session(S + 1)` is called.
-/// *  if session `S+1` starts new era we populate totals and unban all validators whose ban expired.
+/// *  if session `S+1` starts new era we populate totals, unban all validators whose ban expired
+///    and return the bonds of reinstated validators whose probation is over.
 /// *  if session `S+1` % `clean_session_counter_delay` == 0, we clean up underperformed session counter.
 /// * `clean_session_counter_delay` is read from pallet's storage
+/// *  we record who owns the block production keys in session `S+1`, so that its equivocations
+///    can be reported, and forget the keys of the session that falls out of the report window.
 /// 4. `new_session(S + 2)` is called.
-/// *  If session `S+2` starts new era we emit fresh bans events
+/// *  If session `S+2` starts new era we emit fresh bans events, fix the committee selection mode
+///    for the era and refresh the committee randomness.
 /// *  We rotate the validators for session `S + 2` using the information about reserved and non reserved validators.
 
 impl<T> pallet_authorship::EventHandler<T::AccountId, BlockNumberFor<T>> for Pallet<T>
@@ -36,6 +46,71 @@ where
     }
 }
 
+impl<T: Config> Pallet<T> {
+    /// Session hooks are not part of any extrinsic, so their weight has to be registered
+    /// separately. All of them depend on the number of validators in the era.
+    fn register_session_weight(weight: impl FnOnce(u32) -> Weight) {
+        let EraValidators {
+            reserved,
+            non_reserved,
+        } = T::ValidatorProvider::current_era_validators();
+        let validators = reserved.len().saturating_add(non_reserved.len()) as u32;
+        frame_system::Pallet::<T>::register_extra_weight_unchecked(
+            weight(validators),
+            DispatchClass::Mandatory,
+        );
+    }
+
+    pub(crate) fn on_new_session(
+        new_index: SessionIndex,
+        starts_era: bool,
+    ) -> Option<Vec<T::AccountId>> {
+        Self::register_session_weight(T::WeightInfo::new_session);
+        if starts_era {
+            Self::emit_fresh_bans_event();
+            Self::plan_era_committee_selection(new_index);
+        }
+
+        let SessionCommittee {
+            producers,
+            finalizers,
+        } = Self::rotate_committee(new_index)?;
+        // Notify about elected next session finality committee
+        T::FinalityCommitteeManager::on_next_session_finality_committee(finalizers);
+
+        Some(producers)
+    }
+
+    pub(crate) fn on_end_session(end_index: SessionIndex) {
+        Self::register_session_weight(T::WeightInfo::end_session);
+        Self::adjust_rewards_for_session(end_index);
+        Self::calculate_underperforming_validators();
+        Self::calculate_underperforming_finalizers(end_index);
+        // clear block count after calculating stats for underperforming validators, as they use
+        // SessionValidatorBlockCount for that
+        let result = SessionValidatorBlockCount::<T>::clear(u32::MAX, None);
+        debug!(
+            target: LOG_TARGET,
+            "Result of clearing the `SessionValidatorBlockCount`, {:?}",
+            result.deconstruct()
+        );
+
+        T::AbftScoresProvider::clear_nonce();
+    }
+
+    pub(crate) fn on_start_session(start_index: SessionIndex, starts_era: Option<EraIndex>) {
+        Self::register_session_weight(T::WeightInfo::start_session);
+        Self::clear_underperformance_session_counter(start_index);
+        Self::note_session_producer_keys(start_index);
+
+        if let Some(era) = starts_era {
+            Self::update_validator_total_rewards(era);
+            Self::clear_expired_bans(era);
+            Self::end_probations(era);
+        }
+    }
+}
+
 /// SessionManager that also fires EraManager functions. It is responsible for rotation of the committee,
 /// bans and rewards logic.
 ///
@@ -115,45 +190,21 @@ where
 {
     fn new_session(new_index: SessionIndex) -> Option<Vec<C::AccountId>> {
         T::new_session(new_index);
-        if let Some(era) = Self::session_starts_next_era(new_index) {
+        let next_era = Self::session_starts_next_era(new_index);
+        if let Some(era) = next_era {
             EM::on_new_era(era);
-            Pallet::<C>::emit_fresh_bans_event();
         }
 
-        let SessionCommittee {
-            producers,
-            finalizers,
-        } = Pallet::<C>::rotate_committee(new_index)?;
-        // Notify about elected next session finality committee
-        C::FinalityCommitteeManager::on_next_session_finality_committee(finalizers);
-
-        Some(producers)
+        Pallet::<C>::on_new_session(new_index, next_era.is_some())
     }
 
     fn end_session(end_index: SessionIndex) {
         T::end_session(end_index);
-        Pallet::<C>::adjust_rewards_for_session();
-        Pallet::<C>::calculate_underperforming_validators();
-        Pallet::<C>::calculate_underperforming_finalizers(end_index);
-        // clear block count after calculating stats for underperforming validators, as they use
-        // SessionValidatorBlockCount for that
-        let result = SessionValidatorBlockCount::<C>::clear(u32::MAX, None);
-        debug!(
-            target: LOG_TARGET,
-            "Result of clearing the `SessionValidatorBlockCount`, {:?}",
-            result.deconstruct()
-        );
-
-        C::AbftScoresProvider::clear_nonce();
+        Pallet::<C>::on_end_session(end_index);
     }
 
     fn start_session(start_index: SessionIndex) {
         T::start_session(start_index);
-        Pallet::<C>::clear_underperformance_session_counter(start_index);
-
-        if let Some(era) = Self::session_starts_era(start_index) {
-            Pallet::<C>::update_validator_total_rewards(era);
-            Pallet::<C>::clear_expired_bans(era);
-        }
+        Pallet::<C>::on_start_session(start_index, Self::session_starts_era(start_index));
     }
 }
@@ -0,0 +1,78 @@
+pub mod v2 {
+    #[cfg(feature = "try-runtime")]
+    use frame_support::ensure;
+    use frame_support::{
+        pallet_prelude::{StorageVersion, Weight},
+        traits::{Get, OnRuntimeUpgrade},
+    };
+    use log::info;
+    #[cfg(feature = "try-runtime")]
+    use pallets_support::ensure_storage_version;
+    #[cfg(feature = "try-runtime")]
+    use parity_scale_codec::{Decode, Encode};
+    use primitives::{BanInfo, BanReason};
+    #[cfg(feature = "try-runtime")]
+    use sp_runtime::TryRuntimeError;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    use crate::{
+        pallet::{Banned, Config, Pallet},
+        LOG_TARGET,
+    };
+
+    /// Manual bans used to be recorded as [`BanReason::OtherReason`], they are
+    /// [`BanReason::Manual`] now. The current bans also become the first entries of the ban
+    /// history.
+    pub struct Migration<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            Banned::<T>::translate::<BanInfo, _>(|validator, ban_info| {
+                translated += 1;
+                let reason = match ban_info.reason {
+                    BanReason::OtherReason(reason) => BanReason::Manual(reason),
+                    reason => reason,
+                };
+                let ban_info = BanInfo {
+                    reason,
+                    start: ban_info.start,
+                };
+                Pallet::<T>::record_ban(&validator, ban_info.clone());
+                Some(ban_info)
+            });
+            StorageVersion::new(2).put::<Pallet<T>>();
+            info!(
+                target: LOG_TARGET,
+                "Migrated {} bans to storage version 2", translated
+            );
+
+            T::DbWeight::get().reads_writes(2 * translated + 1, 2 * translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            ensure_storage_version::<Pallet<T>>(1)?;
+            Ok((Banned::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure_storage_version::<Pallet<T>>(2)?;
+            let banned = u32::decode(&mut state.as_slice())
+                .map_err(|_| "Failed to decode the number of bans")?;
+            ensure!(
+                Banned::<T>::iter_keys().count() as u32 == banned,
+                "Some bans got lost"
+            );
+            ensure!(
+                Banned::<T>::iter_values()
+                    .all(|ban_info| !matches!(ban_info.reason, BanReason::OtherReason(_))),
+                "Some manual bans were not migrated"
+            );
+
+            Ok(())
+        }
+    }
+}
@@ -8,11 +8,11 @@ use frame_support::{
 use frame_system::pallet_prelude::BlockNumberFor;
 use pallet_staking::{ExposureOf, Forcing};
 use primitives::{
-    AuthorityId, CommitteeSeats, SessionIndex, SessionInfoProvider,
+    AuraId, AuthorityId, CommitteeSeats, SessionIndex, SessionInfoProvider,
     TotalIssuanceProvider as TotalIssuanceProviderT, DEFAULT_MAX_WINNERS, DEFAULT_SESSIONS_PER_ERA,
     DEFAULT_SESSION_PERIOD,
 };
-use sp_core::{ConstU64, H256};
+use sp_core::{sr25519, ConstU64, Pair, H256};
 use sp_runtime::{
     impl_opaque_keys,
     testing::{TestXt, UintAuthorityId},
@@ -200,6 +200,8 @@ impl pallet_aleph::Config for TestRuntime {
     >;
     type NextSessionAuthorityProvider = Session;
     type TotalIssuanceProvider = TotalIssuanceProvider;
+    type AbftScoreHistoryDepth = ConstU32<3>;
+    type WeightInfo = ();
 }
 
 impl<C> frame_system::offchain::SendTransactionTypes<C> for TestRuntime
@@ -234,6 +236,37 @@ impl pallet_elections::Config for TestRuntime {
     type ValidatorProvider = Staking;
     type MaxWinners = MaxWinners;
     type BannedValidators = CommitteeManagement;
+    type WeightInfo = ();
+}
+
+/// Block production key of the given account, used to seal test headers.
+pub fn producer_key(account: AccountId) -> sr25519::Pair {
+    let mut seed = [0; 32];
+    seed[..8].copy_from_slice(&account.to_le_bytes());
+    sr25519::Pair::from_seed(&seed)
+}
+
+pub struct MockProducerKeysProvider;
+impl ProducerKeysProvider for MockProducerKeysProvider {
+    type AccountId = AccountId;
+
+    fn starting_session_keys() -> Vec<(AccountId, AuraId)> {
+        Session::validators()
+            .into_iter()
+            .map(|validator| (validator, producer_key(validator).public().into()))
+            .collect()
+    }
+}
+
+parameter_types! {
+    pub static Slashes: Vec<(AccountId, SessionIndex, Perbill)> = vec![];
+}
+
+pub struct MockEquivocationSlasher;
+impl EquivocationSlasher<AccountId> for MockEquivocationSlasher {
+    fn slash(offender: &AccountId, session: SessionIndex, fraction: Perbill) {
+        Slashes::mutate(|slashes| slashes.push((*offender, session, fraction)));
+    }
 }
 
 impl Config for TestRuntime {
@@ -245,7 +278,14 @@ impl Config for TestRuntime {
     type ValidatorExtractor = Staking;
     type FinalityCommitteeManager = Aleph;
     type SessionPeriod = SessionPeriod;
+    type MaxBanHistory = ConstU32<2>;
     type AbftScoresProvider = Aleph;
+    type ProducerKeysProvider = MockProducerKeysProvider;
+    type EquivocationSlasher = MockEquivocationSlasher;
+    type StashProvider = Staking;
+    type Currency = Balances;
+    type UnbanBondSlash = ();
+    type WeightInfo = ();
 }
 
 pub fn active_era() -> EraIndex {
@@ -400,3 +440,20 @@ impl TestExtBuilder {
         ext
     }
 }
+
+/// Externalities for benchmark tests, with a keystore for signing equivocating headers.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_benchmark_ext() -> sp_io::TestExternalities {
+    use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
+
+    let mut ext = TestExtBuilder::new(TestBuilderConfig {
+        reserved_validators: (0..4).collect(),
+        non_reserved_validators: (4..10).collect(),
+        non_reserved_seats: 3,
+        non_reserved_finality_seats: 3,
+    })
+    .build();
+    ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+
+    ext
+}
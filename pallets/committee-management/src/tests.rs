@@ -1,15 +1,31 @@
 use std::collections::BTreeSet;
 
+use frame_support::{
+    assert_noop, assert_ok,
+    pallet_prelude::{StorageVersion, ValidateUnsigned},
+    traits::{Currency, OnRuntimeUpgrade, ReservableCurrency},
+};
 use pallet_aleph::AbftScores;
-use primitives::{BanInfo, BannedValidators, Score};
+use primitives::{
+    AuraId, BanInfo, BanReason, BannedValidators, BlockNumber, CommitteeSelectionMode, Header,
+    HeaderT, ProductionEquivocationProof, Score, SessionIndex,
+};
+use sp_consensus_aura::{digests::CompatibleDigestItem, sr25519::AuthoritySignature, Slot};
+use sp_core::Pair;
+use sp_runtime::{
+    transaction_validity::{InvalidTransaction, TransactionSource},
+    Perbill,
+};
 
 use crate::{
     mock::{
-        active_era, advance_era, committee_management_events, start_session, AccountId,
-        CommitteeManagement, Elections, SessionPeriod, TestBuilderConfig, TestExtBuilder,
-        TestRuntime,
+        active_era, advance_era, committee_management_events, producer_key, start_session,
+        AccountId, Balances, CommitteeManagement, Elections, RuntimeOrigin, Session, SessionPeriod,
+        SessionsPerEra, Slashes, TestBuilderConfig, TestExtBuilder, TestRuntime,
     },
-    CurrentAndNextSessionValidatorsStorage, Event, ProductionBanConfig, SessionValidatorBlockCount,
+    Banned, Call, CommitteeRandomness, CurrentAndNextSessionValidatorsStorage, Error, Event,
+    FinalityRewardCurveStruct, Probation, ProductionBanConfig, ReportedEquivocations,
+    SessionProducerKeys, SessionValidatorBlockCount, UnbanConfig, EQUIVOCATION_REPORT_SESSIONS,
 };
 
 fn gen_config() -> TestBuilderConfig {
@@ -89,6 +105,55 @@ fn all_reserved_validators_are_chosen() {
     })
 }
 
+#[test]
+fn committee_selection_mode_changes_with_next_era() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        assert_ok!(CommitteeManagement::set_committee_selection_mode(
+            RuntimeOrigin::root(),
+            CommitteeSelectionMode::StakeWeighted
+        ));
+        assert_eq!(
+            *committee_management_events().last().unwrap(),
+            Event::SetCommitteeSelectionMode(CommitteeSelectionMode::StakeWeighted)
+        );
+        assert_eq!(
+            CommitteeManagement::current_era_committee_selection(),
+            CommitteeSelectionMode::RoundRobin
+        );
+        let randomness = CommitteeRandomness::<TestRuntime>::get();
+
+        advance_era();
+
+        assert_eq!(
+            CommitteeManagement::current_era_committee_selection(),
+            CommitteeSelectionMode::StakeWeighted
+        );
+        assert_ne!(CommitteeRandomness::<TestRuntime>::get(), randomness);
+    })
+}
+
+#[test]
+fn stake_weighted_committee_is_predictable_within_era() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        assert_ok!(CommitteeManagement::set_committee_selection_mode(
+            RuntimeOrigin::root(),
+            CommitteeSelectionMode::StakeWeighted
+        ));
+        advance_era();
+
+        let session = pallet_session::CurrentIndex::<TestRuntime>::get() + 1;
+        let predicted = CommitteeManagement::predict_session_committee_for_session(session)
+            .expect("Session should be within the current era");
+        start_session(session);
+        let current = CommitteeManagement::current_session_validators().current;
+
+        assert_eq!(predicted.producers, current.producers);
+        assert_eq!(predicted.finalizers, current.finalizers);
+        let reserved = Elections::current_era_validators().reserved;
+        assert!(reserved.iter().all(|rv| current.producers.contains(rv)));
+    })
+}
+
 #[test]
 fn ban_underperforming_producers() {
     TestExtBuilder::new(gen_config()).build().execute_with(|| {
@@ -201,3 +266,553 @@ fn ban_underperforming_finalizers() {
         );
     })
 }
+
+fn manual_ban_reason(reason: &str) -> BanReason {
+    BanReason::Manual(reason.as_bytes().to_vec().try_into().unwrap())
+}
+
+#[test]
+fn ban_history_keeps_most_recent_bans() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let validator = 15;
+        for reason in ["first", "second", "third"] {
+            assert_ok!(CommitteeManagement::ban_from_committee(
+                RuntimeOrigin::root(),
+                validator,
+                reason.as_bytes().to_vec()
+            ));
+        }
+        assert_ok!(CommitteeManagement::cancel_ban(
+            RuntimeOrigin::root(),
+            validator
+        ));
+
+        let reasons: Vec<_> = CommitteeManagement::ban_history(validator)
+            .into_iter()
+            .map(|info| info.reason)
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![manual_ban_reason("second"), manual_ban_reason("third")]
+        );
+    })
+}
+
+#[test]
+fn migrate_manual_bans_to_v2() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let validator = 15;
+        StorageVersion::new(1).put::<CommitteeManagement>();
+        Banned::<TestRuntime>::insert(
+            validator,
+            BanInfo {
+                reason: BanReason::OtherReason(b"manual".to_vec().try_into().unwrap()),
+                start: 3,
+            },
+        );
+
+        <CommitteeManagement as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+        let expected = BanInfo {
+            reason: manual_ban_reason("manual"),
+            start: 3,
+        };
+        assert_eq!(
+            Banned::<TestRuntime>::get(validator),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            CommitteeManagement::ban_history(validator).into_inner(),
+            vec![expected]
+        );
+        assert_eq!(
+            StorageVersion::get::<CommitteeManagement>(),
+            StorageVersion::new(2)
+        );
+    })
+}
+
+#[test]
+fn set_finality_reward_curve() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        assert_noop!(
+            CommitteeManagement::set_finality_reward_curve(
+                RuntimeOrigin::root(),
+                Some(30),
+                Some(20),
+                None
+            ),
+            Error::<TestRuntime>::InvalidFinalityRewardCurve
+        );
+        assert_ok!(CommitteeManagement::set_finality_reward_curve(
+            RuntimeOrigin::root(),
+            Some(20),
+            Some(30),
+            Some(Perbill::from_percent(50))
+        ));
+
+        let curve = FinalityRewardCurveStruct {
+            full_reward_threshold: 20,
+            minimal_reward_threshold: 30,
+            minimal_reward: Perbill::from_percent(50),
+        };
+        assert_eq!(CommitteeManagement::finality_reward_curve(), curve);
+        assert_eq!(
+            *committee_management_events().last().unwrap(),
+            Event::SetFinalityRewardCurve(curve)
+        );
+    })
+}
+
+#[test]
+fn lagging_finalizers_get_reduced_rewards() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (punctual, lagging) = (0, 1);
+        assert_ok!(CommitteeManagement::set_finality_reward_curve(
+            RuntimeOrigin::root(),
+            Some(0),
+            Some(10),
+            Some(Perbill::zero())
+        ));
+        advance_era();
+        let session_index = SessionsPerEra::get() * active_era();
+
+        CurrentAndNextSessionValidatorsStorage::<TestRuntime>::mutate(|sv| {
+            sv.current.producers = vec![punctual, lagging];
+            sv.current.finalizers = vec![punctual, lagging];
+            sv.current.non_committee = Vec::new();
+        });
+        SessionValidatorBlockCount::<TestRuntime>::insert(punctual, SessionPeriod::get());
+        SessionValidatorBlockCount::<TestRuntime>::insert(lagging, SessionPeriod::get());
+        let score = Score {
+            session_id: session_index,
+            nonce: 1,
+            points: vec![0, 10],
+        };
+        AbftScores::<TestRuntime>::insert(session_index, score);
+        start_session(session_index + 1);
+
+        let points = pallet_staking::ErasRewardPoints::<TestRuntime>::get(active_era()).individual;
+        assert!(points.get(&punctual).copied().unwrap_or(0) > 0);
+        assert_eq!(points.get(&lagging).copied().unwrap_or(0), 0);
+    })
+}
+
+fn reinstate(validator: AccountId, bond: u128, probation_period: u32) {
+    assert_ok!(CommitteeManagement::set_unban_config(
+        RuntimeOrigin::root(),
+        Some(UnbanConfig {
+            bond,
+            minimal_ban_period: 1,
+            probation_period,
+        })
+    ));
+    assert_noop!(
+        CommitteeManagement::request_unban(RuntimeOrigin::signed(validator)),
+        Error::<TestRuntime>::NotBanned
+    );
+    CommitteeManagement::ban_validator(&validator, BanReason::InsufficientUptime(3));
+    assert_noop!(
+        CommitteeManagement::request_unban(RuntimeOrigin::signed(validator)),
+        Error::<TestRuntime>::BanTooRecent
+    );
+
+    advance_era();
+    advance_era();
+    advance_era();
+    assert_ok!(CommitteeManagement::request_unban(RuntimeOrigin::signed(
+        validator
+    )));
+
+    assert!(!Banned::<TestRuntime>::contains_key(validator));
+    assert_eq!(Balances::reserved_balance(validator), bond);
+    assert_eq!(
+        *committee_management_events().last().unwrap(),
+        Event::ValidatorReinstated(validator, bond)
+    );
+}
+
+#[test]
+fn unban_is_disabled_by_default() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        assert_noop!(
+            CommitteeManagement::request_unban(RuntimeOrigin::signed(15)),
+            Error::<TestRuntime>::UnbanDisabled
+        );
+    })
+}
+
+#[test]
+fn only_performance_bans_can_be_lifted() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let validator = 15;
+        assert_ok!(CommitteeManagement::set_unban_config(
+            RuntimeOrigin::root(),
+            Some(UnbanConfig {
+                bond: 1_000,
+                minimal_ban_period: 1,
+                probation_period: 2,
+            })
+        ));
+
+        for reason in [
+            BanReason::ProductionEquivocation(7),
+            BanReason::Manual(Default::default()),
+            BanReason::OtherReason(Default::default()),
+        ] {
+            CommitteeManagement::ban_validator(&validator, reason);
+            advance_era();
+            advance_era();
+            assert_noop!(
+                CommitteeManagement::request_unban(RuntimeOrigin::signed(validator)),
+                Error::<TestRuntime>::BanNotLiftable
+            );
+        }
+
+        CommitteeManagement::ban_validator(&validator, BanReason::InsufficientFinalization(3));
+        advance_era();
+        advance_era();
+        assert_ok!(CommitteeManagement::request_unban(RuntimeOrigin::signed(
+            validator
+        )));
+        assert!(!Banned::<TestRuntime>::contains_key(validator));
+    })
+}
+
+#[test]
+fn unban_bond_is_returned_after_probation() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let validator = 15;
+        let bond = 1_000;
+        let balance = Balances::total_balance(&validator);
+        reinstate(validator, bond, 2);
+
+        advance_era();
+        assert_eq!(Balances::reserved_balance(validator), bond);
+        advance_era();
+
+        assert_eq!(Balances::reserved_balance(validator), 0);
+        assert_eq!(Balances::total_balance(&validator), balance);
+        assert!(!Probation::<TestRuntime>::contains_key(validator));
+        assert!(committee_management_events().contains(&Event::UnbanBondReturned(validator, bond)));
+    })
+}
+
+#[test]
+fn unban_bond_is_slashed_on_another_ban() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let validator = 15;
+        let bond = 1_000;
+        let balance = Balances::total_balance(&validator);
+        reinstate(validator, bond, 2);
+
+        assert_ok!(CommitteeManagement::ban_from_committee(
+            RuntimeOrigin::root(),
+            validator,
+            Vec::new()
+        ));
+
+        assert_eq!(Balances::reserved_balance(validator), 0);
+        assert_eq!(Balances::total_balance(&validator), balance - bond);
+        assert!(!Probation::<TestRuntime>::contains_key(validator));
+        assert_eq!(
+            *committee_management_events().last().unwrap(),
+            Event::UnbanBondSlashed(validator, bond)
+        );
+    })
+}
+
+fn sealed_header(producer: AccountId, number: BlockNumber, slot: u64, parent: u8) -> Header {
+    let mut header = Header::new(
+        number,
+        Default::default(),
+        Default::default(),
+        [parent; 32].into(),
+        Default::default(),
+    );
+    header
+        .digest_mut()
+        .push(CompatibleDigestItem::<AuthoritySignature>::aura_pre_digest(
+            Slot::from(slot),
+        ));
+    let signature = producer_key(producer).sign(header.hash().as_ref());
+    header
+        .digest_mut()
+        .push(CompatibleDigestItem::<AuthoritySignature>::aura_seal(
+            signature.into(),
+        ));
+    header
+}
+
+fn equivocation_proof(
+    offender: AccountId,
+    first_header: Header,
+    second_header: Header,
+) -> ProductionEquivocationProof {
+    ProductionEquivocationProof {
+        offender: AuraId::from(producer_key(offender).public()),
+        first_header,
+        second_header,
+    }
+}
+
+/// Starts the given session and returns one of its non-reserved block producers, which can be
+/// banned, together with the number of a block from that session.
+fn session_producer(session: SessionIndex) -> (AccountId, BlockNumber) {
+    start_session(session);
+    let reserved = gen_config().reserved_validators;
+    let producer = Session::validators()
+        .into_iter()
+        .find(|validator| !reserved.contains(validator))
+        .expect("there are non-reserved producers");
+    (producer, session * SessionPeriod::get() + 1)
+}
+
+#[test]
+fn ban_equivocating_producer() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(1);
+        let proof = equivocation_proof(
+            offender,
+            sealed_header(offender, number, 7, 1),
+            sealed_header(offender, number, 7, 2),
+        );
+
+        assert_ok!(CommitteeManagement::report_production_equivocation(
+            RuntimeOrigin::none(),
+            Box::new(proof)
+        ));
+
+        assert_eq!(
+            Banned::<TestRuntime>::get(offender).map(|info| info.reason),
+            Some(BanReason::ProductionEquivocation(7))
+        );
+        assert_eq!(
+            *committee_management_events().last().unwrap(),
+            Event::ProductionEquivocationReported(offender, 7)
+        );
+        assert!(Slashes::get().is_empty());
+    })
+}
+
+#[test]
+fn slash_equivocating_producer() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(2);
+        let fraction = Perbill::from_percent(10);
+        let proof = equivocation_proof(
+            offender,
+            sealed_header(offender, number, 7, 1),
+            sealed_header(offender, number, 7, 2),
+        );
+
+        assert_ok!(CommitteeManagement::set_equivocation_slash_fraction(
+            RuntimeOrigin::root(),
+            fraction
+        ));
+        assert_ok!(CommitteeManagement::report_production_equivocation(
+            RuntimeOrigin::none(),
+            Box::new(proof)
+        ));
+
+        assert_eq!(Slashes::get(), vec![(offender, 2, fraction)]);
+    })
+}
+
+#[test]
+fn reject_equivocation_proof_with_single_block() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(1);
+        let header = sealed_header(offender, number, 7, 1);
+        let proof = equivocation_proof(offender, header.clone(), header);
+
+        assert_noop!(
+            CommitteeManagement::report_production_equivocation(
+                RuntimeOrigin::none(),
+                Box::new(proof)
+            ),
+            Error::<TestRuntime>::InvalidEquivocationProof
+        );
+    })
+}
+
+#[test]
+fn reject_equivocation_proof_with_different_slots() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(1);
+        let proof = equivocation_proof(
+            offender,
+            sealed_header(offender, number, 7, 1),
+            sealed_header(offender, number, 8, 2),
+        );
+
+        assert_noop!(
+            CommitteeManagement::report_production_equivocation(
+                RuntimeOrigin::none(),
+                Box::new(proof)
+            ),
+            Error::<TestRuntime>::InvalidEquivocationProof
+        );
+    })
+}
+
+#[test]
+fn reject_equivocation_proof_with_blocks_from_different_sessions() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(2);
+        let proof = equivocation_proof(
+            offender,
+            sealed_header(offender, number, 7, 1),
+            sealed_header(offender, number - SessionPeriod::get(), 7, 2),
+        );
+
+        assert_noop!(
+            CommitteeManagement::report_production_equivocation(
+                RuntimeOrigin::none(),
+                Box::new(proof)
+            ),
+            Error::<TestRuntime>::InvalidEquivocationProof
+        );
+    })
+}
+
+#[test]
+fn reject_equivocation_proof_sealed_by_someone_else() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(1);
+        let framer = Session::validators()[1];
+        let proof = equivocation_proof(
+            offender,
+            sealed_header(offender, number, 7, 1),
+            sealed_header(framer, number, 7, 2),
+        );
+
+        assert_noop!(
+            CommitteeManagement::report_production_equivocation(
+                RuntimeOrigin::none(),
+                Box::new(proof)
+            ),
+            Error::<TestRuntime>::InvalidEquivocationProof
+        );
+    })
+}
+
+#[test]
+fn reject_equivocation_of_validator_outside_session_committee() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (_, number) = session_producer(1);
+        let producers = Session::validators();
+        let offender = (0..100)
+            .find(|validator| !producers.contains(validator))
+            .expect("not all validators are in the committee");
+        let proof = equivocation_proof(
+            offender,
+            sealed_header(offender, number, 7, 1),
+            sealed_header(offender, number, 7, 2),
+        );
+
+        assert_noop!(
+            CommitteeManagement::report_production_equivocation(
+                RuntimeOrigin::none(),
+                Box::new(proof)
+            ),
+            Error::<TestRuntime>::UnknownEquivocationOffender
+        );
+    })
+}
+
+#[test]
+fn reject_equivocation_from_future_session() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(1);
+        let number = number + SessionPeriod::get();
+        let proof = equivocation_proof(
+            offender,
+            sealed_header(offender, number, 7, 1),
+            sealed_header(offender, number, 7, 2),
+        );
+
+        assert_noop!(
+            CommitteeManagement::report_production_equivocation(
+                RuntimeOrigin::none(),
+                Box::new(proof)
+            ),
+            Error::<TestRuntime>::EquivocationOutsideReportWindow
+        );
+    })
+}
+
+#[test]
+fn reject_outdated_equivocation_and_prune_reports() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(1);
+        let proof = |slot| {
+            equivocation_proof(
+                offender,
+                sealed_header(offender, number, slot, 1),
+                sealed_header(offender, number, slot, 2),
+            )
+        };
+        assert_ok!(CommitteeManagement::report_production_equivocation(
+            RuntimeOrigin::none(),
+            Box::new(proof(7))
+        ));
+        assert!(ReportedEquivocations::<TestRuntime>::contains_key(
+            1,
+            (offender, 7)
+        ));
+
+        start_session(EQUIVOCATION_REPORT_SESSIONS);
+        assert!(SessionProducerKeys::<TestRuntime>::iter_key_prefix(1)
+            .next()
+            .is_some());
+
+        start_session(EQUIVOCATION_REPORT_SESSIONS + 1);
+        assert!(SessionProducerKeys::<TestRuntime>::iter_key_prefix(1)
+            .next()
+            .is_none());
+        assert!(ReportedEquivocations::<TestRuntime>::iter_key_prefix(1)
+            .next()
+            .is_none());
+        assert_noop!(
+            CommitteeManagement::report_production_equivocation(
+                RuntimeOrigin::none(),
+                Box::new(proof(8))
+            ),
+            Error::<TestRuntime>::EquivocationOutsideReportWindow
+        );
+    })
+}
+
+#[test]
+fn reject_already_reported_equivocation() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (offender, number) = session_producer(1);
+        let proof = equivocation_proof(
+            offender,
+            sealed_header(offender, number, 7, 1),
+            sealed_header(offender, number, 7, 2),
+        );
+        let call = Call::report_production_equivocation {
+            proof: Box::new(proof.clone()),
+        };
+
+        assert!(CommitteeManagement::validate_unsigned(TransactionSource::External, &call).is_ok());
+        assert_ok!(CommitteeManagement::report_production_equivocation(
+            RuntimeOrigin::none(),
+            Box::new(proof.clone())
+        ));
+
+        assert_eq!(
+            CommitteeManagement::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::Stale.into()
+        );
+        assert_noop!(
+            CommitteeManagement::report_production_equivocation(
+                RuntimeOrigin::none(),
+                Box::new(proof)
+            ),
+            Error::<TestRuntime>::EquivocationAlreadyReported
+        );
+    })
+}
@@ -1,4 +1,7 @@
 use frame_support::pallet_prelude::Get;
+use primitives::AuraId;
+use sp_core::crypto::key_types::AURA;
+use sp_runtime::{traits::OpaqueKeys, Perbill};
 use sp_staking::{EraIndex, SessionIndex};
 use sp_std::vec::Vec;
 
@@ -96,3 +99,54 @@ where
         pallet_staking::Pallet::<T>::do_remove_validator(who);
     }
 }
+
+pub trait StashProvider {
+    type AccountId;
+
+    /// Returns the stash controlled by the given account, if any.
+    fn stash_of(controller: &Self::AccountId) -> Option<Self::AccountId>;
+}
+
+impl<T> StashProvider for pallet_staking::Pallet<T>
+where
+    T: pallet_staking::Config,
+{
+    type AccountId = T::AccountId;
+
+    fn stash_of(controller: &Self::AccountId) -> Option<Self::AccountId> {
+        pallet_staking::Ledger::<T>::get(controller).map(|ledger| ledger.stash)
+    }
+}
+
+pub trait ProducerKeysProvider {
+    type AccountId;
+
+    /// Returns the validators of the session that is just starting, together with the block
+    /// production keys they use in that session.
+    fn starting_session_keys() -> Vec<(Self::AccountId, AuraId)>;
+}
+
+impl<T> ProducerKeysProvider for pallet_session::Pallet<T>
+where
+    T: pallet_session::Config,
+{
+    type AccountId = T::ValidatorId;
+
+    /// When a session starts, the queued keys are still the ones of the validators of that
+    /// session, they get replaced by the keys for the next session right afterwards.
+    fn starting_session_keys() -> Vec<(Self::AccountId, AuraId)> {
+        pallet_session::QueuedKeys::<T>::get()
+            .into_iter()
+            .filter_map(|(validator, keys)| Some((validator, keys.get(AURA)?)))
+            .collect()
+    }
+}
+
+pub trait EquivocationSlasher<AccountId> {
+    /// Slashes the given fraction of the stake of a validator that equivocated in `session`.
+    fn slash(offender: &AccountId, session: SessionIndex, fraction: Perbill);
+}
+
+impl<AccountId> EquivocationSlasher<AccountId> for () {
+    fn slash(_offender: &AccountId, _session: SessionIndex, _fraction: Perbill) {}
+}
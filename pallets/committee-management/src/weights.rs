@@ -0,0 +1,364 @@
+//! Weights for pallet_committee_management
+//!
+//! The storage accesses follow the benchmarks in `benchmarking.rs`, but the execution times are
+//! estimates that have not been measured yet. Replace them with the results measured on reference
+//! hardware by running `scripts/run_benchmarks.sh`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_committee_management.
+pub trait WeightInfo {
+    fn set_ban_config() -> Weight;
+    fn ban_from_committee() -> Weight;
+    fn cancel_ban() -> Weight;
+    fn set_lenient_threshold() -> Weight;
+    fn set_finality_ban_config() -> Weight;
+    fn report_production_equivocation() -> Weight;
+    fn set_equivocation_slash_fraction() -> Weight;
+    fn set_committee_selection_mode() -> Weight;
+    fn set_finality_reward_curve() -> Weight;
+    fn request_unban() -> Weight;
+    fn set_unban_config() -> Weight;
+    fn new_session(v: u32, ) -> Weight;
+    fn end_session(v: u32, ) -> Weight;
+    fn start_session(v: u32, ) -> Weight;
+}
+
+/// Estimated weights for pallet_committee_management, see the module documentation.
+pub struct AlephWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for AlephWeight<T> {
+    // Storage: `CommitteeManagement::ProductionBanConfig` (r:1 w:1)
+    fn set_ban_config() -> Weight {
+        Weight::from_parts(14_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:0)
+    // Storage: `Staking::Validators` (r:1 w:1)
+    // Storage: `Staking::CounterForValidators` (r:1 w:1)
+    // Storage: `VoterList::ListNodes` (r:1 w:1)
+    // Storage: `VoterList::ListBags` (r:1 w:1)
+    // Storage: `CommitteeManagement::Banned` (r:0 w:1)
+    // Storage: `CommitteeManagement::Probation` (r:1 w:1)
+    // Storage: `System::Account` (r:1 w:1)
+    fn ban_from_committee() -> Weight {
+        Weight::from_parts(60_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(8_u64))
+            .saturating_add(T::DbWeight::get().writes(7_u64))
+    }
+    // Storage: `CommitteeManagement::Banned` (r:0 w:1)
+    fn cancel_ban() -> Weight {
+        Weight::from_parts(11_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `CommitteeManagement::LenientThreshold` (r:0 w:1)
+    fn set_lenient_threshold() -> Weight {
+        Weight::from_parts(9_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `CommitteeManagement::FinalityBanConfig` (r:1 w:1)
+    fn set_finality_ban_config() -> Weight {
+        Weight::from_parts(13_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `CommitteeManagement::SessionProducerKeys` (r:2 w:0)
+    // Storage: `CommitteeManagement::ReportedEquivocations` (r:1 w:1)
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:0)
+    // Storage: `Staking::Validators` (r:1 w:1)
+    // Storage: `Staking::CounterForValidators` (r:1 w:1)
+    // Storage: `VoterList::ListNodes` (r:1 w:1)
+    // Storage: `VoterList::ListBags` (r:1 w:1)
+    // Storage: `CommitteeManagement::EquivocationSlashFraction` (r:1 w:0)
+    // Storage: `Session::CurrentIndex` (r:1 w:0)
+    // Storage: `CommitteeManagement::Banned` (r:0 w:1)
+    // Storage: `CommitteeManagement::Probation` (r:1 w:1)
+    // Storage: `System::Account` (r:1 w:1)
+    // Storage: `System::Events` (r:0 w:2)
+    fn report_production_equivocation() -> Weight {
+        Weight::from_parts(155_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(13_u64))
+            .saturating_add(T::DbWeight::get().writes(10_u64))
+    }
+    // Storage: `CommitteeManagement::EquivocationSlashFraction` (r:0 w:1)
+    fn set_equivocation_slash_fraction() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `CommitteeManagement::NextEraCommitteeSelection` (r:0 w:1)
+    // Storage: `System::Events` (r:0 w:1)
+    fn set_committee_selection_mode() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    // Storage: `CommitteeManagement::FinalityRewardCurve` (r:1 w:1)
+    // Storage: `System::Events` (r:0 w:1)
+    fn set_finality_reward_curve() -> Weight {
+        Weight::from_parts(13_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    // Storage: `CommitteeManagement::UnbanConfig` (r:1 w:0)
+    // Storage: `CommitteeManagement::Banned` (r:2 w:1)
+    // Storage: `Staking::Ledger` (r:1 w:0)
+    // Storage: `Staking::ActiveEra` (r:1 w:0)
+    // Storage: `System::Account` (r:1 w:1)
+    // Storage: `CommitteeManagement::Probation` (r:0 w:1)
+    // Storage: `System::Events` (r:0 w:1)
+    fn request_unban() -> Weight {
+        Weight::from_parts(40_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    // Storage: `CommitteeManagement::UnbanConfig` (r:0 w:1)
+    // Storage: `System::Events` (r:0 w:1)
+    fn set_unban_config() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    // Storage: `Elections::CurrentEraValidators` (r:1 w:0)
+    // Storage: `Elections::CommitteeSize` (r:1 w:0)
+    // Storage: `Staking::ActiveEra` (r:1 w:0)
+    // Storage: `CommitteeManagement::Banned` (r:1001 w:0)
+    // Storage: `CommitteeManagement::NextEraCommitteeSelection` (r:1 w:0)
+    // Storage: `CommitteeManagement::CurrentEraCommitteeSelection` (r:1 w:1)
+    // Storage: `CommitteeManagement::CommitteeRandomness` (r:1 w:1)
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Staking::ErasStakers` (r:1001 w:0)
+    // Storage: `Staking::ErasStakersOverview` (r:1001 w:0)
+    // Storage: `CommitteeManagement::CurrentAndNextSessionValidatorsStorage` (r:1 w:1)
+    // Storage: `Aleph::NextFinalityCommittee` (r:0 w:1)
+    /// The range of component `v` is `[1, 1000]`.
+    fn new_session(v: u32, ) -> Weight {
+        Weight::from_parts(30_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(250_000_u64, 0).saturating_mul(v as u64))
+            .saturating_add(T::DbWeight::get().reads(11_u64))
+            .saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(v as u64)))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    // Storage: `Elections::CurrentEraValidators` (r:1 w:0)
+    // Storage: `Elections::CommitteeSize` (r:1 w:0)
+    // Storage: `CommitteeManagement::CurrentAndNextSessionValidatorsStorage` (r:1 w:0)
+    // Storage: `Staking::ActiveEra` (r:1 w:0)
+    // Storage: `CommitteeManagement::ValidatorEraTotalReward` (r:1 w:0)
+    // Storage: `CommitteeManagement::LenientThreshold` (r:1 w:0)
+    // Storage: `CommitteeManagement::ProductionBanConfig` (r:1 w:0)
+    // Storage: `CommitteeManagement::FinalityRewardCurve` (r:1 w:0)
+    // Storage: `CommitteeManagement::FinalityBanConfig` (r:1 w:0)
+    // Storage: `Aleph::AbftScores` (r:1 w:0)
+    // Storage: `CommitteeManagement::SessionValidatorBlockCount` (r:1000 w:1000)
+    // Storage: `CommitteeManagement::UnderperformedValidatorSessionCount` (r:1000 w:1000)
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:0)
+    // Storage: `Staking::Validators` (r:1000 w:0)
+    // Storage: `CommitteeManagement::Banned` (r:0 w:1000)
+    // Storage: `CommitteeManagement::Probation` (r:1000 w:1000)
+    // Storage: `System::Account` (r:1000 w:1000)
+    // Storage: `Aleph::LastScoreNonce` (r:0 w:1)
+    /// The range of component `v` is `[1, 1000]`.
+    fn end_session(v: u32, ) -> Weight {
+        Weight::from_parts(40_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(550_000_u64, 0).saturating_mul(v as u64))
+            .saturating_add(T::DbWeight::get().reads(10_u64))
+            .saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(v as u64)))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(v as u64)))
+    }
+    // Storage: `Elections::CurrentEraValidators` (r:1 w:0)
+    // Storage: `CommitteeManagement::ProductionBanConfig` (r:1 w:0)
+    // Storage: `CommitteeManagement::UnderperformedValidatorSessionCount` (r:1000 w:1000)
+    // Storage: `CommitteeManagement::UnderperformedFinalizerSessionCount` (r:1000 w:1000)
+    // Storage: `Aleph::AbftScores` (r:0 w:1)
+    // Storage: `Staking::ErasStakers` (r:1 w:0)
+    // Storage: `Staking::ErasStakersOverview` (r:1 w:0)
+    // Storage: `CommitteeManagement::ValidatorEraTotalReward` (r:0 w:1)
+    // Storage: `CommitteeManagement::Banned` (r:1001 w:1000)
+    // Storage: `CommitteeManagement::Probation` (r:1001 w:1000)
+    // Storage: `System::Account` (r:1000 w:1000)
+    // Storage: `Session::QueuedKeys` (r:1 w:0)
+    // Storage: `CommitteeManagement::SessionProducerKeys` (r:0 w:2000)
+    // Storage: `CommitteeManagement::ReportedEquivocations` (r:0 w:1000)
+    /// The range of component `v` is `[1, 1000]`.
+    fn start_session(v: u32, ) -> Weight {
+        Weight::from_parts(35_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(140_000_u64, 0).saturating_mul(v as u64))
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(v as u64)))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+            .saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(v as u64)))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    // Storage: `CommitteeManagement::ProductionBanConfig` (r:1 w:1)
+    fn set_ban_config() -> Weight {
+        Weight::from_parts(14_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:0)
+    // Storage: `Staking::Validators` (r:1 w:1)
+    // Storage: `Staking::CounterForValidators` (r:1 w:1)
+    // Storage: `VoterList::ListNodes` (r:1 w:1)
+    // Storage: `VoterList::ListBags` (r:1 w:1)
+    // Storage: `CommitteeManagement::Banned` (r:0 w:1)
+    // Storage: `CommitteeManagement::Probation` (r:1 w:1)
+    // Storage: `System::Account` (r:1 w:1)
+    fn ban_from_committee() -> Weight {
+        Weight::from_parts(60_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(8_u64))
+            .saturating_add(RocksDbWeight::get().writes(7_u64))
+    }
+    // Storage: `CommitteeManagement::Banned` (r:0 w:1)
+    fn cancel_ban() -> Weight {
+        Weight::from_parts(11_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `CommitteeManagement::LenientThreshold` (r:0 w:1)
+    fn set_lenient_threshold() -> Weight {
+        Weight::from_parts(9_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `CommitteeManagement::FinalityBanConfig` (r:1 w:1)
+    fn set_finality_ban_config() -> Weight {
+        Weight::from_parts(13_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `CommitteeManagement::SessionProducerKeys` (r:2 w:0)
+    // Storage: `CommitteeManagement::ReportedEquivocations` (r:1 w:1)
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:0)
+    // Storage: `Staking::Validators` (r:1 w:1)
+    // Storage: `Staking::CounterForValidators` (r:1 w:1)
+    // Storage: `VoterList::ListNodes` (r:1 w:1)
+    // Storage: `VoterList::ListBags` (r:1 w:1)
+    // Storage: `CommitteeManagement::EquivocationSlashFraction` (r:1 w:0)
+    // Storage: `Session::CurrentIndex` (r:1 w:0)
+    // Storage: `CommitteeManagement::Banned` (r:0 w:1)
+    // Storage: `CommitteeManagement::Probation` (r:1 w:1)
+    // Storage: `System::Account` (r:1 w:1)
+    // Storage: `System::Events` (r:0 w:2)
+    fn report_production_equivocation() -> Weight {
+        Weight::from_parts(155_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(13_u64))
+            .saturating_add(RocksDbWeight::get().writes(10_u64))
+    }
+    // Storage: `CommitteeManagement::EquivocationSlashFraction` (r:0 w:1)
+    fn set_equivocation_slash_fraction() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `CommitteeManagement::NextEraCommitteeSelection` (r:0 w:1)
+    // Storage: `System::Events` (r:0 w:1)
+    fn set_committee_selection_mode() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    // Storage: `CommitteeManagement::FinalityRewardCurve` (r:1 w:1)
+    // Storage: `System::Events` (r:0 w:1)
+    fn set_finality_reward_curve() -> Weight {
+        Weight::from_parts(13_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    // Storage: `CommitteeManagement::UnbanConfig` (r:1 w:0)
+    // Storage: `CommitteeManagement::Banned` (r:2 w:1)
+    // Storage: `Staking::Ledger` (r:1 w:0)
+    // Storage: `Staking::ActiveEra` (r:1 w:0)
+    // Storage: `System::Account` (r:1 w:1)
+    // Storage: `CommitteeManagement::Probation` (r:0 w:1)
+    // Storage: `System::Events` (r:0 w:1)
+    fn request_unban() -> Weight {
+        Weight::from_parts(40_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    // Storage: `CommitteeManagement::UnbanConfig` (r:0 w:1)
+    // Storage: `System::Events` (r:0 w:1)
+    fn set_unban_config() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    // Storage: `Elections::CurrentEraValidators` (r:1 w:0)
+    // Storage: `Elections::CommitteeSize` (r:1 w:0)
+    // Storage: `Staking::ActiveEra` (r:1 w:0)
+    // Storage: `CommitteeManagement::Banned` (r:1001 w:0)
+    // Storage: `CommitteeManagement::NextEraCommitteeSelection` (r:1 w:0)
+    // Storage: `CommitteeManagement::CurrentEraCommitteeSelection` (r:1 w:1)
+    // Storage: `CommitteeManagement::CommitteeRandomness` (r:1 w:1)
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Staking::ErasStakers` (r:1001 w:0)
+    // Storage: `Staking::ErasStakersOverview` (r:1001 w:0)
+    // Storage: `CommitteeManagement::CurrentAndNextSessionValidatorsStorage` (r:1 w:1)
+    // Storage: `Aleph::NextFinalityCommittee` (r:0 w:1)
+    /// The range of component `v` is `[1, 1000]`.
+    fn new_session(v: u32, ) -> Weight {
+        Weight::from_parts(30_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(250_000_u64, 0).saturating_mul(v as u64))
+            .saturating_add(RocksDbWeight::get().reads(11_u64))
+            .saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(v as u64)))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    // Storage: `Elections::CurrentEraValidators` (r:1 w:0)
+    // Storage: `Elections::CommitteeSize` (r:1 w:0)
+    // Storage: `CommitteeManagement::CurrentAndNextSessionValidatorsStorage` (r:1 w:0)
+    // Storage: `Staking::ActiveEra` (r:1 w:0)
+    // Storage: `CommitteeManagement::ValidatorEraTotalReward` (r:1 w:0)
+    // Storage: `CommitteeManagement::LenientThreshold` (r:1 w:0)
+    // Storage: `CommitteeManagement::ProductionBanConfig` (r:1 w:0)
+    // Storage: `CommitteeManagement::FinalityRewardCurve` (r:1 w:0)
+    // Storage: `CommitteeManagement::FinalityBanConfig` (r:1 w:0)
+    // Storage: `Aleph::AbftScores` (r:1 w:0)
+    // Storage: `CommitteeManagement::SessionValidatorBlockCount` (r:1000 w:1000)
+    // Storage: `CommitteeManagement::UnderperformedValidatorSessionCount` (r:1000 w:1000)
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:0)
+    // Storage: `Staking::Validators` (r:1000 w:0)
+    // Storage: `CommitteeManagement::Banned` (r:0 w:1000)
+    // Storage: `CommitteeManagement::Probation` (r:1000 w:1000)
+    // Storage: `System::Account` (r:1000 w:1000)
+    // Storage: `Aleph::LastScoreNonce` (r:0 w:1)
+    /// The range of component `v` is `[1, 1000]`.
+    fn end_session(v: u32, ) -> Weight {
+        Weight::from_parts(40_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(550_000_u64, 0).saturating_mul(v as u64))
+            .saturating_add(RocksDbWeight::get().reads(10_u64))
+            .saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(v as u64)))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+            .saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(v as u64)))
+    }
+    // Storage: `Elections::CurrentEraValidators` (r:1 w:0)
+    // Storage: `CommitteeManagement::ProductionBanConfig` (r:1 w:0)
+    // Storage: `CommitteeManagement::UnderperformedValidatorSessionCount` (r:1000 w:1000)
+    // Storage: `CommitteeManagement::UnderperformedFinalizerSessionCount` (r:1000 w:1000)
+    // Storage: `Aleph::AbftScores` (r:0 w:1)
+    // Storage: `Staking::ErasStakers` (r:1 w:0)
+    // Storage: `Staking::ErasStakersOverview` (r:1 w:0)
+    // Storage: `CommitteeManagement::ValidatorEraTotalReward` (r:0 w:1)
+    // Storage: `CommitteeManagement::Banned` (r:1001 w:1000)
+    // Storage: `CommitteeManagement::Probation` (r:1001 w:1000)
+    // Storage: `System::Account` (r:1000 w:1000)
+    // Storage: `Session::QueuedKeys` (r:1 w:0)
+    // Storage: `CommitteeManagement::SessionProducerKeys` (r:0 w:2000)
+    // Storage: `CommitteeManagement::ReportedEquivocations` (r:0 w:1000)
+    /// The range of component `v` is `[1, 1000]`.
+    fn start_session(v: u32, ) -> Weight {
+        Weight::from_parts(35_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(140_000_u64, 0).saturating_mul(v as u64))
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(v as u64)))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+            .saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(v as u64)))
+    }
+}
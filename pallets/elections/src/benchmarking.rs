@@ -0,0 +1,130 @@
+use frame_benchmarking::{account, v2::*};
+use frame_system::RawOrigin;
+use primitives::{CommitteeSeats, ElectionOpenness, ElectionStrategy, EraManager};
+use sp_std::vec::Vec;
+
+use crate::{traits::ValidatorProvider, *};
+
+/// The largest validator sets we expect to see.
+const MAX_RESERVED_VALIDATORS: u32 = 100;
+const MAX_VALIDATORS: u32 = 1000;
+
+fn validators<T: Config>(name: &'static str, count: u32) -> Vec<T::AccountId> {
+    (0..count).map(|i| account(name, i, 0)).collect()
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn change_validators(r: Linear<0, MAX_RESERVED_VALIDATORS>, n: Linear<0, MAX_VALIDATORS>) {
+        let reserved = validators::<T>("reserved", r);
+        let non_reserved = validators::<T>("non_reserved", n);
+        let committee_size = CommitteeSeats {
+            reserved_seats: r,
+            non_reserved_seats: n,
+            non_reserved_finality_seats: n,
+        };
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Root,
+            Some(reserved),
+            Some(non_reserved),
+            Some(committee_size),
+        );
+
+        assert_eq!(NextEraCommitteeSize::<T>::get(), committee_size);
+    }
+
+    #[benchmark]
+    fn set_elections_openness() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, ElectionOpenness::Permissionless);
+
+        assert_eq!(Openness::<T>::get(), ElectionOpenness::Permissionless);
+    }
+
+    #[benchmark]
+    fn set_election_strategy() {
+        let strategy = ElectionStrategy::PhragMMS { targets: 1 };
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, strategy);
+
+        assert_eq!(Strategy::<T>::get(), strategy);
+    }
+
+    #[benchmark]
+    fn schedule_change(r: Linear<0, MAX_RESERVED_VALIDATORS>, n: Linear<0, MAX_VALIDATORS>) {
+        let reserved = validators::<T>("reserved", r);
+        let non_reserved = validators::<T>("non_reserved", n);
+        let committee_size = CommitteeSeats {
+            reserved_seats: r,
+            non_reserved_seats: n,
+            non_reserved_finality_seats: n,
+        };
+        let era = T::ValidatorProvider::current_era().unwrap_or(0) + 2;
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Root,
+            era,
+            Some(reserved),
+            Some(non_reserved),
+            Some(committee_size),
+            Some(era + 1),
+        );
+
+        assert!(ScheduledChanges::<T>::contains_key(era));
+    }
+
+    #[benchmark]
+    fn cancel_scheduled_change() {
+        let era = T::ValidatorProvider::current_era().unwrap_or(0) + 2;
+        ScheduledChanges::<T>::insert(
+            era,
+            ScheduledChange::<T::AccountId> {
+                reserved_validators: None,
+                non_reserved_validators: None,
+                committee_size: None,
+                expires_at: None,
+            },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, era);
+
+        assert!(!ScheduledChanges::<T>::contains_key(era));
+    }
+
+    #[benchmark]
+    fn on_new_era(v: Linear<0, MAX_VALIDATORS>) {
+        let reserved_count = v.min(MAX_RESERVED_VALIDATORS);
+        NextEraReservedValidators::<T>::put(validators::<T>("reserved", reserved_count));
+        NextEraNonReservedValidators::<T>::put(validators::<T>("non_reserved", v - reserved_count));
+        // Worst case: a scheduled change expires and another one takes effect in the next era.
+        ScheduledRestorations::<T>::insert(2, Pallet::<T>::next_era_committee());
+        ScheduledChanges::<T>::insert(
+            2,
+            ScheduledChange::<T::AccountId> {
+                reserved_validators: None,
+                non_reserved_validators: None,
+                committee_size: None,
+                expires_at: Some(3),
+            },
+        );
+
+        #[block]
+        {
+            <Pallet<T> as EraManager>::on_new_era(1);
+        }
+    }
+
+    impl_benchmark_test_suite!(
+        Pallet,
+        crate::mock::TestExtBuilder::new(Vec::new(), Vec::new()).build(),
+        crate::mock::Test
+    );
+}
@@ -1,14 +1,66 @@
-use primitives::{CommitteeSeats, EraValidators};
+use frame_election_provider_support::{
+    DataProviderBounds, ElectionDataProvider, ElectionResult, NposSolver, PhragMMS,
+    SequentialPhragmen, Support, VoteWeight,
+};
+use frame_support::{dispatch::DispatchClass, traits::Get};
+use primitives::{
+    Balance, BannedValidators, CommitteeSeats, ElectionOpenness, ElectionPreview, ElectionStrategy,
+    EraValidators,
+};
 use rand::{seq::SliceRandom, SeedableRng};
 use rand_pcg::Pcg32;
+use sp_npos_elections::{assignment_ratio_to_staked_normalized, to_supports, Error as NposError};
+use sp_runtime::Perbill;
 use sp_staking::EraIndex;
-use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    vec::Vec,
+};
 
 use crate::{
-    traits::ValidatorProvider, CommitteeSize, Config, CurrentEraValidators, NextEraCommitteeSize,
-    NextEraNonReservedValidators, NextEraReservedValidators, Pallet,
+    traits::ValidatorProvider, weights::WeightInfo, CommitteeSize, Config, CurrentEraValidators,
+    ElectionError, Event, NextEraCommitteeSize, NextEraNonReservedValidators,
+    NextEraReservedValidators, Openness, Pallet, ScheduledChanges, ScheduledRestorations, Strategy,
 };
 
+type Vote<AccountId> = (AccountId, VoteWeight, Vec<AccountId>);
+type SupportMap<AccountId> = BTreeMap<AccountId, Support<AccountId>>;
+
+/// Outcome of the elections, before anything is stored.
+pub(crate) struct Election<AccountId> {
+    pub reserved: Vec<AccountId>,
+    pub non_reserved: Vec<AccountId>,
+    pub supports: SupportMap<AccountId>,
+}
+
+fn no_support<AccountId>() -> Support<AccountId> {
+    Support {
+        total: 0,
+        voters: Vec::new(),
+    }
+}
+
+/// Backs every validator with the whole stake of the voters nominating it first.
+fn first_nomination_supports<'a, AccountId: Ord + Clone + 'a>(
+    validators: impl IntoIterator<Item = AccountId>,
+    votes: impl IntoIterator<Item = &'a Vote<AccountId>>,
+) -> SupportMap<AccountId> {
+    let mut supports: BTreeMap<_, _> = validators
+        .into_iter()
+        // Under normal circumstances support will never be `0` since 'self-vote' is counted in.
+        .map(|id| (id, no_support()))
+        .collect();
+    for (voter, vote, targets) in votes {
+        // The parameter `Staking::MAX_NOMINATIONS` is set to 1 which guarantees that
+        // `len(targets) == 1`.
+        if let Some(support) = targets.first().and_then(|t| supports.get_mut(t)) {
+            support.total += *vote as u128;
+            support.voters.push((voter.clone(), *vote as u128));
+        }
+    }
+    supports
+}
+
 impl<T> Pallet<T>
 where
     T: Config,
@@ -31,17 +83,202 @@ where
         let non_reserved_validators = NextEraNonReservedValidators::<T>::get();
         let committee_size = NextEraCommitteeSize::<T>::get();
 
+        frame_system::Pallet::<T>::register_extra_weight_unchecked(
+            T::WeightInfo::on_new_era(
+                (reserved_validators.len() + non_reserved_validators.len()) as u32,
+            ),
+            DispatchClass::Mandatory,
+        );
+
         CurrentEraValidators::<T>::put(EraValidators {
             reserved: retain_shuffle_elected(reserved_validators),
             non_reserved: retain_shuffle_elected(non_reserved_validators),
         });
         CommitteeSize::<T>::put(committee_size);
     }
+
+    /// Prepares the committee of the era after `era`: restores the committees of expired
+    /// scheduled changes first, and then applies the change scheduled for that era, if any.
+    fn apply_scheduled_changes(era: EraIndex) {
+        let next_era = era + 1;
+        if let Some(committee) = ScheduledRestorations::<T>::take(next_era) {
+            Self::put_next_era_committee(committee);
+            Self::deposit_event(Event::ScheduledChangeExpired(next_era));
+        }
+
+        let Some(change) = ScheduledChanges::<T>::take(next_era) else {
+            return;
+        };
+        let expires_at = change.expires_at;
+        let previous = Self::next_era_committee();
+        let committee = previous.clone().updated_with(change);
+        if Self::ensure_validators_are_ok(
+            committee.reserved_validators.clone(),
+            committee.non_reserved_validators.clone(),
+            committee.committee_size,
+        )
+        .is_err()
+        {
+            Self::deposit_event(Event::ScheduledChangeFailed(next_era));
+            return;
+        }
+
+        if let Some(expiry) = expires_at {
+            ScheduledRestorations::<T>::insert(expiry, previous);
+        }
+        Self::put_next_era_committee(committee);
+        Self::deposit_event(Event::ScheduledChangeApplied(next_era));
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Chooses the validators for the next era and calculates their supports, according to the
+    /// current [`ElectionOpenness`] and [`ElectionStrategy`].
+    pub(crate) fn run_election() -> Result<Election<T::AccountId>, ElectionError> {
+        let staking_validators = T::DataProvider::electable_targets(DataProviderBounds::default())
+            .map_err(ElectionError::DataProvider)?
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let reserved = NextEraReservedValidators::<T>::get()
+            .into_iter()
+            .filter(|v| staking_validators.contains(v))
+            .collect::<BTreeSet<_>>();
+        let banned_validators = T::BannedValidators::banned()
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+
+        let eligible_non_reserved = staking_validators
+            .into_iter()
+            .filter(|v| !banned_validators.contains(v) && !reserved.contains(v))
+            .collect::<BTreeSet<_>>();
+        let candidates: Vec<_> = match Openness::<T>::get() {
+            ElectionOpenness::Permissioned => NextEraNonReservedValidators::<T>::get()
+                .into_iter()
+                .filter(|v| eligible_non_reserved.contains(v))
+                .collect(),
+            ElectionOpenness::Permissionless => eligible_non_reserved.into_iter().collect(),
+        };
+
+        let votes: Vec<_> = T::DataProvider::electing_voters(DataProviderBounds::default())
+            .map_err(ElectionError::DataProvider)?
+            .into_iter()
+            .map(|(voter, vote, targets)| (voter, vote, targets.into_inner()))
+            .collect();
+
+        let (non_reserved, supports) = match Strategy::<T>::get() {
+            ElectionStrategy::All => {
+                let supports = first_nomination_supports(
+                    reserved.iter().chain(candidates.iter()).cloned(),
+                    &votes,
+                );
+                (candidates, supports)
+            }
+            ElectionStrategy::SequentialPhragmen { targets } => {
+                Self::solve::<SequentialPhragmen<T::AccountId, Perbill>>(
+                    targets, &reserved, candidates, votes,
+                )?
+            }
+            ElectionStrategy::PhragMMS { targets } => {
+                Self::solve::<PhragMMS<T::AccountId, Perbill>>(
+                    targets, &reserved, candidates, votes,
+                )?
+            }
+        };
+
+        Ok(Election {
+            reserved: reserved.into_iter().collect(),
+            non_reserved,
+            supports,
+        })
+    }
+
+    /// Elects at most `targets` of the candidates with the NPoS solver `S`. The reserved
+    /// validators are always elected, backed by the voters nominating them first, and these
+    /// voters do not take part in electing the candidates.
+    fn solve<S: NposSolver<AccountId = T::AccountId, Error = NposError>>(
+        targets: u32,
+        reserved: &BTreeSet<T::AccountId>,
+        candidates: Vec<T::AccountId>,
+        votes: Vec<Vote<T::AccountId>>,
+    ) -> Result<(Vec<T::AccountId>, SupportMap<T::AccountId>), ElectionError> {
+        let (reserved_votes, votes): (Vec<_>, Vec<_>) = votes
+            .into_iter()
+            .partition(|(_, _, t)| t.first().map_or(false, |target| reserved.contains(target)));
+        let mut supports = first_nomination_supports(reserved.iter().cloned(), &reserved_votes);
+
+        let candidate_set: BTreeSet<_> = candidates.iter().collect();
+        let votes: Vec<_> = votes
+            .into_iter()
+            .filter_map(|(voter, vote, targets)| {
+                let targets: Vec<_> = targets
+                    .into_iter()
+                    .filter(|target| candidate_set.contains(target))
+                    .collect();
+                (!targets.is_empty()).then_some((voter, vote, targets))
+            })
+            .collect();
+        let stakes: BTreeMap<_, _> = votes
+            .iter()
+            .map(|(voter, vote, _)| (voter.clone(), *vote))
+            .collect();
+        let to_elect = T::MaxWinners::get()
+            .saturating_sub(reserved.len() as u32)
+            .min(targets) as usize;
+        let to_elect = to_elect.min(candidates.len());
+
+        let ElectionResult {
+            winners,
+            assignments,
+        } = S::solve(to_elect, candidates, votes).map_err(ElectionError::Solver)?;
+        let staked = assignment_ratio_to_staked_normalized(assignments, |voter| {
+            stakes.get(voter).copied().unwrap_or_default()
+        })
+        .map_err(ElectionError::Solver)?;
+
+        let non_reserved: Vec<_> = winners.into_iter().map(|(winner, _)| winner).collect();
+        supports.extend(
+            non_reserved
+                .iter()
+                .cloned()
+                .map(|winner| (winner, no_support())),
+        );
+        supports.extend(to_supports(&staked));
+
+        Ok((non_reserved, supports))
+    }
+
+    /// Shows who would be elected for the next era if the elections were held now, without
+    /// storing anything.
+    pub fn preview_election() -> Result<ElectionPreview<T::AccountId>, ElectionError> {
+        let Election {
+            reserved,
+            non_reserved,
+            supports,
+        } = Self::run_election()?;
+        if supports.len() > T::MaxWinners::get() as usize {
+            return Err(ElectionError::TooManyWinners);
+        }
+        let with_totals = |validators: Vec<T::AccountId>| -> Vec<(T::AccountId, Balance)> {
+            validators
+                .into_iter()
+                .map(|id| {
+                    let total = supports.get(&id).map_or(0, |support| support.total);
+                    (id, total)
+                })
+                .collect()
+        };
+
+        Ok(ElectionPreview {
+            reserved: with_totals(reserved),
+            non_reserved: with_totals(non_reserved),
+        })
+    }
 }
 
 impl<T: Config> primitives::EraManager for Pallet<T> {
     fn on_new_era(era: EraIndex) {
         Self::populate_next_era_validators_on_next_era_start(era);
+        Self::apply_scheduled_changes(era);
     }
 }
 
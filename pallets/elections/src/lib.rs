@@ -1,18 +1,24 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod impls;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 mod traits;
+pub mod weights;
 
 use frame_support::traits::StorageVersion;
 pub use pallet::*;
 use parity_scale_codec::{Decode, Encode};
-pub use primitives::EraValidators;
+use primitives::CommitteeSeats;
+pub use primitives::{ElectionPreview, EraValidators};
 use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_staking::EraIndex;
 use sp_std::{
     collections::{btree_map::BTreeMap, btree_set::BTreeSet},
     prelude::*,
@@ -25,22 +31,64 @@ const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 #[derive(Decode, Encode, TypeInfo)]
 pub struct ValidatorTotalRewards<T>(pub BTreeMap<T, TotalReward>);
 
+/// Change of the committee queued for a future era. Lists and seats that are `None` stay as they
+/// are at the time the change is applied.
+#[derive(Decode, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ScheduledChange<AccountId> {
+    pub reserved_validators: Option<Vec<AccountId>>,
+    pub non_reserved_validators: Option<Vec<AccountId>>,
+    pub committee_size: Option<CommitteeSeats>,
+    /// Era from which the committee from before the change is restored, if any.
+    pub expires_at: Option<EraIndex>,
+}
+
+/// Configuration of the committee for the next era, as kept in the `NextEra*` storages.
+#[derive(Decode, Encode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CommitteeSnapshot<AccountId> {
+    pub reserved_validators: Vec<AccountId>,
+    pub non_reserved_validators: Vec<AccountId>,
+    pub committee_size: CommitteeSeats,
+}
+
+impl<AccountId> ScheduledChange<AccountId> {
+    /// The first era after the change scheduled for `era`, which lasts until it expires, or for
+    /// a single era if it does not expire.
+    pub fn end(&self, era: EraIndex) -> EraIndex {
+        self.expires_at.unwrap_or(era.saturating_add(1))
+    }
+}
+
+impl<AccountId> CommitteeSnapshot<AccountId> {
+    /// The committee after applying the change to it.
+    pub fn updated_with(self, change: ScheduledChange<AccountId>) -> Self {
+        CommitteeSnapshot {
+            reserved_validators: change
+                .reserved_validators
+                .unwrap_or(self.reserved_validators),
+            non_reserved_validators: change
+                .non_reserved_validators
+                .unwrap_or(self.non_reserved_validators),
+            committee_size: change.committee_size.unwrap_or(self.committee_size),
+        }
+    }
+}
+
 #[frame_support::pallet]
 #[pallet_doc("../README.md")]
 pub mod pallet {
     use frame_election_provider_support::{
-        BoundedSupportsOf, DataProviderBounds, ElectionDataProvider, ElectionProvider,
-        ElectionProviderBase, Support, Supports,
+        BoundedSupportsOf, ElectionDataProvider, ElectionProvider, ElectionProviderBase, Supports,
     };
     use frame_support::{pallet_prelude::*, traits::Get};
     use frame_system::{
         ensure_root,
         pallet_prelude::{BlockNumberFor, OriginFor},
     };
-    use primitives::{BannedValidators, CommitteeSeats, ElectionOpenness};
+    use primitives::{BannedValidators, ElectionOpenness, ElectionStrategy};
+    use sp_npos_elections::Error as NposError;
 
     use super::*;
-    use crate::traits::ValidatorProvider;
+    use crate::{impls::Election, traits::ValidatorProvider, weights::WeightInfo};
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -58,6 +106,8 @@ pub mod pallet {
         #[pallet::constant]
         type MaxWinners: Get<u32>;
         type BannedValidators: BannedValidators<AccountId = Self::AccountId>;
+        /// Weight information for extrinsics and era hooks in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::event]
@@ -65,6 +115,19 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// Committee for the next era has changed
         ChangeValidators(Vec<T::AccountId>, Vec<T::AccountId>, CommitteeSeats),
+        /// Strategy of choosing the non-reserved validators has changed
+        SetElectionStrategy(ElectionStrategy),
+        /// Committee change has been scheduled for the first era, expiring at the second one if given
+        ChangeScheduled(EraIndex, Option<EraIndex>),
+        /// Committee change scheduled for the era has been cancelled
+        ScheduledChangeCancelled(EraIndex),
+        /// Committee change scheduled for the era has been applied
+        ScheduledChangeApplied(EraIndex),
+        /// Committee change scheduled for the era could not be applied, since it was not valid
+        /// anymore
+        ScheduledChangeFailed(EraIndex),
+        /// Committee from before a scheduled change has been restored for the era
+        ScheduledChangeExpired(EraIndex),
     }
 
     #[pallet::pallet]
@@ -112,10 +175,32 @@ pub mod pallet {
     #[pallet::storage]
     pub type Openness<T> = StorageValue<_, ElectionOpenness, ValueQuery, DefaultOpenness<T>>;
 
+    /// How the non-reserved validators are chosen among the eligible candidates, see
+    /// [`ElectionStrategy`].
+    #[pallet::storage]
+    pub type Strategy<T> = StorageValue<_, ElectionStrategy, ValueQuery>;
+
+    /// Committee changes waiting to take effect, by the era they take effect in.
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_changes)]
+    pub type ScheduledChanges<T: Config> =
+        StorageMap<_, Twox64Concat, EraIndex, ScheduledChange<T::AccountId>, OptionQuery>;
+
+    /// Committees from before the applied scheduled changes, by the era they are restored in.
+    #[pallet::storage]
+    pub type ScheduledRestorations<T: Config> =
+        StorageMap<_, Twox64Concat, EraIndex, CommitteeSnapshot<T::AccountId>, OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((
+            T::WeightInfo::change_validators(
+                reserved_validators.as_ref().map_or(0, |v| v.len() as u32),
+                non_reserved_validators.as_ref().map_or(0, |v| v.len() as u32),
+            ),
+            DispatchClass::Operational
+        ))]
         pub fn change_validators(
             origin: OriginFor<T>,
             reserved_validators: Option<Vec<T::AccountId>>,
@@ -135,22 +220,18 @@ pub mod pallet {
                 committee_size,
             )?;
 
-            NextEraNonReservedValidators::<T>::put(non_reserved_validators.clone());
-            NextEraReservedValidators::<T>::put(reserved_validators.clone());
-            NextEraCommitteeSize::<T>::put(committee_size);
-
-            Self::deposit_event(Event::ChangeValidators(
+            Self::put_next_era_committee(CommitteeSnapshot {
                 reserved_validators,
                 non_reserved_validators,
                 committee_size,
-            ));
+            });
 
             Ok(())
         }
 
         /// Set openness of the elections
         #[pallet::call_index(4)]
-        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        #[pallet::weight((T::WeightInfo::set_elections_openness(), DispatchClass::Operational))]
         pub fn set_elections_openness(
             origin: OriginFor<T>,
             openness: ElectionOpenness,
@@ -161,6 +242,104 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Set the strategy of choosing the non-reserved validators. The number of validators to
+        /// elect must be positive and must not exceed [`Config::MaxWinners`].
+        #[pallet::call_index(5)]
+        #[pallet::weight((T::WeightInfo::set_election_strategy(), DispatchClass::Operational))]
+        pub fn set_election_strategy(
+            origin: OriginFor<T>,
+            strategy: ElectionStrategy,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            if let ElectionStrategy::SequentialPhragmen { targets }
+            | ElectionStrategy::PhragMMS { targets } = strategy
+            {
+                ensure!(
+                    targets > 0 && targets <= T::MaxWinners::get(),
+                    Error::<T>::InvalidElectionTargets
+                );
+            }
+            Strategy::<T>::put(strategy);
+            Self::deposit_event(Event::SetElectionStrategy(strategy));
+
+            Ok(())
+        }
+
+        /// Schedule a change of the committee taking effect in `era`, as if `change_validators`
+        /// was called in the era before it. If `expires_at` is given, the committee from before
+        /// the change is restored in that era.
+        ///
+        /// Only eras after the next one can be scheduled for, since the committee of the next era
+        /// might have been elected already.
+        #[pallet::call_index(6)]
+        #[pallet::weight((
+            T::WeightInfo::schedule_change(
+                reserved_validators.as_ref().map_or(0, |v| v.len() as u32),
+                non_reserved_validators.as_ref().map_or(0, |v| v.len() as u32),
+            ),
+            DispatchClass::Operational
+        ))]
+        pub fn schedule_change(
+            origin: OriginFor<T>,
+            era: EraIndex,
+            reserved_validators: Option<Vec<T::AccountId>>,
+            non_reserved_validators: Option<Vec<T::AccountId>>,
+            committee_size: Option<CommitteeSeats>,
+            expires_at: Option<EraIndex>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            let current_era = T::ValidatorProvider::current_era().unwrap_or(0);
+            ensure!(
+                era > current_era.saturating_add(1),
+                Error::<T>::ScheduledEraTooEarly
+            );
+            ensure!(
+                expires_at.map_or(true, |expiry| expiry > era),
+                Error::<T>::ExpiryNotAfterScheduledEra
+            );
+            ensure!(
+                !ScheduledChanges::<T>::contains_key(era),
+                Error::<T>::ChangeAlreadyScheduled
+            );
+
+            let change = ScheduledChange {
+                reserved_validators,
+                non_reserved_validators,
+                committee_size,
+                expires_at,
+            };
+            ensure!(
+                !Self::overlaps_other_changes(era, &change),
+                Error::<T>::OverlappingScheduledChange
+            );
+            let committee = Self::next_era_committee().updated_with(change.clone());
+            Self::ensure_validators_are_ok(
+                committee.reserved_validators,
+                committee.non_reserved_validators,
+                committee.committee_size,
+            )?;
+
+            ScheduledChanges::<T>::insert(era, change);
+            Self::deposit_event(Event::ChangeScheduled(era, expires_at));
+
+            Ok(())
+        }
+
+        /// Cancel the committee change scheduled for `era` that has not been applied yet.
+        #[pallet::call_index(7)]
+        #[pallet::weight((T::WeightInfo::cancel_scheduled_change(), DispatchClass::Operational))]
+        pub fn cancel_scheduled_change(origin: OriginFor<T>, era: EraIndex) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                ScheduledChanges::<T>::take(era).is_some(),
+                Error::<T>::NoChangeScheduled
+            );
+            Self::deposit_event(Event::ScheduledChangeCancelled(era));
+
+            Ok(())
+        }
     }
 
     #[pallet::hooks]
@@ -207,7 +386,45 @@ pub mod pallet {
     }
 
     impl<T: Config> Pallet<T> {
-        fn ensure_validators_are_ok(
+        pub(crate) fn next_era_committee() -> CommitteeSnapshot<T::AccountId> {
+            CommitteeSnapshot {
+                reserved_validators: NextEraReservedValidators::<T>::get(),
+                non_reserved_validators: NextEraNonReservedValidators::<T>::get(),
+                committee_size: NextEraCommitteeSize::<T>::get(),
+            }
+        }
+
+        /// Whether the change scheduled for `era` would be in effect in any of the eras another
+        /// scheduled change would, or take effect before an applied change expires. Restoring
+        /// the committee from before an expiring change would undo any changes made in between.
+        pub(crate) fn overlaps_other_changes(
+            era: EraIndex,
+            change: &ScheduledChange<T::AccountId>,
+        ) -> bool {
+            let end = change.end(era);
+            ScheduledRestorations::<T>::iter_keys().any(|expiry| era < expiry)
+                || ScheduledChanges::<T>::iter()
+                    .any(|(other_era, other)| era < other.end(other_era) && other_era < end)
+        }
+
+        pub(crate) fn put_next_era_committee(committee: CommitteeSnapshot<T::AccountId>) {
+            let CommitteeSnapshot {
+                reserved_validators,
+                non_reserved_validators,
+                committee_size,
+            } = committee;
+            NextEraNonReservedValidators::<T>::put(non_reserved_validators.clone());
+            NextEraReservedValidators::<T>::put(reserved_validators.clone());
+            NextEraCommitteeSize::<T>::put(committee_size);
+
+            Self::deposit_event(Event::ChangeValidators(
+                reserved_validators,
+                non_reserved_validators,
+                committee_size,
+            ));
+        }
+
+        pub(crate) fn ensure_validators_are_ok(
             reserved_validators: Vec<T::AccountId>,
             non_reserved_validators: Vec<T::AccountId>,
             committee_size: CommitteeSeats,
@@ -263,6 +480,9 @@ pub mod pallet {
         /// Winner number is greater than
         /// [`Config::MaxWinners`]
         TooManyWinners,
+
+        /// The NPoS solver of the [`ElectionStrategy`] failed
+        Solver(NposError),
     }
 
     #[pallet::error]
@@ -272,6 +492,19 @@ pub mod pallet {
         NotEnoughNonReservedValidators,
         NonUniqueListOfValidators,
         NonReservedFinalitySeatsLargerThanNonReservedSeats,
+        /// Number of validators to elect is zero or greater than [`Config::MaxWinners`]
+        InvalidElectionTargets,
+        /// Changes can only be scheduled for eras after the next one
+        ScheduledEraTooEarly,
+        /// Scheduled change has to expire after it takes effect
+        ExpiryNotAfterScheduledEra,
+        /// There already is a change scheduled for the era
+        ChangeAlreadyScheduled,
+        /// There is no change scheduled for the era
+        NoChangeScheduled,
+        /// Scheduled change would take effect while another one is in effect, or the other way
+        /// round
+        OverlappingScheduledChange,
     }
 
     impl<T: Config> ElectionProviderBase for Pallet<T> {
@@ -290,69 +523,18 @@ pub mod pallet {
         /// We calculate the supports for each validator. The external validators are chosen as:
         /// 1) "`NextEraNonReservedValidators` that are staking and are not banned" in case of Permissioned ElectionOpenness
         /// 2) "All staking and not banned validators" in case of Permissionless ElectionOpenness
+        /// and then, unless the [`ElectionStrategy`] is `All`, narrowed down by the NPoS solver.
         fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
-            let staking_validators =
-                Self::DataProvider::electable_targets(DataProviderBounds::default())
-                    .map_err(Self::Error::DataProvider)?
-                    .into_iter()
-                    .collect::<BTreeSet<_>>();
-            let staking_reserved_validators = NextEraReservedValidators::<T>::get()
-                .into_iter()
-                .filter(|v| staking_validators.contains(v))
-                .collect::<BTreeSet<_>>();
-            let banned_validators = T::BannedValidators::banned()
-                .into_iter()
-                .collect::<BTreeSet<_>>();
-            let old_non_reserved_validators = NextEraNonReservedValidators::<T>::get().into_iter();
-
-            let eligible_non_reserved = staking_validators
-                .into_iter()
-                .filter(|v| {
-                    !banned_validators.contains(v) && !staking_reserved_validators.contains(v)
-                })
-                .collect::<BTreeSet<_>>();
-
-            let new_non_reserved_validators: Vec<_> = match Openness::<T>::get() {
-                ElectionOpenness::Permissioned => old_non_reserved_validators
-                    .filter(|v| eligible_non_reserved.contains(v))
-                    .collect(),
-                ElectionOpenness::Permissionless => eligible_non_reserved.into_iter().collect(),
-            };
+            let Election {
+                non_reserved,
+                supports,
+                ..
+            } = Self::run_election()?;
             // We store new list here to ensure that validators that end up in the result of the elect
             // method are a disjoint union of NextEraReservedValidators and NextEraNonReservedValidators.
             // This condition is important since results of elect ends up in pallet staking while the above lists
             // are used in our session manager, so we have to ensure consistency between them.
-            NextEraNonReservedValidators::<T>::put(new_non_reserved_validators.clone());
-
-            let eligible_validators = staking_reserved_validators
-                .into_iter()
-                .chain(new_non_reserved_validators);
-            let mut supports = eligible_validators
-                .into_iter()
-                .map(|id| {
-                    (
-                        id,
-                        // Under normal circumstances support will never be `0` since 'self-vote'
-                        // is counted in.
-                        Support {
-                            total: 0,
-                            voters: Vec::new(),
-                        },
-                    )
-                })
-                .collect::<BTreeMap<_, _>>();
-
-            let voters = Self::DataProvider::electing_voters(DataProviderBounds::default())
-                .map_err(Self::Error::DataProvider)?;
-            for (voter, vote, targets) in voters {
-                // The parameter `Staking::MAX_NOMINATIONS` is set to 1 which guarantees that
-                // `len(targets) == 1`.
-                let member = &targets[0];
-                if let Some(support) = supports.get_mut(member) {
-                    support.total += vote as u128;
-                    support.voters.push((voter, vote as u128));
-                }
-            }
+            NextEraNonReservedValidators::<T>::put(non_reserved);
 
             supports
                 .into_iter()
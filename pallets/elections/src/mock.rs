@@ -114,10 +114,18 @@ impl ValidatorProvider for MockProvider {
     type AccountId = AccountId;
 
     fn elected_validators(era: EraIndex) -> Vec<Self::AccountId> {
-        ELECTED_VALIDATORS.with(|ev| ev.borrow().get(&era).unwrap().clone())
+        ELECTED_VALIDATORS.with(|ev| ev.borrow().get(&era).cloned().unwrap_or_default())
+    }
+
+    fn current_era() -> Option<EraIndex> {
+        Some(CURRENT_ERA.with(|ce| *ce.borrow()))
     }
 }
 
+pub fn with_current_era(era: EraIndex) {
+    CURRENT_ERA.with(|ce| *ce.borrow_mut() = era);
+}
+
 impl BannedValidators for MockProvider {
     type AccountId = AccountId;
 
@@ -132,6 +140,7 @@ impl Config for Test {
     type ValidatorProvider = MockProvider;
     type MaxWinners = ConstU32<DEFAULT_MAX_WINNERS>;
     type BannedValidators = MockProvider;
+    type WeightInfo = ();
 }
 
 type MaxVotesPerVoter = ConstU32<1>;
@@ -1,14 +1,15 @@
 use frame_election_provider_support::{ElectionProvider, Support};
-use primitives::CommitteeSeats;
+use frame_support::{assert_noop, assert_ok};
+use primitives::{CommitteeSeats, ElectionPreview, ElectionStrategy, EraManager};
 use sp_core::bounded_vec;
 
 use crate::{
     mock::{
-        with_electable_targets, with_electing_voters, AccountId, Balance, Elections, Test,
-        TestExtBuilder,
+        with_current_era, with_electable_targets, with_electing_voters, AccountId, Balance,
+        Elections, RuntimeOrigin, Test, TestExtBuilder,
     },
-    CommitteeSize, CurrentEraValidators, NextEraCommitteeSize, NextEraNonReservedValidators,
-    NextEraReservedValidators,
+    CommitteeSize, CurrentEraValidators, Error, NextEraCommitteeSize, NextEraNonReservedValidators,
+    NextEraReservedValidators, ScheduledChanges, ScheduledRestorations, Strategy,
 };
 
 fn no_support() -> Support<AccountId> {
@@ -80,3 +81,252 @@ fn validators_are_elected_only_when_staking() {
             );
         });
 }
+
+#[test]
+fn npos_strategies_elect_best_backed_non_reserved_validators() {
+    for strategy in [
+        ElectionStrategy::SequentialPhragmen { targets: 2 },
+        ElectionStrategy::PhragMMS { targets: 2 },
+    ] {
+        TestExtBuilder::new(vec![1], vec![2, 3, 4])
+            .build()
+            .execute_with(|| {
+                Strategy::<Test>::put(strategy);
+                with_electable_targets(vec![1, 2, 3, 4]);
+                with_electing_voters(vec![
+                    (1, 10, bounded_vec![1]),
+                    (2, 10, bounded_vec![2]),
+                    (3, 30, bounded_vec![3]),
+                    (4, 20, bounded_vec![4]),
+                    (5, 25, bounded_vec![2]),
+                ]);
+
+                let elected =
+                    <Elections as ElectionProvider>::elect().expect("`elect()` should succeed");
+                let totals: Vec<_> = elected
+                    .into_inner()
+                    .into_iter()
+                    .map(|(id, support)| (id, support.total))
+                    .collect();
+                assert_eq!(totals, vec![(1, 10), (2, 35), (3, 30)]);
+
+                let mut non_reserved = NextEraNonReservedValidators::<Test>::get();
+                non_reserved.sort();
+                assert_eq!(non_reserved, vec![2, 3]);
+            });
+    }
+}
+
+#[test]
+fn election_preview_does_not_store_the_result() {
+    TestExtBuilder::new(vec![1], vec![2, 3])
+        .build()
+        .execute_with(|| {
+            Strategy::<Test>::put(ElectionStrategy::SequentialPhragmen { targets: 1 });
+            with_electable_targets(vec![1, 2, 3]);
+            with_electing_voters(vec![
+                (1, 10, bounded_vec![1]),
+                (2, 10, bounded_vec![2]),
+                (3, 30, bounded_vec![3]),
+            ]);
+
+            assert_eq!(
+                Elections::preview_election().expect("the preview should succeed"),
+                ElectionPreview {
+                    reserved: vec![(1, 10)],
+                    non_reserved: vec![(3, 30)],
+                }
+            );
+            assert_eq!(NextEraNonReservedValidators::<Test>::get(), vec![2, 3]);
+        });
+}
+
+#[test]
+fn election_strategy_needs_valid_number_of_targets() {
+    TestExtBuilder::new(vec![1], vec![2, 3])
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Elections::set_election_strategy(
+                    RuntimeOrigin::root(),
+                    ElectionStrategy::PhragMMS { targets: 0 }
+                ),
+                Error::<Test>::InvalidElectionTargets
+            );
+
+            let strategy = ElectionStrategy::SequentialPhragmen { targets: 2 };
+            assert_ok!(Elections::set_election_strategy(
+                RuntimeOrigin::root(),
+                strategy
+            ));
+            assert_eq!(Strategy::<Test>::get(), strategy);
+        });
+}
+
+#[test]
+fn scheduled_change_is_applied_and_expires() {
+    const MAINTENANCE_SEATS: CommitteeSeats = CommitteeSeats {
+        reserved_seats: 3,
+        non_reserved_seats: 2,
+        non_reserved_finality_seats: 2,
+    };
+
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            let seats = NextEraCommitteeSize::<Test>::get();
+            with_current_era(0);
+            assert_ok!(Elections::schedule_change(
+                RuntimeOrigin::root(),
+                2,
+                Some(vec![1, 2, 5]),
+                None,
+                Some(MAINTENANCE_SEATS),
+                Some(4),
+            ));
+            assert!(ScheduledChanges::<Test>::contains_key(2));
+
+            <Elections as EraManager>::on_new_era(1);
+            assert!(!ScheduledChanges::<Test>::contains_key(2));
+            assert_eq!(NextEraReservedValidators::<Test>::get(), vec![1, 2, 5]);
+            assert_eq!(NextEraNonReservedValidators::<Test>::get(), vec![3, 4]);
+            assert_eq!(NextEraCommitteeSize::<Test>::get(), MAINTENANCE_SEATS);
+
+            <Elections as EraManager>::on_new_era(2);
+            assert_eq!(NextEraReservedValidators::<Test>::get(), vec![1, 2, 5]);
+
+            <Elections as EraManager>::on_new_era(3);
+            assert!(!ScheduledRestorations::<Test>::contains_key(4));
+            assert_eq!(NextEraReservedValidators::<Test>::get(), vec![1, 2]);
+            assert_eq!(NextEraCommitteeSize::<Test>::get(), seats);
+        });
+}
+
+#[test]
+fn scheduling_changes_is_validated() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            with_current_era(3);
+            let schedule = |era, expires_at| {
+                Elections::schedule_change(
+                    RuntimeOrigin::root(),
+                    era,
+                    None,
+                    Some(vec![3]),
+                    None,
+                    expires_at,
+                )
+            };
+
+            assert_noop!(schedule(4, None), Error::<Test>::ScheduledEraTooEarly);
+            assert_noop!(
+                schedule(5, Some(5)),
+                Error::<Test>::ExpiryNotAfterScheduledEra
+            );
+            // One non-reserved validator is not enough for two non-reserved seats.
+            assert_noop!(schedule(5, None), Error::<Test>::NotEnoughValidators);
+
+            assert_ok!(Elections::schedule_change(
+                RuntimeOrigin::root(),
+                5,
+                None,
+                Some(vec![3, 4, 5]),
+                None,
+                None,
+            ));
+            assert_noop!(
+                Elections::schedule_change(RuntimeOrigin::root(), 5, None, None, None, None),
+                Error::<Test>::ChangeAlreadyScheduled
+            );
+
+            assert_ok!(Elections::cancel_scheduled_change(RuntimeOrigin::root(), 5));
+            assert_noop!(
+                Elections::cancel_scheduled_change(RuntimeOrigin::root(), 5),
+                Error::<Test>::NoChangeScheduled
+            );
+        });
+}
+
+#[test]
+fn overlapping_changes_are_rejected() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            with_current_era(0);
+            let schedule = |era, expires_at| {
+                Elections::schedule_change(
+                    RuntimeOrigin::root(),
+                    era,
+                    Some(vec![1, 2, 5]),
+                    None,
+                    None,
+                    expires_at,
+                )
+            };
+
+            assert_ok!(schedule(3, Some(6)));
+            assert_noop!(
+                schedule(4, Some(6)),
+                Error::<Test>::OverlappingScheduledChange
+            );
+            assert_noop!(schedule(5, None), Error::<Test>::OverlappingScheduledChange);
+            assert_noop!(
+                schedule(2, Some(4)),
+                Error::<Test>::OverlappingScheduledChange
+            );
+            assert_noop!(
+                schedule(2, Some(7)),
+                Error::<Test>::OverlappingScheduledChange
+            );
+
+            // Changes right before and right after the expiring one are fine.
+            assert_ok!(schedule(2, None));
+            assert_ok!(schedule(6, Some(8)));
+        });
+}
+
+#[test]
+fn changes_cannot_be_scheduled_before_applied_ones_expire() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            with_current_era(0);
+            assert_ok!(Elections::schedule_change(
+                RuntimeOrigin::root(),
+                2,
+                Some(vec![1, 2, 5]),
+                None,
+                None,
+                Some(5),
+            ));
+            <Elections as EraManager>::on_new_era(1);
+            assert!(ScheduledRestorations::<Test>::contains_key(5));
+
+            with_current_era(1);
+            assert_noop!(
+                Elections::schedule_change(
+                    RuntimeOrigin::root(),
+                    4,
+                    Some(vec![1, 2, 6]),
+                    None,
+                    None,
+                    Some(6),
+                ),
+                Error::<Test>::OverlappingScheduledChange
+            );
+            assert_ok!(Elections::schedule_change(
+                RuntimeOrigin::root(),
+                5,
+                Some(vec![1, 2, 6]),
+                None,
+                None,
+                Some(6),
+            ));
+
+            <Elections as EraManager>::on_new_era(4);
+            assert_eq!(NextEraReservedValidators::<Test>::get(), vec![1, 2, 6]);
+            <Elections as EraManager>::on_new_era(5);
+            assert_eq!(NextEraReservedValidators::<Test>::get(), vec![1, 2]);
+        });
+}
@@ -4,6 +4,8 @@ use sp_std::vec::Vec;
 pub trait ValidatorProvider {
     type AccountId;
     fn elected_validators(era: EraIndex) -> Vec<Self::AccountId>;
+    /// Returns the latest planned era, `None` if no era has been planned yet.
+    fn current_era() -> Option<EraIndex>;
 }
 
 impl<T: pallet_staking::Config> ValidatorProvider for pallet_staking::Pallet<T> {
@@ -16,4 +18,8 @@ impl<T: pallet_staking::Config> ValidatorProvider for pallet_staking::Pallet<T>
             ))
             .collect()
     }
+
+    fn current_era() -> Option<EraIndex> {
+        pallet_staking::CurrentEra::<T>::get()
+    }
 }
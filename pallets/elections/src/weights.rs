@@ -0,0 +1,148 @@
+//! Weights for pallet_elections
+//!
+//! The storage accesses follow the benchmarks in `benchmarking.rs`, but the execution times are
+//! estimates that have not been measured yet. Replace them with the results measured on reference
+//! hardware by running `scripts/run_benchmarks.sh`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_elections.
+pub trait WeightInfo {
+    fn change_validators(r: u32, n: u32, ) -> Weight;
+    fn set_elections_openness() -> Weight;
+    fn set_election_strategy() -> Weight;
+    fn schedule_change(r: u32, n: u32, ) -> Weight;
+    fn cancel_scheduled_change() -> Weight;
+    fn on_new_era(v: u32, ) -> Weight;
+}
+
+/// Estimated weights for pallet_elections, see the module documentation.
+pub struct AlephWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for AlephWeight<T> {
+    // Storage: `Elections::NextEraNonReservedValidators` (r:0 w:1)
+    // Storage: `Elections::NextEraReservedValidators` (r:0 w:1)
+    // Storage: `Elections::NextEraCommitteeSize` (r:0 w:1)
+    /// The range of component `r` is `[0, 100]`.
+    /// The range of component `n` is `[0, 1000]`.
+    fn change_validators(r: u32, n: u32, ) -> Weight {
+        Weight::from_parts(18_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(70_000_u64, 0).saturating_mul(r as u64))
+            .saturating_add(Weight::from_parts(70_000_u64, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+    // Storage: `Elections::Openness` (r:0 w:1)
+    fn set_elections_openness() -> Weight {
+        Weight::from_parts(9_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `Elections::Strategy` (r:0 w:1)
+    fn set_election_strategy() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Elections::ScheduledChanges` (r:1 w:1)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:0)
+    // Storage: `Elections::NextEraNonReservedValidators` (r:1 w:0)
+    // Storage: `Elections::NextEraCommitteeSize` (r:1 w:0)
+    /// The range of component `r` is `[0, 100]`.
+    /// The range of component `n` is `[0, 1000]`.
+    fn schedule_change(r: u32, n: u32, ) -> Weight {
+        Weight::from_parts(22_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(90_000_u64, 0).saturating_mul(r as u64))
+            .saturating_add(Weight::from_parts(90_000_u64, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `Elections::ScheduledChanges` (r:1 w:1)
+    fn cancel_scheduled_change() -> Weight {
+        Weight::from_parts(12_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    // Storage: `Staking::ErasStakers` (r:1 w:0)
+    // Storage: `Staking::ErasStakersOverview` (r:1 w:0)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:1)
+    // Storage: `Elections::NextEraNonReservedValidators` (r:1 w:1)
+    // Storage: `Elections::NextEraCommitteeSize` (r:1 w:1)
+    // Storage: `Elections::CurrentEraValidators` (r:0 w:1)
+    // Storage: `Elections::CommitteeSize` (r:0 w:1)
+    // Storage: `Elections::ScheduledRestorations` (r:1 w:2)
+    // Storage: `Elections::ScheduledChanges` (r:1 w:1)
+    /// The range of component `v` is `[0, 1000]`.
+    fn on_new_era(v: u32, ) -> Weight {
+        Weight::from_parts(25_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(150_000_u64, 0).saturating_mul(v as u64))
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(v as u64)))
+            .saturating_add(T::DbWeight::get().writes(8_u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    // Storage: `Elections::NextEraNonReservedValidators` (r:0 w:1)
+    // Storage: `Elections::NextEraReservedValidators` (r:0 w:1)
+    // Storage: `Elections::NextEraCommitteeSize` (r:0 w:1)
+    /// The range of component `r` is `[0, 100]`.
+    /// The range of component `n` is `[0, 1000]`.
+    fn change_validators(r: u32, n: u32, ) -> Weight {
+        Weight::from_parts(18_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(70_000_u64, 0).saturating_mul(r as u64))
+            .saturating_add(Weight::from_parts(70_000_u64, 0).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+    // Storage: `Elections::Openness` (r:0 w:1)
+    fn set_elections_openness() -> Weight {
+        Weight::from_parts(9_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `Elections::Strategy` (r:0 w:1)
+    fn set_election_strategy() -> Weight {
+        Weight::from_parts(10_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `Staking::CurrentEra` (r:1 w:0)
+    // Storage: `Elections::ScheduledChanges` (r:1 w:1)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:0)
+    // Storage: `Elections::NextEraNonReservedValidators` (r:1 w:0)
+    // Storage: `Elections::NextEraCommitteeSize` (r:1 w:0)
+    /// The range of component `r` is `[0, 100]`.
+    /// The range of component `n` is `[0, 1000]`.
+    fn schedule_change(r: u32, n: u32, ) -> Weight {
+        Weight::from_parts(22_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(90_000_u64, 0).saturating_mul(r as u64))
+            .saturating_add(Weight::from_parts(90_000_u64, 0).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `Elections::ScheduledChanges` (r:1 w:1)
+    fn cancel_scheduled_change() -> Weight {
+        Weight::from_parts(12_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    // Storage: `Staking::ErasStakers` (r:1 w:0)
+    // Storage: `Staking::ErasStakersOverview` (r:1 w:0)
+    // Storage: `Elections::NextEraReservedValidators` (r:1 w:1)
+    // Storage: `Elections::NextEraNonReservedValidators` (r:1 w:1)
+    // Storage: `Elections::NextEraCommitteeSize` (r:1 w:1)
+    // Storage: `Elections::CurrentEraValidators` (r:0 w:1)
+    // Storage: `Elections::CommitteeSize` (r:0 w:1)
+    // Storage: `Elections::ScheduledRestorations` (r:1 w:2)
+    // Storage: `Elections::ScheduledChanges` (r:1 w:1)
+    /// The range of component `v` is `[0, 1000]`.
+    fn on_new_era(v: u32, ) -> Weight {
+        Weight::from_parts(25_000_000_u64, 0)
+            .saturating_add(Weight::from_parts(150_000_u64, 0).saturating_mul(v as u64))
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(v as u64)))
+            .saturating_add(RocksDbWeight::get().writes(8_u64))
+    }
+}
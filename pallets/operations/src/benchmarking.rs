@@ -0,0 +1,30 @@
+use frame_benchmarking::{account, v2::*};
+use frame_system::RawOrigin;
+
+use crate::*;
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    // An account with one consumer too many, which is fixed by decrementing the counter.
+    #[benchmark]
+    fn fix_accounts_consumers_counter() {
+        let caller: T::AccountId = whitelisted_caller();
+        let who: T::AccountId = account("who", 0, 0);
+        frame_system::Pallet::<T>::inc_providers(&who);
+        frame_system::Pallet::<T>::inc_consumers_without_limit(&who)
+            .expect("the account has a provider");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), who.clone());
+
+        assert_eq!(frame_system::Pallet::<T>::consumers(&who), 0);
+    }
+
+    impl_benchmark_test_suite!(
+        Pallet,
+        crate::tests::setup::new_test_ext(&[(1, false, 1_000_000)]),
+        crate::tests::setup::TestRuntime
+    );
+}
@@ -3,8 +3,11 @@
 
 extern crate core;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod impls;
 mod traits;
+pub mod weights;
 
 #[cfg(test)]
 mod tests;
@@ -22,7 +25,7 @@ pub use pallet::*;
 #[frame_support::pallet]
 #[pallet_doc("../README.md")]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, weights::constants::WEIGHT_REF_TIME_PER_MILLIS};
+    use frame_support::pallet_prelude::*;
     use frame_system::{ensure_signed, pallet_prelude::OriginFor};
 
     use crate::{
@@ -30,6 +33,7 @@ pub mod pallet {
             AccountInfoProvider, BalancesProvider, BondedStashProvider, ContractInfoProvider,
             NextKeysSessionProvider,
         },
+        weights::WeightInfo,
         STORAGE_VERSION,
     };
 
@@ -46,6 +50,8 @@ pub mod pallet {
         type BondedStashProvider: BondedStashProvider<AccountId = Self::AccountId>;
         /// Something that tells whether an account is contract one
         type ContractInfoProvider: ContractInfoProvider<AccountId = Self::AccountId>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -84,9 +90,7 @@ pub mod pallet {
         /// - `who`: An account to be fixed
         ///
         #[pallet::call_index(0)]
-        #[pallet::weight(
-        Weight::from_parts(WEIGHT_REF_TIME_PER_MILLIS.saturating_mul(8), 0)
-        )]
+        #[pallet::weight(T::WeightInfo::fix_accounts_consumers_counter())]
         pub fn fix_accounts_consumers_counter(
             origin: OriginFor<T>,
             who: T::AccountId,
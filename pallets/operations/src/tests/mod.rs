@@ -1,2 +1,2 @@
-mod setup;
+pub(crate) mod setup;
 mod suite;
@@ -266,6 +266,7 @@ impl pallet_operations::Config for TestRuntime {
     type NextKeysSessionProvider = Session;
     type BondedStashProvider = Staking;
     type ContractInfoProvider = Contracts;
+    type WeightInfo = ();
 }
 
 pub fn new_test_ext(accounts_and_balances: &[(u64, bool, u128)]) -> sp_io::TestExternalities {
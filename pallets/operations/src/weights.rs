@@ -0,0 +1,47 @@
+//! Weights for pallet_operations
+//!
+//! The storage accesses follow the benchmarks in `benchmarking.rs`, but the execution times are
+//! estimates that have not been measured yet. Replace them with the results measured on reference
+//! hardware by running `scripts/run_benchmarks.sh`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_operations.
+pub trait WeightInfo {
+    fn fix_accounts_consumers_counter() -> Weight;
+}
+
+/// Estimated weights for pallet_operations, see the module documentation.
+pub struct AlephWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for AlephWeight<T> {
+    // Storage: `System::Account` (r:1 w:1)
+    // Storage: `Contracts::ContractInfoOf` (r:1 w:0)
+    // Storage: `Staking::Bonded` (r:1 w:0)
+    // Storage: `Session::NextKeys` (r:1 w:0)
+    // Storage: `Staking::Ledger` (r:1 w:0)
+    fn fix_accounts_consumers_counter() -> Weight {
+        Weight::from_parts(38_000_000_u64, 0)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    // Storage: `System::Account` (r:1 w:1)
+    // Storage: `Contracts::ContractInfoOf` (r:1 w:0)
+    // Storage: `Staking::Bonded` (r:1 w:0)
+    // Storage: `Session::NextKeys` (r:1 w:0)
+    // Storage: `Staking::Ledger` (r:1 w:0)
+    fn fix_accounts_consumers_counter() -> Weight {
+        Weight::from_parts(38_000_000_u64, 0)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+}
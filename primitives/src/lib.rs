@@ -4,7 +4,7 @@ use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::crypto::KeyTypeId;
+use sp_core::{crypto::KeyTypeId, twox_128};
 pub use sp_runtime::{
     generic,
     traits::{BlakeTwo256, ConstU32, Header as HeaderT},
@@ -163,6 +163,20 @@ pub const DEFAULT_MAX_NON_FINALIZED_BLOCKS: u32 = 20;
 /// A relative folder where to store ABFT backups
 pub const DEFAULT_BACKUP_FOLDER: &str = "backup-stash";
 
+/// A relative path of the record of sessions in which ABFT units were signed
+pub const DEFAULT_SLASHING_PROTECTION_FILE: &str = "slashing-protection";
+
+/// Storage keys under which the Aleph pallet keeps the authorities of the next session: the
+/// committee and the emergency finalizer, in that order. Warp sync proves them against the state
+/// of the last block of every session.
+pub fn next_authority_data_storage_keys() -> [Vec<u8>; 2] {
+    let pallet = twox_128(b"Aleph");
+    [
+        [pallet, twox_128(b"NextAuthorities")].concat(),
+        [pallet, twox_128(b"QueuedEmergencyFinalizer")].concat(),
+    ]
+}
+
 /// Hold set of validators that produce blocks and set of validators that participate in finality
 /// during session.
 #[derive(Decode, Encode, TypeInfo, Debug, Clone, PartialEq, Eq)]
@@ -178,6 +192,30 @@ pub enum ElectionOpenness {
     Permissionless,
 }
 
+/// How the non-reserved validators are chosen among the eligible candidates
+#[derive(Decode, Encode, TypeInfo, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ElectionStrategy {
+    /// All eligible candidates are elected, each backed only by the stake nominating it first
+    #[default]
+    All,
+    /// The given number of candidates is elected with sequential Phragmén
+    SequentialPhragmen { targets: u32 },
+    /// The given number of candidates is elected with PhragMMS
+    PhragMMS { targets: u32 },
+}
+
+/// How the non-reserved part of the committee is chosen for every session
+#[derive(Decode, Encode, TypeInfo, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommitteeSelectionMode {
+    /// Non-reserved validators take turns in a fixed order, so all the future committees are
+    /// known in advance
+    #[default]
+    RoundRobin,
+    /// Non-reserved validators are drawn with probability proportional to their exposure, using
+    /// randomness fixed when the era is planned
+    StakeWeighted,
+}
+
 /// Represent desirable size of a committee in a session
 #[derive(Decode, Encode, TypeInfo, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CommitteeSeats {
@@ -213,7 +251,6 @@ pub trait FinalityCommitteeManager<T> {
 
 pub trait AbftScoresProvider {
     fn scores_for_session(session_id: SessionIndex) -> Option<Score>;
-    fn clear_scores();
     fn clear_nonce();
 }
 
@@ -230,6 +267,8 @@ pub struct FinalityBanConfig {
 
 pub const DEFAULT_FINALITY_BAN_MINIMAL_EXPECTED_PERFORMANCE: u16 = 11;
 pub const DEFAULT_FINALITY_BAN_SESSION_COUNT_THRESHOLD: SessionCount = 2;
+/// How many sessions back the ABFT scores are kept on chain.
+pub const DEFAULT_ABFT_SCORE_HISTORY_DEPTH: SessionIndex = 96;
 
 impl Default for FinalityBanConfig {
     fn default() -> Self {
@@ -241,6 +280,30 @@ impl Default for FinalityBanConfig {
     }
 }
 
+/// How the session rewards of finalizers depend on their ABFT scores. Finalizers whose score is
+/// at most `full_reward_threshold` get the whole reward, the reward then decreases linearly down to
+/// `minimal_reward` for scores of `minimal_reward_threshold` and worse.
+#[derive(Decode, Encode, TypeInfo, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalityRewardCurve {
+    /// The worst score which is still rewarded in full.
+    pub full_reward_threshold: u16,
+    /// The score from which only `minimal_reward` is paid.
+    pub minimal_reward_threshold: u16,
+    /// Fraction of the reward paid to the worst performing finalizers.
+    pub minimal_reward: Perbill,
+}
+
+/// By default finalizers are rewarded in full regardless of their scores.
+impl Default for FinalityRewardCurve {
+    fn default() -> Self {
+        FinalityRewardCurve {
+            full_reward_threshold: DEFAULT_FINALITY_BAN_MINIMAL_EXPECTED_PERFORMANCE,
+            minimal_reward_threshold: DEFAULT_FINALITY_BAN_MINIMAL_EXPECTED_PERFORMANCE,
+            minimal_reward: Perbill::one(),
+        }
+    }
+}
+
 /// Configurable parameters for ban validator mechanism related to block production
 #[derive(Decode, Encode, TypeInfo, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProductionBanConfig {
@@ -258,6 +321,8 @@ pub struct ProductionBanConfig {
 pub const DEFAULT_BAN_MINIMAL_EXPECTED_PERFORMANCE: Perbill = Perbill::from_percent(0);
 pub const DEFAULT_BAN_SESSION_COUNT_THRESHOLD: SessionCount = 3;
 pub const DEFAULT_BAN_REASON_LENGTH: u32 = 300;
+/// How many past bans of every validator are kept on chain.
+pub const DEFAULT_BAN_HISTORY_LENGTH: u32 = 16;
 pub const DEFAULT_MAX_WINNERS: u32 = u32::MAX;
 
 impl Default for ProductionBanConfig {
@@ -278,8 +343,19 @@ pub enum BanReason {
     /// of sessions
     InsufficientUptime(u32),
 
-    /// Any arbitrary reason
+    /// Any arbitrary reason. Manual bans used to be recorded with this variant, now they are
+    /// [`BanReason::Manual`].
     OtherReason(BoundedVec<u8, ConstU32<DEFAULT_BAN_REASON_LENGTH>>),
+
+    /// Validator has produced two different blocks in the given slot
+    ProductionEquivocation(u64),
+
+    /// Validator has been removed from the committee due to lagging behind the rest of the
+    /// finality committee in a given number of sessions
+    InsufficientFinalization(SessionCount),
+
+    /// Validator has been banned by the operator, for the given reason
+    Manual(BoundedVec<u8, ConstU32<DEFAULT_BAN_REASON_LENGTH>>),
 }
 
 /// Details of why and for how long a validator is removed from the committee
@@ -291,6 +367,17 @@ pub struct BanInfo {
     pub start: EraIndex,
 }
 
+/// Two headers of different blocks, both sealed by the same block producer in the same slot.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, Debug)]
+pub struct ProductionEquivocationProof {
+    /// Aura key of the block producer that equivocated
+    pub offender: AuraId,
+    /// header of the first of the conflicting blocks, including the seal
+    pub first_header: Header,
+    /// header of the second of the conflicting blocks, including the seal
+    pub second_header: Header,
+}
+
 /// Represent committee, ie set of nodes that produce and finalize blocks in the session
 #[derive(Eq, Clone, PartialEq, Decode, Encode, TypeInfo)]
 pub struct EraValidators<AccountId> {
@@ -309,6 +396,14 @@ impl<AccountId> Default for EraValidators<AccountId> {
     }
 }
 
+/// Validators that would be elected for the next era if the elections were held now, together
+/// with the total stake backing each of them
+#[derive(Eq, Clone, PartialEq, Decode, Encode, TypeInfo, Debug)]
+pub struct ElectionPreview<AccountId> {
+    pub reserved: Vec<(AccountId, Balance)>,
+    pub non_reserved: Vec<(AccountId, Balance)>,
+}
+
 #[derive(Encode, Decode, TypeInfo, PartialEq, Eq, Debug)]
 pub enum ApiError {
     DecodeKey,
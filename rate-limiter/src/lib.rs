@@ -5,7 +5,7 @@ use std::num::{NonZeroU64, TryFromIntError};
 
 use futures::{future::BoxFuture, ready, FutureExt};
 use rate_limiter::RateLimiterFacade;
-use tokio::io::AsyncRead;
+use tokio::io::{AsyncRead, AsyncWrite};
 
 pub use crate::{rate_limiter::SharedRateLimiter, token_bucket::SharedTokenBucket};
 
@@ -128,15 +128,99 @@ where
     }
 }
 
-pub struct FuturesRateLimitedAsyncReadWrite<ReadWrite> {
+pub struct RateLimitedAsyncWrite<Write> {
     rate_limiter: BoxFuture<'static, RateLimiterFacade>,
+    inner: Write,
+}
+
+impl<Write> RateLimitedAsyncWrite<Write> {
+    pub fn new(write: Write, rate_limiter: RateLimiterFacade) -> Self {
+        Self {
+            rate_limiter: Box::pin(rate_limiter.rate_limit(0)),
+            inner: write,
+        }
+    }
+
+    pub fn inner(&self) -> &Write {
+        &self.inner
+    }
+
+    fn get_inner(self: std::pin::Pin<&mut Self>) -> std::pin::Pin<&mut Write>
+    where
+        Write: Unpin,
+    {
+        let this = self.get_mut();
+        std::pin::Pin::new(&mut this.inner)
+    }
+
+    /// Helper method for the use of the [AsyncWrite](tokio::io::AsyncWrite) implementation.
+    fn rate_limit(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>>
+    where
+        Write: AsyncWrite + Unpin,
+    {
+        let this = self.get_mut();
+        let write = std::pin::Pin::new(&mut this.inner);
+
+        let sleeping_rate_limiter = ready!(this.rate_limiter.poll_unpin(cx));
+
+        let result = write.poll_write(cx, buf);
+        let last_write_size = match &result {
+            std::task::Poll::Ready(Ok(write_size)) => write_size.saturating_mul(8),
+            _ => 0,
+        };
+
+        this.rate_limiter = sleeping_rate_limiter.rate_limit(last_write_size).boxed();
+
+        result
+    }
+}
+
+impl<Write> AsyncWrite for RateLimitedAsyncWrite<Write>
+where
+    Write: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        self.rate_limit(cx, buf)
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.get_inner().poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.get_inner().poll_shutdown(cx)
+    }
+}
+
+pub struct FuturesRateLimitedAsyncReadWrite<ReadWrite> {
+    read_rate_limiter: BoxFuture<'static, RateLimiterFacade>,
+    write_rate_limiter: BoxFuture<'static, RateLimiterFacade>,
     inner: ReadWrite,
 }
 
 impl<ReadWrite> FuturesRateLimitedAsyncReadWrite<ReadWrite> {
-    pub fn new(wrapped: ReadWrite, rate_limiter: RateLimiterFacade) -> Self {
+    pub fn new(
+        wrapped: ReadWrite,
+        read_rate_limiter: RateLimiterFacade,
+        write_rate_limiter: RateLimiterFacade,
+    ) -> Self {
         Self {
-            rate_limiter: Box::pin(rate_limiter.rate_limit(0)),
+            read_rate_limiter: Box::pin(read_rate_limiter.rate_limit(0)),
+            write_rate_limiter: Box::pin(write_rate_limiter.rate_limit(0)),
             inner: wrapped,
         }
     }
@@ -150,7 +234,7 @@ impl<ReadWrite> FuturesRateLimitedAsyncReadWrite<ReadWrite> {
     }
 
     /// Helper method for the use of the [AsyncRead](futures::AsyncRead) implementation.
-    fn rate_limit(
+    fn rate_limit_read(
         self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
         buf: &mut [u8],
@@ -161,7 +245,7 @@ impl<ReadWrite> FuturesRateLimitedAsyncReadWrite<ReadWrite> {
         let this = self.get_mut();
         let read = std::pin::Pin::new(&mut this.inner);
 
-        let sleeping_rate_limiter = ready!(this.rate_limiter.poll_unpin(cx));
+        let sleeping_rate_limiter = ready!(this.read_rate_limiter.poll_unpin(cx));
 
         let result = read.poll_read(cx, buf);
         let last_read_size = match &result {
@@ -169,7 +253,32 @@ impl<ReadWrite> FuturesRateLimitedAsyncReadWrite<ReadWrite> {
             _ => 0,
         };
 
-        this.rate_limiter = sleeping_rate_limiter.rate_limit(last_read_size).boxed();
+        this.read_rate_limiter = sleeping_rate_limiter.rate_limit(last_read_size).boxed();
+
+        result
+    }
+
+    /// Helper method for the use of the [AsyncWrite](futures::AsyncWrite) implementation.
+    fn rate_limit_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>>
+    where
+        ReadWrite: futures::AsyncWrite + Unpin,
+    {
+        let this = self.get_mut();
+        let write = std::pin::Pin::new(&mut this.inner);
+
+        let sleeping_rate_limiter = ready!(this.write_rate_limiter.poll_unpin(cx));
+
+        let result = write.poll_write(cx, buf);
+        let last_write_size = match &result {
+            std::task::Poll::Ready(Ok(write_size)) => 8 * *write_size,
+            _ => 0,
+        };
+
+        this.write_rate_limiter = sleeping_rate_limiter.rate_limit(last_write_size).boxed();
 
         result
     }
@@ -184,7 +293,7 @@ where
         cx: &mut std::task::Context<'_>,
         buf: &mut [u8],
     ) -> std::task::Poll<std::io::Result<usize>> {
-        self.rate_limit(cx, buf)
+        self.rate_limit_read(cx, buf)
     }
 }
 
@@ -197,7 +306,7 @@ where
         cx: &mut std::task::Context<'_>,
         buf: &[u8],
     ) -> std::task::Poll<std::io::Result<usize>> {
-        self.get_inner().poll_write(cx, buf)
+        self.rate_limit_write(cx, buf)
     }
 
     fn poll_flush(
@@ -214,3 +323,64 @@ where
         self.get_inner().poll_close(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        time::{Duration, Instant},
+    };
+
+    use futures::future::poll_fn;
+    use tokio::{io::AsyncWrite, time::timeout};
+
+    use crate::{NonZeroRatePerSecond, RateLimitedAsyncWrite, RatePerSecond, SharedRateLimiter};
+
+    async fn write(writer: &mut RateLimitedAsyncWrite<Vec<u8>>, data: &[u8]) -> usize {
+        poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, data))
+            .await
+            .expect("writing to a vector should not fail")
+    }
+
+    #[tokio::test]
+    async fn writes_are_delayed_to_satisfy_the_rate() {
+        // 100 bytes per second.
+        let rate = NonZeroRatePerSecond::try_from(800).expect("rate is not zero");
+        let mut writer =
+            RateLimitedAsyncWrite::new(Vec::new(), SharedRateLimiter::new(rate.into()));
+
+        let start = Instant::now();
+        assert_eq!(write(&mut writer, &[0; 20]).await, 20);
+        assert_eq!(write(&mut writer, &[0; 20]).await, 20);
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(writer.inner().len(), 40);
+    }
+
+    #[tokio::test]
+    async fn blocked_rate_stops_all_writes() {
+        let mut writer =
+            RateLimitedAsyncWrite::new(Vec::new(), SharedRateLimiter::new(RatePerSecond::Block));
+
+        assert!(
+            timeout(Duration::from_millis(100), write(&mut writer, &[0; 20]))
+                .await
+                .is_err()
+        );
+        assert!(writer.inner().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unlimited_rate_does_not_delay_writes() {
+        let mut writer = RateLimitedAsyncWrite::new(Vec::new(), SharedRateLimiter::unlimited());
+
+        let writes = async {
+            for _ in 0..100 {
+                write(&mut writer, &[0; 64 * 1024]).await;
+            }
+        };
+
+        assert!(timeout(Duration::from_secs(5), writes).await.is_ok());
+        assert_eq!(writer.inner().len(), 100 * 64 * 1024);
+    }
+}
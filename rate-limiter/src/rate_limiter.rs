@@ -23,6 +23,7 @@ impl From<Deadline> for Option<Instant> {
 
 pub enum RateLimiterFacade {
     NoTraffic,
+    Unlimited,
     RateLimiter(SharedTokenBucket),
 }
 
@@ -34,12 +35,18 @@ impl RateLimiterFacade {
         }
     }
 
-    pub async fn rate_limit(self, read_size: usize) -> Self {
+    /// Constructs a rate-limiter that never delays any traffic.
+    pub fn unlimited() -> Self {
+        Self::Unlimited
+    }
+
+    pub async fn rate_limit(self, requested: usize) -> Self {
         match self {
             RateLimiterFacade::NoTraffic => pending().await,
+            RateLimiterFacade::Unlimited => RateLimiterFacade::Unlimited,
             RateLimiterFacade::RateLimiter(rate_limiter) => RateLimiterFacade::RateLimiter(
                 rate_limiter
-                    .rate_limit(read_size.try_into().unwrap_or(u64::MAX))
+                    .rate_limit(requested.try_into().unwrap_or(u64::MAX))
                     .await,
             ),
         }
@@ -48,6 +55,7 @@ impl RateLimiterFacade {
     pub fn share(&self) -> Self {
         match self {
             RateLimiterFacade::NoTraffic => RateLimiterFacade::NoTraffic,
+            RateLimiterFacade::Unlimited => RateLimiterFacade::Unlimited,
             RateLimiterFacade::RateLimiter(shared_token_bucket) => {
                 RateLimiterFacade::RateLimiter(shared_token_bucket.share())
             }
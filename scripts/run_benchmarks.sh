@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+
+# This script benchmarks the custom pallets of the AlephZero runtime and regenerates their
+# `weights.rs` files from `.maintain/pallet-weight-template.hbs`. Run it on reference hardware,
+# from the root of the repository.
+
+set -euo pipefail
+
+STEPS=${STEPS:-50}
+REPEAT=${REPEAT:-20}
+
+cargo build --release -p aleph-node --features runtime-benchmarks
+
+for pallet in aleph committee-management elections operations; do
+  echo "Benchmarking pallet_${pallet//-/_}"
+  ./target/release/aleph-node benchmark pallet \
+    --chain=testnet \
+    --pallet="pallet_${pallet//-/_}" \
+    --extrinsic='*' \
+    --steps="${STEPS}" \
+    --repeat="${REPEAT}" \
+    --template=.maintain/pallet-weight-template.hbs \
+    --output="pallets/${pallet}/src/weights.rs"
+done